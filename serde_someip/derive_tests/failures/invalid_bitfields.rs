@@ -0,0 +1,46 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+#[someip(bitfield = u8)]
+struct Test {
+    #[someip(bit = 8)]
+    a: bool,
+}
+
+#[derive(SomeIp)]
+#[someip(bitfield = u8)]
+struct Test2 {
+    #[someip(bit = 1)]
+    a: bool,
+    #[someip(bit = 1)]
+    b: bool,
+}
+
+#[derive(SomeIp)]
+#[someip(bitfield = u8)]
+struct Test3 {
+    #[someip(bit = 1)]
+    a: u8,
+}
+
+#[derive(SomeIp)]
+#[someip(bitfield = u8)]
+struct Test4 {
+    a: bool,
+}
+
+#[derive(SomeIp)]
+#[someip(bitfield = i8)]
+struct Test5 {
+    #[someip(bit = 1)]
+    a: bool,
+}
+
+#[derive(SomeIp)]
+#[someip(bitfield = u8, length_field_size = 1)]
+struct Test6 {
+    #[someip(bit = 1)]
+    a: bool,
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+#[someip(e2e = profile4(data_id = 1))]
+struct Test {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(message_wrapper = true, e2e = profile3(data_id = 1))]
+struct Test2 {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(message_wrapper = true, e2e = profile5(data_id = 1))]
+struct Test3 {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(message_wrapper = true, e2e = profile4(data_id = 1, data_id = 2))]
+struct Test4 {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(message_wrapper = true, e2e = profile4(data_id = 1, foo = 2))]
+struct Test5 {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(message_wrapper = true, e2e = 4)]
+struct Test6 {
+    a: u32,
+}
+
+fn main() {}
@@ -59,4 +59,22 @@ enum Test6 {
     A,
 }
 
+#[derive(SomeIp)]
+#[someip(raw_type = u16)]
+enum Test7 {
+    #[someip(value = 0)]
+    A,
+    #[someip(other)]
+    B(u32),
+}
+
+#[derive(SomeIp)]
+#[someip(raw_type = u16)]
+enum Test8 {
+    #[someip(value = 0, other)]
+    A,
+    #[someip(other)]
+    B(u16),
+}
+
 fn main() {}
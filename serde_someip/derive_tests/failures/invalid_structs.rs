@@ -25,4 +25,60 @@ struct OptionInNonTlvStruct {
     b: Option<f64>,
 }
 
+#[derive(SomeIp)]
+struct InvalidMaps {
+    a: std::collections::HashMap<u8, u8>,
+    #[someip(max_elements = 3)]
+    b: std::collections::HashMap<String, u8>,
+    #[someip(max_elements = 3, elements = (max_size = 3))]
+    c: std::collections::BTreeMap<u8, u8>,
+}
+
+#[derive(SomeIp)]
+struct TlvTuple(#[someip(id = 1)] u32, #[someip(id = 2)] u32);
+
+#[derive(SomeIp)]
+#[someip(alignment = 12)]
+struct InvalidAlignment {
+    a: u32,
+}
+
+#[derive(SomeIp)]
+struct DefaultInNonExtensible {
+    #[someip(default = 1)]
+    a: u32,
+}
+
+#[derive(SomeIp)]
+#[someip(extensible = true)]
+struct ExtensibleTlv {
+    #[someip(id = 1)]
+    a: u32,
+}
+
+#[derive(SomeIp)]
+struct UnknownMembersInNonTlv {
+    a: u32,
+    #[someip(unknown_members)]
+    b: serde_someip::UnknownMembers,
+}
+
+#[derive(SomeIp)]
+struct UnknownMembersWithId {
+    #[someip(id = 1)]
+    a: u32,
+    #[someip(id = 2, unknown_members)]
+    b: serde_someip::UnknownMembers,
+}
+
+#[derive(SomeIp)]
+struct DuplicateUnknownMembers {
+    #[someip(id = 1)]
+    a: u32,
+    #[someip(unknown_members)]
+    b: serde_someip::UnknownMembers,
+    #[someip(unknown_members)]
+    c: serde_someip::UnknownMembers,
+}
+
 fn main() {}
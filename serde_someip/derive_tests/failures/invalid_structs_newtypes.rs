@@ -25,4 +25,8 @@ struct Test4(Vec<Vec<u8>>);
 #[someip(elements = ())]
 struct Test5([String; 6]);
 
+#[derive(SomeIp)]
+#[someip(fixed_size = 16, max_size = 16)]
+struct Test6(String);
+
 fn main() {}
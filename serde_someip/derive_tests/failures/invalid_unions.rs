@@ -0,0 +1,34 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+enum Test {
+    #[someip(selector = 0)]
+    A(u32),
+    B(u32),
+    #[someip(selector = 1)]
+    C(u32),
+    #[someip(selector = 1)]
+    D(u32),
+    #[someip(selector = -1)]
+    E(u32),
+    #[someip(selector = 2, foo = 1)]
+    F,
+    #[someip(selector = 3, foo = 1)]
+    G(u8, u8),
+}
+
+#[derive(SomeIp)]
+#[someip(type_field_size = 3)]
+enum Test2 {
+    #[someip(selector = 1)]
+    A(u32),
+}
+
+#[derive(SomeIp)]
+#[someip(foo = 1)]
+enum Test3 {
+    #[someip(selector = 1)]
+    A(u32),
+}
+
+fn main() {}
@@ -1,6 +0,0 @@
-use serde_someip::SomeIp;
-
-#[derive(SomeIp)]
-struct Test(u32, u64);
-
-fn main() {}
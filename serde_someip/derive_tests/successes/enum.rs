@@ -11,18 +11,60 @@ enum Test {
     C,
 }
 
+#[derive(SomeIp)]
+#[someip(raw_type = u16)]
+enum OtherTest {
+    #[someip(value = 0)]
+    A,
+    #[someip(other)]
+    Unknown(u16),
+}
+
+#[derive(SomeIp)]
+#[someip(raw_type = u8)]
+enum UnitOtherTest {
+    #[someip(value = 0)]
+    A,
+    #[someip(value = 0xFF, other)]
+    Unknown,
+}
+
 fn main() {
     use serde_someip::types::*;
     assert_eq!(
         SomeIpType::Enum(SomeIpEnum {
-            name: "Test",
+            name: SomeIpRef::Static("Test"),
             raw_type: SomeIpPrimitive::I16,
-            values: &[
-                ("A", SomeIpEnumValue::I16(0)),
-                ("B", SomeIpEnumValue::I16(1)),
-                ("C", SomeIpEnumValue::I16(-1))
-            ],
+            values: SomeIpRef::Static(&[
+                (SomeIpRef::Static("A"), SomeIpEnumValue::I16(0)),
+                (SomeIpRef::Static("B"), SomeIpEnumValue::I16(1)),
+                (SomeIpRef::Static("C"), SomeIpEnumValue::I16(-1))
+            ]),
+            other: None,
         }),
         Test::SOMEIP_TYPE
     );
+
+    assert_eq!(
+        SomeIpType::Enum(SomeIpEnum {
+            name: SomeIpRef::Static("OtherTest"),
+            raw_type: SomeIpPrimitive::U16,
+            values: SomeIpRef::Static(&[(SomeIpRef::Static("A"), SomeIpEnumValue::U16(0))]),
+            other: Some(SomeIpRef::Static("Unknown")),
+        }),
+        OtherTest::SOMEIP_TYPE
+    );
+
+    assert_eq!(
+        SomeIpType::Enum(SomeIpEnum {
+            name: SomeIpRef::Static("UnitOtherTest"),
+            raw_type: SomeIpPrimitive::U8,
+            values: SomeIpRef::Static(&[
+                (SomeIpRef::Static("A"), SomeIpEnumValue::U8(0)),
+                (SomeIpRef::Static("Unknown"), SomeIpEnumValue::U8(0xFF))
+            ]),
+            other: Some(SomeIpRef::Static("Unknown")),
+        }),
+        UnitOtherTest::SOMEIP_TYPE
+    );
 }
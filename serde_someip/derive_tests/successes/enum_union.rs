@@ -0,0 +1,135 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+struct Inner {
+    a: u8,
+}
+
+#[derive(SomeIp)]
+#[someip(length_field_size = 2, type_field_size = 1)]
+enum Test {
+    #[someip(selector = 0)]
+    Empty,
+    #[someip(selector = 1)]
+    A(u32),
+    #[someip(selector = 2)]
+    B(Inner),
+    #[someip(selector = 3, max_size = 42)]
+    C(String),
+    #[someip(selector = 4)]
+    D(u8, u16),
+    #[someip(selector = 5)]
+    E {
+        #[someip(max_elements = 3)]
+        foo: Vec<u8>,
+    },
+}
+
+#[derive(SomeIp)]
+enum Test2 {
+    #[someip(selector = 1)]
+    A(u32),
+}
+
+fn main() {
+    use serde_someip::length_fields::LengthFieldSize;
+    use serde_someip::types::*;
+    assert_eq!(
+        SomeIpType::Union(SomeIpUnion {
+            name: SomeIpRef::Static("Test"),
+            variants: SomeIpRef::Static(&[
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("Empty"),
+                    selector: 0,
+                    variant_type: None,
+                },
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("A"),
+                    selector: 1,
+                    variant_type: Some(SomeIpRef::Static(u32::SOMEIP_TYPE_REF)),
+                },
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("B"),
+                    selector: 2,
+                    variant_type: Some(SomeIpRef::Static(Inner::SOMEIP_TYPE_REF)),
+                },
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("C"),
+                    selector: 3,
+                    variant_type: Some(SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                        min_size: 0,
+                        max_size: 42,
+                        length_field_size: None,
+                        padding: false,
+                    }))),
+                },
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("D"),
+                    selector: 4,
+                    variant_type: Some(SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
+                        name: SomeIpRef::Static("Test::D"),
+                        fields: SomeIpRef::Static(&[
+                            SomeIpField {
+                                name: SomeIpRef::Static("0"),
+                                id: None,
+                                field_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                                default: None,
+                            },
+                            SomeIpField {
+                                name: SomeIpRef::Static("1"),
+                                id: None,
+                                field_type: SomeIpRef::Static(u16::SOMEIP_TYPE_REF),
+                                default: None,
+                            },
+                        ]),
+                        uses_tlv_serialization: false,
+                        is_message_wrapper: false,
+                        length_field_size: None,
+                        transformation_properties: None,
+                        extensible: false,
+                    }))),
+                },
+                SomeIpUnionVariant {
+                    name: SomeIpRef::Static("E"),
+                    selector: 5,
+                    variant_type: Some(SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
+                        name: SomeIpRef::Static("Test::E"),
+                        fields: SomeIpRef::Static(&[SomeIpField {
+                            name: SomeIpRef::Static("foo"),
+                            id: None,
+                            field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                                min_elements: 0,
+                                max_elements: 3,
+                                element_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                                length_field_size: None,
+                            })),
+                            default: None,
+                        }]),
+                        uses_tlv_serialization: false,
+                        is_message_wrapper: false,
+                        length_field_size: None,
+                        transformation_properties: None,
+                        extensible: false,
+                    }))),
+                },
+            ]),
+            length_field_size: Some(LengthFieldSize::TwoBytes),
+            type_field_size: Some(LengthFieldSize::OneByte),
+        }),
+        Test::SOMEIP_TYPE
+    );
+
+    assert_eq!(
+        SomeIpType::Union(SomeIpUnion {
+            name: SomeIpRef::Static("Test2"),
+            variants: SomeIpRef::Static(&[SomeIpUnionVariant {
+                name: SomeIpRef::Static("A"),
+                selector: 1,
+                variant_type: Some(SomeIpRef::Static(u32::SOMEIP_TYPE_REF)),
+            }]),
+            length_field_size: None,
+            type_field_size: None,
+        }),
+        Test2::SOMEIP_TYPE
+    );
+}
@@ -42,136 +42,179 @@ struct PropsTest {
     a: i16,
 }
 
+#[derive(SomeIp)]
+#[someip(alignment = 64)]
+struct AlignedTest {
+    a: i16,
+}
+
+#[derive(SomeIp)]
+#[someip(extensible = true, length_field_size = 2)]
+struct ExtensibleTest {
+    a: i16,
+    #[someip(default = 42)]
+    b: u32,
+    #[someip(default = true)]
+    c: bool,
+    #[someip(default = -1)]
+    d: i8,
+}
+
+#[derive(SomeIp)]
+struct UnknownMembersTest {
+    #[someip(id = 1)]
+    a: i16,
+    #[someip(unknown_members)]
+    b: serde_someip::UnknownMembers,
+}
+
 fn main() {
     use serde_someip::length_fields::LengthFieldSize;
     use serde_someip::types::*;
 
     assert_eq!(
         SomeIpType::Struct(SomeIpStruct {
-            name: "NonTlvTest",
+            name: SomeIpRef::Static("NonTlvTest"),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: None,
             transformation_properties: None,
-            fields: &[
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "a",
+                    name: SomeIpRef::Static("a"),
                     id: None,
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "b",
+                    name: SomeIpRef::Static("b"),
                     id: None,
-                    field_type: &f64::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(f64::SOMEIP_TYPE_REF),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "c",
+                    name: SomeIpRef::Static("c"),
                     id: None,
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         min_elements: 0,
                         max_elements: 42,
                         length_field_size: None,
-                        element_type: &u8::SOMEIP_TYPE,
-                    }),
+                        element_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                    })),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "d",
+                    name: SomeIpRef::Static("d"),
                     id: None,
-                    field_type: &SomeIpType::String(SomeIpString {
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
                         min_size: 0,
                         max_size: 1337,
                         length_field_size: None,
-                    }),
+                        padding: false,
+                    })),
+                    default: None,
                 }
-            ],
+            ]),
+            extensible: false,
         }),
         NonTlvTest::SOMEIP_TYPE
     );
 
     assert_eq!(
         SomeIpType::Struct(SomeIpStruct {
-            name: "TlvTest",
+            name: SomeIpRef::Static("TlvTest"),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: None,
             transformation_properties: None,
-            fields: &[
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "a",
+                    name: SomeIpRef::Static("a"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "b",
+                    name: SomeIpRef::Static("b"),
                     id: Some(2),
-                    field_type: &f64::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(f64::SOMEIP_TYPE_REF),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "c",
+                    name: SomeIpRef::Static("c"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         min_elements: 0,
                         max_elements: 42,
                         length_field_size: None,
-                        element_type: &u8::SOMEIP_TYPE,
-                    }),
+                        element_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                    })),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "d",
+                    name: SomeIpRef::Static("d"),
                     id: Some(4),
-                    field_type: &SomeIpType::String(SomeIpString {
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
                         min_size: 0,
                         max_size: 1337,
                         length_field_size: None,
-                    }),
+                        padding: false,
+                    })),
+                    default: None,
                 }
-            ],
+            ]),
+            extensible: false,
         }),
         TlvTest::SOMEIP_TYPE
     );
 
     assert_eq!(
         SomeIpType::Struct(SomeIpStruct {
-            name: "MessageWrapperTest",
+            name: SomeIpRef::Static("MessageWrapperTest"),
             uses_tlv_serialization: false,
             is_message_wrapper: true,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
-            fields: &[SomeIpField {
-                name: "foo",
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("foo"),
                 id: None,
-                field_type: &u32::SOMEIP_TYPE,
-            }],
+                field_type: SomeIpRef::Static(u32::SOMEIP_TYPE_REF),
+                default: None,
+            }]),
+            extensible: false,
         }),
         MessageWrapperTest::SOMEIP_TYPE
     );
 
     assert_eq!(
         SomeIpType::Struct(SomeIpStruct {
-            name: "OptionTest",
+            name: SomeIpRef::Static("OptionTest"),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: None,
             transformation_properties: None,
-            fields: &[
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "a",
+                    name: SomeIpRef::Static("a"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "b",
+                    name: SomeIpRef::Static("b"),
                     id: Some(2),
-                    field_type: &f64::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(f64::SOMEIP_TYPE_REF),
+                    default: None,
                 },
-            ],
+            ]),
+            extensible: false,
         }),
         OptionTest::SOMEIP_TYPE
     );
 
     assert_eq!(
         SomeIpType::Struct(SomeIpStruct {
-            name: "PropsTest",
+            name: SomeIpRef::Static("PropsTest"),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: None,
@@ -179,13 +222,105 @@ fn main() {
                 size_of_array_length_field: None,
                 size_of_struct_length_field: Some(LengthFieldSize::OneByte),
                 size_of_string_length_field: Some(LengthFieldSize::TwoBytes),
+                alignment: None,
             }),
-            fields: &[SomeIpField {
-                name: "a",
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
                 id: None,
-                field_type: &i16::SOMEIP_TYPE,
-            }]
+                field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                default: None,
+            }]),
+            extensible: false,
         }),
         PropsTest::SOMEIP_TYPE
     );
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("AlignedTest"),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: None,
+            transformation_properties: Some(SomeIpTransforationProperties {
+                size_of_array_length_field: None,
+                size_of_struct_length_field: None,
+                size_of_string_length_field: None,
+                alignment: Some(Alignment::SixtyFourBits),
+            }),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: None,
+                field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                default: None,
+            }]),
+            extensible: false,
+        }),
+        AlignedTest::SOMEIP_TYPE
+    );
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("ExtensibleTest"),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: Some(LengthFieldSize::TwoBytes),
+            transformation_properties: None,
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("a"),
+                    id: None,
+                    field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("b"),
+                    id: None,
+                    field_type: SomeIpRef::Static(u32::SOMEIP_TYPE_REF),
+                    default: Some(SomeIpDefault::Unsigned(42)),
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("c"),
+                    id: None,
+                    field_type: SomeIpRef::Static(bool::SOMEIP_TYPE_REF),
+                    default: Some(SomeIpDefault::Bool(true)),
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("d"),
+                    id: None,
+                    field_type: SomeIpRef::Static(i8::SOMEIP_TYPE_REF),
+                    default: Some(SomeIpDefault::Signed(-1)),
+                },
+            ]),
+            extensible: true,
+        }),
+        ExtensibleTest::SOMEIP_TYPE
+    );
+    ExtensibleTest::SOMEIP_TYPE.verify();
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("UnknownMembersTest"),
+            uses_tlv_serialization: true,
+            is_message_wrapper: false,
+            length_field_size: None,
+            transformation_properties: None,
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("a"),
+                    id: Some(1),
+                    field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("b"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::UnknownMembers),
+                    default: None,
+                },
+            ]),
+            extensible: false,
+        }),
+        UnknownMembersTest::SOMEIP_TYPE
+    );
+    UnknownMembersTest::SOMEIP_TYPE.verify();
 }
@@ -0,0 +1,24 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+#[someip(bitfield = u16)]
+struct Test {
+    #[someip(bit = 0)]
+    a: bool,
+    #[someip(bit = 3)]
+    b: bool,
+    #[someip(bit = 15)]
+    c: bool,
+}
+
+fn main() {
+    use serde_someip::types::*;
+    assert_eq!(
+        SomeIpType::Bitfield(SomeIpBitfield {
+            name: SomeIpRef::Static("Test"),
+            flags: SomeIpRef::Static(&[(SomeIpRef::Static("a"), 0), (SomeIpRef::Static("b"), 3), (SomeIpRef::Static("c"), 15)]),
+            raw_type: SomeIpPrimitive::U16,
+        }),
+        Test::SOMEIP_TYPE
+    );
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use serde_someip::e2e::{E2EProfile, E2EProtected, E2EStatus};
+use serde_someip::options::ExampleOptions;
+use serde_someip::SomeIp;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(message_wrapper = true, e2e = profile4(data_id = 0x0A0B0C0D))]
+struct Profile4Message {
+    a: u32,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(message_wrapper = true, e2e = profile5(data_id = 0x1234, data_length = 7, offset = 2))]
+struct Profile5Message(u16, u16);
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(message_wrapper = true, e2e = profile6(data_id = 0x1234, max_delta_counter = 1))]
+struct Profile6Message {
+    a: u8,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(message_wrapper = true, e2e = profile7(data_id = 0x1234))]
+struct Profile7Message {
+    #[someip(max_elements = 1024)]
+    a: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(
+    message_wrapper = true,
+    e2e = profile11(
+        data_id = 0x0123,
+        data_length = 4,
+        data_id_mode = serde_someip::e2e::Profile11DataIdMode::Nibble
+    )
+)]
+struct Profile11Message {
+    a: u16,
+}
+
+const DATA_ID_LIST: [u8; 16] = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16];
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+#[someip(message_wrapper = true, e2e = profile22(data_id_list = DATA_ID_LIST, data_length = 3))]
+struct Profile22Message {
+    a: bool,
+}
+
+fn round_trip<T>(value: T)
+where
+    T: std::fmt::Debug
+        + PartialEq
+        + Serialize
+        + serde::de::DeserializeOwned
+        + SomeIp
+        + E2EProtected,
+{
+    let mut sender = T::e2e_profile();
+    let mut receiver = T::e2e_profile();
+    let data = serde_someip::e2e::to_vec::<ExampleOptions, _>(&value, &mut sender).unwrap();
+    assert_eq!(E2EStatus::Ok, T::e2e_profile().check(&data));
+    let result: T =
+        serde_someip::e2e::from_slice::<ExampleOptions, _>(&data, &mut receiver).unwrap();
+    assert_eq!(value, result);
+    assert!(matches!(
+        serde_someip::e2e::from_slice::<ExampleOptions, T>(&data, &mut receiver),
+        Err(serde_someip::Error::E2ECheckFailed(E2EStatus::Repeated))
+    ));
+}
+
+fn main() {
+    round_trip(Profile4Message { a: 42 });
+    round_trip(Profile5Message(1, 2));
+    assert_eq!(1, Profile6Message::e2e_profile().config().max_delta_counter);
+    round_trip(Profile6Message { a: 42 });
+    round_trip(Profile7Message { a: vec![1, 2, 3] });
+    round_trip(Profile11Message { a: 42 });
+    round_trip(Profile22Message { a: true });
+
+    //the plain functions are not affected by the e2e attribute
+    let unprotected = serde_someip::to_vec::<ExampleOptions, _>(&Profile4Message { a: 42 }).unwrap();
+    assert_eq!(vec![0, 0, 0, 42], unprotected);
+    assert!(matches!(
+        serde_someip::e2e::from_slice::<ExampleOptions, Profile4Message>(
+            &unprotected,
+            &mut Profile4Message::e2e_profile()
+        ),
+        Err(serde_someip::Error::E2ECheckFailed(E2EStatus::Error))
+    ));
+}
@@ -0,0 +1,69 @@
+use serde_someip::SomeIp;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(SomeIp)]
+struct Test {
+    #[someip(max_elements = 42)]
+    a: HashMap<u8, u32>,
+    #[someip(
+        min_elements = 1,
+        max_elements = 3,
+        length_field_size = 2,
+        keys = (max_size = 10),
+        values = (max_elements = 4)
+    )]
+    b: BTreeMap<String, Vec<u16>>,
+}
+
+fn main() {
+    use serde_someip::length_fields::LengthFieldSize;
+    use serde_someip::types::*;
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: None,
+            transformation_properties: None,
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("a"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::Map(SomeIpMap {
+                        min_elements: 0,
+                        max_elements: 42,
+                        key_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                        value_type: SomeIpRef::Static(u32::SOMEIP_TYPE_REF),
+                        length_field_size: None,
+                    })),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("b"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::Map(SomeIpMap {
+                        min_elements: 1,
+                        max_elements: 3,
+                        key_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                            min_size: 0,
+                            max_size: 10,
+                            length_field_size: None,
+                            padding: false,
+                        })),
+                        value_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                            min_elements: 0,
+                            max_elements: 4,
+                            element_type: SomeIpRef::Static(u16::SOMEIP_TYPE_REF),
+                            length_field_size: None,
+                        })),
+                        length_field_size: Some(LengthFieldSize::TwoBytes),
+                    })),
+                    default: None,
+                },
+            ]),
+            extensible: false,
+        }),
+        Test::SOMEIP_TYPE
+    );
+}
@@ -15,6 +15,10 @@ struct TestString2<'a>(&'a str);
 #[someip(min_size = 0, max_size = 42, length_field_size = 1)]
 struct TestString3<'a>(&'a String);
 
+#[derive(SomeIp)]
+#[someip(fixed_size = 32)]
+struct TestFixedString(String);
+
 #[derive(SomeIp)]
 struct TestArray([u8; 10]);
 
@@ -48,6 +52,7 @@ fn main() {
             min_size: 0,
             max_size: 42,
             length_field_size: None,
+            padding: false,
         }),
         TestString::SOMEIP_TYPE
     );
@@ -57,6 +62,7 @@ fn main() {
             min_size: 10,
             max_size: 10,
             length_field_size: None,
+            padding: false,
         }),
         TestString2::SOMEIP_TYPE
     );
@@ -65,16 +71,27 @@ fn main() {
         SomeIpType::String(SomeIpString {
             min_size: 0,
             max_size: 42,
-            length_field_size: Some(LengthFieldSize::OneByte)
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
         }),
         TestString3::SOMEIP_TYPE
     );
 
+    assert_eq!(
+        SomeIpType::String(SomeIpString {
+            min_size: 32,
+            max_size: 32,
+            length_field_size: None,
+            padding: true,
+        }),
+        TestFixedString::SOMEIP_TYPE
+    );
+
     assert_eq!(
         SomeIpType::Sequence(SomeIpSequence {
             min_elements: 10,
             max_elements: 10,
-            element_type: &u8::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
             length_field_size: None,
         }),
         TestArray::SOMEIP_TYPE
@@ -84,7 +101,7 @@ fn main() {
         SomeIpType::Sequence(SomeIpSequence {
             min_elements: 0,
             max_elements: 42,
-            element_type: &f32::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(f32::SOMEIP_TYPE_REF),
             length_field_size: None,
         }),
         TestSlice::SOMEIP_TYPE
@@ -94,7 +111,7 @@ fn main() {
         SomeIpType::Sequence(SomeIpSequence {
             min_elements: 10,
             max_elements: 42,
-            element_type: &i64::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(i64::SOMEIP_TYPE_REF),
             length_field_size: Some(LengthFieldSize::TwoBytes),
         }),
         TestVec::SOMEIP_TYPE
@@ -105,11 +122,12 @@ fn main() {
             min_elements: 0,
             max_elements: 42,
             length_field_size: None,
-            element_type: &SomeIpType::String(SomeIpString {
+            element_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
                 min_size: 0,
                 max_size: 1337,
-                length_field_size: None
-            },)
+                length_field_size: None,
+                padding: false,
+            },))
         }),
         TestVecString::SOMEIP_TYPE
     );
@@ -119,7 +137,7 @@ fn main() {
             min_elements: 0,
             max_elements: 123,
             length_field_size: None,
-            element_type: &u8::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
         },),
         TestBytes::SOMEIP_TYPE,
     );
@@ -0,0 +1,103 @@
+use serde_someip::SomeIp;
+
+#[derive(SomeIp)]
+struct Vec3(f32, f32, f32);
+
+#[derive(SomeIp)]
+#[someip(length_field_size = 2)]
+struct Test(u32, #[someip(max_size = 42)] String, (u8, i16));
+
+fn main() {
+    use serde_someip::length_fields::LengthFieldSize;
+    use serde_someip::types::*;
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Vec3"),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: None,
+            transformation_properties: None,
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("0"),
+                    id: None,
+                    field_type: SomeIpRef::Static(f32::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("1"),
+                    id: None,
+                    field_type: SomeIpRef::Static(f32::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("2"),
+                    id: None,
+                    field_type: SomeIpRef::Static(f32::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+            ]),
+            extensible: false,
+        }),
+        Vec3::SOMEIP_TYPE
+    );
+
+    assert_eq!(
+        SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: Some(LengthFieldSize::TwoBytes),
+            transformation_properties: None,
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("0"),
+                    id: None,
+                    field_type: SomeIpRef::Static(u32::SOMEIP_TYPE_REF),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("1"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                        min_size: 0,
+                        max_size: 42,
+                        length_field_size: None,
+                        padding: false,
+                    })),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("2"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
+                        name: SomeIpRef::Static("Tuple"),
+                        uses_tlv_serialization: false,
+                        is_message_wrapper: false,
+                        length_field_size: None,
+                        transformation_properties: None,
+                        fields: SomeIpRef::Static(&[
+                            SomeIpField {
+                                name: SomeIpRef::Static("0"),
+                                id: None,
+                                field_type: SomeIpRef::Static(u8::SOMEIP_TYPE_REF),
+                                default: None,
+                            },
+                            SomeIpField {
+                                name: SomeIpRef::Static("1"),
+                                id: None,
+                                field_type: SomeIpRef::Static(i16::SOMEIP_TYPE_REF),
+                                default: None,
+                            },
+                        ]),
+                        extensible: false,
+                    })),
+                    default: None,
+                },
+            ]),
+            extensible: false,
+        }),
+        Test::SOMEIP_TYPE
+    );
+}
@@ -0,0 +1,353 @@
+//! Provides [SomeIpCodec] to frame someip messages on byte streams like TCP connections.
+//!
+//! *Only available with the `tokio` feature.*
+//!
+//! The codec implements [Decoder] and [Encoder] of `tokio_util` so it can be used with `Framed`, `FramedRead`
+//! and `FramedWrite`. Decoding splits the stream at the length field of every header and yields the
+//! [SomeIpHeader] together with the still serialized payload as `Bytes`. Encoding accepts the header together
+//! with either a payload that is serialized with [append_to_bytes](super::append_to_bytes) or an already serialized payload.
+//! ```
+//! use bytes::{Bytes, BytesMut};
+//! use serde_someip::codec::SomeIpCodec;
+//! use serde_someip::header::{MessageType, SomeIpHeader};
+//! use serde_someip::options::ExampleOptions;
+//! use tokio_util::codec::{Decoder, Encoder};
+//!
+//! let mut codec = SomeIpCodec::<ExampleOptions>::default();
+//! let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+//! let mut stream = BytesMut::new();
+//! codec.encode((&header, &42u32), &mut stream).unwrap();
+//! codec.encode((header.clone(), Bytes::from_static(&[1, 2])), &mut stream).unwrap();
+//! assert_eq!(16 + 4 + 16 + 2, stream.len());
+//!
+//! let (decoded, payload) = codec.decode(&mut stream).unwrap().unwrap();
+//! assert_eq!(header, decoded);
+//! assert_eq!(42, serde_someip::from_bytes::<ExampleOptions, u32>(payload).unwrap());
+//! let (_, payload) = codec.decode(&mut stream).unwrap().unwrap();
+//! assert_eq!(&[1, 2], &payload[..]);
+//! assert!(codec.decode(&mut stream).unwrap().is_none());
+//! ```
+
+use super::error::{Error, Result};
+use super::header::{SomeIpHeader, HEADER_LEN};
+use super::length_fields::LengthFieldSize;
+use super::options::SomeIpOptions;
+use super::types::SomeIp;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use serde::Serialize;
+use tokio_util::codec::{Decoder, Encoder};
+
+use std::marker::PhantomData;
+
+/// The magic cookie sent by clients to allow servers to resynchronize on the stream.
+pub const MAGIC_COOKIE_CLIENT: [u8; HEADER_LEN] = [
+    0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x08, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x01, 0x01, 0x00,
+];
+
+/// The magic cookie sent by servers to allow clients to resynchronize on the stream.
+pub const MAGIC_COOKIE_SERVER: [u8; HEADER_LEN] = [
+    0xFF, 0xFF, 0x80, 0x00, 0x00, 0x00, 0x00, 0x08, 0xDE, 0xAD, 0xBE, 0xEF, 0x01, 0x01, 0x02, 0x00,
+];
+
+/// The configuration of [SomeIpCodec].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SomeIpCodecConfig {
+    /// The maximum length of a payload, longer messages are rejected before they are buffered.
+    pub max_payload_len: usize,
+    /// If true invalid headers do not cause an error, instead all bytes up to the next magic cookie are skipped.
+    /// Magic cookies are never returned by the decoder in this mode.
+    pub resynchronize: bool,
+}
+
+impl Default for SomeIpCodecConfig {
+    fn default() -> Self {
+        SomeIpCodecConfig {
+            max_payload_len: 1 << 20,
+            resynchronize: false,
+        }
+    }
+}
+
+/// A codec that frames someip messages, see the [module docs](self).
+///
+/// The `Options` are used to serialize payloads during encoding.
+pub struct SomeIpCodec<Options: SomeIpOptions + ?Sized> {
+    config: SomeIpCodecConfig,
+    options: PhantomData<Options>,
+}
+
+impl<Options: SomeIpOptions + ?Sized> SomeIpCodec<Options> {
+    /// Creates a new codec.
+    pub fn new(config: SomeIpCodecConfig) -> SomeIpCodec<Options> {
+        SomeIpCodec {
+            config,
+            options: PhantomData,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &SomeIpCodecConfig {
+        &self.config
+    }
+
+    fn decode_header(&self, src: &[u8]) -> Result<(SomeIpHeader, usize)> {
+        let (header, payload_len) = SomeIpHeader::decode(src)?;
+        if payload_len > self.config.max_payload_len {
+            return Err(Error::TooLong {
+                actual_length: payload_len,
+                length_field_size: LengthFieldSize::FourBytes,
+            });
+        }
+        Ok((header, payload_len))
+    }
+}
+
+impl<Options: SomeIpOptions + ?Sized> Default for SomeIpCodec<Options> {
+    fn default() -> Self {
+        SomeIpCodec::new(SomeIpCodecConfig::default())
+    }
+}
+
+#[inline]
+fn is_magic_cookie(data: &[u8]) -> bool {
+    data == MAGIC_COOKIE_CLIENT || data == MAGIC_COOKIE_SERVER
+}
+
+impl<Options: SomeIpOptions + ?Sized> Decoder for SomeIpCodec<Options> {
+    type Item = (SomeIpHeader, Bytes);
+    type Error = Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>> {
+        loop {
+            if src.len() < HEADER_LEN {
+                return Ok(None);
+            }
+            let (header, payload_len) = match self.decode_header(src) {
+                Ok(v) => v,
+                Err(_) if self.config.resynchronize => {
+                    //a magic cookie may start in the last bytes so they must be kept
+                    let skip = src
+                        .windows(HEADER_LEN)
+                        .skip(1)
+                        .position(is_magic_cookie)
+                        .map(|p| p + 1)
+                        .unwrap_or(src.len() + 1 - HEADER_LEN);
+                    src.advance(skip);
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            let len = HEADER_LEN + payload_len;
+            if src.len() < len {
+                src.reserve(len - src.len());
+                return Ok(None);
+            }
+            let mut message = src.split_to(len);
+            if self.config.resynchronize && is_magic_cookie(&message) {
+                continue;
+            }
+            message.advance(HEADER_LEN);
+            return Ok(Some((header, message.freeze())));
+        }
+    }
+}
+
+impl<Options: SomeIpOptions + ?Sized> Encoder<(SomeIpHeader, Bytes)> for SomeIpCodec<Options> {
+    type Error = Error;
+
+    fn encode(&mut self, item: (SomeIpHeader, Bytes), dst: &mut BytesMut) -> Result<()> {
+        let (header, payload) = item;
+        dst.reserve(HEADER_LEN + payload.len());
+        dst.put_slice(&header.encode(payload.len())?);
+        dst.put_slice(&payload);
+        Ok(())
+    }
+}
+
+impl<'a, Options, T> Encoder<(&'a SomeIpHeader, &'a T)> for SomeIpCodec<Options>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Serialize + SomeIp,
+{
+    type Error = Error;
+
+    fn encode(&mut self, item: (&'a SomeIpHeader, &'a T), dst: &mut BytesMut) -> Result<()> {
+        let (header, payload) = item;
+        let start = dst.len();
+        dst.put_bytes(0, HEADER_LEN);
+        let encoded = super::append_to_bytes::<Options, _>(payload, dst)
+            .and_then(|_| header.encode(dst.len() - start - HEADER_LEN));
+        match encoded {
+            Ok(encoded) => {
+                dst[start..start + HEADER_LEN].copy_from_slice(&encoded);
+                Ok(())
+            }
+            Err(e) => {
+                dst.truncate(start);
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+use super::header::MessageType;
+#[cfg(test)]
+use super::options::ExampleOptions;
+
+#[test]
+fn test_decode_partial() {
+    let mut codec = SomeIpCodec::<ExampleOptions>::default();
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut encoded = BytesMut::new();
+    codec.encode((&header, &0x0102u16), &mut encoded).unwrap();
+    codec.encode((&header, &0x0304u16), &mut encoded).unwrap();
+
+    let mut src = BytesMut::new();
+    src.extend_from_slice(&encoded[..10]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    src.extend_from_slice(&encoded[10..17]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    src.extend_from_slice(&encoded[17..]);
+    let (decoded, payload) = codec.decode(&mut src).unwrap().unwrap();
+    assert_eq!(header, decoded);
+    assert_eq!(&[1, 2], &payload[..]);
+    let (_, payload) = codec.decode(&mut src).unwrap().unwrap();
+    assert_eq!(&[3, 4], &payload[..]);
+    assert!(src.is_empty());
+}
+
+#[test]
+fn test_decode_invalid() {
+    let mut codec = SomeIpCodec::<ExampleOptions>::new(SomeIpCodecConfig {
+        max_payload_len: 4,
+        ..SomeIpCodecConfig::default()
+    });
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut src = BytesMut::new();
+    codec
+        .encode((&header, &0x0102_0304_0506u64), &mut src)
+        .unwrap();
+    assert!(matches!(
+        codec.decode(&mut src),
+        Err(Error::TooLong {
+            actual_length: 8,
+            ..
+        })
+    ));
+
+    let mut src = BytesMut::from(&[0xFF; 16][..]);
+    assert!(matches!(
+        codec.decode(&mut src),
+        Err(Error::InvalidMessageType(0xFF))
+    ));
+    //without resynchronization magic cookies are normal messages
+    let mut src = BytesMut::from(&MAGIC_COOKIE_CLIENT[..]);
+    let (header, payload) = codec.decode(&mut src).unwrap().unwrap();
+    assert_eq!(0xFFFF_0000, header.message_id());
+    assert!(payload.is_empty());
+}
+
+#[test]
+fn test_decode_resynchronize() {
+    let mut codec = SomeIpCodec::<ExampleOptions>::new(SomeIpCodecConfig {
+        resynchronize: true,
+        ..SomeIpCodecConfig::default()
+    });
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut src = BytesMut::new();
+    src.extend_from_slice(&[0xFF; 20]);
+    src.extend_from_slice(&MAGIC_COOKIE_SERVER[..10]);
+    assert!(codec.decode(&mut src).unwrap().is_none());
+    //the start of the magic cookie is kept
+    assert_eq!(15, src.len());
+    src.extend_from_slice(&MAGIC_COOKIE_SERVER[10..]);
+    codec.encode((&header, &1u8), &mut src).unwrap();
+    let (decoded, payload) = codec.decode(&mut src).unwrap().unwrap();
+    assert_eq!(header, decoded);
+    assert_eq!(&[1], &payload[..]);
+    assert!(src.is_empty());
+}
+
+#[test]
+fn test_encode_error() {
+    use super::types::{SomeIpString, SomeIpType};
+
+    struct Short(&'static str);
+
+    impl SomeIp for Short {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+            max_size: 2,
+            min_size: 0,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
+        });
+    }
+
+    impl Serialize for Short {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> std::result::Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.0)
+        }
+    }
+
+    let mut codec = SomeIpCodec::<ExampleOptions>::default();
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut dst = BytesMut::from(&[1, 2, 3][..]);
+    assert!(codec
+        .encode((&header, &Short("too long")), &mut dst)
+        .is_err());
+    assert_eq!(&[1, 2, 3], &dst[..]);
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn test_loopback() {
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+    use tokio::net::{TcpListener, TcpStream};
+    use tokio_util::codec::{FramedRead, FramedWrite};
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+
+    let client_header = header.clone();
+    let client = tokio::spawn(async move {
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut framed = FramedWrite::new(stream, SomeIpCodec::<ExampleOptions>::default());
+        for i in 0..3u32 {
+            framed.send((&client_header, &i)).await.unwrap();
+        }
+        let stream = framed.get_mut();
+        stream.write_all(&[0xFF; 7]).await.unwrap();
+        stream.write_all(&MAGIC_COOKIE_CLIENT).await.unwrap();
+        framed
+            .send((client_header, Bytes::from_static(&[0, 0, 0, 3])))
+            .await
+            .unwrap();
+    });
+
+    let (stream, _) = listener.accept().await.unwrap();
+    let mut framed = FramedRead::new(
+        stream,
+        SomeIpCodec::<ExampleOptions>::new(SomeIpCodecConfig {
+            resynchronize: true,
+            ..SomeIpCodecConfig::default()
+        }),
+    );
+    for i in 0..4u32 {
+        let (decoded, payload) = framed.next().await.unwrap().unwrap();
+        assert_eq!(header, decoded);
+        assert_eq!(
+            i,
+            super::from_bytes::<ExampleOptions, u32>(payload).unwrap()
+        );
+    }
+    assert!(framed.next().await.is_none());
+    client.await.unwrap();
+}
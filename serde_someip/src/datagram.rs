@@ -0,0 +1,280 @@
+//! Provides the splitting and batching of multiple someip messages in a single UDP datagram.
+//!
+//! [messages] iterates over the messages of a received datagram without copying their payloads,
+//! [Batcher] packs messages into datagrams that do not exceed a maximum length.
+//! ```
+//! use serde_someip::datagram::{messages, Batcher};
+//! use serde_someip::header::{MessageType, SomeIpHeader};
+//! use serde_someip::options::ExampleOptions;
+//!
+//! let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+//! let mut batcher = Batcher::new(1400);
+//! assert!(batcher.push::<ExampleOptions, _>(&header, &1u32).unwrap().is_none());
+//! assert!(batcher.push::<ExampleOptions, _>(&header, &2u32).unwrap().is_none());
+//! let datagram = batcher.flush().unwrap();
+//! assert_eq!(2 * (16 + 4), datagram.len());
+//!
+//! let mut values = Vec::new();
+//! for message in messages(&datagram) {
+//!     let (decoded, payload) = message.unwrap();
+//!     assert_eq!(header, decoded);
+//!     values.push(serde_someip::from_slice::<ExampleOptions, u32>(payload).unwrap());
+//! }
+//! assert_eq!(vec![1, 2], values);
+//! ```
+
+use super::error::{Error, Result};
+use super::header::{SomeIpHeader, HEADER_LEN};
+use super::length_fields::LengthFieldSize;
+use super::options::SomeIpOptions;
+use super::types::SomeIp;
+
+use serde::Serialize;
+
+/// Returns an iterator over the messages in the datagram.
+#[inline]
+pub fn messages(datagram: &[u8]) -> Messages<'_> {
+    Messages {
+        remaining: datagram,
+    }
+}
+
+/// An iterator over the messages in a datagram yielding the header and the payload of every message.
+///
+/// If the header of a message is invalid or the datagram ends before the payload does an error is
+/// yielded, for example [TooShort](Error::TooShort), and the iteration ends.
+#[derive(Debug, Clone)]
+pub struct Messages<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> Messages<'a> {
+    /// The bytes that were not yet iterated over.
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.remaining
+    }
+}
+
+impl<'a> Iterator for Messages<'a> {
+    type Item = Result<(SomeIpHeader, &'a [u8])>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let result = SomeIpHeader::decode(self.remaining).and_then(|(header, payload_len)| {
+            if self.remaining.len() < HEADER_LEN + payload_len {
+                Err(Error::TooShort)
+            } else {
+                Ok((header, payload_len))
+            }
+        });
+        match result {
+            Ok((header, payload_len)) => {
+                let (message, remaining) = self.remaining.split_at(HEADER_LEN + payload_len);
+                self.remaining = remaining;
+                Some(Ok((header, &message[HEADER_LEN..])))
+            }
+            Err(e) => {
+                self.remaining = &[];
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Packs messages into datagrams of at most `max_len` bytes.
+///
+/// Messages are appended to the current datagram until the next message does not fit anymore,
+/// in which case the current datagram is returned by [push](Batcher::push) and the message starts
+/// a new datagram. Call [flush](Batcher::flush) to get the last datagram.
+#[derive(Debug, Clone)]
+pub struct Batcher {
+    max_len: usize,
+    current: Vec<u8>,
+}
+
+impl Batcher {
+    /// Creates a new batcher for datagrams of at most `max_len` bytes, usually the MTU minus the IP and UDP headers.
+    pub fn new(max_len: usize) -> Batcher {
+        Batcher {
+            max_len,
+            current: Vec::new(),
+        }
+    }
+
+    /// The maximum length of a datagram.
+    #[inline]
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    /// The length of the current datagram.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.current.len()
+    }
+
+    /// Is the current datagram empty?
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.current.is_empty()
+    }
+
+    /// Serializes the message and appends it to the current datagram.
+    ///
+    /// Returns the previous datagram if the message did not fit into it.
+    /// Fails with [TooLong](Error::TooLong) if the message alone does not fit into a datagram,
+    /// in which case nothing is appended.
+    ///
+    /// # Panics
+    /// This function panics if the implementation of the [SomeIp](super::SomeIp) trait
+    /// produces invalid type information or this information is incompatible with the [Serialize](serde::Serialize) implementation.
+    pub fn push<Options, T>(
+        &mut self,
+        header: &SomeIpHeader,
+        payload: &T,
+    ) -> Result<Option<Vec<u8>>>
+    where
+        Options: SomeIpOptions + ?Sized,
+        T: Serialize + SomeIp,
+    {
+        let start = self.current.len();
+        self.current.resize(start + HEADER_LEN, 0);
+        let encoded = super::append_to_vec::<Options, _>(payload, &mut self.current)
+            .and_then(|_| header.encode(self.current.len() - start - HEADER_LEN));
+        match encoded {
+            Ok(encoded) => self.current[start..start + HEADER_LEN].copy_from_slice(&encoded),
+            Err(e) => {
+                self.current.truncate(start);
+                return Err(e);
+            }
+        }
+        self.split_after(start)
+    }
+
+    /// Appends an already serialized message including its header to the current datagram.
+    ///
+    /// Behaves like [push](Batcher::push).
+    pub fn push_message(&mut self, message: &[u8]) -> Result<Option<Vec<u8>>> {
+        let start = self.current.len();
+        self.current.extend_from_slice(message);
+        self.split_after(start)
+    }
+
+    //moves the message starting at `start` into a new datagram if the current one got too long
+    fn split_after(&mut self, start: usize) -> Result<Option<Vec<u8>>> {
+        let message_len = self.current.len() - start;
+        if message_len > self.max_len {
+            self.current.truncate(start);
+            return Err(Error::TooLong {
+                actual_length: message_len,
+                length_field_size: LengthFieldSize::FourBytes,
+            });
+        }
+        if self.current.len() <= self.max_len {
+            return Ok(None);
+        }
+        let message = self.current.split_off(start);
+        Ok(Some(std::mem::replace(&mut self.current, message)))
+    }
+
+    /// Returns the current datagram if it is not empty and starts a new one.
+    pub fn flush(&mut self) -> Option<Vec<u8>> {
+        if self.current.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.current))
+        }
+    }
+}
+
+#[cfg(test)]
+use super::header::{to_message, MessageType};
+#[cfg(test)]
+use super::options::ExampleOptions;
+
+#[test]
+fn test_messages() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut datagram = to_message::<ExampleOptions, _>(&header, &0x0102u16).unwrap();
+    datagram.extend(header.encode(0).unwrap());
+    datagram.extend(to_message::<ExampleOptions, _>(&header, &0x03u8).unwrap());
+
+    let mut iter = messages(&datagram);
+    let (decoded, payload) = iter.next().unwrap().unwrap();
+    assert_eq!(header, decoded);
+    assert_eq!(&[1, 2], payload);
+    assert_eq!(33, iter.remaining().len());
+    let (_, payload) = iter.next().unwrap().unwrap();
+    assert!(payload.is_empty());
+    let (_, payload) = iter.next().unwrap().unwrap();
+    assert_eq!(&[3], payload);
+    assert!(iter.next().is_none());
+    assert_eq!(0, messages(&[]).count());
+}
+
+#[test]
+fn test_messages_truncated() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let mut datagram = to_message::<ExampleOptions, _>(&header, &0x0102u16).unwrap();
+    datagram.extend(to_message::<ExampleOptions, _>(&header, &0x0304u16).unwrap());
+
+    //truncated payload
+    let mut iter = messages(&datagram[..35]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(matches!(iter.next(), Some(Err(Error::TooShort))));
+    assert!(iter.next().is_none());
+
+    //truncated header
+    let mut iter = messages(&datagram[..25]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(matches!(iter.next(), Some(Err(Error::TooShort))));
+    assert!(iter.next().is_none());
+
+    let mut invalid = datagram.clone();
+    invalid[18 + 14] = 0x03;
+    let mut iter = messages(&invalid);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(matches!(
+        iter.next(),
+        Some(Err(Error::InvalidMessageType(3)))
+    ));
+}
+
+#[test]
+fn test_batcher() {
+    let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+    let mut batcher = Batcher::new(50);
+    assert!(batcher.flush().is_none());
+    assert!(batcher
+        .push::<ExampleOptions, _>(&header, &1u32)
+        .unwrap()
+        .is_none());
+    assert!(batcher
+        .push::<ExampleOptions, _>(&header, &2u32)
+        .unwrap()
+        .is_none());
+    assert_eq!(40, batcher.len());
+    //the third message does not fit anymore
+    let datagram = batcher
+        .push::<ExampleOptions, _>(&header, &3u32)
+        .unwrap()
+        .unwrap();
+    assert_eq!(40, datagram.len());
+    assert_eq!(2, messages(&datagram).count());
+    assert_eq!(20, batcher.len());
+
+    let message = to_message::<ExampleOptions, _>(&header, &(4u64, 5u64)).unwrap();
+    assert!(batcher.push_message(&message).unwrap().is_some());
+    assert!(matches!(
+        batcher.push::<ExampleOptions, _>(&header, &(1u64, 2u64, 3u64, 4u64, 5u64)),
+        Err(Error::TooLong {
+            actual_length: 56,
+            ..
+        })
+    ));
+    assert_eq!(message, batcher.flush().unwrap());
+    assert!(batcher.is_empty());
+}
@@ -4,11 +4,12 @@ use super::error::*;
 use super::length_fields::LengthFieldSize;
 use super::options::*;
 use super::types::*;
+use super::unknown_members::{UnknownMember, UnknownMembers};
 use super::wire_type::WireType;
 
 use serde::de::{
-    Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
-    VariantAccess, Visitor,
+    Deserialize, DeserializeOwned, DeserializeSeed, Deserializer, EnumAccess, IntoDeserializer,
+    MapAccess, SeqAccess, VariantAccess, Visitor,
 };
 
 use std::io::Read;
@@ -174,23 +175,27 @@ impl<'de, R: Read> SomeIpReader<'de> for ExtendedReader<R> {
     }
 }
 
-struct SomeIpDeserializer<'de, Options, Reader>
+struct SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
     reader: Reader,
-    next_type: &'static SomeIpType,
+    next_type: &'t SomeIpType,
     next_length_field_size: Option<LengthFieldSize>,
-    next_field_name: &'static str,
+    next_field_name: &'t str,
     is_in_tlv_struct: bool,
     length_delimited_sections: Vec<usize>,
     transformation_props: Option<SomeIpTransforationProperties>,
+    //the encoded unknown members of the current struct, see UnknownMembers
+    unknown_members: Vec<u8>,
+    //the raw value of an enum that was mapped onto the other variant
+    other_enum_value: Option<SomeIpEnumValue>,
     phantom: PhantomData<Options>,
     phantom2: PhantomData<&'de str>,
 }
 
-impl<'de, Options, Reader> SomeIpReader<'de> for SomeIpDeserializer<'de, Options, Reader>
+impl<'de, 't, Options, Reader> SomeIpReader<'de> for SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -230,15 +235,15 @@ where
     }
 }
 
-impl<'de, Options, Reader> SomeIpDeserializer<'de, Options, Reader>
+impl<'de, 't, Options, Reader> SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
     fn new(
         reader: Reader,
-        root_type: &'static SomeIpType,
-    ) -> SomeIpDeserializer<'de, Options, Reader> {
+        root_type: &'t SomeIpType,
+    ) -> SomeIpDeserializer<'de, 't, Options, Reader> {
         SomeIpDeserializer {
             reader,
             next_type: root_type,
@@ -247,6 +252,8 @@ where
             is_in_tlv_struct: false,
             length_delimited_sections: Vec::default(),
             transformation_props: None,
+            unknown_members: Vec::default(),
+            other_enum_value: None,
             phantom: PhantomData,
             phantom2: PhantomData,
         }
@@ -276,19 +283,27 @@ where
         &mut self,
         length_field_size: LengthFieldSize,
     ) -> Result<usize> {
+        let len = self.read_length_field(length_field_size)?;
+        self.begin_known_length_delimited_section(len)
+    }
+
+    #[inline]
+    fn read_length_field(&mut self, length_field_size: LengthFieldSize) -> Result<usize> {
         let size = if let Some(size) = self.next_length_field_size.take() {
             size
         } else {
             length_field_size
         };
+        self.read_field_of_size(size)
+    }
 
-        let len = match size {
+    #[inline]
+    fn read_field_of_size(&mut self, size: LengthFieldSize) -> Result<usize> {
+        Ok(match size {
             LengthFieldSize::OneByte => self.read_u8()? as usize,
             LengthFieldSize::TwoBytes => self.read_ux::<u16>(Options::BYTE_ORDER)? as usize,
             LengthFieldSize::FourBytes => self.read_ux::<u32>(Options::BYTE_ORDER)? as usize,
-        };
-
-        self.begin_known_length_delimited_section(len)
+        })
     }
 
     #[inline]
@@ -304,7 +319,29 @@ where
         }
     }
 
-    fn read_utf8_string(&mut self, len: usize) -> Result<String> {
+    /// Returns the length of `value` without the terminator and any padding.
+    fn string_content_len(value: &str, padding: bool) -> Result<usize> {
+        let content = if padding {
+            value.trim_end_matches('\0')
+        } else {
+            value
+        };
+        if Options::STRING_WITH_TERMINATOR {
+            if padding && content.len() < value.len() {
+                Ok(content.len())
+            } else if !padding && value.ends_with('\0') {
+                Ok(value.len() - 1)
+            } else {
+                Err(Error::CannotCodeString(
+                    "String must end with 0 terminator".into(),
+                ))
+            }
+        } else {
+            Ok(content.len())
+        }
+    }
+
+    fn read_utf8_string(&mut self, len: usize, padding: bool) -> Result<String> {
         let mut len = len;
         if Options::STRING_WITH_BOM {
             let bom = "\u{FEFF}".as_bytes();
@@ -322,15 +359,7 @@ where
         }
 
         let mut value = String::from_utf8(self.read_vec(len)?)?;
-        if Options::STRING_WITH_TERMINATOR {
-            if value.ends_with('\0') {
-                value.pop();
-            } else {
-                return Err(Error::CannotCodeString(
-                    "String must end with 0 terminator".into(),
-                ));
-            }
-        }
+        value.truncate(Self::string_content_len(&value, padding)?);
         if Options::STRING_ENCODING == StringEncoding::Ascii && !value.is_ascii() {
             return Err(Error::CannotCodeString(
                 "String contained non ascii chars".into(),
@@ -339,7 +368,7 @@ where
         Ok(value)
     }
 
-    fn read_utf16_string(&mut self, len: usize) -> Result<String> {
+    fn read_utf16_string(&mut self, len: usize, padding: bool) -> Result<String> {
         if len % 2 != 0 {
             return Err(Error::CannotCodeString(
                 "UTF-16 strings must always have an even byte length".into(),
@@ -421,30 +450,56 @@ where
         char_iter.last_result?;
         decode_result?;
 
-        if Options::STRING_WITH_TERMINATOR {
-            if !string.ends_with('\0') {
-                return Err(Error::CannotCodeString(
-                    "String must end with 0 terminator".into(),
-                ));
+        string.truncate(Self::string_content_len(&string, padding)?);
+        Ok(string)
+    }
+
+    fn deserialize_struct_with<V: Visitor<'de>>(
+        &mut self,
+        fields: Option<&'static [&'static str]>,
+        visitor: V,
+    ) -> Result<V::Value> {
+        if let SomeIpType::Struct(s) = self.next_type {
+            let is_tlv = s.uses_tlv_serialization;
+            if is_tlv && fields.is_none() {
+                panic!(
+                    "Struct {} uses tlv which is not supported for tuples and tuple structs",
+                    s.name
+                );
             }
-            string.pop();
+            let mut access = SomeIpStructAccess::begin(self, s, self.next_type, fields)?;
+            let result = if is_tlv {
+                visitor.visit_map(&mut access)?
+            } else {
+                visitor.visit_seq(&mut access)?
+            };
+            access.end()?;
+            Ok(result)
+        } else {
+            panic!("Expeceted a struct but found {}", self.next_type)
         }
-        Ok(string)
     }
 
-    fn read_next_tag(&mut self) -> Result<bool> {
+    //If `unknown_members` is given members with unknown ids are collected instead of being discarded
+    fn read_next_tag(&mut self, unknown_members: Option<&mut Vec<UnknownMember>>) -> Result<bool> {
         if let SomeIpType::Struct(s) = self.next_type {
-            let (wire_type, id) = WireType::disect_tag(u16::deserialize(&mut *self)?);
+            let tag = u16::deserialize(&mut *self)?;
+            let (wire_type, id) = WireType::disect_tag(tag);
             let field = s.field_by_id(id);
             if let Some(field) = field {
                 field.field_type.get_wire_type().check(wire_type)?;
                 self.next_length_field_size = wire_type.get_length_field_size();
-                self.next_type = field.field_type;
-                self.next_field_name = field.name;
+                self.next_type = &field.field_type;
+                self.next_field_name = &field.name;
                 Ok(true)
             } else {
                 if let Some(len) = wire_type.get_fixed_size() {
-                    self.discard(len)?;
+                    if let Some(unknown_members) = unknown_members {
+                        let data = self.read_vec(len)?;
+                        unknown_members.push(UnknownMember { tag, data });
+                    } else {
+                        self.discard(len)?;
+                    }
                 } else {
                     self.next_length_field_size = wire_type.get_length_field_size();
                     let lfsize =
@@ -454,9 +509,17 @@ where
                             id, s.name
                         )
                         });
-                    let len = self.begin_length_delimited_section(lfsize)?;
-                    self.discard(len)?;
-                    self.end_length_delimited_section()?;
+                    if let Some(unknown_members) = unknown_members {
+                        let size = self.next_length_field_size.take().unwrap_or(lfsize);
+                        let len = self.read_field_of_size(size)?;
+                        let mut data = encode_length_field(size, len, Options::BYTE_ORDER);
+                        data.extend_from_slice(&self.read_vec(len)?);
+                        unknown_members.push(UnknownMember { tag, data });
+                    } else {
+                        let len = self.begin_length_delimited_section(lfsize)?;
+                        self.discard(len)?;
+                        self.end_length_delimited_section()?;
+                    }
                 }
                 Ok(false)
             }
@@ -466,7 +529,7 @@ where
     }
 }
 
-impl<'de, Options, Reader> EnumAccess<'de> for &mut SomeIpDeserializer<'de, Options, Reader>
+impl<'de, 't, Options, Reader> EnumAccess<'de> for &mut SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -482,7 +545,8 @@ where
     }
 }
 
-impl<'de, Options, Reader> VariantAccess<'de> for &mut SomeIpDeserializer<'de, Options, Reader>
+impl<'de, 't, Options, Reader> VariantAccess<'de>
+    for &mut SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -491,13 +555,17 @@ where
 
     #[inline]
     fn unit_variant(self) -> Result<()> {
+        self.other_enum_value = None;
         Ok(())
     }
 
-    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value>
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
     where
         T: DeserializeSeed<'de>,
     {
+        if let Some(value) = self.other_enum_value.take() {
+            return deserialize_enum_value(seed, value);
+        }
         unimplemented!(
             "Newtype variants (e.g.: enum Foo {{ Bar(u32) }} ) are not supported by someip"
         )
@@ -520,25 +588,192 @@ where
     }
 }
 
-struct SomeIpSeqAccess<'de: 'a, 'a, Options, Reader>
+struct SomeIpBitfieldAccess<'t> {
+    bitfield_type: &'t SomeIpBitfield,
+    value: u64,
+    fields: &'static [&'static str],
+    field_index: usize,
+}
+
+impl<'de, 't> SeqAccess<'de> for SomeIpBitfieldAccess<'t> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if let Some(flag) = self.fields.get(self.field_index) {
+            self.field_index += 1;
+            let bit = self.bitfield_type.flag_to_bit(flag).unwrap_or_else(|| {
+                panic!("Bitfield {} has no flag {}", self.bitfield_type.name, flag)
+            });
+            let is_set = self.value & 1 << bit != 0;
+            seed.deserialize(is_set.into_deserializer()).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.fields.len() - self.field_index)
+    }
+}
+
+struct SomeIpUnionAccess<'de: 'a, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+    union_type: &'t SomeIpUnion,
+    variant_type: Option<&'t SomeIpType>,
+}
+
+impl<'de: 'a, 'a, 't, Options, Reader> SomeIpUnionAccess<'de, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    fn new(
+        deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+        union_type: &'t SomeIpUnion,
+    ) -> SomeIpUnionAccess<'de, 'a, 't, Options, Reader> {
+        SomeIpUnionAccess {
+            deserializer,
+            union_type,
+            variant_type: None,
+        }
+    }
+
+    #[inline]
+    fn payload_type(&self) -> &'t SomeIpType {
+        self.variant_type.unwrap_or_else(|| {
+            panic!(
+                "Variant of union {} carries no data, but data was requested",
+                self.union_type.name
+            )
+        })
+    }
+
+    #[inline]
+    fn end(self) -> Result<()> {
+        //the union may contain padding after the data
+        let padding = self.deserializer.remaining();
+        self.deserializer.discard(padding)?;
+        self.deserializer.end_length_delimited_section()
+    }
+}
+
+impl<'de: 'a, 'a, 't, Options, Reader> EnumAccess<'de>
+    for SomeIpUnionAccess<'de, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let size = self
+            .union_type
+            .wanted_length_field::<Options>(
+                self.deserializer.is_in_tlv_struct,
+                &self.deserializer.transformation_props,
+            )?
+            .unwrap();
+        let mut len = self.deserializer.read_length_field(size)?;
+        let type_field_size = self.union_type.get_type_field_size::<Options>();
+        let selector = self.deserializer.read_field_of_size(type_field_size)? as u32;
+        if self.deserializer.is_in_tlv_struct {
+            //in tlv structs the length also covers the type field
+            len = len
+                .checked_sub(usize::from(type_field_size))
+                .ok_or(Error::TooShort)?;
+        }
+        self.deserializer
+            .begin_known_length_delimited_section(len)?;
+        self.deserializer.is_in_tlv_struct = false;
+
+        let variant = self
+            .union_type
+            .variant_by_selector(selector)
+            .ok_or_else(|| Error::InvalidEnumValue {
+                value: selector.to_string(),
+                name: self.union_type.name.to_string(),
+            })?;
+        self.variant_type = variant.variant_type.as_deref();
+        self.deserializer.next_field_name = &variant.name;
+        Ok((seed.deserialize(&mut *self.deserializer)?, self))
+    }
+}
+
+impl<'de: 'a, 'a, 't, Options, Reader> VariantAccess<'de>
+    for SomeIpUnionAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
-    deserializer: &'a mut SomeIpDeserializer<'de, Options, Reader>,
-    sequence_type: &'static SomeIpSequence,
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        self.end()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.deserializer.next_type = self.payload_type();
+        let value = seed.deserialize(&mut *self.deserializer)?;
+        self.end()?;
+        Ok(value)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserializer.next_type = self.payload_type();
+        let value = self.deserializer.deserialize_struct_with(None, visitor)?;
+        self.end()?;
+        Ok(value)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserializer.next_type = self.payload_type();
+        let value = self
+            .deserializer
+            .deserialize_struct_with(Some(fields), visitor)?;
+        self.end()?;
+        Ok(value)
+    }
+}
+
+struct SomeIpSeqAccess<'de: 'a, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+    sequence_type: &'t SomeIpSequence,
     element_count: usize,
 }
 
-impl<'de: 'a, 'a, Options, Reader> SomeIpSeqAccess<'de, 'a, Options, Reader>
+impl<'de: 'a, 'a, 't, Options, Reader> SomeIpSeqAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
     fn new(
-        deserializer: &'a mut SomeIpDeserializer<'de, Options, Reader>,
-        sequence_type: &'static SomeIpSequence,
-    ) -> SomeIpSeqAccess<'de, 'a, Options, Reader> {
+        deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+        sequence_type: &'t SomeIpSequence,
+    ) -> SomeIpSeqAccess<'de, 'a, 't, Options, Reader> {
         SomeIpSeqAccess {
             deserializer,
             sequence_type,
@@ -547,7 +782,8 @@ where
     }
 }
 
-impl<'de: 'a, 'a, Options, Reader> SeqAccess<'de> for SomeIpSeqAccess<'de, 'a, Options, Reader>
+impl<'de: 'a, 'a, 't, Options, Reader> SeqAccess<'de>
+    for SomeIpSeqAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -556,7 +792,7 @@ where
 
     fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>> {
         if self.deserializer.remaining() > 0 {
-            self.deserializer.next_type = self.sequence_type.element_type;
+            self.deserializer.next_type = &self.sequence_type.element_type;
             self.deserializer.is_in_tlv_struct = false;
             let element = seed.deserialize(&mut *self.deserializer)?;
             self.element_count += 1;
@@ -602,32 +838,170 @@ where
     }
 }
 
-struct SomeIpStructAccess<'de: 'a, 'a, Options, Reader>
+struct SomeIpMapAccess<'de: 'a, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
-    deserializer: &'a mut SomeIpDeserializer<'de, Options, Reader>,
-    struct_type: &'static SomeIpStruct,
-    someip_type: &'static SomeIpType,
-    fields: &'static [&'static str],
+    deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+    map_type: &'t SomeIpMap,
+    element_count: usize,
+}
+
+impl<'de: 'a, 'a, 't, Options, Reader> SomeIpMapAccess<'de, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    fn new(
+        deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+        map_type: &'t SomeIpMap,
+    ) -> SomeIpMapAccess<'de, 'a, 't, Options, Reader> {
+        SomeIpMapAccess {
+            deserializer,
+            map_type,
+            element_count: 0,
+        }
+    }
+}
+
+impl<'de: 'a, 'a, 't, Options, Reader> MapAccess<'de>
+    for SomeIpMapAccess<'de, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>> {
+        if self.deserializer.remaining() > 0 {
+            if self.element_count == self.map_type.max_elements {
+                match Options::DESERIALIZER_ACTION_ON_TOO_MUCH_DATA {
+                    ActionOnTooMuchData::Fail => {
+                        return Err(Error::TooMuchData {
+                            max: self.map_type.max_elements,
+                            actual: 0,
+                        })
+                    }
+                    ActionOnTooMuchData::Discard => {
+                        self.deserializer.discard(self.deserializer.remaining())?;
+                        return Ok(None);
+                    }
+                    ActionOnTooMuchData::Keep => {}
+                }
+            }
+            self.deserializer.next_type = &self.map_type.key_type;
+            self.deserializer.is_in_tlv_struct = false;
+            let key = seed.deserialize(&mut *self.deserializer)?;
+            self.element_count += 1;
+            Ok(Some(key))
+        } else if self.element_count < self.map_type.min_elements {
+            Err(Error::NotEnoughData {
+                min: self.map_type.min_elements,
+                actual: self.element_count,
+            })
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value> {
+        self.deserializer.next_type = &self.map_type.value_type;
+        self.deserializer.is_in_tlv_struct = false;
+        seed.deserialize(&mut *self.deserializer)
+    }
+}
+
+//Encodes a length field exactly as it was read, so that unknown members can be written back verbatim
+fn encode_length_field(size: LengthFieldSize, len: usize, byte_order: ByteOrder) -> Vec<u8> {
+    let bytes = match size {
+        LengthFieldSize::OneByte => 1,
+        LengthFieldSize::TwoBytes => 2,
+        LengthFieldSize::FourBytes => 4,
+    };
+    match byte_order {
+        ByteOrder::BigEndian => (len as u32).to_be_bytes()[4 - bytes..].to_vec(),
+        ByteOrder::LittleEndian => (len as u32).to_le_bytes()[..bytes].to_vec(),
+    }
+}
+
+#[inline]
+fn deserialize_default<'de, S: DeserializeSeed<'de>>(
+    seed: S,
+    default: SomeIpDefault,
+) -> Result<S::Value> {
+    match default {
+        SomeIpDefault::Bool(v) => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v)),
+        SomeIpDefault::Unsigned(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpDefault::Signed(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+    }
+}
+
+#[inline]
+fn deserialize_enum_value<'de, S: DeserializeSeed<'de>>(
+    seed: S,
+    value: SomeIpEnumValue,
+) -> Result<S::Value> {
+    match value {
+        SomeIpEnumValue::U8(v) => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v)),
+        SomeIpEnumValue::U16(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpEnumValue::U32(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpEnumValue::U64(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpEnumValue::I8(v) => seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v)),
+        SomeIpEnumValue::I16(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpEnumValue::I32(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+        SomeIpEnumValue::I64(v) => {
+            seed.deserialize(IntoDeserializer::<Error>::into_deserializer(v))
+        }
+    }
+}
+
+struct SomeIpStructAccess<'de: 'a, 'a, 't, Options, Reader>
+where
+    Options: SomeIpOptions + ?Sized,
+    Reader: SomeIpReader<'de>,
+{
+    deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+    struct_type: &'t SomeIpStruct,
+    someip_type: &'t SomeIpType,
+    //None if the fields are accessed by index e.g. for tuples
+    fields: Option<&'static [&'static str]>,
     in_section: bool,
     was_in_tlv: bool,
     field_index: usize,
     original_transformation_props: Option<SomeIpTransforationProperties>,
+    alignment: Option<Alignment>,
+    //the bytes remaining in the reader at the beginning of the struct, used to calculate padding
+    begin: usize,
+    //Some if the struct collects unknown members
+    unknown_members: Option<Vec<UnknownMember>>,
 }
 
-impl<'de: 'a, 'a, Options, Reader> SomeIpStructAccess<'de, 'a, Options, Reader>
+impl<'de: 'a, 'a, 't, Options, Reader> SomeIpStructAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
 {
     #[inline]
     fn begin(
-        deserializer: &'a mut SomeIpDeserializer<'de, Options, Reader>,
-        struct_type: &'static SomeIpStruct,
-        someip_type: &'static SomeIpType,
-        fields: &'static [&'static str],
+        deserializer: &'a mut SomeIpDeserializer<'de, 't, Options, Reader>,
+        struct_type: &'t SomeIpStruct,
+        someip_type: &'t SomeIpType,
+        fields: Option<&'static [&'static str]>,
     ) -> Result<Self> {
         let original_transformation_props = if struct_type.transformation_properties.is_some() {
             std::mem::replace(
@@ -647,6 +1021,9 @@ where
             false
         };
         let was_in_tlv = deserializer.is_in_tlv_struct;
+        let alignment = deserializer.transformation_props.get_alignment();
+        let begin = deserializer.reader.remaining();
+        let unknown_members = struct_type.unknown_members_field().map(|_| Vec::default());
         Ok(SomeIpStructAccess {
             deserializer,
             struct_type,
@@ -656,11 +1033,67 @@ where
             was_in_tlv,
             field_index: 0,
             original_transformation_props,
+            alignment,
+            begin,
+            unknown_members,
         })
     }
 
+    #[inline]
+    fn field_count(&self) -> usize {
+        if let Some(fields) = self.fields {
+            fields.len()
+        } else {
+            self.struct_type.field_count()
+        }
+    }
+
+    fn next_field(&mut self) -> &'t SomeIpField {
+        let field = if let Some(fields) = self.fields {
+            if self.field_index >= fields.len() {
+                panic!(
+                    "Cannot deserialize more fields than are known to someip in struct {}",
+                    self.struct_type.name
+                )
+            }
+            self.struct_type
+                .field_by_name(fields[self.field_index])
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Struct {} has no field {}",
+                        self.struct_type.name, fields[self.field_index]
+                    )
+                })
+        } else {
+            self.struct_type
+                .fields
+                .get(self.field_index)
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Cannot deserialize more fields than are known to someip in struct {}",
+                        self.struct_type.name
+                    )
+                })
+        };
+        self.field_index += 1;
+        field
+    }
+
+    #[inline]
+    fn skip_padding(&mut self) -> Result<()> {
+        if let Some(alignment) = self.alignment {
+            let offset = self.begin - self.deserializer.reader.remaining();
+            self.deserializer.discard(alignment.padding_for(offset))?;
+        }
+        Ok(())
+    }
+
     #[inline]
     fn end(self) -> Result<()> {
+        if self.struct_type.extensible {
+            //the sender uses a newer version of this struct so skip the unknown members
+            self.deserializer.discard(self.deserializer.remaining())?;
+        }
         if self.in_section {
             self.deserializer.end_length_delimited_section()?;
         }
@@ -672,8 +1105,8 @@ where
     }
 }
 
-impl<'de: 'a, 'a, Options, Reader> SeqAccess<'de>
-    for &mut SomeIpStructAccess<'de, 'a, Options, Reader>
+impl<'de: 'a, 'a, 't, Options, Reader> SeqAccess<'de>
+    for &mut SomeIpStructAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -681,34 +1114,28 @@ where
     type Error = Error;
 
     fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>> {
-        if self.in_section && self.deserializer.remaining() == 0 {
+        let is_extensible = self.struct_type.extensible;
+        if (self.in_section || is_extensible) && self.deserializer.remaining() == 0 {
+            if is_extensible && self.field_index < self.field_count() {
+                //the sender uses an older version of this struct so use the default if there is one
+                if let Some(default) = self.next_field().default {
+                    return Ok(Some(deserialize_default(seed, default)?));
+                }
+            }
             Ok(None)
         } else {
-            if self.field_index >= self.fields.len() {
-                panic!(
-                    "Cannot deserialize more fields than are known to someip in struct {}",
-                    self.struct_type.name
-                )
-            }
-            let field = self
-                .struct_type
-                .field_by_name(self.fields[self.field_index])
-                .unwrap_or_else(|| {
-                    panic!(
-                        "Struct {} has no field {}",
-                        self.struct_type.name, self.fields[self.field_index]
-                    )
-                });
-            self.field_index += 1;
-            self.deserializer.next_type = field.field_type;
+            let field = self.next_field();
+            self.deserializer.next_type = &field.field_type;
             self.deserializer.is_in_tlv_struct = false;
-            Ok(Some(seed.deserialize(&mut *self.deserializer)?))
+            let value = seed.deserialize(&mut *self.deserializer)?;
+            self.skip_padding()?;
+            Ok(Some(value))
         }
     }
 }
 
-impl<'de: 'a, 'a, Options, Reader> MapAccess<'de>
-    for &mut SomeIpStructAccess<'de, 'a, Options, Reader>
+impl<'de: 'a, 'a, 't, Options, Reader> MapAccess<'de>
+    for &mut SomeIpStructAccess<'de, 'a, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -718,22 +1145,48 @@ where
     fn next_key_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>> {
         loop {
             if self.deserializer.remaining() == 0 {
-                return Ok(None);
+                return if let Some(members) = self.unknown_members.take() {
+                    //all members have been read so hand the unknown ones to the UnknownMembers field
+                    let field = self.struct_type.unknown_members_field().unwrap();
+                    self.deserializer.unknown_members = UnknownMembers::encode(&members);
+                    self.deserializer.next_type = &field.field_type;
+                    self.deserializer.next_field_name = &field.name;
+                    Ok(Some(seed.deserialize(&mut *self.deserializer)?))
+                } else {
+                    Ok(None)
+                };
             } else {
                 self.deserializer.next_type = self.someip_type;
-                if self.deserializer.read_next_tag()? {
+                if self
+                    .deserializer
+                    .read_next_tag(self.unknown_members.as_mut())?
+                {
                     return Ok(Some(seed.deserialize(&mut *self.deserializer)?));
                 }
+                if let (Some(alignment), Some(member)) = (
+                    self.alignment,
+                    self.unknown_members.as_mut().and_then(|m| m.last_mut()),
+                ) {
+                    //keep the padding so that the member can be written back at an aligned offset
+                    let offset = self.begin - self.deserializer.reader.remaining();
+                    let padding = self.deserializer.read_vec(alignment.padding_for(offset))?;
+                    member.data.extend_from_slice(&padding);
+                } else {
+                    self.skip_padding()?;
+                }
             }
         }
     }
 
     fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value> {
         self.deserializer.is_in_tlv_struct = true;
-        seed.deserialize(&mut *self.deserializer)
+        let value = seed.deserialize(&mut *self.deserializer)?;
+        self.skip_padding()?;
+        Ok(value)
     }
 }
-impl<'de, Options, Reader> Deserializer<'de> for &mut SomeIpDeserializer<'de, Options, Reader>
+impl<'de, 't, Options, Reader> Deserializer<'de>
+    for &mut SomeIpDeserializer<'de, 't, Options, Reader>
 where
     Options: SomeIpOptions + ?Sized,
     Reader: SomeIpReader<'de>,
@@ -860,15 +1313,8 @@ where
                     ));
                 }
             }
-            if Options::STRING_WITH_TERMINATOR {
-                if let Some(v) = value.strip_suffix('\0') {
-                    value = v;
-                } else {
-                    return Err(Error::CannotCodeString(
-                        "String must end with 0 terminator".into(),
-                    ));
-                }
-            }
+            value = &value
+                [..SomeIpDeserializer::<Options, Reader>::string_content_len(value, s.padding)?];
             if Options::STRING_ENCODING == StringEncoding::Ascii && !value.is_ascii() {
                 return Err(Error::CannotCodeString(
                     "String contained non ascii chars".into(),
@@ -905,25 +1351,25 @@ where
                     }
                     ActionOnTooMuchData::Discard => {
                         let val = if Options::STRING_ENCODING.is_utf16_variant() {
-                            self.read_utf16_string(s.max_size)?
+                            self.read_utf16_string(s.max_size, s.padding)?
                         } else {
-                            self.read_utf8_string(s.max_size)?
+                            self.read_utf8_string(s.max_size, s.padding)?
                         };
                         self.discard(len - s.max_size)?;
                         val
                     }
                     ActionOnTooMuchData::Keep => {
                         if Options::STRING_ENCODING.is_utf16_variant() {
-                            self.read_utf16_string(len)?
+                            self.read_utf16_string(len, s.padding)?
                         } else {
-                            self.read_utf8_string(len)?
+                            self.read_utf8_string(len, s.padding)?
                         }
                     }
                 }
             } else if Options::STRING_ENCODING.is_utf16_variant() {
-                self.read_utf16_string(len)?
+                self.read_utf16_string(len, s.padding)?
             } else {
-                self.read_utf8_string(len)?
+                self.read_utf8_string(len, s.padding)?
             };
 
             self.end_length_delimited_section()?;
@@ -935,12 +1381,12 @@ where
     }
 
     fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
-        if !Reader::CAN_BORROW {
+        if !Reader::CAN_BORROW || matches!(self.next_type, SomeIpType::UnknownMembers) {
             return self.deserialize_byte_buf(visitor);
         }
 
         if let SomeIpType::Sequence(s) = self.next_type {
-            if !matches!(s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
+            if !matches!(*s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
                 panic!("Expeceted Primitive(u8) bu found {}", s.element_type)
             }
             let len = if let Some(size) =
@@ -978,8 +1424,11 @@ where
     }
 
     fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if let SomeIpType::UnknownMembers = self.next_type {
+            return visitor.visit_byte_buf(std::mem::take(&mut self.unknown_members));
+        }
         if let SomeIpType::Sequence(s) = self.next_type {
-            if !matches!(s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
+            if !matches!(*s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
                 panic!("Expeceted Primitive(u8) bu found {}", s.element_type)
             }
             let len = if let Some(size) =
@@ -1065,23 +1514,45 @@ where
         }
     }
 
-    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, _visitor: V) -> Result<V::Value> {
-        unimplemented!("Tuples (e.g.: (u32, i64,...) ) are not supported by someip")
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value> {
+        //fixed size arrays are deserialized as tuples by serde
+        if let SomeIpType::Sequence(_) = self.next_type {
+            self.deserialize_seq(visitor)
+        } else {
+            self.deserialize_struct_with(None, visitor)
+        }
     }
 
     fn deserialize_tuple_struct<V: Visitor<'de>>(
         self,
         _name: &'static str,
         _len: usize,
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value> {
-        unimplemented!(
-            "Tuple structs (e.g.: struct Foor(u32, i64, ...) ) are not supported by someip"
-        )
+        self.deserialize_struct_with(None, visitor)
     }
 
-    fn deserialize_map<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
-        unimplemented!("Maps are not supported by someip")
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        if let SomeIpType::Map(m) = self.next_type {
+            if let Some(size) =
+                m.wanted_length_field::<Options>(self.is_in_tlv_struct, &self.transformation_props)?
+            {
+                self.begin_length_delimited_section(size)?;
+            } else {
+                self.begin_known_length_delimited_section(
+                    m.max_elements
+                        * (m.key_type
+                            .max_len::<Options>(false, &self.transformation_props)?
+                            + m.value_type
+                                .max_len::<Options>(false, &self.transformation_props)?),
+                )?;
+            }
+            let result = visitor.visit_map(SomeIpMapAccess::new(self, m))?;
+            self.end_length_delimited_section()?;
+            Ok(result)
+        } else {
+            panic!("Expeceted a map but found {}", self.next_type)
+        }
     }
 
     fn deserialize_struct<V: Visitor<'de>>(
@@ -1090,18 +1561,28 @@ where
         fields: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
-        if let SomeIpType::Struct(s) = self.next_type {
-            let is_tlv = s.uses_tlv_serialization;
-            let mut access = SomeIpStructAccess::begin(self, s, self.next_type, fields)?;
-            let result = if is_tlv {
-                visitor.visit_map(&mut access)?
-            } else {
-                visitor.visit_seq(&mut access)?
+        if let SomeIpType::Bitfield(b) = self.next_type {
+            let value = match b.raw_type {
+                SomeIpPrimitive::U8 => self.read_u8()? as u64,
+                SomeIpPrimitive::U16 => self.read_ux::<u16>(Options::BYTE_ORDER)? as u64,
+                SomeIpPrimitive::U32 => self.read_ux::<u32>(Options::BYTE_ORDER)? as u64,
+                SomeIpPrimitive::U64 => self.read_ux::<u64>(Options::BYTE_ORDER)?,
+                _ => panic!("Unsupported raw type for bitfields: {}", b.raw_type),
             };
-            access.end()?;
-            Ok(result)
+            if Options::DESERIALIZER_STRICT_BITFIELD && value & !b.known_bits() != 0 {
+                return Err(Error::InvalidBitfieldValue {
+                    value,
+                    name: b.name.to_string(),
+                });
+            }
+            visitor.visit_seq(SomeIpBitfieldAccess {
+                bitfield_type: b,
+                value,
+                fields,
+                field_index: 0,
+            })
         } else {
-            panic!("Expeceted a struct but found {}", self.next_type)
+            self.deserialize_struct_with(Some(fields), visitor)
         }
     }
 
@@ -1111,7 +1592,11 @@ where
         _variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value> {
-        visitor.visit_enum(self)
+        if let SomeIpType::Union(u) = self.next_type {
+            visitor.visit_enum(SomeIpUnionAccess::new(self, u))
+        } else {
+            visitor.visit_enum(self)
+        }
     }
 
     fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
@@ -1129,12 +1614,17 @@ where
                 SomeIpPrimitive::I64 => SomeIpEnumValue::I64(i64::deserialize(&mut *self)?),
                 _ => panic!("Unsupported raw type for enums: {}", e.get_raw_type()),
             };
-            let variant = e
-                .value_to_name(&enum_value)
-                .ok_or_else(|| Error::InvalidEnumValue {
+            let variant = if let Some(variant) = e.value_to_name(&enum_value) {
+                variant
+            } else if let Some(other) = &e.other {
+                self.other_enum_value = Some(enum_value);
+                other
+            } else {
+                return Err(Error::InvalidEnumValue {
                     value: enum_value.display_value(),
-                    name: e.name,
-                })?;
+                    name: e.name.to_string(),
+                });
+            };
             visitor.visit_str(variant)
         } else {
             panic!("Expeceted an enum but found {}", self.next_type)
@@ -1151,13 +1641,14 @@ where
 }
 
 #[inline]
-fn from_internal<'de, Options, T, Reader>(
+fn from_internal_seed<'de, 't, Options, S, Reader>(
     reader: Reader,
-    someip_type: &'static SomeIpType,
-) -> Result<T>
+    someip_type: &'t SomeIpType,
+    seed: S,
+) -> Result<S::Value>
 where
     Options: SomeIpOptions + ?Sized,
-    T: Deserialize<'de> + ?Sized,
+    S: DeserializeSeed<'de>,
     Reader: SomeIpReader<'de>,
 {
     #[cfg(debug_assertions)]
@@ -1167,7 +1658,34 @@ where
     }
 
     let mut deserializer = SomeIpDeserializer::<Options, _>::new(reader, someip_type);
-    T::deserialize(&mut deserializer)
+    seed.deserialize(&mut deserializer)
+}
+
+#[inline]
+fn from_internal<'de, 't, Options, T, Reader>(
+    reader: Reader,
+    someip_type: &'t SomeIpType,
+) -> Result<T>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Deserialize<'de>,
+    Reader: SomeIpReader<'de>,
+{
+    from_internal_seed::<Options, _, _>(reader, someip_type, PhantomData::<T>)
+}
+
+//Used to deserialize values whose type is only known at runtime, see SomeIpValue
+#[inline]
+pub(crate) fn from_slice_with_type<'de, 't, Options, S>(
+    data: &'de [u8],
+    someip_type: &'t SomeIpType,
+    seed: S,
+) -> Result<S::Value>
+where
+    Options: SomeIpOptions + ?Sized,
+    S: DeserializeSeed<'de>,
+{
+    from_internal_seed::<Options, _, _>(data, someip_type, seed)
 }
 
 /// Deserialises the value from a [Read].
@@ -1178,7 +1696,7 @@ where
 pub fn from_reader<Options, T, Reader>(reader: Reader, len: usize) -> Result<T>
 where
     Options: SomeIpOptions + ?Sized,
-    T: DeserializeOwned + SomeIp + ?Sized,
+    T: DeserializeOwned + SomeIp,
     Reader: Read,
 {
     from_internal::<Options, T, _>(ExtendedReader::new(reader, len), &T::SOMEIP_TYPE)
@@ -1192,7 +1710,7 @@ where
 pub fn from_slice<'a, Options, T>(data: &'a [u8]) -> Result<T>
 where
     Options: SomeIpOptions + ?Sized,
-    T: Deserialize<'a> + SomeIp + ?Sized,
+    T: Deserialize<'a> + SomeIp,
 {
     from_internal::<Options, T, _>(data, &T::SOMEIP_TYPE)
 }
@@ -1211,20 +1729,20 @@ where
 pub fn from_bytes<Options, T>(data: bytes::Bytes) -> Result<T>
 where
     Options: SomeIpOptions + ?Sized,
-    T: DeserializeOwned + SomeIp + ?Sized,
+    T: DeserializeOwned + SomeIp,
 {
     from_slice::<Options, T>(&data)
 }
 
 #[test]
 fn test_bool() {
-    assert_eq!(false, from_slice::<ExampleOptions, bool>(&[0]).unwrap());
-    assert_eq!(true, from_slice::<ExampleOptions, bool>(&[1]).unwrap());
+    assert!(!from_slice::<ExampleOptions, bool>(&[0]).unwrap());
+    assert!(from_slice::<ExampleOptions, bool>(&[1]).unwrap());
 }
 
 #[test]
 fn test_invalid_bool() {
-    assert_eq!(true, from_slice::<ExampleOptions, bool>(&[42]).unwrap());
+    assert!(from_slice::<ExampleOptions, bool>(&[42]).unwrap());
 
     struct Options;
     impl SomeIpOptions for Options {
@@ -1235,7 +1753,7 @@ fn test_invalid_bool() {
     if let Err(Error::InvalidBool(v)) = res {
         assert_eq!(42, v);
     } else {
-        assert!(false, "Strict bool parsing must return an error here");
+        panic!("Strict bool parsing must return an error here");
     }
 }
 
@@ -1276,43 +1794,43 @@ macro_rules! test_endianess {
 test_endianess!(test_u16, u16,
     42u16 => vec![0, 42],
     0u16 => vec![0, 0],
-    u16::max_value() => vec![0xFF, 0xFF]);
+    u16::MAX => vec![0xFF, 0xFF]);
 
 #[cfg(test)]
 test_endianess!(test_i16, i16,
     42i16 => vec![0, 42],
     0i16 => vec![0, 0],
-    i16::max_value() => vec![0x7F, 0xFF],
+    i16::MAX => vec![0x7F, 0xFF],
     -1i16 => vec![0xFF, 0xFF],
-    i16::min_value() => vec![0x80, 0]);
+    i16::MIN => vec![0x80, 0]);
 
 #[cfg(test)]
 test_endianess!(test_u32, u32,
     42u32 => vec![0, 0, 0, 42],
     0u32 => vec![0; 4],
-    u32::max_value() => vec![0xFF; 4]);
+    u32::MAX => vec![0xFF; 4]);
 
 #[cfg(test)]
 test_endianess!(test_i32, i32,
     42i32 => vec![0, 0, 0, 42],
     0i32 => vec![0; 4],
-    i32::max_value() => vec![0x7F, 0xFF, 0xFF, 0xFF],
+    i32::MAX => vec![0x7F, 0xFF, 0xFF, 0xFF],
     -1i32 => vec![0xFF; 4],
-    i32::min_value() => vec![0x80, 0, 0, 0]);
+    i32::MIN => vec![0x80, 0, 0, 0]);
 
 #[cfg(test)]
 test_endianess!(test_u64, u64,
     42u64 => vec![0, 0, 0, 0, 0, 0, 0, 42],
     0u64 => vec![0; 8],
-    u64::max_value() => vec![0xFF; 8]);
+    u64::MAX => vec![0xFF; 8]);
 
 #[cfg(test)]
 test_endianess!(test_i64, i64,
     42i64 => vec![0, 0, 0, 0, 0, 0, 0, 42],
     0i64 => vec![0; 8],
-    i64::max_value() => vec![0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    i64::MAX => vec![0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
     -1i64 => vec![0xFF; 8],
-    i64::min_value() => vec![0x80, 0, 0, 0, 0, 0, 0, 0]);
+    i64::MIN => vec![0x80, 0, 0, 0, 0, 0, 0, 0]);
 
 #[cfg(test)]
 test_endianess!(test_f32, f32,
@@ -1349,13 +1867,14 @@ fn test_enum() {
 
     impl SomeIp for TestEnum {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
-            name: "TestEnum",
+            name: SomeIpRef::Static("TestEnum"),
             raw_type: SomeIpPrimitive::I16,
-            values: &[
-                ("First", SomeIpEnumValue::I16(0)),
-                ("Second", SomeIpEnumValue::I16(42)),
-                ("Third", SomeIpEnumValue::I16(-1337)),
-            ],
+            values: SomeIpRef::Static(&[
+                (SomeIpRef::Static("First"), SomeIpEnumValue::I16(0)),
+                (SomeIpRef::Static("Second"), SomeIpEnumValue::I16(42)),
+                (SomeIpRef::Static("Third"), SomeIpEnumValue::I16(-1337)),
+            ]),
+            other: None,
         });
     }
 
@@ -1375,13 +1894,66 @@ fn test_enum() {
     );
 }
 
+#[test]
+fn test_enum_other() {
+    #[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+    enum TestEnum {
+        First,
+        Unknown(u16),
+    }
+
+    impl SomeIp for TestEnum {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+            name: SomeIpRef::Static("TestEnum"),
+            raw_type: SomeIpPrimitive::U16,
+            values: SomeIpRef::Static(&[(SomeIpRef::Static("First"), SomeIpEnumValue::U16(1))]),
+            other: Some(SomeIpRef::Static("Unknown")),
+        });
+    }
+
+    #[derive(Debug, PartialEq, Eq, serde::Deserialize)]
+    enum TestUnitEnum {
+        First,
+        Other,
+    }
+
+    impl SomeIp for TestUnitEnum {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+            name: SomeIpRef::Static("TestUnitEnum"),
+            raw_type: SomeIpPrimitive::U8,
+            values: SomeIpRef::Static(&[
+                (SomeIpRef::Static("First"), SomeIpEnumValue::U8(1)),
+                (SomeIpRef::Static("Other"), SomeIpEnumValue::U8(0xFF)),
+            ]),
+            other: Some(SomeIpRef::Static("Other")),
+        });
+    }
+
+    assert_eq!(
+        TestEnum::First,
+        from_slice::<ExampleOptions, TestEnum>(&[0, 1]).unwrap()
+    );
+    assert_eq!(
+        TestEnum::Unknown(0x1234),
+        from_slice::<ExampleOptions, TestEnum>(&[0x12, 0x34]).unwrap()
+    );
+    assert_eq!(
+        TestUnitEnum::First,
+        from_slice::<ExampleOptions, TestUnitEnum>(&[1]).unwrap()
+    );
+    assert_eq!(
+        TestUnitEnum::Other,
+        from_slice::<ExampleOptions, TestUnitEnum>(&[42]).unwrap()
+    );
+}
+
 #[test]
 fn test_bytes() {
     use bytes::{BufMut, Bytes, BytesMut};
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 32,
         min_elements: 0,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(32);
@@ -1413,7 +1985,7 @@ fn test_bytes_no_length_field() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 4,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(4);
@@ -1434,7 +2006,7 @@ fn test_bytes_borrowed() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 32,
         min_elements: 0,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(32);
@@ -1466,7 +2038,7 @@ fn test_bytes_no_length_field_borrowed() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 4,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(4);
@@ -1486,7 +2058,7 @@ fn test_i16_seq() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 0,
-        element_type: &i16::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1506,7 +2078,7 @@ fn test_i16_seq_no_length_field() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 4,
-        element_type: &i16::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1526,12 +2098,12 @@ fn test_2_dimensional_seq() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 0,
-        element_type: &SomeIpType::Sequence(SomeIpSequence {
+        element_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
             max_elements: 3,
             min_elements: 0,
-            element_type: &i16::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
             length_field_size: Some(LengthFieldSize::OneByte),
-        }),
+        })),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1553,6 +2125,7 @@ fn test_str() {
         min_size: 0,
         max_size: 2,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1570,6 +2143,7 @@ fn test_str_ascii() {
         min_size: 0,
         max_size: 2,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1587,6 +2161,7 @@ fn test_str_bom() {
         min_size: 0,
         max_size: 5,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1608,6 +2183,7 @@ fn test_str_terminator() {
         min_size: 0,
         max_size: 3,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1621,6 +2197,7 @@ fn test_string() {
         min_size: 0,
         max_size: 2,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1639,6 +2216,7 @@ fn test_string_ascii() {
         min_size: 0,
         max_size: 2,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1656,6 +2234,7 @@ fn test_string_bom() {
         min_size: 0,
         max_size: 5,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1677,6 +2256,7 @@ fn test_string_terminator() {
         min_size: 0,
         max_size: 3,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     assert_eq!(
         "hi",
@@ -1690,6 +2270,7 @@ fn test_string_utf16() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -1710,6 +2291,7 @@ fn test_string_utf16_bom() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1733,6 +2315,7 @@ fn test_string_utf16_bom_le() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1757,6 +2340,7 @@ fn test_string_utf16_terminator() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1780,6 +2364,7 @@ fn test_string_utf16_bom_detect_swapped_byte_order() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1799,22 +2384,96 @@ fn test_string_utf16_bom_detect_swapped_byte_order() {
 }
 
 #[test]
-fn test_string_utf16_bom_le_detect_swapped_byte_order() {
-    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
-        max_size: 6,
-        min_size: 0,
-        length_field_size: Some(LengthFieldSize::OneByte),
-    });
+fn test_string_utf16_bom_le_detect_swapped_byte_order() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        max_size: 6,
+        min_size: 0,
+        length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
+    });
+    struct Options;
+    impl SomeIpOptions for Options {
+        const STRING_ENCODING: StringEncoding = StringEncoding::Utf16;
+        const STRING_WITH_BOM: bool = true;
+    }
+
+    assert_eq!(
+        "hi",
+        from_internal::<Options, String, _>(
+            vec![6, 0xFF, 0xFE, 0x68, 0, 0x69, 0].as_slice(),
+            &SOMEIP_TYPE
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_string_padding() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        min_size: 5,
+        max_size: 5,
+        length_field_size: None,
+        padding: true,
+    });
+    assert_eq!(
+        "hi",
+        from_internal::<ExampleOptions, &str, _>([0x68, 0x69, 0, 0, 0].as_slice(), &SOMEIP_TYPE)
+            .unwrap()
+    );
+    assert_eq!(
+        "hi",
+        from_internal::<ExampleOptions, String, _>([0x68, 0x69, 0, 0, 0].as_slice(), &SOMEIP_TYPE)
+            .unwrap()
+    );
+    assert_eq!(
+        "hello",
+        from_internal::<ExampleOptions, String, _>(
+            [0x68, 0x65, 0x6C, 0x6C, 0x6F].as_slice(),
+            &SOMEIP_TYPE
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_string_padding_terminator() {
+    struct Options;
+    impl SomeIpOptions for Options {
+        const STRING_WITH_TERMINATOR: bool = true;
+    }
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        min_size: 4,
+        max_size: 4,
+        length_field_size: None,
+        padding: true,
+    });
+    assert_eq!(
+        "hi",
+        from_internal::<Options, &str, _>([0x68, 0x69, 0, 0].as_slice(), &SOMEIP_TYPE).unwrap()
+    );
+    assert!(matches!(
+        from_internal::<Options, String, _>([0x68, 0x65, 0x6C, 0x6C].as_slice(), &SOMEIP_TYPE),
+        Err(Error::CannotCodeString(_))
+    ));
+}
+
+#[test]
+fn test_string_padding_utf16_bom() {
     struct Options;
     impl SomeIpOptions for Options {
         const STRING_ENCODING: StringEncoding = StringEncoding::Utf16;
         const STRING_WITH_BOM: bool = true;
     }
-
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        min_size: 8,
+        max_size: 8,
+        length_field_size: None,
+        padding: true,
+    });
     assert_eq!(
         "hi",
         from_internal::<Options, String, _>(
-            vec![6, 0xFF, 0xFE, 0x68, 0, 0x69, 0].as_slice(),
+            [0xFF, 0xFE, 0x68, 0, 0x69, 0, 0, 0].as_slice(),
             &SOMEIP_TYPE
         )
         .unwrap()
@@ -1831,33 +2490,37 @@ fn test_struct() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: None,
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: None,
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: None,
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: None,
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1869,7 +2532,7 @@ fn test_struct() {
 
     assert_eq!(
         expected,
-        from_slice::<ExampleOptions, Test>(&vec![0xFF, 0xFF, 0, 0, 0, 42, 3, 1, 2, 3]).unwrap()
+        from_slice::<ExampleOptions, Test>(&[0xFF, 0xFF, 0, 0, 0, 42, 3, 1, 2, 3]).unwrap()
     );
 }
 
@@ -1883,33 +2546,37 @@ fn test_struct_beginning_length_field() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: None,
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: None,
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: None,
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1920,8 +2587,7 @@ fn test_struct_beginning_length_field() {
     };
     assert_eq!(
         expected,
-        from_slice::<ExampleOptions, Test>(&vec![0, 9, 0xFF, 0xFF, 0, 0, 0, 42, 2, 1, 2, 3])
-            .unwrap()
+        from_slice::<ExampleOptions, Test>(&[0, 9, 0xFF, 0xFF, 0, 0, 0, 42, 2, 1, 2, 3]).unwrap()
     );
 }
 
@@ -1936,33 +2602,37 @@ fn test_struct_tlv() {
 
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1973,7 +2643,7 @@ fn test_struct_tlv() {
     };
     assert_eq!(
         expected,
-        from_slice::<ExampleOptions, Test>(&vec![
+        from_slice::<ExampleOptions, Test>(&[
             0, 16, 0x10, 0x01, 0xFF, 0xFF, 0x20, 0x02, 0, 0, 0, 42, 0x40, 0x03, 3, 1, 2, 3
         ])
         .unwrap(),
@@ -1990,33 +2660,37 @@ fn test_struct_tlv_message_wrapper() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: true,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -2027,7 +2701,7 @@ fn test_struct_tlv_message_wrapper() {
     };
     assert_eq!(
         expected,
-        from_slice::<ExampleOptions, Test>(&vec![
+        from_slice::<ExampleOptions, Test>(&[
             0x10, 0x01, 0xFF, 0xFF, 0x20, 0x02, 0, 0, 0, 42, 0x40, 0x03, 3, 1, 2, 3
         ])
         .unwrap(),
@@ -2045,33 +2719,37 @@ fn test_struct_tlv_alternate_length_field_size() {
 
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -2082,7 +2760,7 @@ fn test_struct_tlv_alternate_length_field_size() {
     };
     assert_eq!(
         expected,
-        from_slice::<ExampleOptions, Test>(&vec![
+        from_slice::<ExampleOptions, Test>(&[
             0, 17, 0x10, 0x01, 0xFF, 0xFF, 0x20, 0x02, 0, 0, 0, 42, 0x60, 0x03, 0, 3, 1, 2, 3
         ])
         .unwrap(),
@@ -2097,27 +2775,29 @@ fn test_optional() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[SomeIpField {
-                name: "a",
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
                 id: Some(0),
-                field_type: &u32::SOMEIP_TYPE,
-            }],
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            }]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::OneByte),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
     assert_eq!(
         Test { a: Some(42) },
-        from_slice::<ExampleOptions, Test>(&vec![6, 0x20, 0x00, 0, 0, 0, 42]).unwrap()
+        from_slice::<ExampleOptions, Test>(&[6, 0x20, 0x00, 0, 0, 0, 42]).unwrap()
     );
 
     assert_eq!(
         Test { a: None },
-        from_slice::<ExampleOptions, Test>(&vec![0]).unwrap()
+        from_slice::<ExampleOptions, Test>(&[0]).unwrap()
     );
 }
 
@@ -2131,12 +2811,13 @@ fn test_newtype() {
             min_size: 0,
             max_size: 42,
             length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
         });
     }
 
     assert_eq!(
         Test("hi".into()),
-        from_slice::<ExampleOptions, Test>(&vec![2, 0x68, 0x69]).unwrap()
+        from_slice::<ExampleOptions, Test>(&[2, 0x68, 0x69]).unwrap()
     );
 }
 
@@ -2151,33 +2832,37 @@ fn test_reader() {
 
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -2205,16 +2890,18 @@ fn test_unkown_ids() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[SomeIpField {
-                name: "a",
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
                 id: Some(0),
-                field_type: &u32::SOMEIP_TYPE,
-            }],
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            }]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::OneByte),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -2243,25 +2930,29 @@ fn test_struct_in_struct() {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
             is_message_wrapper: false,
             length_field_size: None,
-            name: "Outer",
+            name: SomeIpRef::Static("Outer"),
             uses_tlv_serialization: true,
             transformation_properties: None,
-            fields: &[SomeIpField {
+            fields: SomeIpRef::Static(&[SomeIpField {
                 id: Some(1),
-                name: "inner",
-                field_type: &SomeIpType::Struct(SomeIpStruct {
+                name: SomeIpRef::Static("inner"),
+                field_type: SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
                     is_message_wrapper: false,
                     length_field_size: None,
-                    name: "Inner",
+                    name: SomeIpRef::Static("Inner"),
                     uses_tlv_serialization: true,
                     transformation_properties: None,
-                    fields: &[SomeIpField {
+                    fields: SomeIpRef::Static(&[SomeIpField {
                         id: Some(1),
-                        name: "some_field",
-                        field_type: &u32::SOMEIP_TYPE,
-                    }],
-                }),
-            }],
+                        name: SomeIpRef::Static("some_field"),
+                        field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                        default: None,
+                    }]),
+                    extensible: false,
+                })),
+                default: None,
+            }]),
+            extensible: false,
         });
     }
 
@@ -2286,34 +2977,39 @@ fn test_struct_with_props() {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
             is_message_wrapper: false,
             length_field_size: None,
-            name: "TestStruct",
+            name: SomeIpRef::Static("TestStruct"),
             uses_tlv_serialization: true,
             transformation_properties: Some(SomeIpTransforationProperties {
                 size_of_array_length_field: Some(LengthFieldSize::OneByte),
                 size_of_string_length_field: Some(LengthFieldSize::TwoBytes),
                 size_of_struct_length_field: Some(LengthFieldSize::FourBytes),
+                alignment: None,
             }),
-            fields: &[
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
                     id: Some(1),
-                    name: "a",
-                    field_type: &SomeIpType::String(SomeIpString {
+                    name: SomeIpRef::Static("a"),
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
                         min_size: 0,
                         max_size: 1337,
                         length_field_size: None,
-                    }),
+                        padding: false,
+                    })),
+                    default: None,
                 },
                 SomeIpField {
                     id: Some(2),
-                    name: "b",
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
-                        element_type: &u16::SOMEIP_TYPE,
+                    name: SomeIpRef::Static("b"),
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                        element_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
                         min_elements: 0,
                         max_elements: 42,
                         length_field_size: None,
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
+            extensible: false,
         });
     }
     let expected = TestStruct {
@@ -2334,6 +3030,7 @@ fn test_string_utf16le_ints_be() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -2355,6 +3052,7 @@ fn test_string_utf16le_ints_be_detect_swapped_byte_order() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -2379,6 +3077,7 @@ fn test_string_utf16be_ints_le() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -2400,6 +3099,7 @@ fn test_string_utf16be_ints_le_detect_swapped_byte_order() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -2417,3 +3117,492 @@ fn test_string_utf16be_ints_le_detect_swapped_byte_order() {
         .unwrap()
     );
 }
+
+#[test]
+fn test_union() {
+    use super::ser::TestUnion;
+
+    assert_eq!(
+        TestUnion::Empty,
+        from_slice::<ExampleOptions, TestUnion>(&[0, 0]).unwrap()
+    );
+    assert_eq!(
+        TestUnion::Newtype(42),
+        from_slice::<ExampleOptions, TestUnion>(&[4, 1, 0, 0, 0, 42]).unwrap()
+    );
+    assert_eq!(
+        TestUnion::Struct { a: 1, b: 2 },
+        from_slice::<ExampleOptions, TestUnion>(&[3, 2, 1, 0, 2]).unwrap()
+    );
+    assert_eq!(
+        TestUnion::Tuple(1, 2),
+        from_slice::<ExampleOptions, TestUnion>(&[2, 3, 1, 2]).unwrap()
+    );
+    //unions may be padded
+    assert_eq!(
+        TestUnion::Tuple(1, 2),
+        from_slice::<ExampleOptions, TestUnion>(&[4, 3, 1, 2, 0, 0]).unwrap()
+    );
+}
+
+#[test]
+fn test_union_unknown_selector() {
+    use super::ser::TestUnion;
+
+    let result = from_slice::<ExampleOptions, TestUnion>(&[0, 42]);
+    assert!(matches!(
+        result,
+        Err(Error::InvalidEnumValue { ref name, .. }) if name == "TestUnion"
+    ));
+}
+
+#[test]
+fn test_union_in_tlv_struct() {
+    use super::ser::TestUnion;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Test {
+        a: TestUnion,
+    }
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: Some(1),
+                field_type: SomeIpRef::Static(&TestUnion::SOMEIP_TYPE),
+                default: None,
+            }]),
+            uses_tlv_serialization: true,
+            is_message_wrapper: true,
+            length_field_size: None,
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    assert_eq!(
+        Test {
+            a: TestUnion::Newtype(42)
+        },
+        from_slice::<ExampleOptions, Test>(&[0x60, 1, 0, 5, 1, 0, 0, 0, 42]).unwrap()
+    );
+}
+
+#[test]
+fn test_union_skipped_in_tlv_struct() {
+    use super::ser::TestUnion;
+
+    #[derive(Debug, serde::Serialize)]
+    struct New {
+        a: u8,
+        u: TestUnion,
+        c: u16,
+    }
+    impl SomeIp for New {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("New"),
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("a"),
+                    id: Some(1),
+                    field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("u"),
+                    id: Some(2),
+                    field_type: SomeIpRef::Static(&TestUnion::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("c"),
+                    id: Some(3),
+                    field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                    default: None,
+                },
+            ]),
+            uses_tlv_serialization: true,
+            is_message_wrapper: true,
+            length_field_size: None,
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    //an older receiver only knows a and c and must skip the union using its length
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Old {
+        a: u8,
+        c: u16,
+    }
+    impl SomeIp for Old {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Old"),
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("a"),
+                    id: Some(1),
+                    field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("c"),
+                    id: Some(3),
+                    field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                    default: None,
+                },
+            ]),
+            uses_tlv_serialization: true,
+            is_message_wrapper: true,
+            length_field_size: None,
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    let data = crate::to_vec::<ExampleOptions, _>(&New {
+        a: 1,
+        u: TestUnion::Struct { a: 2, b: 3 },
+        c: 7,
+    })
+    .unwrap();
+    assert_eq!(vec![0, 1, 1, 0x50, 2, 4, 2, 2, 0, 3, 0x10, 3, 0, 7], data);
+    assert_eq!(
+        Old { a: 1, c: 7 },
+        from_slice::<ExampleOptions, Old>(&data).unwrap()
+    );
+}
+
+#[test]
+fn test_bitfield() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Test {
+        a: bool,
+        b: bool,
+        c: bool,
+    }
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Bitfield(SomeIpBitfield {
+            name: SomeIpRef::Static("Test"),
+            flags: SomeIpRef::Static(&[
+                (SomeIpRef::Static("a"), 0),
+                (SomeIpRef::Static("b"), 3),
+                (SomeIpRef::Static("c"), 15),
+            ]),
+            raw_type: SomeIpPrimitive::U16,
+        });
+    }
+
+    let expected = Test {
+        a: true,
+        b: true,
+        c: false,
+    };
+    assert_eq!(
+        expected,
+        from_slice::<ExampleOptions, Test>(&[0x00, 0x09]).unwrap()
+    );
+    assert_eq!(
+        expected,
+        from_slice::<test::LittleEndianOptions, Test>(&[0x09, 0x00]).unwrap()
+    );
+    //unknown bits are ignored by default
+    assert_eq!(
+        expected,
+        from_slice::<ExampleOptions, Test>(&[0x01, 0x09]).unwrap()
+    );
+
+    struct Options;
+    impl SomeIpOptions for Options {
+        const DESERIALIZER_STRICT_BITFIELD: bool = true;
+    }
+
+    assert_eq!(
+        expected,
+        from_slice::<Options, Test>(&[0x00, 0x09]).unwrap()
+    );
+    let res = from_slice::<Options, Test>(&[0x01, 0x09]);
+    if let Err(Error::InvalidBitfieldValue { value, name }) = res {
+        assert_eq!(0x0109, value);
+        assert_eq!("Test", name);
+    } else {
+        panic!("Strict bitfield parsing must return an error here");
+    }
+}
+
+#[test]
+fn test_map() {
+    use std::collections::HashMap;
+
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Map(SomeIpMap {
+        min_elements: 1,
+        max_elements: 2,
+        key_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+        value_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+            min_size: 0,
+            max_size: 10,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
+        })),
+        length_field_size: Some(LengthFieldSize::OneByte),
+    });
+
+    let mut expected = HashMap::new();
+    expected.insert(1u8, String::from("a"));
+    expected.insert(2u8, String::from("bc"));
+    let serialized = [7, 1, 1, 0x61, 2, 2, 0x62, 0x63];
+    assert_eq!(
+        expected,
+        from_internal::<ExampleOptions, HashMap<u8, String>, _>(&serialized[..], &SOMEIP_TYPE)
+            .unwrap()
+    );
+
+    //too much data is discarded by default
+    let serialized = [10, 1, 1, 0x61, 2, 2, 0x62, 0x63, 3, 1, 0x64];
+    assert_eq!(
+        expected,
+        from_internal::<ExampleOptions, HashMap<u8, String>, _>(&serialized[..], &SOMEIP_TYPE)
+            .unwrap()
+    );
+
+    let serialized = [0];
+    assert!(matches!(
+        from_internal::<ExampleOptions, HashMap<u8, String>, _>(&serialized[..], &SOMEIP_TYPE),
+        Err(Error::NotEnoughData { min: 1, actual: 0 })
+    ));
+}
+
+#[test]
+fn test_array() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 3,
+        max_elements: 3,
+        element_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+        length_field_size: None,
+    });
+
+    let serialized = [0, 1, 0, 2, 0, 3];
+    assert_eq!(
+        [1u16, 2, 3],
+        from_internal::<ExampleOptions, [u16; 3], _>(&serialized[..], &SOMEIP_TYPE).unwrap()
+    );
+}
+
+#[test]
+fn test_nested_array() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 3,
+        max_elements: 3,
+        element_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+            min_elements: 3,
+            max_elements: 3,
+            element_type: SomeIpRef::Static(&f32::SOMEIP_TYPE),
+            length_field_size: None,
+        })),
+        length_field_size: None,
+    });
+
+    let expected = [[1f32, 0f32, 0f32], [0f32, 1f32, 0f32], [0f32, 0f32, 1f32]];
+    let mut serialized = Vec::new();
+    for row in &expected {
+        for v in row {
+            serialized.extend_from_slice(&v.to_be_bytes());
+        }
+    }
+    assert_eq!(
+        expected,
+        from_internal::<ExampleOptions, [[f32; 3]; 3], _>(&serialized[..], &SOMEIP_TYPE).unwrap()
+    );
+}
+
+#[test]
+fn test_array_with_length_field() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 2,
+        max_elements: 2,
+        element_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+            min_size: 0,
+            max_size: 10,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
+        })),
+        length_field_size: Some(LengthFieldSize::OneByte),
+    });
+
+    let serialized = [5, 1, 0x61, 2, 0x62, 0x63];
+    assert_eq!(
+        [String::from("a"), String::from("bc")],
+        from_internal::<ExampleOptions, [String; 2], _>(&serialized[..], &SOMEIP_TYPE).unwrap()
+    );
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Test(u8, u16, String);
+
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("0"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("1"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("2"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                        min_size: 0,
+                        max_size: 10,
+                        length_field_size: Some(LengthFieldSize::OneByte),
+                        padding: false,
+                    })),
+                    default: None,
+                },
+            ]),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    assert_eq!(
+        Test(1, 2, "hi".into()),
+        from_slice::<ExampleOptions, Test>(&[6, 1, 0, 2, 2, 0x68, 0x69]).unwrap()
+    );
+}
+
+#[test]
+fn test_tuple() {
+    assert_eq!(
+        (1u8, 2i16, 3u32),
+        from_slice::<ExampleOptions, (u8, i16, u32)>(&[1, 0, 2, 0, 0, 0, 3]).unwrap()
+    );
+    assert_eq!(
+        (1u8, (2u8, 3u8)),
+        from_slice::<ExampleOptions, (u8, (u8, u8))>(&[1, 2, 3]).unwrap()
+    );
+}
+
+#[test]
+fn test_struct_aligned() {
+    use super::ser::TestAligned;
+
+    assert_eq!(
+        TestAligned {
+            a: 1,
+            b: "hi".into(),
+            c: 0x1234,
+        },
+        from_slice::<ExampleOptions, TestAligned>(&[
+            12, 1, 0, 0, 0, 2, 0x68, 0x69, 0, 0x12, 0x34, 0, 0
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_struct_aligned_tlv() {
+    use super::ser::TestAlignedTlv;
+
+    //contains an unknown member with id 4 that must be skipped including its padding
+    assert_eq!(
+        TestAlignedTlv {
+            a: 1,
+            b: None,
+            c: 5,
+        },
+        from_slice::<ExampleOptions, TestAlignedTlv>(&[
+            0, 0, 0, 24, 0, 1, 1, 0, 0, 0, 0, 0, 0, 4, 7, 0, 0, 0, 0, 0, 0x20, 3, 0, 0, 0, 5, 0, 0
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_struct_extensible_missing_members() {
+    use super::ser::TestExtensible;
+
+    assert_eq!(
+        TestExtensible { a: 1, b: 7, c: 0 },
+        from_slice::<ExampleOptions, TestExtensible>(&[0, 0, 0, 2, 0, 1]).unwrap()
+    );
+    assert_eq!(
+        TestExtensible { a: 1, b: 2, c: 0 },
+        from_slice::<ExampleOptions, TestExtensible>(&[0, 0, 0, 3, 0, 1, 2]).unwrap()
+    );
+}
+
+#[test]
+fn test_struct_extensible_unknown_members() {
+    use super::ser::TestExtensible;
+
+    struct Options;
+    impl SomeIpOptions for Options {
+        const DESERIALIZER_ACTION_ON_TOO_MUCH_DATA: ActionOnTooMuchData = ActionOnTooMuchData::Fail;
+    }
+
+    assert_eq!(
+        (TestExtensible { a: 1, b: 2, c: 3 }, 0x42),
+        from_slice::<Options, (TestExtensible, u8)>(&[
+            0, 0, 0, 9, 0, 1, 2, 0, 0, 0, 3, 0xAA, 0xBB, 0x42
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_struct_unknown_members() {
+    use super::ser::TestUnknownMembers;
+    use super::unknown_members::UnknownMember;
+
+    //the unknown members are collected in the order they are received
+    assert_eq!(
+        TestUnknownMembers {
+            a: 1,
+            unknown: UnknownMembers(vec![
+                UnknownMember {
+                    tag: 0x5005,
+                    data: vec![2, 0xAA, 0xBB],
+                },
+                UnknownMember {
+                    tag: 0x2004,
+                    data: vec![0, 0, 0, 5],
+                },
+            ]),
+            c: None,
+        },
+        from_slice::<ExampleOptions, TestUnknownMembers>(&[
+            0, 0, 0, 14, 0x50, 5, 2, 0xAA, 0xBB, 0, 1, 1, 0x20, 4, 0, 0, 0, 5
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_struct_unknown_members_round_trip() {
+    use super::ser::TestUnknownMembers;
+
+    let data = [
+        0, 0, 0, 20, 0, 1, 1, 0x20, 4, 0, 0, 0, 5, 0x60, 6, 0, 3, 0xAA, 0xBB, 0xCC, 0x10, 3, 0, 3,
+    ];
+    let value = from_slice::<ExampleOptions, TestUnknownMembers>(&data).unwrap();
+    assert_eq!(2, value.unknown.0.len());
+    assert_eq!(6, value.unknown.0[1].id());
+    assert_eq!(
+        data.to_vec(),
+        crate::to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
@@ -0,0 +1,212 @@
+//! Provides the end-to-end (E2E) protection of serialized data as defined by the autosar E2E protocol.
+//!
+//! A sender serializes its data, for example with [append_to_vec](super::append_to_vec), and then
+//! calls [protect](E2EProfile::protect) which inserts the E2E header containing a counter and a CRC at the configured offset.
+//! A receiver calls [check](E2EProfile::check) with the received data to get the [E2EStatus] and on success removes
+//! the header with [payload](E2EProfile::payload) before passing the data to [from_slice](super::from_slice).
+//! ```
+//! use serde_someip::e2e::{E2EProfile, E2EStatus, Profile4, Profile4Config};
+//! use serde_someip::options::ExampleOptions;
+//!
+//! let config = Profile4Config::new(0x0A0B0C0D);
+//! let mut sender = Profile4::new(config.clone());
+//! let mut receiver = Profile4::new(config);
+//!
+//! let mut data = Vec::new();
+//! serde_someip::append_to_vec::<ExampleOptions, _>(&(1u32, 2u16), &mut data).unwrap();
+//! sender.protect(&mut data).unwrap();
+//! assert_eq!(6 + 12, data.len());
+//!
+//! assert_eq!(E2EStatus::Ok, receiver.check(&data));
+//! let value: (u32, u16) =
+//!     serde_someip::from_slice::<ExampleOptions, _>(&receiver.payload(&data)).unwrap();
+//! assert_eq!((1, 2), value);
+//!
+//! //receiving the same data again is detected
+//! assert_eq!(E2EStatus::Repeated, receiver.check(&data));
+//! ```
+//!
+//! Message wrappers can be tied to a profile with the `e2e` attribute of the derive macro,
+//! in which case [to_vec] and [from_slice] protect and check the data:
+//! ```
+//! # #[cfg(feature = "derive")]
+//! # {
+//! use serde::{Deserialize, Serialize};
+//! use serde_someip::e2e::E2EProtected;
+//! use serde_someip::options::ExampleOptions;
+//! use serde_someip::SomeIp;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize, SomeIp)]
+//! #[someip(message_wrapper = true, e2e = profile7(data_id = 0x1234))]
+//! struct Event {
+//!     speed: u32,
+//! }
+//!
+//! let mut sender = Event::e2e_profile();
+//! let mut receiver = Event::e2e_profile();
+//! let data =
+//!     serde_someip::e2e::to_vec::<ExampleOptions, _>(&Event { speed: 42 }, &mut sender).unwrap();
+//! assert_eq!(20 + 4, data.len());
+//! let event: Event =
+//!     serde_someip::e2e::from_slice::<ExampleOptions, _>(&data, &mut receiver).unwrap();
+//! assert_eq!(Event { speed: 42 }, event);
+//!
+//! //the plain functions ignore the attribute and neither protect nor check the data
+//! let unprotected = serde_someip::to_vec::<ExampleOptions, _>(&Event { speed: 42 }).unwrap();
+//! assert_eq!(4, unprotected.len());
+//! # }
+//! ```
+//! The counters live in the profile instances, so the attribute cannot change the plain
+//! [to_vec](super::to_vec) and [from_slice](super::from_slice), only the functions of this module protect and check the data.
+
+use super::error::{Error, Result};
+use super::options::SomeIpOptions;
+use super::types::SomeIp;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use std::borrow::Cow;
+
+pub mod crc;
+mod profile11;
+mod profile22;
+mod profile4;
+mod profile5;
+mod profile6;
+mod profile7;
+pub mod state_machine;
+
+pub use profile11::{Profile11, Profile11Config, Profile11DataIdMode};
+pub use profile22::{Profile22, Profile22Config};
+pub use profile4::{Profile4, Profile4Config};
+pub use profile5::{Profile5, Profile5Config};
+pub use profile6::{Profile6, Profile6Config};
+pub use profile7::{Profile7, Profile7Config};
+
+/// The result of checking received data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum E2EStatus {
+    /// The data is valid and the counter was incremented by exactly one.
+    Ok,
+    /// The data is valid but some messages were lost, the counter was incremented by at most the configured maximum delta.
+    OkSomeLost,
+    /// The data is valid but the counter did not change, so this data was already received.
+    Repeated,
+    /// The data is valid but too many messages were lost, the counter was incremented by more than the configured maximum delta.
+    WrongSequence,
+    /// The data is corrupted, the CRC, data id or length does not match.
+    Error,
+    /// No data was received, this is never returned by the profiles but can be passed to the
+    /// [E2EStateMachine](state_machine::E2EStateMachine) if no data was received within the expected time.
+    NoNewData,
+}
+
+impl E2EStatus {
+    /// Is the data valid and new?
+    #[inline]
+    pub fn is_ok(&self) -> bool {
+        matches!(self, E2EStatus::Ok | E2EStatus::OkSomeLost)
+    }
+}
+
+/// The common interface of all E2E profiles.
+///
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+pub trait E2EProfile {
+    /// Inserts the header at the configured offset into the data and increments the counter.
+    ///
+    /// Fails if the data including the header does not fit the configured length(s).
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()>;
+
+    /// Checks the received data and updates the counter for the next check.
+    fn check(&mut self, data: &[u8]) -> E2EStatus;
+
+    /// Removes the header from the data.
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]>;
+}
+
+/// Ties a message wrapper to the E2E profile protecting it.
+///
+/// This is usually implemented via the `e2e` attribute of the derive macro.
+pub trait E2EProtected {
+    /// The profile protecting the message wrapper.
+    type Profile: E2EProfile;
+
+    /// Creates a new instance of the profile with the configuration of the message wrapper.
+    fn e2e_profile() -> Self::Profile;
+}
+
+/// Serializes the value to a [Vec<u8>] and protects it with the profile.
+///
+/// # Panics
+/// This function panics if the implementation of the [SomeIp] trait
+/// produces invalid type information or this information is incompatible with the [Serialize] implementation.
+pub fn to_vec<Options, T>(value: &T, profile: &mut T::Profile) -> Result<Vec<u8>>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Serialize + SomeIp + E2EProtected,
+{
+    let mut data = super::to_vec::<Options, _>(value)?;
+    profile.protect(&mut data)?;
+    Ok(data)
+}
+
+/// Checks the data with the profile and deserializes the value from the payload.
+///
+/// Fails with [Error::E2ECheckFailed] if the status is not [ok](E2EStatus::is_ok).
+///
+/// # Panics
+/// This function panics if the implementation of the [SomeIp] trait
+/// produces invalid type information or this information is incompatible with the [Deserialize](serde::Deserialize) implementation.
+pub fn from_slice<Options, T>(data: &[u8], profile: &mut T::Profile) -> Result<T>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: DeserializeOwned + SomeIp + E2EProtected,
+{
+    let status = profile.check(data);
+    if !status.is_ok() {
+        return Err(Error::E2ECheckFailed(status));
+    }
+    super::from_slice::<Options, _>(&profile.payload(data))
+}
+
+//computes the status from the received counter and updates the last counter,
+//counters wrap around to zero when reaching the modulus
+fn check_counter(last: &mut u64, received: u64, modulus: u64, max_delta: u64) -> E2EStatus {
+    let delta = (received + modulus - *last) % modulus;
+    *last = received;
+    match delta {
+        0 => E2EStatus::Repeated,
+        1 => E2EStatus::Ok,
+        delta if delta <= max_delta => E2EStatus::OkSomeLost,
+        _ => E2EStatus::WrongSequence,
+    }
+}
+
+//inserts a zeroed header at the offset
+fn insert_header(data: &mut Vec<u8>, offset: usize, header_len: usize) -> Result<()> {
+    if offset > data.len() {
+        return Err(Error::InvalidE2EData(format!(
+            "The offset {} of the E2E header is behind the end of the data, len={}",
+            offset,
+            data.len()
+        )));
+    }
+    data.splice(offset..offset, std::iter::repeat(0).take(header_len));
+    Ok(())
+}
+
+//removes the header at the offset
+fn remove_header(data: &[u8], offset: usize, header_len: usize) -> Cow<'_, [u8]> {
+    if data.len() < offset + header_len {
+        Cow::Borrowed(&[])
+    } else if offset == 0 {
+        Cow::Borrowed(&data[header_len..])
+    } else {
+        let mut payload = Vec::with_capacity(data.len() - header_len);
+        payload.extend_from_slice(&data[..offset]);
+        payload.extend_from_slice(&data[offset + header_len..]);
+        Cow::Owned(payload)
+    }
+}
@@ -0,0 +1,275 @@
+//! Provides the CRC algorithms used by the E2E profiles.
+//!
+//! All functions take the data to protect and return the final CRC value, the `*_update` variants allow
+//! computing the CRC over data that is not contiguous by passing the result of the previous call as `crc`.
+
+const fn reflected_table32(poly: u32) -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn reflected_table64(poly: u64) -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn table8(poly: u8) -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ poly
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const fn table16(poly: u16) -> [u16; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = (i as u16) << 8;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ poly
+            } else {
+                crc << 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+//0xF4ACFB13 reflected
+const CRC32_P4_TABLE: [u32; 256] = reflected_table32(0xC8DF_352F);
+const CRC16_CCITT_TABLE: [u16; 256] = table16(0x1021);
+//0x42F0E1EBA9EA3693 reflected
+const CRC64_ECMA_TABLE: [u64; 256] = reflected_table64(0xC96C_5795_D787_0F42);
+const CRC8_SAE_J1850_TABLE: [u8; 256] = table8(0x1D);
+const CRC8_H2F_TABLE: [u8; 256] = table8(0x2F);
+
+/// The start value of [crc32_p4_update].
+pub const CRC32_P4_START: u32 = 0;
+
+/// Computes the CRC-32P4 (polynomial `0xF4ACFB13`) of the data.
+#[inline]
+pub fn crc32_p4(data: &[u8]) -> u32 {
+    crc32_p4_update(CRC32_P4_START, data)
+}
+
+/// Continues the CRC-32P4 computation of a previous call with more data.
+pub fn crc32_p4_update(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for byte in data {
+        crc = (crc >> 8) ^ CRC32_P4_TABLE[((crc ^ *byte as u32) & 0xFF) as usize];
+    }
+    !crc
+}
+
+/// The start value of [crc16_ccitt_update].
+pub const CRC16_CCITT_START: u16 = 0xFFFF;
+
+/// Computes the CRC-16 CCITT-FALSE (polynomial `0x1021`) of the data.
+#[inline]
+pub fn crc16_ccitt(data: &[u8]) -> u16 {
+    crc16_ccitt_update(CRC16_CCITT_START, data)
+}
+
+/// Continues the CRC-16 CCITT-FALSE computation of a previous call with more data.
+pub fn crc16_ccitt_update(crc: u16, data: &[u8]) -> u16 {
+    let mut crc = crc;
+    for byte in data {
+        crc = (crc << 8) ^ CRC16_CCITT_TABLE[((crc >> 8) ^ *byte as u16) as usize];
+    }
+    crc
+}
+
+/// The start value of [crc64_ecma_update].
+pub const CRC64_ECMA_START: u64 = 0;
+
+/// Computes the CRC-64 ECMA (polynomial `0x42F0E1EBA9EA3693`) of the data.
+#[inline]
+pub fn crc64_ecma(data: &[u8]) -> u64 {
+    crc64_ecma_update(CRC64_ECMA_START, data)
+}
+
+/// Continues the CRC-64 ECMA computation of a previous call with more data.
+pub fn crc64_ecma_update(crc: u64, data: &[u8]) -> u64 {
+    let mut crc = !crc;
+    for byte in data {
+        crc = (crc >> 8) ^ CRC64_ECMA_TABLE[((crc ^ *byte as u64) & 0xFF) as usize];
+    }
+    !crc
+}
+
+/// The start value of [crc8_sae_j1850_update].
+pub const CRC8_SAE_J1850_START: u8 = 0;
+
+/// Computes the CRC-8 SAE J1850 (polynomial `0x1D`) of the data.
+#[inline]
+pub fn crc8_sae_j1850(data: &[u8]) -> u8 {
+    crc8_sae_j1850_update(CRC8_SAE_J1850_START, data)
+}
+
+/// Continues the CRC-8 SAE J1850 computation of a previous call with more data.
+pub fn crc8_sae_j1850_update(crc: u8, data: &[u8]) -> u8 {
+    let mut crc = !crc;
+    for byte in data {
+        crc = CRC8_SAE_J1850_TABLE[(crc ^ byte) as usize];
+    }
+    !crc
+}
+
+/// The start value of [crc8_h2f_update].
+pub const CRC8_H2F_START: u8 = 0;
+
+/// Computes the CRC-8H2F (polynomial `0x2F`) of the data.
+#[inline]
+pub fn crc8_h2f(data: &[u8]) -> u8 {
+    crc8_h2f_update(CRC8_H2F_START, data)
+}
+
+/// Continues the CRC-8H2F computation of a previous call with more data.
+pub fn crc8_h2f_update(crc: u8, data: &[u8]) -> u8 {
+    let mut crc = !crc;
+    for byte in data {
+        crc = CRC8_H2F_TABLE[(crc ^ byte) as usize];
+    }
+    !crc
+}
+
+//the examples from the autosar specification of the crc routines
+#[cfg(test)]
+const TEST_DATA: [&[u8]; 7] = [
+    &[0x00, 0x00, 0x00, 0x00],
+    &[0xF2, 0x01, 0x83],
+    &[0x0F, 0xAA, 0x00, 0x55],
+    &[0x00, 0xFF, 0x55, 0x11],
+    &[0x33, 0x22, 0x55, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF],
+    &[0x92, 0x6B, 0x55],
+    &[0xFF, 0xFF, 0xFF, 0xFF],
+];
+
+#[test]
+fn test_crc32_p4() {
+    let expected = [
+        0x6FB3_2240,
+        0x4F72_1A25,
+        0x2066_2DF8,
+        0x9BD7_996E,
+        0xA65A_343D,
+        0xEE68_8A78,
+        0xFFFF_FFFF,
+    ];
+    for (data, expected) in TEST_DATA.iter().zip(expected) {
+        assert_eq!(expected, crc32_p4(data));
+    }
+    assert_eq!(0x1697_D06A, crc32_p4(b"123456789"));
+    assert_eq!(
+        crc32_p4(b"123456789"),
+        crc32_p4_update(crc32_p4(b"1234"), b"56789")
+    );
+}
+
+#[test]
+fn test_crc16_ccitt() {
+    let expected = [0x84C0, 0xD374, 0x2023, 0xB8F9, 0xF53F, 0x0745, 0x1D0F];
+    for (data, expected) in TEST_DATA.iter().zip(expected) {
+        assert_eq!(expected, crc16_ccitt(data));
+    }
+    assert_eq!(0x29B1, crc16_ccitt(b"123456789"));
+    assert_eq!(
+        crc16_ccitt(b"123456789"),
+        crc16_ccitt_update(crc16_ccitt(b"1234"), b"56789")
+    );
+}
+
+#[test]
+fn test_crc64_ecma() {
+    let expected = [
+        0xF4A5_8635_1E1B_9F4B,
+        0x319C_2766_8164_F1C6,
+        0x54C5_D0F7_667C_1575,
+        0xA638_22BE_7E07_04E6,
+        0x701E_CEB2_19A8_E5D5,
+        0x5FAA_96A9_B59F_3E4E,
+        0xFFFF_FFFF_0000_0000,
+    ];
+    for (data, expected) in TEST_DATA.iter().zip(expected) {
+        assert_eq!(expected, crc64_ecma(data));
+    }
+    assert_eq!(0x995D_C9BB_DF19_39FA, crc64_ecma(b"123456789"));
+    assert_eq!(
+        crc64_ecma(b"123456789"),
+        crc64_ecma_update(crc64_ecma(b"1234"), b"56789")
+    );
+}
+
+#[test]
+fn test_crc8_sae_j1850() {
+    let expected = [0x59, 0x37, 0x79, 0xB8, 0xCB, 0x8C, 0x74];
+    for (data, expected) in TEST_DATA.iter().zip(expected) {
+        assert_eq!(expected, crc8_sae_j1850(data));
+    }
+    assert_eq!(0x4B, crc8_sae_j1850(b"123456789"));
+    assert_eq!(
+        crc8_sae_j1850(b"123456789"),
+        crc8_sae_j1850_update(crc8_sae_j1850(b"1234"), b"56789")
+    );
+}
+
+#[test]
+fn test_crc8_h2f() {
+    let expected = [0x12, 0xC2, 0xC6, 0x77, 0x11, 0x33, 0x6C];
+    for (data, expected) in TEST_DATA.iter().zip(expected) {
+        assert_eq!(expected, crc8_h2f(data));
+    }
+    assert_eq!(0xDF, crc8_h2f(b"123456789"));
+    assert_eq!(
+        crc8_h2f(b"123456789"),
+        crc8_h2f_update(crc8_h2f(b"1234"), b"56789")
+    );
+}
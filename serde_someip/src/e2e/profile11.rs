@@ -0,0 +1,232 @@
+use super::crc::{crc8_sae_j1850_update, CRC8_SAE_J1850_START};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 2;
+//the counter counts from 0 to 14, 15 is invalid
+const COUNTER_MODULUS: u8 = 15;
+
+/// How the data id of [Profile11] is protected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile11DataIdMode {
+    /// Both bytes of the data id are included in the CRC, nothing is transmitted.
+    Both,
+    /// The low byte of the data id is included in the CRC and
+    /// the low nibble of the high byte is transmitted in the header.
+    Nibble,
+}
+
+/// The configuration of [Profile11].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile11Config {
+    /// The id identifying the protected data, how it is protected depends on the `data_id_mode`.
+    pub data_id: u16,
+    /// How the data id is protected.
+    pub data_id_mode: Profile11DataIdMode,
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The length of the protected data in bytes, including the header.
+    pub data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u8,
+}
+
+impl Profile11Config {
+    /// Creates a config with the header at offset 0, the data id mode [Both](Profile11DataIdMode::Both)
+    /// and allowing one lost message.
+    pub fn new(data_id: u16, data_length: usize) -> Profile11Config {
+        Profile11Config {
+            data_id,
+            data_id_mode: Profile11DataIdMode::Both,
+            offset: 0,
+            data_length,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 11 which protects small data of fixed length with a 4 bit counter and a CRC-8 SAE J1850.
+/// Like in Profile 1 the CRC uses the start value `0x00` and no final XOR.
+///
+/// The 2 byte header consists of the CRC followed by a byte with the counter in the low nibble
+/// and the data id nibble in the high nibble, the data id is prepended to the data when computing the CRC.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile11 {
+    config: Profile11Config,
+    counter: u8,
+    last_counter: u64,
+}
+
+impl Profile11 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile11Config) -> Profile11 {
+        Profile11 {
+            config,
+            counter: 0,
+            last_counter: (COUNTER_MODULUS - 1) as u64,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile11Config {
+        &self.config
+    }
+
+    fn data_id_nibble(&self) -> u8 {
+        match self.config.data_id_mode {
+            Profile11DataIdMode::Both => 0,
+            Profile11DataIdMode::Nibble => ((self.config.data_id >> 8) & 0x0F) as u8,
+        }
+    }
+
+    fn crc(&self, data: &[u8]) -> u8 {
+        let [low, high] = self.config.data_id.to_le_bytes();
+        let high = match self.config.data_id_mode {
+            Profile11DataIdMode::Both => high,
+            Profile11DataIdMode::Nibble => 0,
+        };
+        let offset = self.config.offset;
+        //like profile 1 the crc starts with 0x00 and is not inverted at the end
+        let crc = crc8_sae_j1850_update(!CRC8_SAE_J1850_START, &[low, high]);
+        let crc = crc8_sae_j1850_update(crc, &data[..offset]);
+        !crc8_sae_j1850_update(crc, &data[offset + 1..])
+    }
+}
+
+impl E2EProfile for Profile11 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        if data.len() + HEADER_LEN != self.config.data_length {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is not the configured length {}",
+                data.len() + HEADER_LEN,
+                self.config.data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset + 1] = self.data_id_nibble() << 4 | self.counter;
+        data[offset] = self.crc(data);
+        self.counter = (self.counter + 1) % COUNTER_MODULUS;
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() != self.config.data_length || data.len() < offset + HEADER_LEN {
+            return E2EStatus::Error;
+        }
+        let counter = data[offset + 1] & 0x0F;
+        if counter >= COUNTER_MODULUS
+            || data[offset + 1] >> 4 != self.data_id_nibble()
+            || data[offset] != self.crc(data)
+        {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            counter as u64,
+            COUNTER_MODULUS as u64,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[test]
+fn test_profile11_spec_example() {
+    let config = Profile11Config::new(0x0123, 8);
+    let mut profile = Profile11::new(config.clone());
+    let mut data = vec![0; 6];
+    profile.protect(&mut data).unwrap();
+    assert_eq!(vec![0xCC, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], data);
+    let first = data;
+    let mut second = vec![0; 6];
+    profile.protect(&mut second).unwrap();
+    assert_eq!(vec![0x91, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], second);
+
+    let mut receiver = Profile11::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Ok, receiver.check(&second));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&second));
+}
+
+#[test]
+fn test_profile11_spec_example_nibble() {
+    let config = Profile11Config {
+        data_id_mode: Profile11DataIdMode::Nibble,
+        ..Profile11Config::new(0x0123, 8)
+    };
+    let mut sender = Profile11::new(config.clone());
+    let mut receiver = Profile11::new(config);
+    let mut data = vec![0; 6];
+    sender.protect(&mut data).unwrap();
+    assert_eq!(vec![0x2A, 0x10, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], data);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    let mut data = vec![0; 6];
+    sender.protect(&mut data).unwrap();
+    assert_eq!(vec![0x77, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], data);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+
+    //the nibble is checked
+    let mut other = Profile11::new(Profile11Config {
+        data_id_mode: Profile11DataIdMode::Nibble,
+        ..Profile11Config::new(0x0223, 8)
+    });
+    let mut data = vec![0; 6];
+    other.protect(&mut data).unwrap();
+    assert_eq!(E2EStatus::Error, receiver.check(&data));
+}
+
+#[test]
+fn test_profile11_check() {
+    let mut config = Profile11Config::new(0x4321, 5);
+    config.offset = 1;
+    let mut sender = Profile11::new(config.clone());
+    let mut receiver = Profile11::new(config);
+    let protect = |sender: &mut Profile11| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    //the counter wraps around after 14
+    for _ in 0..14 {
+        protect(&mut sender);
+    }
+    let data = protect(&mut sender);
+    assert_eq!(0x0E, data[2]);
+    //the receiver starts with the last counter 14
+    assert_eq!(E2EStatus::Repeated, receiver.check(&data));
+    let data = protect(&mut sender);
+    assert_eq!(0, data[2]);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[4] ^= 0x80;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..4]));
+
+    //the data id is part of the crc
+    let mut other = Profile11::new(Profile11Config {
+        offset: 1,
+        ..Profile11Config::new(0x4322, 5)
+    });
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
@@ -0,0 +1,189 @@
+use super::crc::{crc8_h2f, crc8_h2f_update};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 2;
+
+/// The configuration of [Profile22].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile22Config {
+    /// The ids identifying the protected data, one for every counter value.
+    /// They are not transmitted but the id of the current counter is included in the CRC.
+    pub data_id_list: [u8; 16],
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The length of the protected data in bytes, including the header.
+    pub data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u8,
+}
+
+impl Profile22Config {
+    /// Creates a config with the header at offset 0, allowing one lost message.
+    pub fn new(data_id_list: [u8; 16], data_length: usize) -> Profile22Config {
+        Profile22Config {
+            data_id_list,
+            offset: 0,
+            data_length,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 22 which protects small data of fixed length with a 4 bit counter and a CRC-8H2F.
+///
+/// The 2 byte header consists of the CRC followed by a byte with the counter in the low nibble,
+/// the data id of the counter is not transmitted but appended to the data when computing the CRC.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile22 {
+    config: Profile22Config,
+    counter: u8,
+    last_counter: u64,
+}
+
+impl Profile22 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile22Config) -> Profile22 {
+        Profile22 {
+            config,
+            counter: 0,
+            last_counter: 0x0F,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile22Config {
+        &self.config
+    }
+
+    fn crc(&self, data: &[u8], counter: u8) -> u8 {
+        let offset = self.config.offset;
+        let crc = crc8_h2f(&data[..offset]);
+        let crc = crc8_h2f_update(crc, &data[offset + 1..]);
+        crc8_h2f_update(crc, &[self.config.data_id_list[counter as usize]])
+    }
+}
+
+impl E2EProfile for Profile22 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        if data.len() + HEADER_LEN != self.config.data_length {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is not the configured length {}",
+                data.len() + HEADER_LEN,
+                self.config.data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset + 1] = self.counter;
+        data[offset] = self.crc(data, self.counter);
+        self.counter = (self.counter + 1) & 0x0F;
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() != self.config.data_length || data.len() < offset + HEADER_LEN {
+            return E2EStatus::Error;
+        }
+        let counter = data[offset + 1] & 0x0F;
+        if data[offset] != self.crc(data, counter) {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            counter as u64,
+            1 << 4,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[cfg(test)]
+const TEST_DATA_ID_LIST: [u8; 16] = [
+    0x10, 0x21, 0x32, 0x43, 0x54, 0x65, 0x76, 0x87, 0x98, 0xA9, 0xBA, 0xCB, 0xDC, 0xED, 0xFE, 0x0F,
+];
+
+#[test]
+fn test_profile22_protect() {
+    //the crc inputs are the published CRC8H2F examples of the autosar crc library specification:
+    //00 00 00 00 => 12 and 0F AA 00 55 => C6
+    let mut data_id_list = [0; 16];
+    data_id_list[15] = 0x55;
+    let config = Profile22Config::new(data_id_list, 4);
+    let mut profile = Profile22::new(config.clone());
+    let mut first = vec![0x00, 0x00];
+    profile.protect(&mut first).unwrap();
+    assert_eq!(vec![0x12, 0x00, 0x00, 0x00], first);
+    for _ in 1..15 {
+        profile.protect(&mut vec![0x00, 0x00]).unwrap();
+    }
+    let mut last = vec![0xAA, 0x00];
+    profile.protect(&mut last).unwrap();
+    assert_eq!(vec![0xC6, 0x0F, 0xAA, 0x00], last);
+
+    let mut receiver = Profile22::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&first));
+    assert_eq!(E2EStatus::WrongSequence, receiver.check(&last));
+}
+
+#[test]
+fn test_profile22_check() {
+    let mut config = Profile22Config::new(TEST_DATA_ID_LIST, 5);
+    config.offset = 1;
+    let mut sender = Profile22::new(config.clone());
+    let mut receiver = Profile22::new(config);
+    let protect = |sender: &mut Profile22| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    //the counter wraps around after 15
+    for _ in 0..15 {
+        protect(&mut sender);
+    }
+    let data = protect(&mut sender);
+    assert_eq!(0x0F, data[2]);
+    //the receiver starts with the last counter 15
+    assert_eq!(E2EStatus::Repeated, receiver.check(&data));
+    let data = protect(&mut sender);
+    assert_eq!(0, data[2]);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[4] ^= 0x80;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..4]));
+
+    //the data id list is part of the crc
+    let mut data_id_list = TEST_DATA_ID_LIST;
+    data_id_list[5] ^= 1;
+    let mut other = Profile22::new(Profile22Config {
+        offset: 1,
+        ..Profile22Config::new(data_id_list, 5)
+    });
+    for _ in 0..5 {
+        protect(&mut other);
+    }
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
@@ -0,0 +1,196 @@
+use super::crc::{crc32_p4, crc32_p4_update};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 12;
+
+/// The configuration of [Profile4].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile4Config {
+    /// The id identifying the protected data, it is transmitted in the header.
+    pub data_id: u32,
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The minimum length of the protected data in bytes, including the header.
+    pub min_data_length: usize,
+    /// The maximum length of the protected data in bytes, including the header.
+    pub max_data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u16,
+}
+
+impl Profile4Config {
+    /// Creates a config with the header at offset 0, allowing any length and one lost message.
+    pub fn new(data_id: u32) -> Profile4Config {
+        Profile4Config {
+            data_id,
+            offset: 0,
+            min_data_length: HEADER_LEN,
+            max_data_length: u16::MAX as usize,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 4 which protects data of variable length with a 16 bit counter, a 32 bit data id and a CRC-32P4.
+///
+/// The 12 byte header consists of the length, counter, data id and CRC, all in big endian.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile4 {
+    config: Profile4Config,
+    counter: u16,
+    last_counter: u64,
+}
+
+impl Profile4 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile4Config) -> Profile4 {
+        Profile4 {
+            config,
+            counter: 0,
+            last_counter: u16::MAX as u64,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile4Config {
+        &self.config
+    }
+
+    fn crc(&self, data: &[u8]) -> u32 {
+        let crc_offset = self.config.offset + 8;
+        crc32_p4_update(crc32_p4(&data[..crc_offset]), &data[crc_offset + 4..])
+    }
+}
+
+impl E2EProfile for Profile4 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        let len = data.len() + HEADER_LEN;
+        if len < self.config.min_data_length
+            || len > self.config.max_data_length
+            || len > u16::MAX as usize
+        {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is outside of {}..={}",
+                len, self.config.min_data_length, self.config.max_data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset..offset + 2].copy_from_slice(&(len as u16).to_be_bytes());
+        data[offset + 2..offset + 4].copy_from_slice(&self.counter.to_be_bytes());
+        data[offset + 4..offset + 8].copy_from_slice(&self.config.data_id.to_be_bytes());
+        let crc = self.crc(data);
+        data[offset + 8..offset + 12].copy_from_slice(&crc.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() < self.config.min_data_length
+            || data.len() > self.config.max_data_length
+            || data.len() < offset + HEADER_LEN
+        {
+            return E2EStatus::Error;
+        }
+        let header = &data[offset..offset + HEADER_LEN];
+        let len = u16::from_be_bytes([header[0], header[1]]);
+        let counter = u16::from_be_bytes([header[2], header[3]]);
+        let data_id = u32::from_be_bytes([header[4], header[5], header[6], header[7]]);
+        let crc = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+        if len as usize != data.len() || data_id != self.config.data_id || crc != self.crc(data) {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            counter as u64,
+            1 << 16,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[test]
+fn test_profile4_spec_example() {
+    let config = Profile4Config::new(0x0A0B_0C0D);
+    let mut profile = Profile4::new(config.clone());
+    let mut data = vec![0; 4];
+    profile.protect(&mut data).unwrap();
+    assert_eq!(
+        vec![
+            0x00, 0x10, 0x00, 0x00, 0x0A, 0x0B, 0x0C, 0x0D, 0x86, 0x2B, 0x05, 0x56, 0x00, 0x00,
+            0x00, 0x00
+        ],
+        data
+    );
+    let first = data;
+    let mut second = vec![0; 4];
+    profile.protect(&mut second).unwrap();
+    assert_eq!(
+        &[0x00, 0x10, 0x00, 0x01, 0x0A, 0x0B, 0x0C, 0x0D],
+        &second[..8]
+    );
+
+    let mut receiver = Profile4::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Ok, receiver.check(&second));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&second));
+}
+
+#[test]
+fn test_profile4_check() {
+    let mut config = Profile4Config::new(0x1234_5678);
+    config.offset = 2;
+    let mut sender = Profile4::new(config.clone());
+    let mut receiver = Profile4::new(config);
+    let protect = |sender: &mut Profile4| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    let data = protect(&mut sender);
+    assert_eq!(15, data.len());
+    assert_eq!(&[1, 2, 0, 15], &data[..4]);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+    protect(&mut sender);
+    protect(&mut sender);
+    assert_eq!(
+        E2EStatus::WrongSequence,
+        receiver.check(&protect(&mut sender))
+    );
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[14] ^= 1;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..14]));
+
+    let mut other = Profile4::new(Profile4Config {
+        offset: 2,
+        ..Profile4Config::new(0x1234_5679)
+    });
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    let mut sender = Profile4::new(Profile4Config {
+        max_data_length: 14,
+        ..Profile4Config::new(0)
+    });
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2, 3]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
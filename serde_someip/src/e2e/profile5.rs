@@ -0,0 +1,173 @@
+use super::crc::{crc16_ccitt, crc16_ccitt_update};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 3;
+
+/// The configuration of [Profile5].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile5Config {
+    /// The id identifying the protected data, it is not transmitted but included in the CRC.
+    pub data_id: u16,
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The length of the protected data in bytes, including the header.
+    pub data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u8,
+}
+
+impl Profile5Config {
+    /// Creates a config with the header at offset 0, allowing one lost message.
+    pub fn new(data_id: u16, data_length: usize) -> Profile5Config {
+        Profile5Config {
+            data_id,
+            offset: 0,
+            data_length,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 5 which protects data of fixed length with an 8 bit counter and a CRC-16.
+///
+/// The 3 byte header consists of the CRC in little endian followed by the counter,
+/// the data id is not transmitted but appended to the data when computing the CRC.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile5 {
+    config: Profile5Config,
+    counter: u8,
+    last_counter: u64,
+}
+
+impl Profile5 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile5Config) -> Profile5 {
+        Profile5 {
+            config,
+            counter: 0,
+            last_counter: u8::MAX as u64,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile5Config {
+        &self.config
+    }
+
+    fn crc(&self, data: &[u8]) -> u16 {
+        let offset = self.config.offset;
+        let crc = crc16_ccitt(&data[..offset]);
+        let crc = crc16_ccitt_update(crc, &data[offset + 2..]);
+        crc16_ccitt_update(crc, &self.config.data_id.to_le_bytes())
+    }
+}
+
+impl E2EProfile for Profile5 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        if data.len() + HEADER_LEN != self.config.data_length {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is not the configured length {}",
+                data.len() + HEADER_LEN,
+                self.config.data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset + 2] = self.counter;
+        let crc = self.crc(data);
+        data[offset..offset + 2].copy_from_slice(&crc.to_le_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() != self.config.data_length || data.len() < offset + HEADER_LEN {
+            return E2EStatus::Error;
+        }
+        let crc = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        if crc != self.crc(data) {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            data[offset + 2] as u64,
+            1 << 8,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[test]
+fn test_profile5_spec_example() {
+    let config = Profile5Config::new(0x1234, 8);
+    let mut profile = Profile5::new(config.clone());
+    let mut data = vec![0; 5];
+    profile.protect(&mut data).unwrap();
+    assert_eq!(vec![0x1C, 0xCA, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], data);
+    let first = data;
+    let mut second = vec![0; 5];
+    profile.protect(&mut second).unwrap();
+    assert_eq!(0x01, second[2]);
+
+    let mut receiver = Profile5::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Ok, receiver.check(&second));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&second));
+}
+
+#[test]
+fn test_profile5_check() {
+    let mut config = Profile5Config::new(0x4321, 6);
+    config.offset = 1;
+    let mut sender = Profile5::new(config.clone());
+    let mut receiver = Profile5::new(config);
+    let protect = |sender: &mut Profile5| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    //the counter wraps around after 255
+    for _ in 0..255 {
+        protect(&mut sender);
+    }
+    let data = protect(&mut sender);
+    assert_eq!(0xFF, data[3]);
+    //the receiver starts with the last counter 0xFF
+    assert_eq!(E2EStatus::Repeated, receiver.check(&data));
+    let data = protect(&mut sender);
+    assert_eq!(0, data[3]);
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[0] ^= 0x80;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..5]));
+
+    //the data id is part of the crc
+    let mut other = Profile5::new(Profile5Config {
+        offset: 1,
+        ..Profile5Config::new(0x4322, 6)
+    });
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
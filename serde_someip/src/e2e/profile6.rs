@@ -0,0 +1,187 @@
+use super::crc::{crc16_ccitt, crc16_ccitt_update};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 5;
+
+/// The configuration of [Profile6].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile6Config {
+    /// The id identifying the protected data, it is not transmitted but included in the CRC.
+    pub data_id: u16,
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The minimum length of the protected data in bytes, including the header.
+    pub min_data_length: usize,
+    /// The maximum length of the protected data in bytes, including the header.
+    pub max_data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u8,
+}
+
+impl Profile6Config {
+    /// Creates a config with the header at offset 0, allowing any length and one lost message.
+    pub fn new(data_id: u16) -> Profile6Config {
+        Profile6Config {
+            data_id,
+            offset: 0,
+            min_data_length: HEADER_LEN,
+            max_data_length: u16::MAX as usize,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 6 which protects small data of variable length with an 8 bit counter and a CRC-16.
+///
+/// The 5 byte header consists of the CRC, length and counter, all in big endian,
+/// the data id is not transmitted but appended to the data when computing the CRC.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile6 {
+    config: Profile6Config,
+    counter: u8,
+    last_counter: u64,
+}
+
+impl Profile6 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile6Config) -> Profile6 {
+        Profile6 {
+            config,
+            counter: 0,
+            last_counter: u8::MAX as u64,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile6Config {
+        &self.config
+    }
+
+    fn crc(&self, data: &[u8]) -> u16 {
+        let offset = self.config.offset;
+        let crc = crc16_ccitt(&data[..offset]);
+        let crc = crc16_ccitt_update(crc, &data[offset + 2..]);
+        crc16_ccitt_update(crc, &self.config.data_id.to_be_bytes())
+    }
+}
+
+impl E2EProfile for Profile6 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        let len = data.len() + HEADER_LEN;
+        if len < self.config.min_data_length
+            || len > self.config.max_data_length
+            || len > u16::MAX as usize
+        {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is outside of {}..={}",
+                len, self.config.min_data_length, self.config.max_data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset + 2..offset + 4].copy_from_slice(&(len as u16).to_be_bytes());
+        data[offset + 4] = self.counter;
+        let crc = self.crc(data);
+        data[offset..offset + 2].copy_from_slice(&crc.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() < self.config.min_data_length
+            || data.len() > self.config.max_data_length
+            || data.len() < offset + HEADER_LEN
+        {
+            return E2EStatus::Error;
+        }
+        let header = &data[offset..offset + HEADER_LEN];
+        let crc = u16::from_be_bytes([header[0], header[1]]);
+        let len = u16::from_be_bytes([header[2], header[3]]);
+        if len as usize != data.len() || crc != self.crc(data) {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            header[4] as u64,
+            1 << 8,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[test]
+fn test_profile6_protect() {
+    let config = Profile6Config::new(0x1234);
+    let mut profile = Profile6::new(config.clone());
+    //the example of the autosar E2E protocol specification
+    let mut first = vec![0; 3];
+    profile.protect(&mut first).unwrap();
+    assert_eq!(vec![0xB1, 0x55, 0x00, 0x08, 0x00, 0x00, 0x00, 0x00], first);
+    let mut second = vec![0; 3];
+    profile.protect(&mut second).unwrap();
+    assert_eq!(vec![0xF4, 0xF5, 0x00, 0x08, 0x01, 0x00, 0x00, 0x00], second);
+
+    let mut receiver = Profile6::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Ok, receiver.check(&second));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&second));
+}
+
+#[test]
+fn test_profile6_check() {
+    let mut config = Profile6Config::new(0x4321);
+    config.offset = 1;
+    let mut sender = Profile6::new(config.clone());
+    let mut receiver = Profile6::new(config);
+    let protect = |sender: &mut Profile6| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    let data = protect(&mut sender);
+    assert_eq!(8, data.len());
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+    protect(&mut sender);
+    protect(&mut sender);
+    assert_eq!(
+        E2EStatus::WrongSequence,
+        receiver.check(&protect(&mut sender))
+    );
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[7] ^= 1;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..7]));
+
+    //the data id is part of the crc
+    let mut other = Profile6::new(Profile6Config {
+        offset: 1,
+        ..Profile6Config::new(0x4322)
+    });
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    let mut sender = Profile6::new(Profile6Config {
+        min_data_length: 9,
+        ..Profile6Config::new(0)
+    });
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2, 3]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
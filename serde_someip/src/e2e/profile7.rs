@@ -0,0 +1,194 @@
+use super::crc::{crc64_ecma, crc64_ecma_update};
+use super::{check_counter, insert_header, remove_header, E2EProfile, E2EStatus};
+use crate::error::{Error, Result};
+
+use std::borrow::Cow;
+
+const HEADER_LEN: usize = 20;
+
+/// The configuration of [Profile7].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile7Config {
+    /// The id identifying the protected data, it is transmitted in the header.
+    pub data_id: u32,
+    /// The offset of the header in bytes.
+    pub offset: usize,
+    /// The minimum length of the protected data in bytes, including the header.
+    pub min_data_length: usize,
+    /// The maximum length of the protected data in bytes, including the header.
+    pub max_data_length: usize,
+    /// The maximum counter increment that is still reported as [OkSomeLost](E2EStatus::OkSomeLost).
+    pub max_delta_counter: u32,
+}
+
+impl Profile7Config {
+    /// Creates a config with the header at offset 0, allowing any length and one lost message.
+    pub fn new(data_id: u32) -> Profile7Config {
+        Profile7Config {
+            data_id,
+            offset: 0,
+            min_data_length: HEADER_LEN,
+            max_data_length: u32::MAX as usize,
+            max_delta_counter: 2,
+        }
+    }
+}
+
+/// E2E Profile 7 which protects large data of variable length with a 32 bit counter, a 32 bit data id and a CRC-64.
+///
+/// The 20 byte header consists of the CRC, length, counter and data id, all in big endian.
+/// The same instance can be used for sending and receiving, the counters are tracked independently.
+#[derive(Debug, Clone)]
+pub struct Profile7 {
+    config: Profile7Config,
+    counter: u32,
+    last_counter: u64,
+}
+
+impl Profile7 {
+    /// Creates a new instance, the first protected data has counter 0.
+    pub fn new(config: Profile7Config) -> Profile7 {
+        Profile7 {
+            config,
+            counter: 0,
+            last_counter: u32::MAX as u64,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &Profile7Config {
+        &self.config
+    }
+
+    fn crc(&self, data: &[u8]) -> u64 {
+        let offset = self.config.offset;
+        crc64_ecma_update(crc64_ecma(&data[..offset]), &data[offset + 8..])
+    }
+}
+
+impl E2EProfile for Profile7 {
+    fn protect(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        let len = data.len() + HEADER_LEN;
+        if len < self.config.min_data_length
+            || len > self.config.max_data_length
+            || len > u32::MAX as usize
+        {
+            return Err(Error::InvalidE2EData(format!(
+                "The protected length {} is outside of {}..={}",
+                len, self.config.min_data_length, self.config.max_data_length
+            )));
+        }
+        let offset = self.config.offset;
+        insert_header(data, offset, HEADER_LEN)?;
+        data[offset + 8..offset + 12].copy_from_slice(&(len as u32).to_be_bytes());
+        data[offset + 12..offset + 16].copy_from_slice(&self.counter.to_be_bytes());
+        data[offset + 16..offset + 20].copy_from_slice(&self.config.data_id.to_be_bytes());
+        let crc = self.crc(data);
+        data[offset..offset + 8].copy_from_slice(&crc.to_be_bytes());
+        self.counter = self.counter.wrapping_add(1);
+        Ok(())
+    }
+
+    fn check(&mut self, data: &[u8]) -> E2EStatus {
+        let offset = self.config.offset;
+        if data.len() < self.config.min_data_length
+            || data.len() > self.config.max_data_length
+            || data.len() < offset + HEADER_LEN
+        {
+            return E2EStatus::Error;
+        }
+        let header = &data[offset..offset + HEADER_LEN];
+        let mut crc = [0; 8];
+        crc.copy_from_slice(&header[..8]);
+        let crc = u64::from_be_bytes(crc);
+        let len = u32::from_be_bytes([header[8], header[9], header[10], header[11]]);
+        let counter = u32::from_be_bytes([header[12], header[13], header[14], header[15]]);
+        let data_id = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+        if len as usize != data.len() || data_id != self.config.data_id || crc != self.crc(data) {
+            return E2EStatus::Error;
+        }
+        check_counter(
+            &mut self.last_counter,
+            counter as u64,
+            1 << 32,
+            self.config.max_delta_counter as u64,
+        )
+    }
+
+    #[inline]
+    fn payload<'a>(&self, data: &'a [u8]) -> Cow<'a, [u8]> {
+        remove_header(data, self.config.offset, HEADER_LEN)
+    }
+}
+
+#[test]
+fn test_profile7_protect() {
+    let config = Profile7Config::new(0x0A0B_0C0D);
+    let mut profile = Profile7::new(config.clone());
+    //the example of the autosar E2E protocol specification
+    let mut first = vec![0; 4];
+    profile.protect(&mut first).unwrap();
+    assert_eq!(
+        vec![
+            0x1F, 0xB2, 0xE7, 0x37, 0xFC, 0xED, 0xBC, 0xD9, 0x00, 0x00, 0x00, 0x18, 0x00, 0x00,
+            0x00, 0x00, 0x0A, 0x0B, 0x0C, 0x0D, 0x00, 0x00, 0x00, 0x00
+        ],
+        first
+    );
+    let mut second = vec![0; 4];
+    profile.protect(&mut second).unwrap();
+    assert_eq!(&[0x00, 0x00, 0x00, 0x01], &second[12..16]);
+
+    let mut receiver = Profile7::new(config);
+    assert_eq!(E2EStatus::Ok, receiver.check(&first));
+    assert_eq!(E2EStatus::Ok, receiver.check(&second));
+    assert_eq!(E2EStatus::Repeated, receiver.check(&second));
+}
+
+#[test]
+fn test_profile7_check() {
+    let mut config = Profile7Config::new(0x1234_5678);
+    config.offset = 2;
+    let mut sender = Profile7::new(config.clone());
+    let mut receiver = Profile7::new(config);
+    let protect = |sender: &mut Profile7| {
+        let mut data = vec![1, 2, 3];
+        sender.protect(&mut data).unwrap();
+        data
+    };
+
+    let data = protect(&mut sender);
+    assert_eq!(23, data.len());
+    assert_eq!(E2EStatus::Ok, receiver.check(&data));
+    assert_eq!(vec![1, 2, 3], receiver.payload(&data).into_owned());
+
+    protect(&mut sender);
+    assert_eq!(E2EStatus::OkSomeLost, receiver.check(&protect(&mut sender)));
+    protect(&mut sender);
+    protect(&mut sender);
+    assert_eq!(
+        E2EStatus::WrongSequence,
+        receiver.check(&protect(&mut sender))
+    );
+
+    let mut corrupted = protect(&mut sender);
+    corrupted[22] ^= 1;
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted));
+    assert_eq!(E2EStatus::Error, receiver.check(&corrupted[..22]));
+
+    let mut other = Profile7::new(Profile7Config {
+        offset: 2,
+        ..Profile7Config::new(0x1234_5679)
+    });
+    assert_eq!(E2EStatus::Error, receiver.check(&protect(&mut other)));
+
+    let mut sender = Profile7::new(Profile7Config {
+        max_data_length: 22,
+        ..Profile7Config::new(0)
+    });
+    assert!(matches!(
+        sender.protect(&mut vec![1, 2, 3]),
+        Err(Error::InvalidE2EData(_))
+    ));
+}
@@ -0,0 +1,236 @@
+//! Provides the E2E state machine which evaluates the [E2EStatus] of the recent checks.
+//!
+//! A single failed check does not necessarily mean that the communication is broken, instead the
+//! [E2EStateMachine] counts the ok and erroneous statuses within a window of the recent checks and
+//! derives the [E2EState] of the communication from them. It is independent of any specific profile,
+//! every status returned by [check](super::E2EProfile::check) is passed to [check](E2EStateMachine::check).
+//! If no data was received in a cycle [NoNewData](E2EStatus::NoNewData) should be passed instead.
+//! ```
+//! use serde_someip::e2e::state_machine::*;
+//! use serde_someip::e2e::E2EStatus;
+//!
+//! //valid after 2 ok statuses within the last 3 checks, invalid after 1 error
+//! let mut sm = E2EStateMachine::new(E2EStateMachineConfig::new(3, 2, 0));
+//! assert_eq!(E2EState::NoData, sm.state());
+//! assert_eq!(E2EState::Init, sm.check(E2EStatus::Ok));
+//! assert_eq!(E2EState::Init, sm.check(E2EStatus::Ok));
+//! assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+//! assert_eq!(E2EState::Invalid, sm.check(E2EStatus::Error));
+//! ```
+
+use super::E2EStatus;
+
+use std::collections::VecDeque;
+
+/// The window size and thresholds of one [E2EState].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct E2EWindowConfig {
+    /// The number of recent checks that are considered.
+    pub window_size: usize,
+    /// The minimum number of ok statuses within the window required to become or stay [Valid](E2EState::Valid).
+    pub min_ok: usize,
+    /// The maximum number of erroneous statuses within the window that still allow becoming or staying
+    /// [Valid](E2EState::Valid).
+    pub max_error: usize,
+}
+
+/// The configuration of the [E2EStateMachine].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct E2EStateMachineConfig {
+    /// The window used in state [Init](E2EState::Init).
+    pub init: E2EWindowConfig,
+    /// The window used in state [Valid](E2EState::Valid).
+    pub valid: E2EWindowConfig,
+    /// The window used in state [Invalid](E2EState::Invalid).
+    pub invalid: E2EWindowConfig,
+    /// If the window is cleared when the state changes to [Invalid](E2EState::Invalid),
+    /// so that the statuses that lead to it are not considered anymore.
+    pub clear_to_invalid: bool,
+}
+
+impl E2EStateMachineConfig {
+    /// Creates a config using the same window for all states, the window is not cleared when becoming invalid.
+    pub fn new(window_size: usize, min_ok: usize, max_error: usize) -> E2EStateMachineConfig {
+        let window = E2EWindowConfig {
+            window_size,
+            min_ok,
+            max_error,
+        };
+        E2EStateMachineConfig {
+            init: window.clone(),
+            valid: window.clone(),
+            invalid: window,
+            clear_to_invalid: false,
+        }
+    }
+}
+
+/// The state of the communication as determined by the [E2EStateMachine].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum E2EState {
+    /// No valid data was received yet.
+    NoData,
+    /// Valid data was received but not yet enough to decide whether the communication works.
+    Init,
+    /// The communication works, received data can be used.
+    Valid,
+    /// The communication does not work, received data should not be used.
+    Invalid,
+}
+
+impl E2EState {
+    /// Can received data be used?
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        matches!(self, E2EState::Valid)
+    }
+}
+
+/// The E2E state machine which derives the [E2EState] of the communication from the recent [E2EStatus]es.
+///
+/// [Ok](E2EStatus::Ok) and [OkSomeLost](E2EStatus::OkSomeLost) count as ok, [Error](E2EStatus::Error) counts
+/// as erroneous, all other statuses are added to the window without counting as either.
+#[derive(Debug, Clone)]
+pub struct E2EStateMachine {
+    config: E2EStateMachineConfig,
+    state: E2EState,
+    window: VecDeque<E2EStatus>,
+}
+
+impl E2EStateMachine {
+    /// Creates a new state machine in state [NoData](E2EState::NoData).
+    pub fn new(config: E2EStateMachineConfig) -> E2EStateMachine {
+        E2EStateMachine {
+            config,
+            state: E2EState::NoData,
+            window: VecDeque::new(),
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &E2EStateMachineConfig {
+        &self.config
+    }
+
+    /// The current state.
+    #[inline]
+    pub fn state(&self) -> E2EState {
+        self.state
+    }
+
+    /// Returns to state [NoData](E2EState::NoData) and clears the window, for example after a restart of the sender.
+    pub fn reset(&mut self) {
+        self.state = E2EState::NoData;
+        self.window.clear();
+    }
+
+    /// Evaluates the status of the most recent check and returns the new state.
+    pub fn check(&mut self, status: E2EStatus) -> E2EState {
+        let window = match self.state {
+            E2EState::NoData => {
+                //the first data only starts the initialization and is not added to the window
+                if !matches!(status, E2EStatus::Error | E2EStatus::NoNewData) {
+                    self.state = E2EState::Init;
+                }
+                return self.state;
+            }
+            E2EState::Init => &self.config.init,
+            E2EState::Valid => &self.config.valid,
+            E2EState::Invalid => &self.config.invalid,
+        };
+        self.window.push_back(status);
+        while self.window.len() > window.window_size {
+            self.window.pop_front();
+        }
+
+        let ok = self.window.iter().filter(|s| s.is_ok()).count();
+        let error = self
+            .window
+            .iter()
+            .filter(|s| **s == E2EStatus::Error)
+            .count();
+        let is_valid = ok >= window.min_ok && error <= window.max_error;
+        let next = match self.state {
+            E2EState::Init if is_valid => E2EState::Valid,
+            E2EState::Init if error > window.max_error => E2EState::Invalid,
+            E2EState::Init => E2EState::Init,
+            _ if is_valid => E2EState::Valid,
+            _ => E2EState::Invalid,
+        };
+        if next == E2EState::Invalid
+            && self.state != E2EState::Invalid
+            && self.config.clear_to_invalid
+        {
+            self.window.clear();
+        }
+        self.state = next;
+        self.state
+    }
+}
+
+#[test]
+fn test_no_data() {
+    let mut sm = E2EStateMachine::new(E2EStateMachineConfig::new(3, 1, 1));
+    assert_eq!(E2EState::NoData, sm.check(E2EStatus::NoNewData));
+    assert_eq!(E2EState::NoData, sm.check(E2EStatus::Error));
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Repeated));
+    assert!(sm.window.is_empty());
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::OkSomeLost));
+    assert!(sm.state().is_valid());
+
+    sm.reset();
+    assert_eq!(E2EState::NoData, sm.state());
+    assert!(sm.window.is_empty());
+}
+
+#[test]
+fn test_init() {
+    let mut sm = E2EStateMachine::new(E2EStateMachineConfig::new(4, 3, 1));
+    sm.check(E2EStatus::Ok);
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Ok));
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Error));
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Ok));
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+
+    let mut sm = E2EStateMachine::new(E2EStateMachineConfig::new(4, 3, 1));
+    sm.check(E2EStatus::Ok);
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Error));
+    assert_eq!(E2EState::Invalid, sm.check(E2EStatus::Error));
+}
+
+#[test]
+fn test_window() {
+    let mut config = E2EStateMachineConfig::new(3, 2, 1);
+    config.valid.window_size = 5;
+    let mut sm = E2EStateMachine::new(config);
+    sm.check(E2EStatus::Ok);
+    assert_eq!(E2EState::Init, sm.check(E2EStatus::Ok));
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+    //the larger window of the valid state keeps older statuses
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Repeated));
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::WrongSequence));
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::NoNewData));
+    assert_eq!(5, sm.window.len());
+    //older statuses drop out of the window
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+    assert_eq!(E2EState::Invalid, sm.check(E2EStatus::Repeated));
+    //the window shrinks to the size of the invalid state
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+    assert_eq!(3, sm.window.len());
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Error));
+    assert_eq!(E2EState::Invalid, sm.check(E2EStatus::Error));
+}
+
+#[test]
+fn test_clear_to_invalid() {
+    let mut config = E2EStateMachineConfig::new(3, 1, 0);
+    config.clear_to_invalid = true;
+    let mut sm = E2EStateMachine::new(config);
+    sm.check(E2EStatus::Ok);
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+    assert_eq!(E2EState::Invalid, sm.check(E2EStatus::Error));
+    assert!(sm.window.is_empty());
+    //without clearing the error would still be in the window
+    assert_eq!(E2EState::Valid, sm.check(E2EStatus::Ok));
+}
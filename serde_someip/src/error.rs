@@ -1,5 +1,6 @@
 //! This module contains the [Error] type used by the serializer and deserializer of this crate.
 
+use super::e2e::E2EStatus;
 use super::length_fields::LengthFieldSize;
 
 use serde::{de, ser};
@@ -22,8 +23,46 @@ pub enum Error {
         /// The value that was received. Allready converted to [String].
         value: String,
         /// The name of the enum that we attempted to deserialize.
-        name: &'static str,
+        name: String,
     },
+    /// The deserialized raw value of a bitfield has bits set that do not belong to any flag.
+    /// This error is only possible if [SomeIpOptions::DESERIALIZER_STRICT_BITFIELD](super::options::SomeIpOptions::DESERIALIZER_STRICT_BITFIELD) is `true`.
+    InvalidBitfieldValue {
+        /// The value that was received.
+        value: u64,
+        /// The name of the bitfield that we attempted to deserialize.
+        name: String,
+    },
+    /// The message type of a someip header is not defined by the spec.
+    InvalidMessageType(u8),
+    /// A segment of a segmented message is invalid or does not fit to the other segments of the message.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidSegment(String),
+    /// A service discovery message is invalid, for example it contains an unknown entry type.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidSdMessage(String),
+    /// The data that should be E2E protected does not fit to the configuration of the E2E profile.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidE2EData(String),
+    /// The check of E2E protected data did not return an [ok](super::e2e::E2EStatus::is_ok) status.
+    E2ECheckFailed(E2EStatus),
+    /// The data that should be authenticated or verified with SecOC does not fit to the configuration,
+    /// for example because there is no key. The [String] parameter gives more info as to what went wrong.
+    ///
+    /// *Only available with the `secoc` feature.*
+    #[cfg(feature = "secoc")]
+    InvalidSecOcData(String),
+    /// The verification of SecOC authenticated data failed, either the freshness value or the MAC is wrong.
+    ///
+    /// *Only available with the `secoc` feature.*
+    #[cfg(feature = "secoc")]
+    SecOcVerificationFailed,
+    /// A JSON schema could not be turned into a type description.
+    /// The [String] parameter gives more info as to what went wrong.
+    ///
+    /// *Only available with the `schema` feature.*
+    #[cfg(feature = "schema")]
+    InvalidSchema(String),
     /// The deserialized wiretype is incompatible with the expected one.
     /// This can only occure if TLV structs are used.
     InvalidWireType {
@@ -119,6 +158,31 @@ impl Display for Error {
                 "Invalid enum value {} for enum {}",
                 value, name
             )),
+            Error::InvalidBitfieldValue { value, name } => formatter.write_fmt(format_args!(
+                "Invalid value {:#X} for bitfield {}",
+                value, name
+            )),
+            Error::InvalidMessageType(v) => {
+                formatter.write_fmt(format_args!("Invalid message type: {:#X}", v))
+            }
+            Error::InvalidSegment(v) => formatter.write_fmt(format_args!("Invalid segment: {}", v)),
+            Error::InvalidSdMessage(v) => {
+                formatter.write_fmt(format_args!("Invalid SD message: {}", v))
+            }
+            Error::InvalidE2EData(v) => {
+                formatter.write_fmt(format_args!("Invalid E2E data: {}", v))
+            }
+            Error::E2ECheckFailed(v) => {
+                formatter.write_fmt(format_args!("E2E check failed with status {:?}", v))
+            }
+            #[cfg(feature = "secoc")]
+            Error::InvalidSecOcData(v) => {
+                formatter.write_fmt(format_args!("Invalid SecOC data: {}", v))
+            }
+            #[cfg(feature = "secoc")]
+            Error::SecOcVerificationFailed => formatter.write_str("SecOC verification failed"),
+            #[cfg(feature = "schema")]
+            Error::InvalidSchema(v) => formatter.write_fmt(format_args!("Invalid schema: {}", v)),
             Error::InvalidWireType { expected, actual } => formatter.write_fmt(format_args!(
                 "Invalid wire type expected {} but got {}",
                 expected, actual
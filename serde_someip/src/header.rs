@@ -0,0 +1,443 @@
+//! Provides [SomeIpHeader] to en/decode the header that preceeds the payload of every someip message.
+//!
+//! The header is always encoded in big endian regardless of the [SomeIpOptions] used for the payload.
+//! Use [to_message] and [from_message] to de/serialize a complete message:
+//! ```
+//! use serde_someip::header::{from_message, to_message, MessageType, SomeIpHeader};
+//! use serde_someip::options::ExampleOptions;
+//!
+//! let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+//! let message = to_message::<ExampleOptions, _>(&header, &42u32).unwrap();
+//! assert_eq!(20, message.len());
+//!
+//! let (decoded, payload) = from_message::<ExampleOptions, u32>(&message).unwrap();
+//! assert_eq!(header, decoded);
+//! assert_eq!(42, payload);
+//! ```
+
+use super::error::{Error, Result};
+use super::length_fields::LengthFieldSize;
+use super::options::SomeIpOptions;
+use super::types::SomeIp;
+
+use serde::{Deserialize, Serialize};
+
+use std::convert::TryFrom;
+
+/// The size of the someip header in bytes.
+pub const HEADER_LEN: usize = 16;
+
+/// The protocol version defined by the someip spec.
+pub const PROTOCOL_VERSION: u8 = 1;
+
+//The length field covers everything after itself, so the request id, versions, message type and return code
+const LENGTH_OFFSET: usize = 8;
+
+/// The type of a someip message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageType {
+    /// A request expecting a response (even void).
+    Request,
+    /// A fire&forget request.
+    RequestNoReturn,
+    /// A request of a notification/event callback expecting no response.
+    Notification,
+    /// The response message.
+    Response,
+    /// The response containing an error.
+    Error,
+    /// A segment of a request.
+    TpRequest,
+    /// A segment of a fire&forget request.
+    TpRequestNoReturn,
+    /// A segment of a notification.
+    TpNotification,
+    /// A segment of a response.
+    TpResponse,
+    /// A segment of an error response.
+    TpError,
+}
+
+//the bit that is set in the message type of all segmented messages
+const TP_FLAG: u8 = 0x20;
+
+impl MessageType {
+    /// If this message type is used for a segment of a larger message.
+    #[inline]
+    pub fn is_tp(&self) -> bool {
+        u8::from(*self) & TP_FLAG != 0
+    }
+
+    /// The message type used for the segments of a message of this type.
+    #[inline]
+    pub fn with_tp(self) -> MessageType {
+        MessageType::try_from(u8::from(self) | TP_FLAG).unwrap()
+    }
+
+    /// The message type of the complete message for a segment of this type.
+    #[inline]
+    pub fn without_tp(self) -> MessageType {
+        MessageType::try_from(u8::from(self) & !TP_FLAG).unwrap()
+    }
+}
+
+impl From<MessageType> for u8 {
+    fn from(v: MessageType) -> u8 {
+        match v {
+            MessageType::Request => 0x00,
+            MessageType::RequestNoReturn => 0x01,
+            MessageType::Notification => 0x02,
+            MessageType::Response => 0x80,
+            MessageType::Error => 0x81,
+            MessageType::TpRequest => 0x20,
+            MessageType::TpRequestNoReturn => 0x21,
+            MessageType::TpNotification => 0x22,
+            MessageType::TpResponse => 0xA0,
+            MessageType::TpError => 0xA1,
+        }
+    }
+}
+
+impl TryFrom<u8> for MessageType {
+    type Error = Error;
+
+    fn try_from(v: u8) -> Result<MessageType> {
+        Ok(match v {
+            0x00 => MessageType::Request,
+            0x01 => MessageType::RequestNoReturn,
+            0x02 => MessageType::Notification,
+            0x80 => MessageType::Response,
+            0x81 => MessageType::Error,
+            0x20 => MessageType::TpRequest,
+            0x21 => MessageType::TpRequestNoReturn,
+            0x22 => MessageType::TpNotification,
+            0xA0 => MessageType::TpResponse,
+            0xA1 => MessageType::TpError,
+            _ => return Err(Error::InvalidMessageType(v)),
+        })
+    }
+}
+
+/// The return code of a someip message.
+///
+/// Codes that are reserved by the spec or specific to a service are kept as [Other](ReturnCode::Other).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReturnCode {
+    /// No error occurred.
+    Ok,
+    /// An unspecified error occurred.
+    NotOk,
+    /// The requested service id is unknown.
+    UnknownService,
+    /// The requested method id is unknown.
+    UnknownMethod,
+    /// The service is not ready.
+    NotReady,
+    /// The system running the service is not reachable.
+    NotReachable,
+    /// A timeout occurred.
+    Timeout,
+    /// The protocol version is not supported.
+    WrongProtocolVersion,
+    /// The interface version does not match.
+    WrongInterfaceVersion,
+    /// The payload could not be deserialized.
+    MalformedMessage,
+    /// The message type is not expected.
+    WrongMessageType,
+    /// E2E detected a repeated message.
+    E2eRepeated,
+    /// E2E detected a wrong sequence of messages.
+    E2eWrongSequence,
+    /// E2E detected an unspecified error.
+    E2e,
+    /// E2E is not available.
+    E2eNotAvailable,
+    /// E2E detected that no new data is available.
+    E2eNoNewData,
+    /// Any other return code.
+    Other(u8),
+}
+
+impl From<ReturnCode> for u8 {
+    fn from(v: ReturnCode) -> u8 {
+        match v {
+            ReturnCode::Ok => 0x00,
+            ReturnCode::NotOk => 0x01,
+            ReturnCode::UnknownService => 0x02,
+            ReturnCode::UnknownMethod => 0x03,
+            ReturnCode::NotReady => 0x04,
+            ReturnCode::NotReachable => 0x05,
+            ReturnCode::Timeout => 0x06,
+            ReturnCode::WrongProtocolVersion => 0x07,
+            ReturnCode::WrongInterfaceVersion => 0x08,
+            ReturnCode::MalformedMessage => 0x09,
+            ReturnCode::WrongMessageType => 0x0A,
+            ReturnCode::E2eRepeated => 0x0B,
+            ReturnCode::E2eWrongSequence => 0x0C,
+            ReturnCode::E2e => 0x0D,
+            ReturnCode::E2eNotAvailable => 0x0E,
+            ReturnCode::E2eNoNewData => 0x0F,
+            ReturnCode::Other(v) => v,
+        }
+    }
+}
+
+impl From<u8> for ReturnCode {
+    fn from(v: u8) -> ReturnCode {
+        match v {
+            0x00 => ReturnCode::Ok,
+            0x01 => ReturnCode::NotOk,
+            0x02 => ReturnCode::UnknownService,
+            0x03 => ReturnCode::UnknownMethod,
+            0x04 => ReturnCode::NotReady,
+            0x05 => ReturnCode::NotReachable,
+            0x06 => ReturnCode::Timeout,
+            0x07 => ReturnCode::WrongProtocolVersion,
+            0x08 => ReturnCode::WrongInterfaceVersion,
+            0x09 => ReturnCode::MalformedMessage,
+            0x0A => ReturnCode::WrongMessageType,
+            0x0B => ReturnCode::E2eRepeated,
+            0x0C => ReturnCode::E2eWrongSequence,
+            0x0D => ReturnCode::E2e,
+            0x0E => ReturnCode::E2eNotAvailable,
+            0x0F => ReturnCode::E2eNoNewData,
+            v => ReturnCode::Other(v),
+        }
+    }
+}
+
+/// The header of a someip message.
+///
+/// The length field is not part of this struct since it is computed from the payload during encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SomeIpHeader {
+    /// The id of the service.
+    pub service_id: u16,
+    /// The id of the method or event, events have the most significant bit set.
+    pub method_id: u16,
+    /// The id of the client.
+    pub client_id: u16,
+    /// The id of the session, used to match responses to requests.
+    pub session_id: u16,
+    /// The version of the someip protocol, should be [PROTOCOL_VERSION].
+    pub protocol_version: u8,
+    /// The major version of the service interface.
+    pub interface_version: u8,
+    /// The type of the message.
+    pub message_type: MessageType,
+    /// The return code, must be [Ok](ReturnCode::Ok) for requests and notifications.
+    pub return_code: ReturnCode,
+}
+
+impl SomeIpHeader {
+    /// Creates a header with the given ids and message type.
+    ///
+    /// All other values are `0` except for the protocol version which is [PROTOCOL_VERSION].
+    pub fn new(service_id: u16, method_id: u16, message_type: MessageType) -> SomeIpHeader {
+        SomeIpHeader {
+            service_id,
+            method_id,
+            client_id: 0,
+            session_id: 0,
+            protocol_version: PROTOCOL_VERSION,
+            interface_version: 0,
+            message_type,
+            return_code: ReturnCode::Ok,
+        }
+    }
+
+    /// The message id made up of the service id and method id.
+    #[inline]
+    pub fn message_id(&self) -> u32 {
+        (self.service_id as u32) << 16 | self.method_id as u32
+    }
+
+    /// The request id made up of the client id and session id.
+    #[inline]
+    pub fn request_id(&self) -> u32 {
+        (self.client_id as u32) << 16 | self.session_id as u32
+    }
+
+    /// Encodes the header for a message with a payload of `payload_len` bytes.
+    ///
+    /// Fails with [TooLong](Error::TooLong) if the length does not fit into the length field of the header.
+    pub fn encode(&self, payload_len: usize) -> Result<[u8; HEADER_LEN]> {
+        let length = u32::try_from(payload_len + LENGTH_OFFSET).map_err(|_| Error::TooLong {
+            actual_length: payload_len,
+            length_field_size: LengthFieldSize::FourBytes,
+        })?;
+        let mut header = [0; HEADER_LEN];
+        header[0..2].copy_from_slice(&self.service_id.to_be_bytes());
+        header[2..4].copy_from_slice(&self.method_id.to_be_bytes());
+        header[4..8].copy_from_slice(&length.to_be_bytes());
+        header[8..10].copy_from_slice(&self.client_id.to_be_bytes());
+        header[10..12].copy_from_slice(&self.session_id.to_be_bytes());
+        header[12] = self.protocol_version;
+        header[13] = self.interface_version;
+        header[14] = self.message_type.into();
+        header[15] = self.return_code.into();
+        Ok(header)
+    }
+
+    /// Decodes the header at the beginning of `data` and returns it together with the length of the payload.
+    ///
+    /// Only the header is decoded, `data` may contain more or less bytes than the payload length indicates.
+    pub fn decode(data: &[u8]) -> Result<(SomeIpHeader, usize)> {
+        if data.len() < HEADER_LEN {
+            return Err(Error::TooShort);
+        }
+        let length = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+        if length < LENGTH_OFFSET {
+            return Err(Error::TooShort);
+        }
+        let header = SomeIpHeader {
+            service_id: u16::from_be_bytes([data[0], data[1]]),
+            method_id: u16::from_be_bytes([data[2], data[3]]),
+            client_id: u16::from_be_bytes([data[8], data[9]]),
+            session_id: u16::from_be_bytes([data[10], data[11]]),
+            protocol_version: data[12],
+            interface_version: data[13],
+            message_type: MessageType::try_from(data[14])?,
+            return_code: ReturnCode::from(data[15]),
+        };
+        Ok((header, length - LENGTH_OFFSET))
+    }
+}
+
+/// Serializes a complete someip message consisting of the header and the payload.
+///
+/// The length field of the header is computed from the serialized payload.
+///
+/// # Panics
+/// This function panics if the implementation of the [SomeIp](super::SomeIp) trait
+/// produces invalid type information or this information is incompatible with the [Serialize](serde::Serialize) implementation.
+pub fn to_message<Options, T>(header: &SomeIpHeader, payload: &T) -> Result<Vec<u8>>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Serialize + SomeIp,
+{
+    let mut message = vec![0; HEADER_LEN];
+    super::append_to_vec::<Options, _>(payload, &mut message)?;
+    let header = header.encode(message.len() - HEADER_LEN)?;
+    message[..HEADER_LEN].copy_from_slice(&header);
+    Ok(message)
+}
+
+/// Deserializes a complete someip message consisting of the header and the payload.
+///
+/// The message must contain exactly as many bytes as the length field of the header indicates,
+/// otherwise [TooShort](Error::TooShort) or [NotAllBytesConsumed](Error::NotAllBytesConsumed) is returned.
+///
+/// # Panics
+/// This function panics if the implementation of the [SomeIp](super::SomeIp) trait
+/// produces invalid type information or this information is incompatible with the [Deserialize](serde::Deserialize) implementation.
+pub fn from_message<'a, Options, T>(message: &'a [u8]) -> Result<(SomeIpHeader, T)>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Deserialize<'a> + SomeIp,
+{
+    let (header, payload_len) = SomeIpHeader::decode(message)?;
+    let payload = &message[HEADER_LEN..];
+    if payload.len() < payload_len {
+        return Err(Error::TooShort);
+    } else if payload.len() > payload_len {
+        return Err(Error::NotAllBytesConsumed(payload.len() - payload_len));
+    }
+    let payload = super::from_slice::<Options, T>(payload)?;
+    Ok((header, payload))
+}
+
+#[cfg(test)]
+use super::options::ExampleOptions;
+
+#[test]
+fn test_header_encode() {
+    let header = SomeIpHeader {
+        service_id: 0x1234,
+        method_id: 0x0421,
+        client_id: 0x0013,
+        session_id: 0x0001,
+        protocol_version: PROTOCOL_VERSION,
+        interface_version: 2,
+        message_type: MessageType::Response,
+        return_code: ReturnCode::NotReady,
+    };
+    assert_eq!(
+        [0x12, 0x34, 0x04, 0x21, 0, 0, 0, 12, 0, 0x13, 0, 1, 1, 2, 0x80, 4],
+        header.encode(4).unwrap()
+    );
+    assert_eq!(0x12340421, header.message_id());
+    assert_eq!(0x00130001, header.request_id());
+}
+
+#[test]
+fn test_header_decode() {
+    let (header, payload_len) = SomeIpHeader::decode(&[
+        0x12, 0x34, 0x80, 0x01, 0, 0, 0, 8, 0, 0, 0, 0, 1, 1, 0x02, 0x42,
+    ])
+    .unwrap();
+    assert_eq!(0, payload_len);
+    assert_eq!(
+        SomeIpHeader {
+            service_id: 0x1234,
+            method_id: 0x8001,
+            client_id: 0,
+            session_id: 0,
+            protocol_version: PROTOCOL_VERSION,
+            interface_version: 1,
+            message_type: MessageType::Notification,
+            return_code: ReturnCode::Other(0x42),
+        },
+        header
+    );
+
+    assert!(matches!(
+        SomeIpHeader::decode(&[0x12, 0x34, 0x80, 0x01, 0, 0, 0, 8, 0, 0, 0, 0, 1, 1, 0x02]),
+        Err(Error::TooShort)
+    ));
+    assert!(matches!(
+        SomeIpHeader::decode(&[0x12, 0x34, 0x80, 0x01, 0, 0, 0, 7, 0, 0, 0, 0, 1, 1, 0x02, 0]),
+        Err(Error::TooShort)
+    ));
+    assert!(matches!(
+        SomeIpHeader::decode(&[0x12, 0x34, 0x80, 0x01, 0, 0, 0, 8, 0, 0, 0, 0, 1, 1, 0x03, 0]),
+        Err(Error::InvalidMessageType(3))
+    ));
+}
+
+#[test]
+fn test_message_type_tp() {
+    assert!(!MessageType::Request.is_tp());
+    assert!(MessageType::TpResponse.is_tp());
+    assert_eq!(MessageType::TpError, MessageType::Error.with_tp());
+    assert_eq!(
+        MessageType::Notification,
+        MessageType::TpNotification.without_tp()
+    );
+}
+
+#[test]
+fn test_message() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let message = to_message::<ExampleOptions, _>(&header, &0x0102u16).unwrap();
+    assert_eq!(
+        vec![0x12, 0x34, 0, 1, 0, 0, 0, 10, 0, 0, 0, 0, 1, 0, 0, 0, 1, 2],
+        message
+    );
+
+    let (decoded, payload) = from_message::<ExampleOptions, u16>(&message).unwrap();
+    assert_eq!(header, decoded);
+    assert_eq!(0x0102, payload);
+
+    assert!(matches!(
+        from_message::<ExampleOptions, u16>(&message[..17]),
+        Err(Error::TooShort)
+    ));
+    let mut too_long = message.clone();
+    too_long.push(0);
+    assert!(matches!(
+        from_message::<ExampleOptions, u16>(&too_long),
+        Err(Error::NotAllBytesConsumed(1))
+    ));
+}
@@ -18,11 +18,11 @@ pub enum LengthFieldSize {
 
 impl LengthFieldSize {
     pub(crate) fn minimum_length_for(len: usize) -> LengthFieldSize {
-        if len <= u8::max_value() as usize {
+        if len <= u8::MAX as usize {
             LengthFieldSize::OneByte
-        } else if len <= u16::max_value() as usize {
+        } else if len <= u16::MAX as usize {
             LengthFieldSize::TwoBytes
-        } else if len <= u32::max_value() as usize {
+        } else if len <= u32::MAX as usize {
             LengthFieldSize::FourBytes
         } else {
             panic!("Cannot handle message with len={} bytes", len)
@@ -4,11 +4,23 @@
 //!
 //! This crate does not aim to provide a full someip stack instead it only deals with the serialization of
 //! Data Structures (chapter 4.1.4 of the linked spec) but fully handles that part.
+//! To build complete messages the [header] module provides the en/decoding of the someip header,
+//! with the `tokio` feature the `codec` module frames such messages on byte streams.
+//! Service discovery messages can be en/decoded with the [sd] module.
+//! Data whose type is only known at runtime can be de/serialized as a [SomeIpValue] with the [value] module,
+//! the descriptions of such types can be exported to and imported from JSON by the `schema` module which requires the `schema` feature.
+//! Serialized data can be protected with the E2E profiles of the [e2e] module
+//! and authenticated with SecOC by the `secoc` module which requires the `secoc` feature.
+//! Both are applied explicitly, even a type with an `e2e` attribute is serialized unprotected by [to_vec].
 //!
 //! [`SOME/IP`]: https://www.autosar.org/fileadmin/user_upload/standards/foundation/19-11/AUTOSAR_PRS_SOMEIPProtocol.pdf
 #![deny(missing_docs)]
 
+#[cfg(feature = "tokio")]
+pub mod codec;
+pub mod datagram;
 pub mod de;
+pub mod e2e;
 pub mod error;
 pub mod ser;
 
@@ -20,14 +32,25 @@ pub use error::{Error, Result};
 pub use ser::{append_to_bytes, to_bytes};
 pub use ser::{append_to_vec, to_vec};
 
+pub mod header;
 pub mod length_fields;
 pub mod options;
+#[cfg(feature = "schema")]
+pub mod schema;
+pub mod sd;
+#[cfg(feature = "secoc")]
+pub mod secoc;
+pub mod tp;
 pub mod types;
+pub mod unknown_members;
+pub mod value;
 
 pub(crate) mod wire_type;
 
 pub use options::SomeIpOptions;
 pub use types::SomeIp;
+pub use unknown_members::UnknownMembers;
+pub use value::{from_slice_dynamic, to_vec_dynamic, SomeIpValue};
 
 #[cfg(feature = "derive")]
 extern crate serde_someip_derive;
@@ -46,7 +69,32 @@ extern crate serde_someip_derive;
 /// # Eums
 /// For enums you must provide a `raw_type` to indicate how to serialize the enum
 /// and every variant must be assigned a `value` that is valid for that raw_type.
-/// SomeIp only supports unit variants, so tuple and struct variants will cause a compile error.
+/// Such enums only support unit variants, so tuple and struct variants will cause a compile error.
+/// For enums that carry data see [Unions](#unions).
+///
+/// By default unknown values cause an [Error::InvalidEnumValue] during deserialization.
+/// To accept them one variant can be marked as `other`, which is either a newtype variant carrying the raw value
+/// that is serialized as is or a unit variant with a `value` that is used for serialization:
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(raw_type = u16)]
+/// enum Foo {
+///     #[someip(value = 1)]
+///     Bar,
+///     #[someip(other)]
+///     Unknown(u16),
+/// }
+///
+/// #[derive(SomeIp)]
+/// #[someip(raw_type = u8)]
+/// enum Baz {
+///     #[someip(value = 1)]
+///     Bar,
+///     #[someip(value = 0xFF, other)]
+///     Unknown,
+/// }
+/// ```
 /// ```
 /// # use serde_someip::SomeIp;
 /// #[derive(SomeIp)]
@@ -66,6 +114,37 @@ extern crate serde_someip_derive;
 /// }
 /// ```
 ///
+/// # Unions
+/// Enums without a `raw_type` are unions, every variant must be assigned a `selector` which is written to the type field.
+/// Newtype variants take the attributes of their type just like fields do, tuple and struct variants are treated
+/// like a struct with the fields `0`, `1`, ... or the named fields. The selector `0` is reserved for the empty union,
+/// so only unit variants can use it. Unions can optionally have a `length_field_size` and a `type_field_size`.
+/// If no `type_field_size` is given [DEFAULT_UNION_TYPE_FIELD_SIZE](SomeIpOptions::DEFAULT_UNION_TYPE_FIELD_SIZE) is used.
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(length_field_size = 2, type_field_size = 1)]
+/// enum Foo {
+///     #[someip(selector = 0)]
+///     Empty,
+///     #[someip(selector = 1)]
+///     Bar(u32),
+///     #[someip(selector = 2, max_size = 42)]
+///     Baz(String),
+///     #[someip(selector = 3)]
+///     Point { x: i32, y: i32 },
+/// }
+/// ```
+/// Only unit variants can use the selector `0`:
+/// ```compile_fail
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// enum Foo {
+///     #[someip(selector = 0)]
+///     Bar(u32),
+/// }
+/// ```
+///
 /// # Strings
 /// For strings you must provide the `max_size` and can optionally provide a `min_size` (defaults to 0 if not present)
 ///  and a `length_field_size`. The size is in bytes after encoding the string with the string encoding specified by [SomeIpOptions].
@@ -82,6 +161,16 @@ extern crate serde_someip_derive;
 /// struct AnotherString<'a>(&'a str);
 /// ```
 ///
+/// Strings that always occupy a fixed number of bytes can use `fixed_size` instead of `min_size` and `max_size`.
+/// Shorter strings are padded with zeros by the serializer and the deserializer strips any trailing zeros.
+/// If [SomeIpOptions] require a terminator at least one zero must be present.
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(fixed_size = 32)]
+/// struct PaddedString(String);
+/// ```
+///
 /// # Sequences
 /// For Sequences you must provide the `max_elements` inside the sequence and can provide a `min_elements` (defaults to 0 if not present)
 /// and a `length_field_size`:
@@ -111,10 +200,25 @@ extern crate serde_someip_derive;
 /// struct AVec(Vec<Vec<String>>);
 /// ```
 ///
+/// # Maps
+/// Maps ([HashMap](std::collections::HashMap) and [BTreeMap](std::collections::BTreeMap)) are serialized as a sequence
+/// of key/value structs and take the same attributes as sequences. Instead of `elements` you can use `keys` and `values`
+/// to provide additional information for the key and value types:
+/// ```
+/// # use serde_someip::SomeIp;
+/// use std::collections::HashMap;
+///
+/// #[derive(SomeIp)]
+/// #[someip(max_elements = 42, keys = (max_size = 10))] //`values` works just like `keys`
+/// struct AMap(HashMap<String, u32>);
+/// ```
+///
 /// # Structs
 /// Structs can have `message_wrapper`, `length_field_size`, `arrays_length_field_size`, `structs_length_field_size` and a `strings_length_field_size` attribute
 /// though none are required. The three `xs_length_field_size` attributes correspond to similarily named values in the someip transformation properties of autosar.
 /// When present these attribtues will set the length_field_sizes for all matching types used in this struct.
+/// Likewise the `alignment` attribute (8, 16, 32 or 64 bits) corresponds to the alignment of the transformation properties,
+/// when present every member is followed by zeros so that the next member starts at an aligned offset relative to the beginning of the struct.
 /// The `message_wrapper` attribute indicates that this struct is a wrapper for a someip message
 /// (the parameters/return values of a function call or the data of an event) this must be considered during de/serialization
 /// since such structs must not beginn with a length field.
@@ -135,13 +239,29 @@ extern crate serde_someip_derive;
 ///     length_field_size = 2,
 ///     arrays_length_field_size = 2,
 ///     structs_length_field_size = 2,
-///     strings_length_field_size = 2
+///     strings_length_field_size = 2,
+///     alignment = 32
 /// )]
 /// struct AnotherStruct {
 ///     #[someip(max_elements = 42)]
 ///     foo: Vec<f64>,
 /// };
 /// ```
+/// Non TLV structs can be marked as `extensible`, such structs always have a length field so that newer versions can append members.
+/// Unknown members at the end of the struct are skipped and missing members at the end are reported as missing to serde,
+/// which can fill them using `#[serde(default)]`. Alternativly primitive fields can provide their own `default`:
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp, serde::Deserialize)]
+/// #[someip(extensible = true)]
+/// struct AStruct {
+///     foo: u32,
+///     #[someip(default = 42)]
+///     bar: u16,
+///     #[serde(default)]
+///     baz: bool,
+/// };
+/// ```
 /// Structs can also use the TLV encoding in which case every field must have an `id` which is in `0..=0xFFF` and the id
 /// must be unique within the struct.
 /// ```
@@ -154,6 +274,21 @@ extern crate serde_someip_derive;
 ///     bar: Option<f64>,
 /// };
 /// ```
+/// Members with unknown ids are skipped during deserialization unless the struct has a field of type
+/// [UnknownMembers] marked with `unknown_members`, which collects them so that they are written back verbatim
+/// during serialization:
+/// ```
+/// # use serde_someip::SomeIp;
+/// use serde_someip::UnknownMembers;
+///
+/// #[derive(SomeIp)]
+/// struct AStruct {
+///     #[someip(id = 0)]
+///     foo: u32,
+///     #[someip(unknown_members)]
+///     unknown: UnknownMembers,
+/// };
+/// ```
 /// Either all fields must have an id or none:
 /// ```compile_fail
 /// # use serde_someip::SomeIp;
@@ -173,6 +308,50 @@ extern crate serde_someip_derive;
 ///     bar: Option<f64>,
 /// };
 /// ```
+/// Message wrappers can be E2E protected by providing an `e2e` attribute with the profile
+/// (one of `profile4`, `profile5`, `profile6`, `profile7`, `profile11` or `profile22`) and its configuration.
+/// The arguments of the `new` function of the profile's config are required, all other fields of the config are optional.
+/// This implements [E2EProtected](e2e::E2EProtected) so that the struct can be used with [e2e::to_vec] and [e2e::from_slice],
+/// the plain [to_vec] and [from_slice] still produce and expect unprotected data:
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(message_wrapper = true, e2e = profile5(data_id = 0x1234, data_length = 11, offset = 8))]
+/// struct AStruct {
+///     foo: u64,
+/// };
+/// ```
+/// Tuple structs with more than one field and tuples are structs with the fields `0`, `1`, ... they take the
+/// same attributes as structs but cannot use the TLV encoding:
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(length_field_size = 2)]
+/// struct Vec3(f32, f32, f32);
+///
+/// #[derive(SomeIp)]
+/// struct AStruct {
+///     foo: (u32, f64),
+/// };
+/// ```
+///
+/// # Bitfields
+/// Structs of bools can be serialized as a bitfield by providing a `bitfield` attribute with the raw type
+/// (one of `u8`, `u16`, `u32` or `u64`) on the struct and a `bit` attribute on every field.
+/// The bits are numbered starting at the least significant bit and must be unique within the bitfield.
+/// ```
+/// # use serde_someip::SomeIp;
+/// #[derive(SomeIp)]
+/// #[someip(bitfield = u16)]
+/// struct Flags {
+///     #[someip(bit = 0)]
+///     foo: bool,
+///     #[someip(bit = 3)]
+///     bar: bool,
+/// };
+/// ```
+/// Unknown bits are ignored during deserialization unless
+/// [DESERIALIZER_STRICT_BITFIELD](SomeIpOptions::DESERIALIZER_STRICT_BITFIELD) is set.
 ///
 /// # `treat_as`
 /// You can use `treat_as` to workaround types that someip does not know.
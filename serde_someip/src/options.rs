@@ -122,6 +122,11 @@ pub trait SomeIpOptions {
     /// The default length field size to use if the type does not define one.
     const DEFAULT_LENGTH_FIELD_SIZE: Option<LengthFieldSize> = Some(LengthFieldSize::FourBytes);
 
+    /// The size of the type field of unions if the union does not define one.
+    ///
+    /// Defaults to four bytes as mandated by the someip standard.
+    const DEFAULT_UNION_TYPE_FIELD_SIZE: LengthFieldSize = LengthFieldSize::FourBytes;
+
     /// Should the serializer output the legacy wire type for length delimited fields?
     ///
     /// By default wiretypes 5(length delimited one byte), 6(length delimited two bytes) or
@@ -141,6 +146,12 @@ pub trait SomeIpOptions {
     /// they are interpreted as true.
     const DESERIALIZER_STRICT_BOOL: bool = false;
 
+    /// Should the deserializer issue a error if a bitfield has bits set that do not belong to any flag?
+    ///
+    /// With strict bitfields such values lead to [InvalidBitfieldValue](super::Error::InvalidBitfieldValue)
+    /// in lenient mode the unknown bits are ignored.
+    const DESERIALIZER_STRICT_BITFIELD: bool = false;
+
     /// How the deserializer treats strings or sequnces with too much data, see [ActionOnTooMuchData].
     const DESERIALIZER_ACTION_ON_TOO_MUCH_DATA: ActionOnTooMuchData = ActionOnTooMuchData::Discard;
 
@@ -159,7 +170,7 @@ pub trait SomeIpOptions {
     #[inline]
     fn from_reader<T, Reader>(reader: Reader, len: usize) -> Result<T>
     where
-        T: DeserializeOwned + SomeIp + ?Sized,
+        T: DeserializeOwned + SomeIp,
         Reader: Read,
     {
         super::from_reader::<Self, T, _>(reader, len)
@@ -169,7 +180,7 @@ pub trait SomeIpOptions {
     #[inline]
     fn from_slice<'a, T>(slice: &'a [u8]) -> Result<T>
     where
-        T: Deserialize<'a> + SomeIp + ?Sized,
+        T: Deserialize<'a> + SomeIp,
     {
         super::from_slice::<'a, Self, T>(slice)
     }
@@ -181,7 +192,7 @@ pub trait SomeIpOptions {
     #[inline]
     fn from_bytes<T>(data: bytes::Bytes) -> Result<T>
     where
-        T: DeserializeOwned + SomeIp + ?Sized,
+        T: DeserializeOwned + SomeIp,
     {
         super::from_bytes::<Self, T>(data)
     }
@@ -0,0 +1,706 @@
+//! Provides the export of type descriptions to JSON and their import from JSON.
+//!
+//! *Only available with the `schema` feature.*
+//!
+//! [to_json] renders a [SomeIpType] including all nested types, length field sizes, TLV ids,
+//! transformation properties and enum values, so the wire layout of an interface can be shared
+//! with tools that are not written in rust or diffed in reviews.
+//! The document is stable, every key is always present and keys are always in the same order.
+//! [from_json] parses such a document back into a [SomeIpType] using [Shared](SomeIpRef::Shared) references.
+//! ```
+//! use serde_someip::schema::{from_json, to_json};
+//! use serde_someip::SomeIp;
+//!
+//! let json = to_json(&<(u8, u16)>::SOMEIP_TYPE);
+//! assert!(json.contains(r#""kind": "struct""#));
+//! assert_eq!(<(u8, u16)>::SOMEIP_TYPE, from_json(&json).unwrap());
+//! ```
+//!
+//! A struct is rendered as:
+//! ```json
+//! {
+//!   "kind": "struct",
+//!   "name": "Tuple",
+//!   "uses_tlv_serialization": false,
+//!   "is_message_wrapper": false,
+//!   "extensible": false,
+//!   "length_field_size": null,
+//!   "transformation_properties": null,
+//!   "fields": [
+//!     {
+//!       "name": "0",
+//!       "id": null,
+//!       "default": null,
+//!       "type": {
+//!         "kind": "primitive",
+//!         "type": "u8"
+//!       }
+//!     }
+//!   ]
+//! }
+//! ```
+//! Length field sizes are given in bytes and alignments in bits.
+
+use super::error::{Error, Result};
+use super::length_fields::LengthFieldSize;
+use super::types::*;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Number, Value};
+
+/// Renders the type as a pretty printed JSON document.
+pub fn to_json(someip_type: &SomeIpType) -> String {
+    serde_json::to_string_pretty(&TypeSchema::from(someip_type))
+        .expect("Serializing a schema to a string cannot fail")
+}
+
+/// Parses a JSON document created by [to_json].
+///
+/// Fails with [InvalidSchema](Error::InvalidSchema) if the document is malformed, contains values that
+/// cannot be represented, like a length field size of 3 bytes, or describes a type that
+/// [verify](VerifySomeIpType::verify) would reject, like a union without variants.
+pub fn from_json(json: &str) -> Result<SomeIpType> {
+    let someip_type = serde_json::from_str::<TypeSchema>(json)
+        .map_err(|e| Error::InvalidSchema(e.to_string()))?
+        .into_type()?;
+    someip_type.validate().map_err(Error::InvalidSchema)?;
+    Ok(someip_type)
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum PrimitiveSchema {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl From<&SomeIpPrimitive> for PrimitiveSchema {
+    fn from(primitive: &SomeIpPrimitive) -> Self {
+        match primitive {
+            SomeIpPrimitive::Bool => PrimitiveSchema::Bool,
+            SomeIpPrimitive::U8 => PrimitiveSchema::U8,
+            SomeIpPrimitive::U16 => PrimitiveSchema::U16,
+            SomeIpPrimitive::U32 => PrimitiveSchema::U32,
+            SomeIpPrimitive::U64 => PrimitiveSchema::U64,
+            SomeIpPrimitive::I8 => PrimitiveSchema::I8,
+            SomeIpPrimitive::I16 => PrimitiveSchema::I16,
+            SomeIpPrimitive::I32 => PrimitiveSchema::I32,
+            SomeIpPrimitive::I64 => PrimitiveSchema::I64,
+            SomeIpPrimitive::F32 => PrimitiveSchema::F32,
+            SomeIpPrimitive::F64 => PrimitiveSchema::F64,
+        }
+    }
+}
+
+impl From<PrimitiveSchema> for SomeIpPrimitive {
+    fn from(primitive: PrimitiveSchema) -> Self {
+        match primitive {
+            PrimitiveSchema::Bool => SomeIpPrimitive::Bool,
+            PrimitiveSchema::U8 => SomeIpPrimitive::U8,
+            PrimitiveSchema::U16 => SomeIpPrimitive::U16,
+            PrimitiveSchema::U32 => SomeIpPrimitive::U32,
+            PrimitiveSchema::U64 => SomeIpPrimitive::U64,
+            PrimitiveSchema::I8 => SomeIpPrimitive::I8,
+            PrimitiveSchema::I16 => SomeIpPrimitive::I16,
+            PrimitiveSchema::I32 => SomeIpPrimitive::I32,
+            PrimitiveSchema::I64 => SomeIpPrimitive::I64,
+            PrimitiveSchema::F32 => SomeIpPrimitive::F32,
+            PrimitiveSchema::F64 => SomeIpPrimitive::F64,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EnumValueSchema {
+    name: String,
+    value: Number,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FlagSchema {
+    name: String,
+    bit: u8,
+}
+
+#[derive(Serialize, Deserialize)]
+struct TransformationPropertiesSchema {
+    array_length_field_size: Option<u8>,
+    struct_length_field_size: Option<u8>,
+    string_length_field_size: Option<u8>,
+    alignment: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FieldSchema {
+    name: String,
+    id: Option<u16>,
+    default: Option<Value>,
+    #[serde(rename = "type")]
+    field_type: TypeSchema,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VariantSchema {
+    name: String,
+    selector: u32,
+    #[serde(rename = "type")]
+    variant_type: Option<TypeSchema>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TypeSchema {
+    Primitive {
+        #[serde(rename = "type")]
+        primitive: PrimitiveSchema,
+    },
+    Enum {
+        name: String,
+        raw_type: PrimitiveSchema,
+        values: Vec<EnumValueSchema>,
+        other: Option<String>,
+    },
+    Bitfield {
+        name: String,
+        raw_type: PrimitiveSchema,
+        flags: Vec<FlagSchema>,
+    },
+    String {
+        min_size: usize,
+        max_size: usize,
+        length_field_size: Option<u8>,
+        padding: bool,
+    },
+    Sequence {
+        min_elements: usize,
+        max_elements: usize,
+        length_field_size: Option<u8>,
+        element_type: Box<TypeSchema>,
+    },
+    Map {
+        min_elements: usize,
+        max_elements: usize,
+        length_field_size: Option<u8>,
+        key_type: Box<TypeSchema>,
+        value_type: Box<TypeSchema>,
+    },
+    Struct {
+        name: String,
+        uses_tlv_serialization: bool,
+        is_message_wrapper: bool,
+        extensible: bool,
+        length_field_size: Option<u8>,
+        transformation_properties: Option<TransformationPropertiesSchema>,
+        fields: Vec<FieldSchema>,
+    },
+    Union {
+        name: String,
+        length_field_size: Option<u8>,
+        type_field_size: Option<u8>,
+        variants: Vec<VariantSchema>,
+    },
+    UnknownMembers,
+}
+
+#[inline]
+fn length_field_size_to_schema(size: Option<LengthFieldSize>) -> Option<u8> {
+    size.map(|s| usize::from(s) as u8)
+}
+
+fn length_field_size_from_schema(size: Option<u8>) -> Result<Option<LengthFieldSize>> {
+    match size {
+        None => Ok(None),
+        Some(1) => Ok(Some(LengthFieldSize::OneByte)),
+        Some(2) => Ok(Some(LengthFieldSize::TwoBytes)),
+        Some(4) => Ok(Some(LengthFieldSize::FourBytes)),
+        Some(s) => Err(Error::InvalidSchema(format!(
+            "Length fields must have 1, 2 or 4 bytes, was {}",
+            s
+        ))),
+    }
+}
+
+fn alignment_from_schema(alignment: Option<u8>) -> Result<Option<Alignment>> {
+    match alignment {
+        None => Ok(None),
+        Some(8) => Ok(Some(Alignment::EightBits)),
+        Some(16) => Ok(Some(Alignment::SixteenBits)),
+        Some(32) => Ok(Some(Alignment::ThirtyTwoBits)),
+        Some(64) => Ok(Some(Alignment::SixtyFourBits)),
+        Some(a) => Err(Error::InvalidSchema(format!(
+            "The alignment must be 8, 16, 32 or 64 bits, was {}",
+            a
+        ))),
+    }
+}
+
+fn enum_value_to_schema(value: &SomeIpEnumValue) -> Number {
+    match *value {
+        SomeIpEnumValue::U8(v) => v.into(),
+        SomeIpEnumValue::U16(v) => v.into(),
+        SomeIpEnumValue::U32(v) => v.into(),
+        SomeIpEnumValue::U64(v) => v.into(),
+        SomeIpEnumValue::I8(v) => v.into(),
+        SomeIpEnumValue::I16(v) => v.into(),
+        SomeIpEnumValue::I32(v) => v.into(),
+        SomeIpEnumValue::I64(v) => v.into(),
+    }
+}
+
+fn enum_value_from_schema(value: &Number, raw_type: &SomeIpPrimitive) -> Result<SomeIpEnumValue> {
+    let unsigned = value.as_u64();
+    let signed = value.as_i64();
+    let converted = match raw_type {
+        SomeIpPrimitive::U8 => unsigned.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::U8)),
+        SomeIpPrimitive::U16 => unsigned.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::U16)),
+        SomeIpPrimitive::U32 => unsigned.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::U32)),
+        SomeIpPrimitive::U64 => unsigned.map(SomeIpEnumValue::U64),
+        SomeIpPrimitive::I8 => signed.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::I8)),
+        SomeIpPrimitive::I16 => signed.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::I16)),
+        SomeIpPrimitive::I32 => signed.and_then(|v| v.try_into().ok().map(SomeIpEnumValue::I32)),
+        SomeIpPrimitive::I64 => signed.map(SomeIpEnumValue::I64),
+        _ => None,
+    };
+    converted.ok_or_else(|| {
+        Error::InvalidSchema(format!(
+            "The enum value {} does not fit into {}",
+            value, raw_type
+        ))
+    })
+}
+
+fn default_to_schema(default: &SomeIpDefault) -> Value {
+    match *default {
+        SomeIpDefault::Bool(v) => v.into(),
+        SomeIpDefault::Unsigned(v) => v.into(),
+        SomeIpDefault::Signed(v) => v.into(),
+    }
+}
+
+fn default_from_schema(default: &Value) -> Result<SomeIpDefault> {
+    match default {
+        Value::Bool(v) => Ok(SomeIpDefault::Bool(*v)),
+        Value::Number(n) if n.is_u64() => Ok(SomeIpDefault::Unsigned(n.as_u64().unwrap())),
+        Value::Number(n) if n.is_i64() => Ok(SomeIpDefault::Signed(n.as_i64().unwrap())),
+        _ => Err(Error::InvalidSchema(format!(
+            "Defaults must be bools or integers, was {}",
+            default
+        ))),
+    }
+}
+
+impl From<&SomeIpType> for TypeSchema {
+    fn from(someip_type: &SomeIpType) -> Self {
+        match someip_type {
+            SomeIpType::Primitive(p) => TypeSchema::Primitive {
+                primitive: p.into(),
+            },
+            SomeIpType::Enum(e) => TypeSchema::Enum {
+                name: e.name.to_string(),
+                raw_type: (&e.raw_type).into(),
+                values: e
+                    .values
+                    .iter()
+                    .map(|(name, value)| EnumValueSchema {
+                        name: name.to_string(),
+                        value: enum_value_to_schema(value),
+                    })
+                    .collect(),
+                other: e.other.as_ref().map(|o| o.to_string()),
+            },
+            SomeIpType::Bitfield(b) => TypeSchema::Bitfield {
+                name: b.name.to_string(),
+                raw_type: (&b.raw_type).into(),
+                flags: b
+                    .flags
+                    .iter()
+                    .map(|(name, bit)| FlagSchema {
+                        name: name.to_string(),
+                        bit: *bit,
+                    })
+                    .collect(),
+            },
+            SomeIpType::String(s) => TypeSchema::String {
+                min_size: s.min_size,
+                max_size: s.max_size,
+                length_field_size: length_field_size_to_schema(s.length_field_size),
+                padding: s.padding,
+            },
+            SomeIpType::Sequence(s) => TypeSchema::Sequence {
+                min_elements: s.min_elements,
+                max_elements: s.max_elements,
+                length_field_size: length_field_size_to_schema(s.length_field_size),
+                element_type: Box::new((&*s.element_type).into()),
+            },
+            SomeIpType::Map(m) => TypeSchema::Map {
+                min_elements: m.min_elements,
+                max_elements: m.max_elements,
+                length_field_size: length_field_size_to_schema(m.length_field_size),
+                key_type: Box::new((&*m.key_type).into()),
+                value_type: Box::new((&*m.value_type).into()),
+            },
+            SomeIpType::Struct(s) => TypeSchema::Struct {
+                name: s.name.to_string(),
+                uses_tlv_serialization: s.uses_tlv_serialization,
+                is_message_wrapper: s.is_message_wrapper,
+                extensible: s.extensible,
+                length_field_size: length_field_size_to_schema(s.length_field_size),
+                transformation_properties: s.transformation_properties.as_ref().map(|p| {
+                    TransformationPropertiesSchema {
+                        array_length_field_size: length_field_size_to_schema(
+                            p.size_of_array_length_field,
+                        ),
+                        struct_length_field_size: length_field_size_to_schema(
+                            p.size_of_struct_length_field,
+                        ),
+                        string_length_field_size: length_field_size_to_schema(
+                            p.size_of_string_length_field,
+                        ),
+                        alignment: p.alignment.map(|a| (a.bytes() * 8) as u8),
+                    }
+                }),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|f| FieldSchema {
+                        name: f.name.to_string(),
+                        id: f.id,
+                        default: f.default.as_ref().map(default_to_schema),
+                        field_type: (&*f.field_type).into(),
+                    })
+                    .collect(),
+            },
+            SomeIpType::Union(u) => TypeSchema::Union {
+                name: u.name.to_string(),
+                length_field_size: length_field_size_to_schema(u.length_field_size),
+                type_field_size: length_field_size_to_schema(u.type_field_size),
+                variants: u
+                    .variants
+                    .iter()
+                    .map(|v| VariantSchema {
+                        name: v.name.to_string(),
+                        selector: v.selector,
+                        variant_type: v.variant_type.as_deref().map(TypeSchema::from),
+                    })
+                    .collect(),
+            },
+            SomeIpType::UnknownMembers => TypeSchema::UnknownMembers,
+        }
+    }
+}
+
+impl TypeSchema {
+    fn into_type(self) -> Result<SomeIpType> {
+        Ok(match self {
+            TypeSchema::Primitive { primitive } => SomeIpType::Primitive(primitive.into()),
+            TypeSchema::Enum {
+                name,
+                raw_type,
+                values,
+                other,
+            } => {
+                let raw_type = SomeIpPrimitive::from(raw_type);
+                let values = values
+                    .into_iter()
+                    .map(|v| Ok((v.name.into(), enum_value_from_schema(&v.value, &raw_type)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                SomeIpType::Enum(SomeIpEnum {
+                    name: name.into(),
+                    values: values.into(),
+                    raw_type,
+                    other: other.map(SomeIpRef::from),
+                })
+            }
+            TypeSchema::Bitfield {
+                name,
+                raw_type,
+                flags,
+            } => SomeIpType::Bitfield(SomeIpBitfield {
+                name: name.into(),
+                flags: flags
+                    .into_iter()
+                    .map(|f| (f.name.into(), f.bit))
+                    .collect::<Vec<_>>()
+                    .into(),
+                raw_type: raw_type.into(),
+            }),
+            TypeSchema::String {
+                min_size,
+                max_size,
+                length_field_size,
+                padding,
+            } => SomeIpType::String(SomeIpString {
+                max_size,
+                min_size,
+                length_field_size: length_field_size_from_schema(length_field_size)?,
+                padding,
+            }),
+            TypeSchema::Sequence {
+                min_elements,
+                max_elements,
+                length_field_size,
+                element_type,
+            } => SomeIpType::Sequence(SomeIpSequence {
+                max_elements,
+                min_elements,
+                element_type: SomeIpRef::new(element_type.into_type()?),
+                length_field_size: length_field_size_from_schema(length_field_size)?,
+            }),
+            TypeSchema::Map {
+                min_elements,
+                max_elements,
+                length_field_size,
+                key_type,
+                value_type,
+            } => SomeIpType::Map(SomeIpMap {
+                max_elements,
+                min_elements,
+                key_type: SomeIpRef::new(key_type.into_type()?),
+                value_type: SomeIpRef::new(value_type.into_type()?),
+                length_field_size: length_field_size_from_schema(length_field_size)?,
+            }),
+            TypeSchema::Struct {
+                name,
+                uses_tlv_serialization,
+                is_message_wrapper,
+                extensible,
+                length_field_size,
+                transformation_properties,
+                fields,
+            } => {
+                let transformation_properties = match transformation_properties {
+                    Some(p) => Some(SomeIpTransforationProperties {
+                        size_of_array_length_field: length_field_size_from_schema(
+                            p.array_length_field_size,
+                        )?,
+                        size_of_struct_length_field: length_field_size_from_schema(
+                            p.struct_length_field_size,
+                        )?,
+                        size_of_string_length_field: length_field_size_from_schema(
+                            p.string_length_field_size,
+                        )?,
+                        alignment: alignment_from_schema(p.alignment)?,
+                    }),
+                    None => None,
+                };
+                let fields = fields
+                    .into_iter()
+                    .map(|f| {
+                        Ok(SomeIpField {
+                            name: f.name.into(),
+                            id: f.id,
+                            field_type: SomeIpRef::new(f.field_type.into_type()?),
+                            default: f.default.as_ref().map(default_from_schema).transpose()?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                SomeIpType::Struct(SomeIpStruct {
+                    name: name.into(),
+                    fields: fields.into(),
+                    uses_tlv_serialization,
+                    is_message_wrapper,
+                    length_field_size: length_field_size_from_schema(length_field_size)?,
+                    transformation_properties,
+                    extensible,
+                })
+            }
+            TypeSchema::Union {
+                name,
+                length_field_size,
+                type_field_size,
+                variants,
+            } => {
+                let variants = variants
+                    .into_iter()
+                    .map(|v| {
+                        Ok(SomeIpUnionVariant {
+                            name: v.name.into(),
+                            selector: v.selector,
+                            variant_type: match v.variant_type {
+                                Some(t) => Some(SomeIpRef::new(t.into_type()?)),
+                                None => None,
+                            },
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                SomeIpType::Union(SomeIpUnion {
+                    name: name.into(),
+                    variants: variants.into(),
+                    length_field_size: length_field_size_from_schema(length_field_size)?,
+                    type_field_size: length_field_size_from_schema(type_field_size)?,
+                })
+            }
+            TypeSchema::UnknownMembers => SomeIpType::UnknownMembers,
+        })
+    }
+}
+
+#[cfg(test)]
+use super::sd::TransportProtocol;
+#[cfg(test)]
+use super::ser::{TestAligned, TestExtensible, TestUnion, TestUnknownMembers};
+#[cfg(test)]
+use super::SomeIp;
+
+#[cfg(test)]
+fn round_trip(someip_type: &SomeIpType) {
+    let json = to_json(someip_type);
+    let parsed = from_json(&json).unwrap();
+    parsed.verify();
+    assert_eq!(*someip_type, parsed);
+    assert_eq!(json, to_json(&parsed));
+}
+
+#[test]
+fn test_round_trip() {
+    round_trip(&u8::SOMEIP_TYPE);
+    round_trip(&f64::SOMEIP_TYPE);
+    round_trip(&TestAligned::SOMEIP_TYPE);
+    round_trip(&TestExtensible::SOMEIP_TYPE);
+    round_trip(&TestUnion::SOMEIP_TYPE);
+    round_trip(&TestUnknownMembers::SOMEIP_TYPE);
+    round_trip(&TransportProtocol::SOMEIP_TYPE);
+    round_trip(&<(i8, bool, i64)>::SOMEIP_TYPE);
+}
+
+#[test]
+fn test_nested() {
+    static BITFIELD: SomeIpType = SomeIpType::Bitfield(SomeIpBitfield {
+        name: SomeIpRef::Static("Flags"),
+        flags: SomeIpRef::Static(&[(SomeIpRef::Static("a"), 0), (SomeIpRef::Static("b"), 7)]),
+        raw_type: SomeIpPrimitive::U8,
+    });
+    static SIGNED_ENUM: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+        name: SomeIpRef::Static("Signed"),
+        values: SomeIpRef::Static(&[
+            (SomeIpRef::Static("Min"), SomeIpEnumValue::I64(i64::MIN)),
+            (SomeIpRef::Static("One"), SomeIpEnumValue::I64(1)),
+        ]),
+        raw_type: SomeIpPrimitive::I64,
+        other: None,
+    });
+    static MAP: SomeIpType = SomeIpType::Map(SomeIpMap {
+        max_elements: 10,
+        min_elements: 1,
+        key_type: SomeIpRef::Static(&SIGNED_ENUM),
+        value_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+            max_elements: 3,
+            min_elements: 3,
+            element_type: SomeIpRef::Static(&BITFIELD),
+            length_field_size: Some(LengthFieldSize::TwoBytes),
+        })),
+        length_field_size: Some(LengthFieldSize::FourBytes),
+    });
+    round_trip(&MAP);
+}
+
+#[test]
+fn test_json() {
+    assert_eq!(
+        r#"{
+  "kind": "sequence",
+  "min_elements": 0,
+  "max_elements": 4,
+  "length_field_size": 1,
+  "element_type": {
+    "kind": "string",
+    "min_size": 2,
+    "max_size": 2,
+    "length_field_size": null,
+    "padding": true
+  }
+}"#,
+        to_json(&SomeIpType::Sequence(SomeIpSequence {
+            max_elements: 4,
+            min_elements: 0,
+            element_type: SomeIpRef::new(SomeIpType::String(SomeIpString {
+                max_size: 2,
+                min_size: 2,
+                length_field_size: None,
+                padding: true,
+            })),
+            length_field_size: Some(LengthFieldSize::OneByte),
+        }))
+    );
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(
+        from_json(r#"{"kind": "complex"}"#),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "string", "min_size": 0, "max_size": 4, "length_field_size": 3, "padding": false}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "enum", "name": "E", "raw_type": "u8", "values": [{"name": "A", "value": 256}], "other": null}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "enum", "name": "E", "raw_type": "u8", "values": [{"name": "A", "value": -1}], "other": null}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "union", "name": "U", "length_field_size": null, "type_field_size": 0, "variants": [{"name": "A", "selector": 1, "type": null}]}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+}
+
+#[test]
+fn test_invalid_type() {
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "union", "name": "U", "length_field_size": null, "type_field_size": null, "variants": []}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "union", "name": "U", "length_field_size": null, "type_field_size": 1, "variants": [{"name": "A", "selector": 256, "type": null}]}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "string", "min_size": 5, "max_size": 4, "length_field_size": null, "padding": false}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "sequence", "min_elements": 0, "max_elements": 4, "length_field_size": null, "element_type": {"kind": "string", "min_size": 0, "max_size": 4, "length_field_size": null, "padding": true}}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "bitfield", "name": "B", "raw_type": "u8", "flags": [{"name": "a", "bit": 8}]}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+    assert!(matches!(
+        from_json(
+            r#"{"kind": "struct", "name": "S", "uses_tlv_serialization": true, "is_message_wrapper": false, "extensible": false, "length_field_size": null, "transformation_properties": null, "fields": [{"name": "a", "id": null, "default": null, "type": {"kind": "primitive", "type": "u8"}}]}"#
+        ),
+        Err(Error::InvalidSchema(_))
+    ));
+}
@@ -0,0 +1,1013 @@
+//! Provides the en/decoding of SOME/IP Service Discovery (SD) messages.
+//!
+//! SD messages are someip messages with the service id [SD_SERVICE_ID] and method id [SD_METHOD_ID]
+//! whose payload contains a list of entries and a list of options. On the wire entries reference their options
+//! by indices into the shared options array, an [SdMessage] instead stores the resolved options with every entry.
+//! The client and server state machines are provided by the [state_machine] module.
+//! ```
+//! use serde_someip::sd::*;
+//! use std::net::Ipv4Addr;
+//!
+//! let message = SdMessage {
+//!     reboot: true,
+//!     unicast: true,
+//!     entries: vec![SdEntry::OfferService(ServiceEntry {
+//!         service_id: 0x1234,
+//!         instance_id: 1,
+//!         major_version: 1,
+//!         ttl: 3,
+//!         minor_version: 0,
+//!         options: vec![SdOption::Ipv4Endpoint {
+//!             address: Ipv4Addr::new(192, 168, 0, 1),
+//!             protocol: TransportProtocol::Udp,
+//!             port: 30509,
+//!         }],
+//!     })],
+//! };
+//! let payload = message.to_payload().unwrap();
+//! assert_eq!(message, SdMessage::from_payload(&payload).unwrap());
+//! ```
+
+use super::error::{Error, Result};
+use super::header::{MessageType, SomeIpHeader, PROTOCOL_VERSION};
+use super::length_fields::LengthFieldSize;
+use super::options::{ActionOnTooMuchData, LengthFieldSizeSelection, SomeIpOptions};
+use super::types::*;
+
+use serde::{Deserialize, Serialize};
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+pub mod state_machine;
+
+/// The service id of all SD messages.
+pub const SD_SERVICE_ID: u16 = 0xFFFF;
+/// The method id of all SD messages.
+pub const SD_METHOD_ID: u16 = 0x8100;
+/// The interface version of all SD messages.
+pub const SD_INTERFACE_VERSION: u8 = 1;
+/// The TTL of entries that are valid until the next reboot.
+pub const TTL_INFINITE: u32 = 0xFF_FFFF;
+
+const REBOOT_FLAG: u32 = 0x8000_0000;
+const UNICAST_FLAG: u32 = 0x4000_0000;
+
+const FIND_SERVICE: u8 = 0x00;
+const OFFER_SERVICE: u8 = 0x01;
+const SUBSCRIBE_EVENTGROUP: u8 = 0x06;
+const SUBSCRIBE_EVENTGROUP_ACK: u8 = 0x07;
+
+const CONFIGURATION_OPTION: u8 = 0x01;
+const LOAD_BALANCING_OPTION: u8 = 0x02;
+const IPV4_ENDPOINT_OPTION: u8 = 0x04;
+const IPV6_ENDPOINT_OPTION: u8 = 0x06;
+const IPV4_MULTICAST_OPTION: u8 = 0x14;
+const IPV6_MULTICAST_OPTION: u8 = 0x16;
+const IPV4_SD_ENDPOINT_OPTION: u8 = 0x24;
+const IPV6_SD_ENDPOINT_OPTION: u8 = 0x26;
+
+//the number of options in a run is stored in four bits
+const MAX_OPTIONS_PER_RUN: usize = 0xF;
+
+//SD always uses big endian and four byte length fields for the entries and options arrays
+struct SdOptions;
+
+impl SomeIpOptions for SdOptions {
+    const SERIALIZER_LENGTH_FIELD_SIZE_SELECTION: LengthFieldSizeSelection =
+        LengthFieldSizeSelection::AsConfigured;
+    const DESERIALIZER_ACTION_ON_TOO_MUCH_DATA: ActionOnTooMuchData = ActionOnTooMuchData::Fail;
+}
+
+/// Creates the someip header for an SD message with the given session id.
+pub fn sd_header(session_id: u16) -> SomeIpHeader {
+    let mut header = SomeIpHeader::new(SD_SERVICE_ID, SD_METHOD_ID, MessageType::Notification);
+    header.session_id = session_id;
+    header.protocol_version = PROTOCOL_VERSION;
+    header.interface_version = SD_INTERFACE_VERSION;
+    header
+}
+
+/// The transport protocol used by an endpoint option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TransportProtocol {
+    /// TCP
+    Tcp,
+    /// UDP
+    Udp,
+    /// Any other protocol number.
+    Other(u8),
+}
+
+impl SomeIp for TransportProtocol {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+        name: SomeIpRef::Static("TransportProtocol"),
+        raw_type: SomeIpPrimitive::U8,
+        values: SomeIpRef::Static(&[
+            (SomeIpRef::Static("Tcp"), SomeIpEnumValue::U8(0x06)),
+            (SomeIpRef::Static("Udp"), SomeIpEnumValue::U8(0x11)),
+        ]),
+        other: Some(SomeIpRef::Static("Other")),
+    });
+}
+
+/// An option of an SD entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdOption {
+    /// Arbitrary configuration strings, usually of the form `key=value`.
+    Configuration(Vec<String>),
+    /// The priority and weight used to select between multiple instances of a service.
+    LoadBalancing {
+        /// Lower values mean higher priority.
+        priority: u16,
+        /// Higher values mean a higher chance of being selected.
+        weight: u16,
+    },
+    /// The unicast endpoint of a service or client.
+    Ipv4Endpoint {
+        /// The ip address of the endpoint.
+        address: Ipv4Addr,
+        /// The transport protocol of the endpoint.
+        protocol: TransportProtocol,
+        /// The port of the endpoint.
+        port: u16,
+    },
+    /// The unicast endpoint of a service or client.
+    Ipv6Endpoint {
+        /// The ip address of the endpoint.
+        address: Ipv6Addr,
+        /// The transport protocol of the endpoint.
+        protocol: TransportProtocol,
+        /// The port of the endpoint.
+        port: u16,
+    },
+    /// The multicast address an eventgroup is sent to.
+    Ipv4Multicast {
+        /// The multicast address.
+        address: Ipv4Addr,
+        /// The transport protocol, should be UDP.
+        protocol: TransportProtocol,
+        /// The port.
+        port: u16,
+    },
+    /// The multicast address an eventgroup is sent to.
+    Ipv6Multicast {
+        /// The multicast address.
+        address: Ipv6Addr,
+        /// The transport protocol, should be UDP.
+        protocol: TransportProtocol,
+        /// The port.
+        port: u16,
+    },
+    /// The endpoint used for SD messages.
+    Ipv4SdEndpoint {
+        /// The ip address of the endpoint.
+        address: Ipv4Addr,
+        /// The transport protocol, should be UDP.
+        protocol: TransportProtocol,
+        /// The port of the endpoint.
+        port: u16,
+    },
+    /// The endpoint used for SD messages.
+    Ipv6SdEndpoint {
+        /// The ip address of the endpoint.
+        address: Ipv6Addr,
+        /// The transport protocol, should be UDP.
+        protocol: TransportProtocol,
+        /// The port of the endpoint.
+        port: u16,
+    },
+    /// An option of unknown type, kept so that it can be encoded again.
+    Unknown {
+        /// The type of the option.
+        option_type: u8,
+        /// The data following the type field, including the discardable flag.
+        data: Vec<u8>,
+    },
+}
+
+/// A service entry used to find or offer a service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceEntry {
+    /// The id of the service.
+    pub service_id: u16,
+    /// The id of the instance, `0xFFFF` means any instance.
+    pub instance_id: u16,
+    /// The major version of the service, `0xFF` means any version.
+    pub major_version: u8,
+    /// The time to live in seconds, `0` stops an offer.
+    pub ttl: u32,
+    /// The minor version of the service, `0xFFFFFFFF` means any version.
+    pub minor_version: u32,
+    /// The options of this entry.
+    pub options: Vec<SdOption>,
+}
+
+/// An eventgroup entry used to subscribe to an eventgroup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventgroupEntry {
+    /// The id of the service.
+    pub service_id: u16,
+    /// The id of the instance.
+    pub instance_id: u16,
+    /// The major version of the service.
+    pub major_version: u8,
+    /// The time to live in seconds, `0` stops a subscription or rejects it.
+    pub ttl: u32,
+    /// Distinguishes multiple subscriptions to the same eventgroup, only the lower four bits are used.
+    pub counter: u8,
+    /// The id of the eventgroup.
+    pub eventgroup_id: u16,
+    /// The options of this entry.
+    pub options: Vec<SdOption>,
+}
+
+/// An entry of an SD message.
+///
+/// Entries with a TTL of `0` stop the offer or subscription or reject the subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SdEntry {
+    /// Searches for a service.
+    FindService(ServiceEntry),
+    /// Offers a service, or stops the offer.
+    OfferService(ServiceEntry),
+    /// Subscribes to an eventgroup, or stops the subscription.
+    SubscribeEventgroup(EventgroupEntry),
+    /// Acknowledges a subscription, or rejects it.
+    SubscribeEventgroupAck(EventgroupEntry),
+}
+
+impl SdEntry {
+    /// The time to live of this entry.
+    #[inline]
+    pub fn ttl(&self) -> u32 {
+        match self {
+            SdEntry::FindService(e) | SdEntry::OfferService(e) => e.ttl,
+            SdEntry::SubscribeEventgroup(e) | SdEntry::SubscribeEventgroupAck(e) => e.ttl,
+        }
+    }
+
+    /// The options of this entry.
+    #[inline]
+    pub fn options(&self) -> &[SdOption] {
+        match self {
+            SdEntry::FindService(e) | SdEntry::OfferService(e) => &e.options,
+            SdEntry::SubscribeEventgroup(e) | SdEntry::SubscribeEventgroupAck(e) => &e.options,
+        }
+    }
+}
+
+/// The payload of an SD message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdMessage {
+    /// Set until the session id wraps around for the first time after a reboot.
+    pub reboot: bool,
+    /// Set if the sender supports receiving unicast messages.
+    pub unicast: bool,
+    /// The entries of this message.
+    pub entries: Vec<SdEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SdPayloadWire {
+    flags: u32,
+    entries: Vec<EntryWire>,
+    options: Vec<u8>,
+}
+
+impl SomeIp for SdPayloadWire {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("SdPayload"),
+        fields: SomeIpRef::Static(&[
+            SomeIpField {
+                name: SomeIpRef::Static("flags"),
+                id: None,
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("entries"),
+                id: None,
+                field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                    min_elements: 0,
+                    max_elements: u16::MAX as usize,
+                    element_type: SomeIpRef::Static(&EntryWire::SOMEIP_TYPE),
+                    length_field_size: Some(LengthFieldSize::FourBytes),
+                })),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("options"),
+                id: None,
+                field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                    min_elements: 0,
+                    max_elements: u16::MAX as usize,
+                    element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                    length_field_size: Some(LengthFieldSize::FourBytes),
+                })),
+                default: None,
+            },
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: true,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+//service and eventgroup entries share the same layout except for the last four bytes
+#[derive(Serialize, Deserialize)]
+struct EntryWire {
+    entry_type: u8,
+    first_option: u8,
+    second_option: u8,
+    number_of_options: u8,
+    service_id: u16,
+    instance_id: u16,
+    major_version_ttl: u32,
+    data: u32,
+}
+
+macro_rules! entry_wire_field {
+    ($name:literal, $ty:ty) => {
+        SomeIpField {
+            name: SomeIpRef::Static($name),
+            id: None,
+            field_type: SomeIpRef::Static(&<$ty>::SOMEIP_TYPE),
+            default: None,
+        }
+    };
+}
+
+impl SomeIp for EntryWire {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("SdEntry"),
+        fields: SomeIpRef::Static(&[
+            entry_wire_field!("entry_type", u8),
+            entry_wire_field!("first_option", u8),
+            entry_wire_field!("second_option", u8),
+            entry_wire_field!("number_of_options", u8),
+            entry_wire_field!("service_id", u16),
+            entry_wire_field!("instance_id", u16),
+            entry_wire_field!("major_version_ttl", u32),
+            entry_wire_field!("data", u32),
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+#[derive(Serialize, Deserialize)]
+struct Ipv4EndpointWire {
+    flags: u8,
+    address: u32,
+    reserved: u8,
+    protocol: TransportProtocol,
+    port: u16,
+}
+
+impl SomeIp for Ipv4EndpointWire {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("Ipv4EndpointOption"),
+        fields: SomeIpRef::Static(&[
+            entry_wire_field!("flags", u8),
+            entry_wire_field!("address", u32),
+            entry_wire_field!("reserved", u8),
+            entry_wire_field!("protocol", TransportProtocol),
+            entry_wire_field!("port", u16),
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+#[derive(Serialize, Deserialize)]
+struct Ipv6EndpointWire {
+    flags: u8,
+    address: (u64, u64),
+    reserved: u8,
+    protocol: TransportProtocol,
+    port: u16,
+}
+
+impl SomeIp for Ipv6EndpointWire {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("Ipv6EndpointOption"),
+        fields: SomeIpRef::Static(&[
+            entry_wire_field!("flags", u8),
+            entry_wire_field!("address", (u64, u64)),
+            entry_wire_field!("reserved", u8),
+            entry_wire_field!("protocol", TransportProtocol),
+            entry_wire_field!("port", u16),
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+#[derive(Serialize, Deserialize)]
+struct LoadBalancingWire {
+    flags: u8,
+    priority: u16,
+    weight: u16,
+}
+
+impl SomeIp for LoadBalancingWire {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("LoadBalancingOption"),
+        fields: SomeIpRef::Static(&[
+            entry_wire_field!("flags", u8),
+            entry_wire_field!("priority", u16),
+            entry_wire_field!("weight", u16),
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+#[inline]
+fn invalid(message: String) -> Error {
+    Error::InvalidSdMessage(message)
+}
+
+impl SdOption {
+    fn decode(option_type: u8, data: &[u8]) -> Result<SdOption> {
+        let ipv4 = |data: &[u8]| -> Result<(Ipv4Addr, TransportProtocol, u16)> {
+            let wire = super::from_slice::<SdOptions, Ipv4EndpointWire>(data)?;
+            Ok((Ipv4Addr::from(wire.address), wire.protocol, wire.port))
+        };
+        let ipv6 = |data: &[u8]| -> Result<(Ipv6Addr, TransportProtocol, u16)> {
+            let wire = super::from_slice::<SdOptions, Ipv6EndpointWire>(data)?;
+            let address = (wire.address.0 as u128) << 64 | wire.address.1 as u128;
+            Ok((Ipv6Addr::from(address), wire.protocol, wire.port))
+        };
+        Ok(match option_type {
+            CONFIGURATION_OPTION => SdOption::Configuration(decode_configuration(data)?),
+            LOAD_BALANCING_OPTION => {
+                let wire = super::from_slice::<SdOptions, LoadBalancingWire>(data)?;
+                SdOption::LoadBalancing {
+                    priority: wire.priority,
+                    weight: wire.weight,
+                }
+            }
+            IPV4_ENDPOINT_OPTION => {
+                let (address, protocol, port) = ipv4(data)?;
+                SdOption::Ipv4Endpoint {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            IPV6_ENDPOINT_OPTION => {
+                let (address, protocol, port) = ipv6(data)?;
+                SdOption::Ipv6Endpoint {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            IPV4_MULTICAST_OPTION => {
+                let (address, protocol, port) = ipv4(data)?;
+                SdOption::Ipv4Multicast {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            IPV6_MULTICAST_OPTION => {
+                let (address, protocol, port) = ipv6(data)?;
+                SdOption::Ipv6Multicast {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            IPV4_SD_ENDPOINT_OPTION => {
+                let (address, protocol, port) = ipv4(data)?;
+                SdOption::Ipv4SdEndpoint {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            IPV6_SD_ENDPOINT_OPTION => {
+                let (address, protocol, port) = ipv6(data)?;
+                SdOption::Ipv6SdEndpoint {
+                    address,
+                    protocol,
+                    port,
+                }
+            }
+            option_type => SdOption::Unknown {
+                option_type,
+                data: data.to_vec(),
+            },
+        })
+    }
+
+    //returns the type and the data following the type field
+    fn encode(&self) -> Result<(u8, Vec<u8>)> {
+        let ipv4 = |address: &Ipv4Addr, protocol: &TransportProtocol, port: &u16| {
+            super::to_vec::<SdOptions, _>(&Ipv4EndpointWire {
+                flags: 0,
+                address: u32::from(*address),
+                reserved: 0,
+                protocol: *protocol,
+                port: *port,
+            })
+        };
+        let ipv6 = |address: &Ipv6Addr, protocol: &TransportProtocol, port: &u16| {
+            let address = u128::from(*address);
+            super::to_vec::<SdOptions, _>(&Ipv6EndpointWire {
+                flags: 0,
+                address: ((address >> 64) as u64, address as u64),
+                reserved: 0,
+                protocol: *protocol,
+                port: *port,
+            })
+        };
+        Ok(match self {
+            SdOption::Configuration(items) => (CONFIGURATION_OPTION, encode_configuration(items)?),
+            SdOption::LoadBalancing { priority, weight } => (
+                LOAD_BALANCING_OPTION,
+                super::to_vec::<SdOptions, _>(&LoadBalancingWire {
+                    flags: 0,
+                    priority: *priority,
+                    weight: *weight,
+                })?,
+            ),
+            SdOption::Ipv4Endpoint {
+                address,
+                protocol,
+                port,
+            } => (IPV4_ENDPOINT_OPTION, ipv4(address, protocol, port)?),
+            SdOption::Ipv6Endpoint {
+                address,
+                protocol,
+                port,
+            } => (IPV6_ENDPOINT_OPTION, ipv6(address, protocol, port)?),
+            SdOption::Ipv4Multicast {
+                address,
+                protocol,
+                port,
+            } => (IPV4_MULTICAST_OPTION, ipv4(address, protocol, port)?),
+            SdOption::Ipv6Multicast {
+                address,
+                protocol,
+                port,
+            } => (IPV6_MULTICAST_OPTION, ipv6(address, protocol, port)?),
+            SdOption::Ipv4SdEndpoint {
+                address,
+                protocol,
+                port,
+            } => (IPV4_SD_ENDPOINT_OPTION, ipv4(address, protocol, port)?),
+            SdOption::Ipv6SdEndpoint {
+                address,
+                protocol,
+                port,
+            } => (IPV6_SD_ENDPOINT_OPTION, ipv6(address, protocol, port)?),
+            SdOption::Unknown { option_type, data } => (*option_type, data.clone()),
+        })
+    }
+}
+
+//configuration strings are a sequence of strings each prefixed by a one byte length and terminated by a zero length
+fn decode_configuration(data: &[u8]) -> Result<Vec<String>> {
+    //skip the discardable flag
+    let mut remaining = data.get(1..).ok_or(Error::TooShort)?;
+    let mut items = Vec::new();
+    loop {
+        let (len, rest) = remaining.split_first().ok_or(Error::TooShort)?;
+        let len = *len as usize;
+        if len == 0 {
+            if !rest.is_empty() {
+                return Err(Error::NotAllBytesConsumed(rest.len()));
+            }
+            return Ok(items);
+        }
+        if rest.len() < len {
+            return Err(Error::TooShort);
+        }
+        items.push(String::from_utf8(rest[..len].to_vec())?);
+        remaining = &rest[len..];
+    }
+}
+
+fn encode_configuration(items: &[String]) -> Result<Vec<u8>> {
+    let mut data = vec![0];
+    for item in items {
+        if item.is_empty() || item.len() > u8::MAX as usize {
+            return Err(invalid(format!(
+                "Configuration strings must have a length of 1 to 255 bytes, len={}",
+                item.len()
+            )));
+        }
+        data.push(item.len() as u8);
+        data.extend_from_slice(item.as_bytes());
+    }
+    data.push(0);
+    Ok(data)
+}
+
+//finds the index at which `run` starts in `options`
+fn find_run(options: &[SdOption], run: &[SdOption]) -> Option<usize> {
+    if run.is_empty() {
+        return Some(0);
+    }
+    options.windows(run.len()).position(|w| w == run)
+}
+
+//finds or appends the options of an entry and returns the two runs as (index, count)
+fn add_options(options: &mut Vec<SdOption>, entry: &[SdOption]) -> Result<[(u8, u8); 2]> {
+    if entry.len() > 2 * MAX_OPTIONS_PER_RUN {
+        return Err(invalid(format!(
+            "An entry can reference at most {} options, was={}",
+            2 * MAX_OPTIONS_PER_RUN,
+            entry.len()
+        )));
+    }
+    let min_split = entry.len().saturating_sub(MAX_OPTIONS_PER_RUN);
+    let max_split = entry.len().min(MAX_OPTIONS_PER_RUN);
+    let splits = || {
+        (min_split..=max_split)
+            .rev()
+            .map(|split| entry.split_at(split))
+    };
+    //reuse options that are already present, for example options shared by multiple entries,
+    //first try to find both runs, then one run while appending the other and finally append all options
+    let (first, second) = if let Some(runs) = splits().find_map(|(first, second)| {
+        Some((
+            (find_run(options, first)?, first.len()),
+            (find_run(options, second)?, second.len()),
+        ))
+    }) {
+        runs
+    } else if let Some((first, second)) = splits().find(|(first, second)| {
+        let found = |run: &[SdOption]| !run.is_empty() && find_run(options, run).is_some();
+        found(first) || found(second)
+    }) {
+        let mut find_or_append = |run: &[SdOption]| {
+            let index = find_run(options, run).unwrap_or_else(|| {
+                options.extend_from_slice(run);
+                options.len() - run.len()
+            });
+            (index, run.len())
+        };
+        (find_or_append(first), find_or_append(second))
+    } else {
+        let index = options.len();
+        options.extend_from_slice(entry);
+        (
+            (index, max_split),
+            (index + max_split, entry.len() - max_split),
+        )
+    };
+    let to_index = |(index, len): (usize, usize)| -> Result<(u8, u8)> {
+        if len == 0 {
+            Ok((0, 0))
+        } else if index > u8::MAX as usize {
+            Err(invalid(format!(
+                "An SD message can contain at most 256 options, index={}",
+                index
+            )))
+        } else {
+            Ok((index as u8, len as u8))
+        }
+    };
+    Ok([to_index(first)?, to_index(second)?])
+}
+
+impl SdMessage {
+    /// Encodes the payload of this SD message, use [sd_header] to create the matching header.
+    ///
+    /// Identical runs of options are shared between entries.
+    pub fn to_payload(&self) -> Result<Vec<u8>> {
+        let mut options = Vec::new();
+        let mut entries = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let runs = add_options(&mut options, entry.options())?;
+            let (entry_type, service_id, instance_id, major_version, ttl, data) = match entry {
+                SdEntry::FindService(e) | SdEntry::OfferService(e) => {
+                    let entry_type = if let SdEntry::FindService(_) = entry {
+                        FIND_SERVICE
+                    } else {
+                        OFFER_SERVICE
+                    };
+                    (
+                        entry_type,
+                        e.service_id,
+                        e.instance_id,
+                        e.major_version,
+                        e.ttl,
+                        e.minor_version,
+                    )
+                }
+                SdEntry::SubscribeEventgroup(e) | SdEntry::SubscribeEventgroupAck(e) => {
+                    let entry_type = if let SdEntry::SubscribeEventgroup(_) = entry {
+                        SUBSCRIBE_EVENTGROUP
+                    } else {
+                        SUBSCRIBE_EVENTGROUP_ACK
+                    };
+                    (
+                        entry_type,
+                        e.service_id,
+                        e.instance_id,
+                        e.major_version,
+                        e.ttl,
+                        ((e.counter & 0xF) as u32) << 16 | e.eventgroup_id as u32,
+                    )
+                }
+            };
+            if ttl > TTL_INFINITE {
+                return Err(invalid(format!(
+                    "The TTL must fit into 24 bits, was={}",
+                    ttl
+                )));
+            }
+            entries.push(EntryWire {
+                entry_type,
+                first_option: runs[0].0,
+                second_option: runs[1].0,
+                number_of_options: runs[0].1 << 4 | runs[1].1,
+                service_id,
+                instance_id,
+                major_version_ttl: (major_version as u32) << 24 | ttl,
+                data,
+            });
+        }
+
+        let mut encoded_options = Vec::new();
+        for option in &options {
+            let (option_type, data) = option.encode()?;
+            if data.len() > u16::MAX as usize {
+                return Err(Error::TooLong {
+                    actual_length: data.len(),
+                    length_field_size: LengthFieldSize::TwoBytes,
+                });
+            }
+            encoded_options.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            encoded_options.push(option_type);
+            encoded_options.extend_from_slice(&data);
+        }
+
+        let mut flags = 0;
+        if self.reboot {
+            flags |= REBOOT_FLAG;
+        }
+        if self.unicast {
+            flags |= UNICAST_FLAG;
+        }
+        super::to_vec::<SdOptions, _>(&SdPayloadWire {
+            flags,
+            entries,
+            options: encoded_options,
+        })
+    }
+
+    /// Decodes the payload of an SD message.
+    pub fn from_payload(payload: &[u8]) -> Result<SdMessage> {
+        let wire = super::from_slice::<SdOptions, SdPayloadWire>(payload)?;
+
+        let mut options = Vec::new();
+        let mut remaining = wire.options.as_slice();
+        while !remaining.is_empty() {
+            if remaining.len() < 3 {
+                return Err(Error::TooShort);
+            }
+            let len = u16::from_be_bytes([remaining[0], remaining[1]]) as usize;
+            let option_type = remaining[2];
+            let data = remaining.get(3..3 + len).ok_or(Error::TooShort)?;
+            options.push(SdOption::decode(option_type, data)?);
+            remaining = &remaining[3 + len..];
+        }
+
+        let run = |index: u8, len: u8| -> Result<&[SdOption]> {
+            let (index, len) = (index as usize, len as usize);
+            options.get(index..index + len).ok_or_else(|| {
+                invalid(format!(
+                    "Option run {}..{} is out of bounds for {} options",
+                    index,
+                    index + len,
+                    options.len()
+                ))
+            })
+        };
+
+        let mut entries = Vec::with_capacity(wire.entries.len());
+        for e in wire.entries {
+            let mut entry_options = run(e.first_option, e.number_of_options >> 4)?.to_vec();
+            entry_options.extend_from_slice(run(e.second_option, e.number_of_options & 0xF)?);
+            let major_version = (e.major_version_ttl >> 24) as u8;
+            let ttl = e.major_version_ttl & TTL_INFINITE;
+            let service = || ServiceEntry {
+                service_id: e.service_id,
+                instance_id: e.instance_id,
+                major_version,
+                ttl,
+                minor_version: e.data,
+                options: entry_options.clone(),
+            };
+            let eventgroup = || EventgroupEntry {
+                service_id: e.service_id,
+                instance_id: e.instance_id,
+                major_version,
+                ttl,
+                counter: (e.data >> 16) as u8 & 0xF,
+                eventgroup_id: e.data as u16,
+                options: entry_options.clone(),
+            };
+            entries.push(match e.entry_type {
+                FIND_SERVICE => SdEntry::FindService(service()),
+                OFFER_SERVICE => SdEntry::OfferService(service()),
+                SUBSCRIBE_EVENTGROUP => SdEntry::SubscribeEventgroup(eventgroup()),
+                SUBSCRIBE_EVENTGROUP_ACK => SdEntry::SubscribeEventgroupAck(eventgroup()),
+                entry_type => {
+                    return Err(invalid(format!("Unknown entry type {:#X}", entry_type)));
+                }
+            });
+        }
+
+        Ok(SdMessage {
+            reboot: wire.flags & REBOOT_FLAG != 0,
+            unicast: wire.flags & UNICAST_FLAG != 0,
+            entries,
+        })
+    }
+}
+
+#[cfg(test)]
+fn test_endpoint() -> SdOption {
+    SdOption::Ipv4Endpoint {
+        address: Ipv4Addr::new(192, 168, 0, 1),
+        protocol: TransportProtocol::Udp,
+        port: 0x3456,
+    }
+}
+
+#[test]
+fn test_offer_service() {
+    let message = SdMessage {
+        reboot: true,
+        unicast: true,
+        entries: vec![SdEntry::OfferService(ServiceEntry {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 2,
+            ttl: 3,
+            minor_version: 0x10,
+            options: vec![test_endpoint()],
+        })],
+    };
+    let expected = vec![
+        0xC0, 0, 0, 0, //flags
+        0, 0, 0, 16, //entries length
+        0x01, 0, 0, 0x10, 0x12, 0x34, 0, 1, 2, 0, 0, 3, 0, 0, 0, 0x10, //offer service
+        0, 0, 0, 12, //options length
+        0, 9, 0x04, 0, 192, 168, 0, 1, 0, 0x11, 0x34, 0x56, //ipv4 endpoint
+    ];
+    assert_eq!(expected, message.to_payload().unwrap());
+    assert_eq!(message, SdMessage::from_payload(&expected).unwrap());
+}
+
+#[test]
+fn test_subscribe_eventgroup() {
+    let message = SdMessage {
+        reboot: false,
+        unicast: true,
+        entries: vec![
+            SdEntry::SubscribeEventgroup(EventgroupEntry {
+                service_id: 0x1234,
+                instance_id: 0x0001,
+                major_version: 2,
+                ttl: TTL_INFINITE,
+                counter: 1,
+                eventgroup_id: 0x4321,
+                options: vec![test_endpoint()],
+            }),
+            SdEntry::SubscribeEventgroupAck(EventgroupEntry {
+                service_id: 0x1234,
+                instance_id: 0x0001,
+                major_version: 2,
+                ttl: 0,
+                counter: 0,
+                eventgroup_id: 0x4321,
+                options: vec![],
+            }),
+        ],
+    };
+    let expected = vec![
+        0x40, 0, 0, 0, //flags
+        0, 0, 0, 32, //entries length
+        0x06, 0, 0, 0x10, 0x12, 0x34, 0, 1, 2, 0xFF, 0xFF, 0xFF, 0, 1, 0x43, 0x21, //subscribe
+        0x07, 0, 0, 0, 0x12, 0x34, 0, 1, 2, 0, 0, 0, 0, 0, 0x43, 0x21, //nack
+        0, 0, 0, 12, //options length
+        0, 9, 0x04, 0, 192, 168, 0, 1, 0, 0x11, 0x34, 0x56, //ipv4 endpoint
+    ];
+    assert_eq!(expected, message.to_payload().unwrap());
+    assert_eq!(message, SdMessage::from_payload(&expected).unwrap());
+}
+
+#[test]
+fn test_shared_options() {
+    let config = SdOption::Configuration(vec!["a=b".into(), "c".into()]);
+    let multicast = SdOption::Ipv6Multicast {
+        address: "ff14::1".parse().unwrap(),
+        protocol: TransportProtocol::Udp,
+        port: 30490,
+    };
+    let service = |options| {
+        SdEntry::OfferService(ServiceEntry {
+            service_id: 0x1234,
+            instance_id: 0x0001,
+            major_version: 1,
+            ttl: 3,
+            minor_version: 0,
+            options,
+        })
+    };
+    let message = SdMessage {
+        reboot: false,
+        unicast: false,
+        entries: vec![
+            service(vec![test_endpoint(), config.clone()]),
+            service(vec![multicast.clone(), config.clone()]),
+            service(vec![config.clone()]),
+        ],
+    };
+    let payload = message.to_payload().unwrap();
+    //the configuration is shared by all entries, the second entry references it via its second option run
+    assert_eq!(
+        &[0, 0, 0x20, 2, 1, 0x11, 1, 0, 0x10],
+        &[&payload[9..12], &payload[25..28], &payload[41..44]].concat()[..]
+    );
+    assert_eq!(message, SdMessage::from_payload(&payload).unwrap());
+}
+
+#[test]
+fn test_unknown_options_and_protocols() {
+    let message = SdMessage {
+        reboot: false,
+        unicast: false,
+        entries: vec![SdEntry::FindService(ServiceEntry {
+            service_id: 0x1234,
+            instance_id: 0xFFFF,
+            major_version: 0xFF,
+            ttl: 3,
+            minor_version: 0xFFFF_FFFF,
+            options: vec![
+                SdOption::Unknown {
+                    option_type: 0x42,
+                    data: vec![0x80, 1, 2],
+                },
+                SdOption::Ipv4SdEndpoint {
+                    address: Ipv4Addr::new(10, 0, 0, 1),
+                    protocol: TransportProtocol::Other(0x84),
+                    port: 30490,
+                },
+                SdOption::LoadBalancing {
+                    priority: 1,
+                    weight: 2,
+                },
+            ],
+        })],
+    };
+    let payload = message.to_payload().unwrap();
+    assert_eq!(message, SdMessage::from_payload(&payload).unwrap());
+}
+
+#[test]
+fn test_invalid_messages() {
+    let mut payload = vec![
+        0, 0, 0, 0, //flags
+        0, 0, 0, 16, //entries length
+        0x01, 1, 0, 0x10, 0x12, 0x34, 0, 1, 2, 0, 0, 3, 0, 0, 0, 0x10, //offer service
+        0, 0, 0, 0, //options length
+    ];
+    assert!(matches!(
+        SdMessage::from_payload(&payload),
+        Err(Error::InvalidSdMessage(_))
+    ));
+    payload[8] = 0x03;
+    payload[9] = 0;
+    payload[11] = 0;
+    assert!(matches!(
+        SdMessage::from_payload(&payload),
+        Err(Error::InvalidSdMessage(_))
+    ));
+    assert!(matches!(
+        SdMessage::from_payload(&payload[..20]),
+        Err(Error::TooShort)
+    ));
+}
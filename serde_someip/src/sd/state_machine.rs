@@ -0,0 +1,962 @@
+//! Provides the client and server state machines of SOME/IP service discovery.
+//!
+//! The state machines neither do any io nor read the system time, instead they are driven by
+//! calls to `handle_message` with the decoded [SdMessage]s received from a peer and calls to `poll` with the
+//! current time. Both return the entries that should be sent as [SdOutput]s, it is up to the caller to
+//! combine them into [SdMessage]s and send them. [next_deadline](SdServer::next_deadline) returns
+//! the time at which `poll` must be called next.
+//!
+//! The type `P` identifies a peer, usually it is the [SocketAddr](std::net::SocketAddr) a message was received from.
+//! ```
+//! use serde_someip::sd::state_machine::*;
+//! use serde_someip::sd::*;
+//! use std::net::SocketAddr;
+//! use std::time::{Duration, Instant};
+//!
+//! let offer = ServiceEntry {
+//!     service_id: 0x1234,
+//!     instance_id: 1,
+//!     major_version: 1,
+//!     ttl: 3,
+//!     minor_version: 0,
+//!     options: vec![],
+//! };
+//! let mut server = SdServer::<SocketAddr>::new(offer, vec![], SdConfig::default());
+//! let start = Instant::now();
+//! server.start(start);
+//! assert_eq!(Phase::InitialWait, server.phase());
+//!
+//! let outputs = server.poll(server.next_deadline().unwrap());
+//! assert_eq!(1, outputs.len());
+//! assert_eq!(Destination::Multicast, outputs[0].destination);
+//! assert!(matches!(outputs[0].entry, SdEntry::OfferService(_)));
+//! assert_eq!(Phase::Repetition(0), server.phase());
+//! ```
+
+use super::{EventgroupEntry, SdEntry, SdMessage, ServiceEntry, TTL_INFINITE};
+
+use std::time::{Duration, Instant};
+
+/// The timing parameters of the state machines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdConfig {
+    /// The time to wait after starting before the first entry is sent.
+    ///
+    /// The spec requires this to be a random value between `INITIAL_DELAY_MIN` and `INITIAL_DELAY_MAX`,
+    /// since the state machines do not generate random numbers the caller must choose it.
+    pub initial_delay: Duration,
+    /// The delay before the first repetition, it is doubled for every further repetition.
+    pub repetitions_base_delay: Duration,
+    /// The number of repetitions, `0` skips the repetition phase.
+    pub repetitions_max: u32,
+    /// The interval of offers in the main phase, `None` disables cyclic offers.
+    pub cyclic_offer_delay: Option<Duration>,
+}
+
+impl Default for SdConfig {
+    fn default() -> Self {
+        SdConfig {
+            initial_delay: Duration::from_millis(10),
+            repetitions_base_delay: Duration::from_millis(30),
+            repetitions_max: 3,
+            cyclic_offer_delay: Some(Duration::from_secs(1)),
+        }
+    }
+}
+
+/// The phase of a state machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// The service is not offered or not requested.
+    Down,
+    /// Waiting for the initial delay.
+    InitialWait,
+    /// Repeating the entry with doubling delays, the parameter is the number of repetitions already sent.
+    Repetition(u32),
+    /// The server offers the service cyclically, the client found the service or stopped searching.
+    Main,
+}
+
+/// Where an [SdOutput] should be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Destination<P> {
+    /// To the SD multicast group.
+    Multicast,
+    /// To a single peer.
+    Unicast(P),
+}
+
+/// An entry that should be sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdOutput<P> {
+    /// Where to send the entry.
+    pub destination: Destination<P>,
+    /// The entry to send.
+    pub entry: SdEntry,
+}
+
+#[inline]
+fn output<P>(destination: Destination<P>, entry: SdEntry) -> SdOutput<P> {
+    SdOutput { destination, entry }
+}
+
+#[inline]
+fn expiry(now: Instant, ttl: u32) -> Option<Instant> {
+    if ttl == TTL_INFINITE {
+        None
+    } else {
+        Some(now + Duration::from_secs(ttl as u64))
+    }
+}
+
+#[inline]
+fn is_expired(expires: Option<Instant>, now: Instant) -> bool {
+    expires.is_some_and(|e| e <= now)
+}
+
+//the initial wait, repetition and main phases shared by client and server
+#[derive(Debug)]
+struct Schedule {
+    phase: Phase,
+    deadline: Option<Instant>,
+}
+
+impl Schedule {
+    fn new() -> Schedule {
+        Schedule {
+            phase: Phase::Down,
+            deadline: None,
+        }
+    }
+
+    fn start(&mut self, now: Instant, config: &SdConfig) {
+        self.phase = Phase::InitialWait;
+        self.deadline = Some(now + config.initial_delay);
+    }
+
+    fn stop(&mut self) {
+        self.phase = Phase::Down;
+        self.deadline = None;
+    }
+
+    fn enter_main(&mut self, now: Instant, cyclic_delay: Option<Duration>) {
+        self.phase = Phase::Main;
+        self.deadline = cyclic_delay.map(|d| now + d);
+    }
+
+    //returns true if the entry must be sent now
+    fn poll(&mut self, now: Instant, config: &SdConfig, cyclic_delay: Option<Duration>) -> bool {
+        if self.deadline.map_or(true, |d| d > now) {
+            return false;
+        }
+        let next = match self.phase {
+            Phase::Down => {
+                self.deadline = None;
+                return false;
+            }
+            Phase::InitialWait => 0,
+            Phase::Repetition(sent) => sent + 1,
+            Phase::Main => {
+                self.enter_main(now, cyclic_delay);
+                return true;
+            }
+        };
+        if next < config.repetitions_max {
+            self.phase = Phase::Repetition(next);
+            let factor = 1u32.checked_shl(next).unwrap_or(u32::MAX);
+            self.deadline = Some(now + config.repetitions_base_delay.saturating_mul(factor));
+        } else {
+            self.enter_main(now, cyclic_delay);
+        }
+        true
+    }
+}
+
+//checks if a find or offer entry refers to the given service, honoring the wildcards of find entries
+fn matches_service(service: &ServiceEntry, entry: &ServiceEntry) -> bool {
+    service.service_id == entry.service_id
+        && (entry.instance_id == 0xFFFF || service.instance_id == entry.instance_id)
+        && (entry.major_version == 0xFF || service.major_version == entry.major_version)
+        && (entry.minor_version == 0xFFFF_FFFF || service.minor_version == entry.minor_version)
+}
+
+fn matches_eventgroup(service: &ServiceEntry, entry: &EventgroupEntry) -> bool {
+    service.service_id == entry.service_id
+        && service.instance_id == entry.instance_id
+        && service.major_version == entry.major_version
+}
+
+/// A subscription of a client to an eventgroup of an [SdServer].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Subscriber<P> {
+    /// The client.
+    pub peer: P,
+    /// The subscribed eventgroup.
+    pub eventgroup_id: u16,
+    /// The counter of the subscription.
+    pub counter: u8,
+    /// The options of the subscription, usually the endpoint the events should be sent to.
+    pub options: Vec<super::SdOption>,
+    expires: Option<Instant>,
+}
+
+/// The state machine offering one service instance.
+///
+/// The server answers find entries for its service and accepts subscriptions to its eventgroups.
+/// Subscriptions expire when their TTL passes without being renewed.
+#[derive(Debug)]
+pub struct SdServer<P> {
+    offer: ServiceEntry,
+    eventgroups: Vec<EventgroupEntry>,
+    config: SdConfig,
+    schedule: Schedule,
+    subscribers: Vec<Subscriber<P>>,
+}
+
+impl<P: Clone + PartialEq> SdServer<P> {
+    /// Creates a new server that is not yet offering its service.
+    ///
+    /// `offer` is sent as the offer entry, its TTL is the TTL of the offer.
+    /// `eventgroups` are the eventgroups that can be subscribed, they are used as the acknowledge entries
+    /// and their options, for example a multicast option, are sent with the acknowledge.
+    pub fn new(
+        offer: ServiceEntry,
+        eventgroups: Vec<EventgroupEntry>,
+        config: SdConfig,
+    ) -> SdServer<P> {
+        SdServer {
+            offer,
+            eventgroups,
+            config,
+            schedule: Schedule::new(),
+            subscribers: Vec::new(),
+        }
+    }
+
+    /// The current phase.
+    #[inline]
+    pub fn phase(&self) -> Phase {
+        self.schedule.phase
+    }
+
+    /// All current subscriptions.
+    #[inline]
+    pub fn subscribers(&self) -> &[Subscriber<P>] {
+        &self.subscribers
+    }
+
+    /// Starts offering the service, the first offer is sent after the initial delay.
+    pub fn start(&mut self, now: Instant) {
+        if self.schedule.phase == Phase::Down {
+            self.schedule.start(now, &self.config);
+        }
+    }
+
+    /// Stops offering the service, returns the stop offer if the service was offered before.
+    ///
+    /// All subscriptions are removed.
+    pub fn stop(&mut self) -> Vec<SdOutput<P>> {
+        let was_offered = !matches!(self.schedule.phase, Phase::Down | Phase::InitialWait);
+        self.schedule.stop();
+        self.subscribers.clear();
+        if was_offered {
+            let mut offer = self.offer.clone();
+            offer.ttl = 0;
+            vec![output(Destination::Multicast, SdEntry::OfferService(offer))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// The time at which [poll](SdServer::poll) must be called next.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.subscribers
+            .iter()
+            .filter_map(|s| s.expires)
+            .chain(self.schedule.deadline)
+            .min()
+    }
+
+    /// Sends the offers that are due and removes expired subscriptions.
+    pub fn poll(&mut self, now: Instant) -> Vec<SdOutput<P>> {
+        self.subscribers.retain(|s| !is_expired(s.expires, now));
+        if self
+            .schedule
+            .poll(now, &self.config, self.config.cyclic_offer_delay)
+        {
+            vec![output(
+                Destination::Multicast,
+                SdEntry::OfferService(self.offer.clone()),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Handles the entries of a message received from `peer`.
+    ///
+    /// Find entries for this service are answered with an offer, via unicast if the message has the unicast flag set.
+    /// Subscriptions to known eventgroups are acknowledged, subscriptions to unknown eventgroups or while the service is
+    /// not offered are rejected. Entries for other services are ignored.
+    pub fn handle_message(
+        &mut self,
+        peer: &P,
+        message: &SdMessage,
+        now: Instant,
+    ) -> Vec<SdOutput<P>> {
+        let mut outputs = Vec::new();
+        for entry in &message.entries {
+            match entry {
+                //finds during the initial wait are ignored since the first offer is sent soon
+                SdEntry::FindService(find)
+                    if matches_service(&self.offer, find)
+                        && !matches!(self.schedule.phase, Phase::Down | Phase::InitialWait) =>
+                {
+                    let destination = if message.unicast {
+                        Destination::Unicast(peer.clone())
+                    } else {
+                        Destination::Multicast
+                    };
+                    outputs.push(output(
+                        destination,
+                        SdEntry::OfferService(self.offer.clone()),
+                    ));
+                }
+                SdEntry::SubscribeEventgroup(subscribe)
+                    if matches_eventgroup(&self.offer, subscribe) =>
+                {
+                    if let Some(ack) = self.handle_subscribe(peer, subscribe, now) {
+                        outputs.push(output(
+                            Destination::Unicast(peer.clone()),
+                            SdEntry::SubscribeEventgroupAck(ack),
+                        ));
+                    }
+                }
+                _ => {}
+            }
+        }
+        outputs
+    }
+
+    fn handle_subscribe(
+        &mut self,
+        peer: &P,
+        subscribe: &EventgroupEntry,
+        now: Instant,
+    ) -> Option<EventgroupEntry> {
+        let position = self
+            .subscribers
+            .iter()
+            .position(|s| &s.peer == peer && s.eventgroup_id == subscribe.eventgroup_id);
+        if subscribe.ttl == 0 {
+            if let Some(position) = position {
+                self.subscribers.remove(position);
+            }
+            return None;
+        }
+        let eventgroup = self
+            .eventgroups
+            .iter()
+            .find(|e| e.eventgroup_id == subscribe.eventgroup_id);
+        let mut ack = match eventgroup {
+            Some(eventgroup) if self.schedule.phase != Phase::Down => {
+                let subscriber = Subscriber {
+                    peer: peer.clone(),
+                    eventgroup_id: subscribe.eventgroup_id,
+                    counter: subscribe.counter,
+                    options: subscribe.options.clone(),
+                    expires: expiry(now, subscribe.ttl),
+                };
+                match position {
+                    Some(position) => self.subscribers[position] = subscriber,
+                    None => self.subscribers.push(subscriber),
+                }
+                let mut ack = eventgroup.clone();
+                ack.ttl = subscribe.ttl;
+                ack
+            }
+            //rejected subscriptions are acknowledged with a TTL of 0
+            _ => EventgroupEntry {
+                ttl: 0,
+                options: Vec::new(),
+                ..subscribe.clone()
+            },
+        };
+        ack.counter = subscribe.counter;
+        Some(ack)
+    }
+}
+
+/// The state of a subscription of an [SdClient].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubscriptionState {
+    /// Not subscribed, either because the service is not available or the subscription expired.
+    Unsubscribed,
+    /// The subscription was sent but not yet acknowledged.
+    Pending,
+    /// The subscription was acknowledged.
+    Subscribed,
+    /// The subscription was rejected by the server.
+    Rejected,
+}
+
+#[derive(Debug)]
+struct Subscription {
+    entry: EventgroupEntry,
+    state: SubscriptionState,
+    expires: Option<Instant>,
+}
+
+#[derive(Debug)]
+struct Offer<P> {
+    server: P,
+    entry: ServiceEntry,
+    expires: Option<Instant>,
+}
+
+/// The state machine searching for one service instance and subscribing to its eventgroups.
+///
+/// After being requested the client searches for the service until it is found or the repetition phase ends.
+/// Every received offer is answered by subscribing to all eventgroups, which renews the subscriptions.
+/// Only the server of the current offer can renew or stop it, offers of other servers are ignored until it ends.
+/// If the offer expires or is stopped the subscriptions are reset and the search restarts.
+#[derive(Debug)]
+pub struct SdClient<P> {
+    find: ServiceEntry,
+    subscriptions: Vec<Subscription>,
+    config: SdConfig,
+    schedule: Schedule,
+    offer: Option<Offer<P>>,
+}
+
+impl<P: Clone + PartialEq> SdClient<P> {
+    /// Creates a new client that has not yet requested the service.
+    ///
+    /// `find` is sent as the find entry and may use the wildcards of the spec.
+    /// `subscriptions` are sent to subscribe to eventgroups, their TTL is the TTL of the subscription
+    /// and their options should contain the endpoint of the client.
+    pub fn new(
+        find: ServiceEntry,
+        subscriptions: Vec<EventgroupEntry>,
+        config: SdConfig,
+    ) -> SdClient<P> {
+        SdClient {
+            find,
+            subscriptions: subscriptions
+                .into_iter()
+                .map(|entry| Subscription {
+                    entry,
+                    state: SubscriptionState::Unsubscribed,
+                    expires: None,
+                })
+                .collect(),
+            config,
+            schedule: Schedule::new(),
+            offer: None,
+        }
+    }
+
+    /// The current phase.
+    #[inline]
+    pub fn phase(&self) -> Phase {
+        self.schedule.phase
+    }
+
+    /// The peer that offers the service and its offer, if the service is available.
+    pub fn offer(&self) -> Option<(&P, &ServiceEntry)> {
+        self.offer.as_ref().map(|o| (&o.server, &o.entry))
+    }
+
+    /// The state of the subscription to the eventgroup, `None` if the eventgroup is not subscribed by this client.
+    pub fn subscription_state(&self, eventgroup_id: u16) -> Option<SubscriptionState> {
+        self.subscriptions
+            .iter()
+            .find(|s| s.entry.eventgroup_id == eventgroup_id)
+            .map(|s| s.state)
+    }
+
+    /// Requests the service.
+    ///
+    /// If the service is already available the eventgroups are subscribed immediately,
+    /// otherwise the search starts after the initial delay.
+    pub fn request(&mut self, now: Instant) -> Vec<SdOutput<P>> {
+        if self.schedule.phase != Phase::Down {
+            return Vec::new();
+        }
+        if self.offer.is_some() {
+            self.schedule.enter_main(now, None);
+            self.subscribe()
+        } else {
+            self.schedule.start(now, &self.config);
+            Vec::new()
+        }
+    }
+
+    /// Releases the service, stopping all subscriptions.
+    pub fn release(&mut self) -> Vec<SdOutput<P>> {
+        self.schedule.stop();
+        let mut outputs = Vec::new();
+        for subscription in &mut self.subscriptions {
+            if let (SubscriptionState::Pending | SubscriptionState::Subscribed, Some(offer)) =
+                (subscription.state, &self.offer)
+            {
+                let mut stop = subscription.entry.clone();
+                stop.ttl = 0;
+                outputs.push(output(
+                    Destination::Unicast(offer.server.clone()),
+                    SdEntry::SubscribeEventgroup(stop),
+                ));
+            }
+            subscription.state = SubscriptionState::Unsubscribed;
+            subscription.expires = None;
+        }
+        outputs
+    }
+
+    /// The time at which [poll](SdClient::poll) must be called next.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.subscriptions
+            .iter()
+            .filter_map(|s| s.expires)
+            .chain(self.offer.as_ref().and_then(|o| o.expires))
+            .chain(self.schedule.deadline)
+            .min()
+    }
+
+    /// Sends the find entries that are due and handles expired offers and subscriptions.
+    pub fn poll(&mut self, now: Instant) -> Vec<SdOutput<P>> {
+        if self
+            .offer
+            .as_ref()
+            .is_some_and(|o| is_expired(o.expires, now))
+        {
+            self.offer = None;
+            self.reset_subscriptions();
+            if self.schedule.phase != Phase::Down {
+                self.schedule.start(now, &self.config);
+            }
+        }
+        for subscription in &mut self.subscriptions {
+            if is_expired(subscription.expires, now) {
+                subscription.state = SubscriptionState::Unsubscribed;
+                subscription.expires = None;
+            }
+        }
+        if self.schedule.poll(now, &self.config, None) {
+            vec![output(
+                Destination::Multicast,
+                SdEntry::FindService(self.find.clone()),
+            )]
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Handles the entries of a message received from `peer`.
+    ///
+    /// Offers for the service make it available and are answered by subscribing to all eventgroups if the service
+    /// is requested, offers and stop offers of servers other than the current one are ignored.
+    /// Acknowledges update the state of the subscriptions. Entries for other services are ignored.
+    pub fn handle_message(
+        &mut self,
+        peer: &P,
+        message: &SdMessage,
+        now: Instant,
+    ) -> Vec<SdOutput<P>> {
+        let mut outputs = Vec::new();
+        for entry in &message.entries {
+            match entry {
+                SdEntry::OfferService(offer) if matches_service(offer, &self.find) => {
+                    let current_server = self
+                        .offer
+                        .as_ref()
+                        .filter(|o| !is_expired(o.expires, now))
+                        .map(|o| &o.server);
+                    if current_server.is_some_and(|server| server != peer) {
+                        continue;
+                    }
+                    if offer.ttl == 0 {
+                        if self.offer.take().is_some() {
+                            self.reset_subscriptions();
+                            if self.schedule.phase != Phase::Down {
+                                self.schedule.start(now, &self.config);
+                            }
+                        }
+                        continue;
+                    }
+                    if current_server.is_none() {
+                        //the subscriptions of an expired offer do not carry over
+                        self.reset_subscriptions();
+                    }
+                    self.offer = Some(Offer {
+                        server: peer.clone(),
+                        entry: offer.clone(),
+                        expires: expiry(now, offer.ttl),
+                    });
+                    if self.schedule.phase != Phase::Down {
+                        self.schedule.enter_main(now, None);
+                        outputs.append(&mut self.subscribe());
+                    }
+                }
+                SdEntry::SubscribeEventgroupAck(ack) => {
+                    let from_server = self
+                        .offer
+                        .as_ref()
+                        .is_some_and(|o| &o.server == peer && matches_eventgroup(&o.entry, ack));
+                    let subscription = self.subscriptions.iter_mut().find(|s| {
+                        s.entry.eventgroup_id == ack.eventgroup_id
+                            && s.state != SubscriptionState::Unsubscribed
+                    });
+                    if let (true, Some(subscription)) = (from_server, subscription) {
+                        if ack.ttl == 0 {
+                            subscription.state = SubscriptionState::Rejected;
+                            subscription.expires = None;
+                        } else {
+                            subscription.state = SubscriptionState::Subscribed;
+                            subscription.expires = expiry(now, ack.ttl);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        outputs
+    }
+
+    fn subscribe(&mut self) -> Vec<SdOutput<P>> {
+        let server = match &self.offer {
+            Some(offer) => offer.server.clone(),
+            None => return Vec::new(),
+        };
+        let offer = &self.offer.as_ref().unwrap().entry;
+        let mut outputs = Vec::with_capacity(self.subscriptions.len());
+        for subscription in &mut self.subscriptions {
+            let mut entry = subscription.entry.clone();
+            entry.service_id = offer.service_id;
+            entry.instance_id = offer.instance_id;
+            entry.major_version = offer.major_version;
+            //renewals keep the subscribed state until the acknowledge arrives
+            if subscription.state != SubscriptionState::Subscribed {
+                subscription.state = SubscriptionState::Pending;
+            }
+            outputs.push(output(
+                Destination::Unicast(server.clone()),
+                SdEntry::SubscribeEventgroup(entry),
+            ));
+        }
+        outputs
+    }
+
+    fn reset_subscriptions(&mut self) {
+        for subscription in &mut self.subscriptions {
+            subscription.state = SubscriptionState::Unsubscribed;
+            subscription.expires = None;
+        }
+    }
+}
+
+#[cfg(test)]
+fn test_offer() -> ServiceEntry {
+    ServiceEntry {
+        service_id: 0x1234,
+        instance_id: 1,
+        major_version: 1,
+        ttl: 3,
+        minor_version: 0,
+        options: vec![],
+    }
+}
+
+#[cfg(test)]
+fn test_eventgroup(ttl: u32) -> EventgroupEntry {
+    EventgroupEntry {
+        service_id: 0x1234,
+        instance_id: 1,
+        major_version: 1,
+        ttl,
+        counter: 0,
+        eventgroup_id: 0x10,
+        options: vec![],
+    }
+}
+
+#[cfg(test)]
+fn test_message(entries: Vec<SdEntry>) -> SdMessage {
+    SdMessage {
+        reboot: false,
+        unicast: true,
+        entries,
+    }
+}
+
+#[cfg(test)]
+fn test_config() -> SdConfig {
+    SdConfig {
+        initial_delay: Duration::from_millis(10),
+        repetitions_base_delay: Duration::from_millis(100),
+        repetitions_max: 3,
+        cyclic_offer_delay: Some(Duration::from_secs(1)),
+    }
+}
+
+#[test]
+fn test_server_phases() {
+    let start = Instant::now();
+    let ms = |ms| start + Duration::from_millis(ms);
+    let mut server = SdServer::<u32>::new(test_offer(), vec![], test_config());
+    assert_eq!(Phase::Down, server.phase());
+    assert_eq!(None, server.next_deadline());
+    server.start(start);
+    assert_eq!(Phase::InitialWait, server.phase());
+    assert!(server.poll(ms(9)).is_empty());
+
+    let mut sent = Vec::new();
+    for (time, phase) in [
+        (10, Phase::Repetition(0)),
+        (110, Phase::Repetition(1)),
+        (310, Phase::Repetition(2)),
+        (710, Phase::Main),
+        (1710, Phase::Main),
+        (2710, Phase::Main),
+    ] {
+        assert_eq!(Some(ms(time)), server.next_deadline());
+        let outputs = server.poll(ms(time));
+        assert_eq!(phase, server.phase());
+        assert_eq!(1, outputs.len());
+        assert_eq!(Destination::Multicast, outputs[0].destination);
+        sent.push(outputs[0].entry.clone());
+        assert!(server.poll(ms(time + 1)).is_empty());
+    }
+    assert!(sent
+        .iter()
+        .all(|e| e == &SdEntry::OfferService(test_offer())));
+
+    let outputs = server.stop();
+    assert_eq!(1, outputs.len());
+    assert_eq!(0, outputs[0].entry.ttl());
+    assert_eq!(Phase::Down, server.phase());
+    assert_eq!(None, server.next_deadline());
+}
+
+#[test]
+fn test_server_find_and_subscribe() {
+    let start = Instant::now();
+    let mut server = SdServer::<u32>::new(test_offer(), vec![test_eventgroup(0)], test_config());
+    let find = test_message(vec![SdEntry::FindService(ServiceEntry {
+        instance_id: 0xFFFF,
+        minor_version: 0xFFFF_FFFF,
+        ..test_offer()
+    })]);
+    let subscribe = test_message(vec![SdEntry::SubscribeEventgroup(test_eventgroup(2))]);
+
+    //not offered yet
+    assert!(server.handle_message(&1, &find, start).is_empty());
+    let outputs = server.handle_message(&1, &subscribe, start);
+    assert_eq!(Destination::Unicast(1), outputs[0].destination);
+    assert_eq!(
+        SdEntry::SubscribeEventgroupAck(test_eventgroup(0)),
+        outputs[0].entry
+    );
+    assert!(server.subscribers().is_empty());
+
+    server.start(start);
+    server.poll(start + Duration::from_millis(10));
+    let outputs = server.handle_message(&1, &find, start);
+    assert_eq!(
+        vec![output(
+            Destination::Unicast(1),
+            SdEntry::OfferService(test_offer())
+        )],
+        outputs
+    );
+
+    let outputs = server.handle_message(&1, &subscribe, start);
+    assert_eq!(
+        SdEntry::SubscribeEventgroupAck(test_eventgroup(2)),
+        outputs[0].entry
+    );
+    assert_eq!(1, server.subscribers().len());
+    assert_eq!(1, server.subscribers()[0].peer);
+
+    //unknown eventgroups are rejected
+    let mut unknown = test_eventgroup(2);
+    unknown.eventgroup_id = 0x11;
+    let outputs = server.handle_message(
+        &2,
+        &test_message(vec![SdEntry::SubscribeEventgroup(unknown)]),
+        start,
+    );
+    assert_eq!(0, outputs[0].entry.ttl());
+    assert_eq!(1, server.subscribers().len());
+
+    //the subscription expires after its ttl
+    assert!(server.next_deadline().unwrap() <= start + Duration::from_secs(2));
+    server.poll(start + Duration::from_secs(2));
+    assert!(server.subscribers().is_empty());
+
+    //stop subscribe
+    server.handle_message(&1, &subscribe, start);
+    assert_eq!(1, server.subscribers().len());
+    let stop = test_message(vec![SdEntry::SubscribeEventgroup(test_eventgroup(0))]);
+    assert!(server.handle_message(&1, &stop, start).is_empty());
+    assert!(server.subscribers().is_empty());
+}
+
+#[test]
+fn test_client_search_and_subscribe() {
+    let start = Instant::now();
+    let ms = |ms| start + Duration::from_millis(ms);
+    let mut client = SdClient::<u32>::new(test_offer(), vec![test_eventgroup(5)], test_config());
+    assert!(client.request(start).is_empty());
+    assert_eq!(Phase::InitialWait, client.phase());
+
+    for time in [10, 110, 310, 710] {
+        let outputs = client.poll(ms(time));
+        assert_eq!(
+            vec![output(
+                Destination::Multicast,
+                SdEntry::FindService(test_offer())
+            )],
+            outputs
+        );
+    }
+    //the client does not search in the main phase
+    assert_eq!(Phase::Main, client.phase());
+    assert_eq!(None, client.next_deadline());
+
+    let offer = test_message(vec![SdEntry::OfferService(test_offer())]);
+    let outputs = client.handle_message(&7, &offer, ms(1000));
+    assert_eq!(
+        vec![output(
+            Destination::Unicast(7),
+            SdEntry::SubscribeEventgroup(test_eventgroup(5))
+        )],
+        outputs
+    );
+    assert_eq!(
+        Some(SubscriptionState::Pending),
+        client.subscription_state(0x10)
+    );
+
+    let ack = test_message(vec![SdEntry::SubscribeEventgroupAck(test_eventgroup(5))]);
+    client.handle_message(&7, &ack, ms(1000));
+    assert_eq!(
+        Some(SubscriptionState::Subscribed),
+        client.subscription_state(0x10)
+    );
+
+    //the offer expires after 3 seconds which restarts the search
+    assert_eq!(Some(ms(4000)), client.next_deadline());
+    assert!(client.poll(ms(4000)).is_empty());
+    assert_eq!(Phase::InitialWait, client.phase());
+    assert_eq!(None, client.offer());
+    assert_eq!(
+        Some(SubscriptionState::Unsubscribed),
+        client.subscription_state(0x10)
+    );
+}
+
+#[test]
+fn test_client_stop_and_release() {
+    let start = Instant::now();
+    let mut client = SdClient::<u32>::new(test_offer(), vec![test_eventgroup(5)], test_config());
+    let offer = test_message(vec![SdEntry::OfferService(test_offer())]);
+
+    //offers received before the request make the service available immediately
+    assert!(client.handle_message(&7, &offer, start).is_empty());
+    let outputs = client.request(start);
+    assert_eq!(Phase::Main, client.phase());
+    assert_eq!(1, outputs.len());
+
+    //rejected subscription
+    let nack = test_message(vec![SdEntry::SubscribeEventgroupAck(test_eventgroup(0))]);
+    client.handle_message(&7, &nack, start);
+    assert_eq!(
+        Some(SubscriptionState::Rejected),
+        client.subscription_state(0x10)
+    );
+
+    //acks from other peers are ignored
+    client.handle_message(&7, &offer, start);
+    let ack = test_message(vec![SdEntry::SubscribeEventgroupAck(test_eventgroup(5))]);
+    client.handle_message(&8, &ack, start);
+    assert_eq!(
+        Some(SubscriptionState::Pending),
+        client.subscription_state(0x10)
+    );
+    client.handle_message(&7, &ack, start);
+
+    let outputs = client.release();
+    assert_eq!(
+        vec![output(
+            Destination::Unicast(7),
+            SdEntry::SubscribeEventgroup(test_eventgroup(0))
+        )],
+        outputs
+    );
+    assert_eq!(Phase::Down, client.phase());
+
+    //stop offer
+    client.request(start);
+    let mut stop = test_offer();
+    stop.ttl = 0;
+    client.handle_message(&7, &test_message(vec![SdEntry::OfferService(stop)]), start);
+    assert_eq!(None, client.offer());
+    assert_eq!(
+        Some(SubscriptionState::Unsubscribed),
+        client.subscription_state(0x10)
+    );
+}
+
+#[test]
+fn test_client_other_servers() {
+    let start = Instant::now();
+    let ms = |ms| start + Duration::from_millis(ms);
+    let mut client = SdClient::<u32>::new(test_offer(), vec![test_eventgroup(5)], test_config());
+    let offer = test_message(vec![SdEntry::OfferService(test_offer())]);
+    let mut stop = test_offer();
+    stop.ttl = 0;
+    let stop = test_message(vec![SdEntry::OfferService(stop)]);
+
+    client.request(start);
+    assert_eq!(1, client.handle_message(&7, &offer, start).len());
+    let ack = test_message(vec![SdEntry::SubscribeEventgroupAck(test_eventgroup(5))]);
+    client.handle_message(&7, &ack, start);
+
+    //another server can neither stop nor take over the offer
+    assert!(client.handle_message(&8, &stop, ms(100)).is_empty());
+    assert!(client.handle_message(&8, &offer, ms(100)).is_empty());
+    assert_eq!(Some(&7), client.offer().map(|(server, _)| server));
+    assert_eq!(Phase::Main, client.phase());
+    assert_eq!(
+        Some(SubscriptionState::Subscribed),
+        client.subscription_state(0x10)
+    );
+
+    //stopping the offer restarts the search
+    assert!(client.handle_message(&7, &stop, ms(200)).is_empty());
+    assert_eq!(None, client.offer());
+    assert_eq!(Phase::InitialWait, client.phase());
+    assert_eq!(Some(ms(210)), client.next_deadline());
+    assert_eq!(
+        vec![output(
+            Destination::Multicast,
+            SdEntry::FindService(test_offer())
+        )],
+        client.poll(ms(210))
+    );
+
+    //afterwards the other server is accepted
+    assert_eq!(
+        vec![output(
+            Destination::Unicast(8),
+            SdEntry::SubscribeEventgroup(test_eventgroup(5))
+        )],
+        client.handle_message(&8, &offer, ms(300))
+    );
+    assert_eq!(Some(&8), client.offer().map(|(server, _)| server));
+    assert_eq!(
+        Some(SubscriptionState::Pending),
+        client.subscription_state(0x10)
+    );
+}
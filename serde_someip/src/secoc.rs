@@ -0,0 +1,451 @@
+//! Provides the authentication of serialized data as defined by autosar Secure Onboard Communication (SecOC).
+//!
+//! *Only available with the `secoc` feature.*
+//!
+//! A sender serializes its data, for example with [to_vec](super::to_vec), and then calls
+//! [authenticate](SecOc::authenticate) which appends the truncated freshness value and the truncated AES-128-CMAC.
+//! A receiver calls [verify](SecOc::verify) with the received data which returns the authentic data that can be passed
+//! to [from_slice](super::from_slice).
+//!
+//! The MAC is computed over the data id, the authentic data and the complete freshness value, all in big endian.
+//! The freshness values are provided by a [FreshnessValueManager] and the keys by a [KeyProvider].
+//! ```
+//! use serde_someip::options::ExampleOptions;
+//! use serde_someip::secoc::{CounterFreshnessValueManager, SecOc, SecOcConfig};
+//!
+//! let key = [0x2B; 16];
+//! let mut sender = SecOc::new(SecOcConfig::new(0x42), CounterFreshnessValueManager::default(), key);
+//! let mut receiver = SecOc::new(SecOcConfig::new(0x42), CounterFreshnessValueManager::default(), key);
+//!
+//! let mut data = serde_someip::to_vec::<ExampleOptions, _>(&(1u32, 2u16)).unwrap();
+//! sender.authenticate(&mut data).unwrap();
+//! assert_eq!(6 + 1 + 3, data.len());
+//!
+//! let value: (u32, u16) =
+//!     serde_someip::from_slice::<ExampleOptions, _>(receiver.verify(&data).unwrap()).unwrap();
+//! assert_eq!((1, 2), value);
+//!
+//! //replaying the same data is detected
+//! assert!(receiver.verify(&data).is_err());
+//! ```
+
+use super::error::{Error, Result};
+
+use aes::Aes128;
+use cmac::{Cmac, Mac};
+
+use std::collections::HashMap;
+
+/// The length of the AES-128 keys in bytes.
+pub const KEY_LEN: usize = 16;
+
+/// The maximum length of a MAC in bytes.
+pub const MAX_MAC_LEN: usize = 16;
+
+/// The maximum length of a freshness value in bytes.
+pub const MAX_FRESHNESS_VALUE_LEN: usize = 8;
+
+/// Provides the freshness values that protect against replaying old data.
+pub trait FreshnessValueManager {
+    /// Returns the freshness value to authenticate the next data with.
+    fn tx_freshness(&mut self, freshness_value_id: u16) -> Result<u64>;
+
+    /// Reconstructs the complete freshness value from the truncated freshness value of received data,
+    /// `truncated_len` is the number of bytes that were transmitted.
+    ///
+    /// Returns [None] if no valid freshness value can be reconstructed, for example because the data is too old.
+    fn rx_freshness(
+        &mut self,
+        freshness_value_id: u16,
+        truncated: u64,
+        truncated_len: usize,
+    ) -> Option<u64>;
+
+    /// Called when received data with the freshness value was successfully verified.
+    fn verified(&mut self, freshness_value_id: u16, freshness_value: u64);
+}
+
+/// A [FreshnessValueManager] that uses a counter per freshness value id.
+///
+/// The counters for sending start at `1` and received freshness values must be greater than the last verified one.
+#[derive(Debug, Clone, Default)]
+pub struct CounterFreshnessValueManager {
+    tx: HashMap<u16, u64>,
+    rx: HashMap<u16, u64>,
+}
+
+impl FreshnessValueManager for CounterFreshnessValueManager {
+    fn tx_freshness(&mut self, freshness_value_id: u16) -> Result<u64> {
+        let counter = self.tx.entry(freshness_value_id).or_insert(0);
+        *counter = counter.checked_add(1).ok_or_else(|| {
+            Error::InvalidSecOcData(format!(
+                "The freshness value of id {} overflowed",
+                freshness_value_id
+            ))
+        })?;
+        Ok(*counter)
+    }
+
+    fn rx_freshness(
+        &mut self,
+        freshness_value_id: u16,
+        truncated: u64,
+        truncated_len: usize,
+    ) -> Option<u64> {
+        let latest = self.rx.get(&freshness_value_id).copied().unwrap_or(0);
+        if truncated_len >= MAX_FRESHNESS_VALUE_LEN {
+            return Some(truncated).filter(|fv| *fv > latest);
+        }
+        //the upper bytes are taken from the latest value, if the result is not newer they must have been incremented
+        let mask = (1 << (truncated_len * 8)) - 1;
+        let candidate = (latest & !mask) | truncated;
+        if candidate > latest {
+            Some(candidate)
+        } else {
+            candidate.checked_add(mask + 1)
+        }
+    }
+
+    fn verified(&mut self, freshness_value_id: u16, freshness_value: u64) {
+        self.rx.insert(freshness_value_id, freshness_value);
+    }
+}
+
+/// Provides the AES-128 keys.
+pub trait KeyProvider {
+    /// Returns the key for the data id or [None] if there is none.
+    fn key(&self, data_id: u16) -> Option<[u8; KEY_LEN]>;
+}
+
+/// A single key is used for all data ids.
+impl KeyProvider for [u8; KEY_LEN] {
+    #[inline]
+    fn key(&self, _data_id: u16) -> Option<[u8; KEY_LEN]> {
+        Some(*self)
+    }
+}
+
+/// The keys are looked up by data id.
+impl KeyProvider for HashMap<u16, [u8; KEY_LEN]> {
+    #[inline]
+    fn key(&self, data_id: u16) -> Option<[u8; KEY_LEN]> {
+        self.get(&data_id).copied()
+    }
+}
+
+/// The configuration of [SecOc].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecOcConfig {
+    /// The id identifying the authenticated data, it is not transmitted but included in the MAC.
+    pub data_id: u16,
+    /// The id passed to the [FreshnessValueManager].
+    pub freshness_value_id: u16,
+    /// The length of the complete freshness value in bytes that is included in the MAC,
+    /// at most [MAX_FRESHNESS_VALUE_LEN].
+    pub freshness_value_len: usize,
+    /// The number of least significant bytes of the freshness value that are transmitted,
+    /// between 1 and `freshness_value_len`.
+    pub freshness_value_tx_len: usize,
+    /// The number of most significant bytes of the MAC that are transmitted, between 1 and [MAX_MAC_LEN].
+    pub mac_len: usize,
+}
+
+impl SecOcConfig {
+    /// Creates a config with an 8 byte freshness value of which 1 byte is transmitted together with 3 bytes of the MAC,
+    /// the data id is also used as freshness value id.
+    pub fn new(data_id: u16) -> SecOcConfig {
+        SecOcConfig {
+            data_id,
+            freshness_value_id: data_id,
+            freshness_value_len: 8,
+            freshness_value_tx_len: 1,
+            mac_len: 3,
+        }
+    }
+
+    fn verify(&self) -> Result<()> {
+        if self.freshness_value_len > MAX_FRESHNESS_VALUE_LEN
+            || self.freshness_value_tx_len == 0
+            || self.freshness_value_tx_len > self.freshness_value_len
+            || self.mac_len == 0
+            || self.mac_len > MAX_MAC_LEN
+        {
+            return Err(Error::InvalidSecOcData(format!(
+                "Invalid SecOC config: {:?}",
+                self
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Authenticates and verifies data with the configured freshness value manager and key provider.
+///
+/// This does not implement [Debug] so that the keys do not end up in logs.
+#[derive(Clone)]
+pub struct SecOc<F, K> {
+    config: SecOcConfig,
+    freshness_value_manager: F,
+    key_provider: K,
+}
+
+impl<F, K> SecOc<F, K>
+where
+    F: FreshnessValueManager,
+    K: KeyProvider,
+{
+    /// Creates a new instance.
+    pub fn new(config: SecOcConfig, freshness_value_manager: F, key_provider: K) -> SecOc<F, K> {
+        SecOc {
+            config,
+            freshness_value_manager,
+            key_provider,
+        }
+    }
+
+    /// The configuration.
+    #[inline]
+    pub fn config(&self) -> &SecOcConfig {
+        &self.config
+    }
+
+    /// The freshness value manager.
+    #[inline]
+    pub fn freshness_value_manager(&mut self) -> &mut F {
+        &mut self.freshness_value_manager
+    }
+
+    fn mac(&self, data: &[u8], freshness_value: u64) -> Result<Cmac<Aes128>> {
+        let key = self.key_provider.key(self.config.data_id).ok_or_else(|| {
+            Error::InvalidSecOcData(format!("No key for data id {}", self.config.data_id))
+        })?;
+        let mut mac = <Cmac<Aes128> as Mac>::new(&key.into());
+        mac.update(&self.config.data_id.to_be_bytes());
+        mac.update(data);
+        mac.update(
+            &freshness_value.to_be_bytes()
+                [MAX_FRESHNESS_VALUE_LEN - self.config.freshness_value_len..],
+        );
+        Ok(mac)
+    }
+
+    /// Appends the truncated freshness value and the truncated MAC to the data.
+    ///
+    /// Fails if the config is invalid, there is no key or the freshness value manager fails.
+    pub fn authenticate(&mut self, data: &mut Vec<u8>) -> Result<()> {
+        self.config.verify()?;
+        let freshness_value = self
+            .freshness_value_manager
+            .tx_freshness(self.config.freshness_value_id)?;
+        let mac = self.mac(data, freshness_value)?.finalize().into_bytes();
+        data.extend_from_slice(
+            &freshness_value.to_be_bytes()
+                [MAX_FRESHNESS_VALUE_LEN - self.config.freshness_value_tx_len..],
+        );
+        data.extend_from_slice(&mac[..self.config.mac_len]);
+        Ok(())
+    }
+
+    /// Verifies the received data and returns the authentic data without the freshness value and MAC.
+    ///
+    /// Fails with [Error::SecOcVerificationFailed] if no freshness value can be reconstructed or the MAC does not match.
+    pub fn verify<'a>(&mut self, data: &'a [u8]) -> Result<&'a [u8]> {
+        self.config.verify()?;
+        let trailer_len = self.config.freshness_value_tx_len + self.config.mac_len;
+        if data.len() < trailer_len {
+            return Err(Error::InvalidSecOcData(format!(
+                "The data is shorter than the freshness value and MAC, len={}",
+                data.len()
+            )));
+        }
+        let (authentic, trailer) = data.split_at(data.len() - trailer_len);
+        let (truncated, received_mac) = trailer.split_at(self.config.freshness_value_tx_len);
+        let truncated = truncated
+            .iter()
+            .fold(0u64, |fv, byte| (fv << 8) | *byte as u64);
+
+        let freshness_value = self
+            .freshness_value_manager
+            .rx_freshness(
+                self.config.freshness_value_id,
+                truncated,
+                self.config.freshness_value_tx_len,
+            )
+            .ok_or(Error::SecOcVerificationFailed)?;
+        //compare in constant time to not leak how many bytes of the MAC matched
+        self.mac(authentic, freshness_value)?
+            .verify_truncated_left(received_mac)
+            .map_err(|_| Error::SecOcVerificationFailed)?;
+        self.freshness_value_manager
+            .verified(self.config.freshness_value_id, freshness_value);
+        Ok(authentic)
+    }
+}
+
+#[cfg(test)]
+const TEST_KEY: [u8; KEY_LEN] = [
+    0x2B, 0x7E, 0x15, 0x16, 0x28, 0xAE, 0xD2, 0xA6, 0xAB, 0xF7, 0x15, 0x88, 0x09, 0xCF, 0x4F, 0x3C,
+];
+
+#[cfg(test)]
+struct FixedFreshnessValue(u64);
+
+#[cfg(test)]
+impl FreshnessValueManager for FixedFreshnessValue {
+    fn tx_freshness(&mut self, _freshness_value_id: u16) -> Result<u64> {
+        Ok(self.0)
+    }
+
+    fn rx_freshness(&mut self, _: u16, _: u64, _: usize) -> Option<u64> {
+        Some(self.0)
+    }
+
+    fn verified(&mut self, _: u16, _: u64) {}
+}
+
+#[test]
+fn test_mac() {
+    //the data id, data and freshness value form the 16 byte example message of RFC 4493
+    let config = SecOcConfig {
+        freshness_value_len: 4,
+        freshness_value_tx_len: 4,
+        mac_len: 16,
+        ..SecOcConfig::new(0x6BC1)
+    };
+    let mut secoc = SecOc::new(config, FixedFreshnessValue(0x7393_172A), TEST_KEY);
+    let mut data = vec![0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11];
+    secoc.authenticate(&mut data).unwrap();
+    assert_eq!(
+        vec![
+            0xBE, 0xE2, 0x2E, 0x40, 0x9F, 0x96, 0xE9, 0x3D, 0x7E, 0x11, 0x73, 0x93, 0x17, 0x2A,
+            0x07, 0x0A, 0x16, 0xB4, 0x6B, 0x4D, 0x41, 0x44, 0xF7, 0x9B, 0xDD, 0x9D, 0xD0, 0x4A,
+            0x28, 0x7C
+        ],
+        data
+    );
+    assert_eq!(&data[..10], secoc.verify(&data).unwrap());
+
+    let mut corrupted = data.clone();
+    corrupted[0] ^= 1;
+    assert!(matches!(
+        secoc.verify(&corrupted),
+        Err(Error::SecOcVerificationFailed)
+    ));
+    let mut corrupted = data.clone();
+    *corrupted.last_mut().unwrap() ^= 1;
+    assert!(matches!(
+        secoc.verify(&corrupted),
+        Err(Error::SecOcVerificationFailed)
+    ));
+}
+
+#[test]
+fn test_truncation() {
+    let config = SecOcConfig {
+        freshness_value_tx_len: 2,
+        mac_len: 4,
+        ..SecOcConfig::new(0x1234)
+    };
+    let mut sender = SecOc::new(
+        config.clone(),
+        CounterFreshnessValueManager::default(),
+        TEST_KEY,
+    );
+    let mut receiver = SecOc::new(config, CounterFreshnessValueManager::default(), TEST_KEY);
+
+    let mut data = vec![1, 2, 3];
+    sender.authenticate(&mut data).unwrap();
+    assert_eq!(9, data.len());
+    assert_eq!(&[0x00, 0x01], &data[3..5]);
+    assert_eq!(&[1, 2, 3], receiver.verify(&data).unwrap());
+    assert!(matches!(
+        receiver.verify(&data),
+        Err(Error::SecOcVerificationFailed)
+    ));
+
+    //the upper bytes of the freshness value are reconstructed when the truncated value wraps around
+    sender.freshness_value_manager().tx.insert(0x1234, 0x1_FFFE);
+    receiver
+        .freshness_value_manager()
+        .rx
+        .insert(0x1234, 0x1_FFF0);
+    let mut data = vec![4];
+    sender.authenticate(&mut data).unwrap();
+    assert_eq!(&[0xFF, 0xFF], &data[1..3]);
+    assert_eq!(&[4], receiver.verify(&data).unwrap());
+    let mut data = vec![5];
+    sender.authenticate(&mut data).unwrap();
+    assert_eq!(&[0x00, 0x00], &data[1..3]);
+    assert_eq!(&[5], receiver.verify(&data).unwrap());
+    assert_eq!(
+        Some(&0x2_0000),
+        receiver.freshness_value_manager().rx.get(&0x1234)
+    );
+
+    let mut other = SecOc::new(
+        SecOcConfig::new(0x1234),
+        CounterFreshnessValueManager::default(),
+        [0; KEY_LEN],
+    );
+    let mut data = vec![1, 2, 3];
+    other.authenticate(&mut data).unwrap();
+    assert!(matches!(
+        SecOc::new(
+            SecOcConfig::new(0x1234),
+            CounterFreshnessValueManager::default(),
+            TEST_KEY
+        )
+        .verify(&data),
+        Err(Error::SecOcVerificationFailed)
+    ));
+}
+
+#[test]
+fn test_invalid() {
+    let mut keys = HashMap::new();
+    keys.insert(1, TEST_KEY);
+    let mut secoc = SecOc::new(
+        SecOcConfig::new(2),
+        CounterFreshnessValueManager::default(),
+        keys,
+    );
+    assert!(matches!(
+        secoc.authenticate(&mut vec![1, 2, 3]),
+        Err(Error::InvalidSecOcData(_))
+    ));
+    assert!(matches!(
+        secoc.verify(&[1, 2, 3]),
+        Err(Error::InvalidSecOcData(_))
+    ));
+
+    let mut secoc = SecOc::new(
+        SecOcConfig {
+            mac_len: 17,
+            ..SecOcConfig::new(1)
+        },
+        CounterFreshnessValueManager::default(),
+        TEST_KEY,
+    );
+    assert!(matches!(
+        secoc.authenticate(&mut vec![1, 2, 3]),
+        Err(Error::InvalidSecOcData(_))
+    ));
+
+    //without any transmitted bytes the receiver could not reconstruct the freshness value
+    let mut secoc = SecOc::new(
+        SecOcConfig {
+            freshness_value_tx_len: 0,
+            ..SecOcConfig::new(1)
+        },
+        CounterFreshnessValueManager::default(),
+        TEST_KEY,
+    );
+    assert!(matches!(
+        secoc.authenticate(&mut vec![1, 2, 3]),
+        Err(Error::InvalidSecOcData(_))
+    ));
+    assert!(matches!(
+        secoc.verify(&[1, 2, 3, 4, 5, 6]),
+        Err(Error::InvalidSecOcData(_))
+    ));
+}
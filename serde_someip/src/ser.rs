@@ -4,6 +4,7 @@ use super::error::{Error, Result};
 use super::length_fields::LengthFieldSize;
 use super::options::*;
 use super::types::*;
+use super::unknown_members::UnknownMembers;
 use super::wire_type::WireType;
 
 use serde::ser::*;
@@ -13,6 +14,10 @@ use bytes::BytesMut;
 
 use std::marker::PhantomData;
 
+//passing this as the name of a union selects the variant by its index instead of its name,
+//which allows serializing unions whose variant names are not 'static
+pub(crate) const UNION_VARIANT_BY_INDEX: &str = "$serde_someip::UnionVariantByIndex";
+
 trait SomeIpWriter {
     fn write(&mut self, data: &[u8]) -> Result<()>;
 
@@ -24,9 +29,14 @@ trait SomeIpWriter {
 
     #[inline]
     fn put_zeros(&mut self, len: usize) -> Result<()> {
-        assert!(len <= 4);
-        let array = [0; 4];
-        self.write(&array[..len])
+        let array = [0; 16];
+        let mut remaining = len;
+        while remaining > 0 {
+            let chunk = remaining.min(array.len());
+            self.write(&array[..chunk])?;
+            remaining -= chunk;
+        }
+        Ok(())
     }
 
     #[inline]
@@ -138,121 +148,139 @@ impl SomeIpWriter for BytesMut {
     }
 }
 
-struct Phony;
-impl SerializeTuple for Phony {
-    type Ok = ();
-    type Error = Error;
-
-    fn serialize_element<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn end(self) -> Result<()> {
-        unimplemented!()
-    }
+struct SomeIpSeqSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
+    someip_type: &'t SomeIpSequence,
+    length_field_size: Option<LengthFieldSize>,
+    element_count: usize,
 }
 
-impl SerializeTupleStruct for Phony {
-    type Ok = ();
-    type Error = Error;
-
-    fn serialize_field<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn end(self) -> Result<()> {
-        unimplemented!()
-    }
-}
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
+    SomeIpSeqSerializer<'a, 't, Options, Writer>
+{
+    fn new(
+        serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
+        len: Option<usize>,
+    ) -> Result<Self> {
+        if let SomeIpType::Sequence(s) = serializer.next_type {
+            if let Some(len) = len {
+                if len < s.min_elements {
+                    return Err(Error::NotEnoughData {
+                        min: s.min_elements,
+                        actual: len,
+                    });
+                } else if len > s.max_elements {
+                    return Err(Error::TooMuchData {
+                        max: s.max_elements,
+                        actual: len,
+                    });
+                }
+            }
 
-impl SerializeTupleVariant for Phony {
-    type Ok = ();
-    type Error = Error;
+            let length_field_size = s.wanted_length_field::<Options>(
+                serializer.is_in_tlv_struct,
+                &serializer.transformation_props,
+            )?;
 
-    fn serialize_field<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unimplemented!()
+            Ok(SomeIpSeqSerializer {
+                serializer,
+                someip_type: s,
+                length_field_size,
+                element_count: 0,
+            })
+        } else {
+            panic!("Expeceted a sequence but found {}", serializer.next_type)
+        }
     }
 
-    fn end(self) -> Result<()> {
-        unimplemented!()
+    fn start(&mut self) -> Result<()> {
+        if let Some(configured) = self.length_field_size {
+            self.serializer.begin_length_delimited_section(
+                configured,
+                LengthFieldSize::minimum_length_for(self.someip_type.max_len::<Options>(
+                    self.serializer.is_in_tlv_struct,
+                    &self.serializer.transformation_props,
+                )?),
+            )?;
+        }
+        Ok(())
     }
 }
 
-impl SerializeMap for Phony {
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeSeq
+    for SomeIpSeqSerializer<'a, 't, Options, Writer>
+{
     type Ok = ();
     type Error = Error;
 
-    fn serialize_key<T: Serialize + ?Sized>(&mut self, _key: &T) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn serialize_value<T: Serialize + ?Sized>(&mut self, _value: &T) -> Result<()> {
-        unimplemented!()
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.element_count += 1;
+        self.serializer.next_type = &self.someip_type.element_type;
+        self.serializer.is_in_tlv_struct = false;
+        value.serialize(&mut *self.serializer)
     }
 
     fn end(self) -> Result<()> {
-        unimplemented!()
+        if self.element_count < self.someip_type.min_elements {
+            return Err(Error::NotEnoughData {
+                min: self.someip_type.min_elements,
+                actual: self.element_count,
+            });
+        } else if self.element_count > self.someip_type.max_elements {
+            return Err(Error::TooMuchData {
+                max: self.someip_type.max_elements,
+                actual: self.element_count,
+            });
+        }
+        if let Some(s) = self.length_field_size {
+            self.serializer.end_length_delimited_section(s)?;
+        }
+        Ok(())
     }
 }
 
-impl SerializeStructVariant for Phony {
-    type Ok = ();
-    type Error = Error;
-
-    fn serialize_field<T: Serialize + ?Sized>(
-        &mut self,
-        _key: &'static str,
-        _value: &T,
-    ) -> Result<()> {
-        unimplemented!()
-    }
-
-    fn end(self) -> Result<()> {
-        unimplemented!()
-    }
-}
-struct SomeIpSeqSerializer<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
-    serializer: &'a mut SomeIpSerializer<Options, Writer>,
-    someip_type: &'static SomeIpSequence,
+struct SomeIpMapSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
+    someip_type: &'t SomeIpMap,
     length_field_size: Option<LengthFieldSize>,
     element_count: usize,
 }
 
-impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
-    SomeIpSeqSerializer<'a, Options, Writer>
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
+    SomeIpMapSerializer<'a, 't, Options, Writer>
 {
     fn new(
-        serializer: &'a mut SomeIpSerializer<Options, Writer>,
+        serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
         len: Option<usize>,
     ) -> Result<Self> {
-        if let SomeIpType::Sequence(s) = serializer.next_type {
+        if let SomeIpType::Map(m) = serializer.next_type {
             if let Some(len) = len {
-                if len < s.min_elements {
+                if len < m.min_elements {
                     return Err(Error::NotEnoughData {
-                        min: s.min_elements,
+                        min: m.min_elements,
                         actual: len,
                     });
-                } else if len > s.max_elements {
+                } else if len > m.max_elements {
                     return Err(Error::TooMuchData {
-                        max: s.max_elements,
+                        max: m.max_elements,
                         actual: len,
                     });
                 }
             }
 
-            let length_field_size = s.wanted_length_field::<Options>(
+            let length_field_size = m.wanted_length_field::<Options>(
                 serializer.is_in_tlv_struct,
                 &serializer.transformation_props,
             )?;
 
-            Ok(SomeIpSeqSerializer {
+            Ok(SomeIpMapSerializer {
                 serializer,
-                someip_type: s,
+                someip_type: m,
                 length_field_size,
                 element_count: 0,
             })
         } else {
-            panic!("Expeceted a sequence but found {}", serializer.next_type)
+            panic!("Expeceted a map but found {}", serializer.next_type)
         }
     }
 
@@ -270,15 +298,21 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
     }
 }
 
-impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeSeq
-    for SomeIpSeqSerializer<'a, Options, Writer>
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeMap
+    for SomeIpMapSerializer<'a, 't, Options, Writer>
 {
     type Ok = ();
     type Error = Error;
 
-    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<()> {
         self.element_count += 1;
-        self.serializer.next_type = self.someip_type.element_type;
+        self.serializer.next_type = &self.someip_type.key_type;
+        self.serializer.is_in_tlv_struct = false;
+        key.serialize(&mut *self.serializer)
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.serializer.next_type = &self.someip_type.value_type;
         self.serializer.is_in_tlv_struct = false;
         value.serialize(&mut *self.serializer)
     }
@@ -302,17 +336,20 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeSeq
     }
 }
 
-struct SomeIpStructSerializer<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
-    serializer: &'a mut SomeIpSerializer<Options, Writer>,
-    struct_type: &'static SomeIpStruct,
+struct SomeIpStructSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
+    struct_type: &'t SomeIpStruct,
     length_field_size: Option<LengthFieldSize>,
     original_transformation_props: Option<SomeIpTransforationProperties>,
+    alignment: Option<Alignment>,
+    begin: usize,
+    field_index: usize,
 }
 
-impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
-    SomeIpStructSerializer<'a, Options, Writer>
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
+    SomeIpStructSerializer<'a, 't, Options, Writer>
 {
-    fn new(serializer: &'a mut SomeIpSerializer<Options, Writer>, len: usize) -> Result<Self> {
+    fn new(serializer: &'a mut SomeIpSerializer<'t, Options, Writer>, len: usize) -> Result<Self> {
         if let SomeIpType::Struct(s) = serializer.next_type {
             if len != s.field_count() {
                 panic!("Cannot serialize more fields than struct {} has", s.name);
@@ -329,11 +366,15 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
                 serializer.is_in_tlv_struct,
                 &serializer.transformation_props,
             )?;
+            let alignment = serializer.transformation_props.get_alignment();
             Ok(SomeIpStructSerializer {
                 serializer,
                 struct_type: s,
                 length_field_size,
                 original_transformation_props,
+                alignment,
+                begin: 0,
+                field_index: 0,
             })
         } else {
             panic!("Expeceted a struct but found {}", serializer.next_type)
@@ -350,33 +391,50 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
                 )?),
             )?;
         }
+        self.begin = self.serializer.writer.len();
         Ok(())
     }
-}
 
-impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
-    for SomeIpStructSerializer<'a, Options, Writer>
-{
-    type Ok = ();
-    type Error = Error;
+    fn write_padding(&mut self) -> Result<()> {
+        if let Some(alignment) = self.alignment {
+            let offset = self.serializer.writer.len() - self.begin;
+            self.serializer
+                .writer
+                .put_zeros(alignment.padding_for(offset))?;
+        }
+        Ok(())
+    }
 
-    fn serialize_field<T: Serialize + ?Sized>(
+    fn serialize_next_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        let field = self
+            .struct_type
+            .fields
+            .get(self.field_index)
+            .unwrap_or_else(|| {
+                panic!(
+                    "Cannot serialize more fields than struct {} has",
+                    self.struct_type.name
+                )
+            });
+        self.serialize_field_of(field, value)
+    }
+
+    fn serialize_field_of<T: Serialize + ?Sized>(
         &mut self,
-        key: &'static str,
+        field: &'t SomeIpField,
         value: &T,
     ) -> Result<()> {
-        let field = self.struct_type.field_by_name(key).unwrap_or_else(|| {
-            panic!(
-                "Cannot find field {} in struct {}",
-                key, self.struct_type.name
-            )
-        });
-
-        if self.struct_type.uses_tlv() {
+        self.field_index += 1;
+        if let SomeIpType::UnknownMembers = *field.field_type {
+            //unknown members are written verbatim including their tags
+            self.serializer.is_in_tlv_struct = self.struct_type.uses_tlv();
+            self.serializer.next_type = &field.field_type;
+            value.serialize(&mut *self.serializer)?;
+        } else if self.struct_type.uses_tlv() {
             if field.id.is_none() {
                 panic!(
                     "Field {} in struct {} has no id despite the struct using tlv",
-                    key, self.struct_type.name
+                    field.name, self.struct_type.name
                 );
             }
 
@@ -386,7 +444,7 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
             tag.serialize(&mut *self.serializer)?;
 
             self.serializer.is_in_tlv_struct = self.struct_type.uses_tlv();
-            self.serializer.next_type = field.field_type;
+            self.serializer.next_type = &field.field_type;
             value.serialize(&mut *self.serializer)?;
             if wire_type == WireType::LengthDelimitedFromConfig {
                 //will be None if value was None
@@ -397,15 +455,15 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
                     }
                 }
             }
-            Ok(())
         } else {
             self.serializer.is_in_tlv_struct = self.struct_type.uses_tlv();
-            self.serializer.next_type = field.field_type;
-            value.serialize(&mut *self.serializer)
+            self.serializer.next_type = &field.field_type;
+            value.serialize(&mut *self.serializer)?;
         }
+        self.write_padding()
     }
 
-    fn end(self) -> Result<()> {
+    fn finish(self) -> Result<&'a mut SomeIpSerializer<'t, Options, Writer>> {
         if let Some(s) = self.length_field_size {
             self.serializer.end_length_delimited_section(s)?;
         }
@@ -413,22 +471,290 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
         if self.struct_type.transformation_properties.is_some() {
             self.serializer.transformation_props = self.original_transformation_props;
         }
+        Ok(self.serializer)
+    }
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeTupleStruct
+    for SomeIpStructSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.serialize_next_field(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()?;
         Ok(())
     }
 }
 
-struct SomeIpSerializer<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+//Tuples may either be fixed size arrays or tuples like (u32, f64)
+enum SomeIpTupleSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    Sequence(SomeIpSeqSerializer<'a, 't, Options, Writer>),
+    Struct(SomeIpStructSerializer<'a, 't, Options, Writer>),
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeTuple
+    for SomeIpTupleSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        match self {
+            SomeIpTupleSerializer::Sequence(s) => s.serialize_element(value),
+            SomeIpTupleSerializer::Struct(s) => s.serialize_next_field(value),
+        }
+    }
+
+    fn end(self) -> Result<()> {
+        match self {
+            SomeIpTupleSerializer::Sequence(s) => SerializeSeq::end(s),
+            SomeIpTupleSerializer::Struct(s) => SerializeTupleStruct::end(s),
+        }
+    }
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
+    for SomeIpStructSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let field = self.struct_type.field_by_name(key).unwrap_or_else(|| {
+            panic!(
+                "Cannot find field {} in struct {}",
+                key, self.struct_type.name
+            )
+        });
+        self.serialize_field_of(field, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.finish()?;
+        Ok(())
+    }
+}
+
+struct SomeIpBitfieldSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    serializer: &'a mut SomeIpSerializer<'t, Options, Writer>,
+    bitfield_type: &'t SomeIpBitfield,
+    value: u64,
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
+    SomeIpBitfieldSerializer<'a, 't, Options, Writer>
+{
+    fn serialize_flag<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        let bit = self.bitfield_type.flag_to_bit(key).unwrap_or_else(|| {
+            panic!(
+                "Cannot find flag {} in bitfield {}",
+                key, self.bitfield_type.name
+            )
+        });
+        if value.serialize(FlagSerializer)? {
+            self.value |= 1 << bit;
+        }
+        Ok(())
+    }
+
+    fn finish(self) -> Result<()> {
+        let value = self.value;
+        match self.bitfield_type.raw_type {
+            SomeIpPrimitive::U8 => self.serializer.serialize_u8(value as u8),
+            SomeIpPrimitive::U16 => self.serializer.serialize_u16(value as u16),
+            SomeIpPrimitive::U32 => self.serializer.serialize_u32(value as u32),
+            SomeIpPrimitive::U64 => self.serializer.serialize_u64(value),
+            _ => panic!(
+                "Unsupported raw type for bitfields: {}",
+                self.bitfield_type.raw_type
+            ),
+        }
+    }
+}
+
+//Serializer that extracts the value of a single flag of a bitfield
+struct FlagSerializer;
+
+macro_rules! no_flag {
+    ($($method:ident($($arg:ty),*) -> $ret:ty;)*) => {
+        $(
+            fn $method(self, $(_: $arg),*) -> Result<$ret> {
+                panic!("Flags of bitfields must be bools")
+            }
+        )*
+    };
+}
+
+impl Serializer for FlagSerializer {
+    type Ok = bool;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<bool, Error>;
+    type SerializeTuple = Impossible<bool, Error>;
+    type SerializeTupleStruct = Impossible<bool, Error>;
+    type SerializeTupleVariant = Impossible<bool, Error>;
+    type SerializeMap = Impossible<bool, Error>;
+    type SerializeStruct = Impossible<bool, Error>;
+    type SerializeStructVariant = Impossible<bool, Error>;
+
+    fn serialize_bool(self, v: bool) -> Result<bool> {
+        Ok(v)
+    }
+
+    no_flag! {
+        serialize_i8(i8) -> bool;
+        serialize_i16(i16) -> bool;
+        serialize_i32(i32) -> bool;
+        serialize_i64(i64) -> bool;
+        serialize_u8(u8) -> bool;
+        serialize_u16(u16) -> bool;
+        serialize_u32(u32) -> bool;
+        serialize_u64(u64) -> bool;
+        serialize_f32(f32) -> bool;
+        serialize_f64(f64) -> bool;
+        serialize_char(char) -> bool;
+        serialize_str(&str) -> bool;
+        serialize_bytes(&[u8]) -> bool;
+        serialize_none() -> bool;
+        serialize_unit() -> bool;
+        serialize_unit_struct(&'static str) -> bool;
+        serialize_unit_variant(&'static str, u32, &'static str) -> bool;
+        serialize_seq(Option<usize>) -> Self::SerializeSeq;
+        serialize_tuple(usize) -> Self::SerializeTuple;
+        serialize_tuple_struct(&'static str, usize) -> Self::SerializeTupleStruct;
+        serialize_tuple_variant(&'static str, u32, &'static str, usize) -> Self::SerializeTupleVariant;
+        serialize_map(Option<usize>) -> Self::SerializeMap;
+        serialize_struct(&'static str, usize) -> Self::SerializeStruct;
+        serialize_struct_variant(&'static str, u32, &'static str, usize) -> Self::SerializeStructVariant;
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<bool> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<bool> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<bool> {
+        panic!("Flags of bitfields must be bools")
+    }
+}
+
+//Structs of bools may either be someip structs or bitfields
+enum SomeIpStructLikeSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    Struct(SomeIpStructSerializer<'a, 't, Options, Writer>),
+    Bitfield(SomeIpBitfieldSerializer<'a, 't, Options, Writer>),
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStruct
+    for SomeIpStructLikeSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        match self {
+            SomeIpStructLikeSerializer::Struct(s) => {
+                SerializeStruct::serialize_field(s, key, value)
+            }
+            SomeIpStructLikeSerializer::Bitfield(b) => b.serialize_flag(key, value),
+        }
+    }
+
+    fn end(self) -> Result<()> {
+        match self {
+            SomeIpStructLikeSerializer::Struct(s) => SerializeStruct::end(s),
+            SomeIpStructLikeSerializer::Bitfield(b) => b.finish(),
+        }
+    }
+}
+
+struct SomeIpUnionSerializer<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
+    inner: SomeIpStructSerializer<'a, 't, Options, Writer>,
+    length_field_size: LengthFieldSize,
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeTupleVariant
+    for SomeIpUnionSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.inner.serialize_next_field(value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.inner
+            .finish()?
+            .end_length_delimited_section(self.length_field_size)
+    }
+}
+
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SerializeStructVariant
+    for SomeIpUnionSerializer<'a, 't, Options, Writer>
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<()> {
+        SerializeStruct::serialize_field(&mut self.inner, key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        self.inner
+            .finish()?
+            .end_length_delimited_section(self.length_field_size)
+    }
+}
+
+struct SomeIpSerializer<'t, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> {
     writer: Writer,
-    next_type: &'static SomeIpType,
+    next_type: &'t SomeIpType,
     is_in_tlv_struct: bool,
     last_length_field: Option<(LengthFieldSize, bool)>,
-    length_delimited_sections: Vec<(usize, LengthFieldSize, bool)>,
+    //(position, reserved length field size, was in tlv struct, bytes at the beginning not counted by the length field)
+    length_delimited_sections: Vec<(usize, LengthFieldSize, bool, usize)>,
     transformation_props: Option<SomeIpTransforationProperties>,
     phantom: PhantomData<Options>,
 }
 
-impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Options, Writer> {
-    fn new(writer: Writer, root_type: &'static SomeIpType) -> SomeIpSerializer<Options, Writer> {
+impl<'t, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter>
+    SomeIpSerializer<'t, Options, Writer>
+{
+    fn new(writer: Writer, root_type: &'t SomeIpType) -> SomeIpSerializer<'t, Options, Writer> {
         SomeIpSerializer {
             writer,
             next_type: root_type,
@@ -452,21 +778,21 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
         let pos = self.writer.len();
         let reserved = std::cmp::max(configured, maximum_needed);
         self.length_delimited_sections
-            .push((pos, reserved, self.is_in_tlv_struct));
+            .push((pos, reserved, self.is_in_tlv_struct, 0));
         let size = reserved.into();
         self.writer.put_zeros(size)
     }
 
     fn end_length_delimited_section(&mut self, configured: LengthFieldSize) -> Result<()> {
-        let (pos, reserved, was_in_tlv) = self.length_delimited_sections.pop().unwrap();
+        let (pos, reserved, was_in_tlv, not_counted) =
+            self.length_delimited_sections.pop().unwrap();
         let end = self.writer.len();
-        let len = end - pos - usize::from(reserved);
+        let len = end - pos - usize::from(reserved) - not_counted;
 
         let actual = select_length_field_size::<Options>(configured, len, was_in_tlv)?;
         self.last_length_field = Some((actual, actual == configured));
 
-        if actual != reserved {
-            assert!(reserved > actual);
+        if actual < reserved {
             self.writer
                 .copy_within(pos + usize::from(reserved)..end, pos + usize::from(actual));
             let difference = usize::from(reserved) - usize::from(actual);
@@ -474,11 +800,17 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
                 //safe we just trim the buffer to size
                 self.writer.set_len(end - difference);
             }
+        } else if actual > reserved {
+            //can only happen if the section contains unknown members which are not part of max_len
+            let difference = usize::from(actual) - usize::from(reserved);
+            self.writer.put_zeros(difference)?;
+            self.writer
+                .copy_within(pos + usize::from(reserved)..end, pos + usize::from(actual));
         }
 
         match actual {
             LengthFieldSize::OneByte => {
-                if len > u8::max_value() as usize {
+                if len > u8::MAX as usize {
                     Err(Error::TooLong {
                         actual_length: len,
                         length_field_size: actual,
@@ -488,7 +820,7 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
                 }
             }
             LengthFieldSize::TwoBytes => {
-                if len > u16::max_value() as usize {
+                if len > u16::MAX as usize {
                     Err(Error::TooLong {
                         actual_length: len,
                         length_field_size: actual,
@@ -498,7 +830,7 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
                 }
             }
             LengthFieldSize::FourBytes => {
-                if len > u32::max_value() as usize {
+                if len > u32::MAX as usize {
                     Err(Error::TooLong {
                         actual_length: len,
                         length_field_size: actual,
@@ -525,6 +857,58 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
         Ok(())
     }
 
+    fn begin_union(
+        &mut self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(Option<&'t SomeIpType>, LengthFieldSize)> {
+        let u = if let SomeIpType::Union(u) = self.next_type {
+            u
+        } else {
+            panic!("Expeceted a union but found {}", self.next_type)
+        };
+        let v = if name == UNION_VARIANT_BY_INDEX {
+            u.variants.get(variant_index as usize)
+        } else {
+            u.variant_by_name(variant)
+        }
+        .unwrap_or_else(|| panic!("Union {} has no variant {}", u.name, variant));
+
+        let configured = u
+            .wanted_length_field::<Options>(self.is_in_tlv_struct, &self.transformation_props)?
+            .unwrap();
+        self.begin_length_delimited_section(
+            configured,
+            LengthFieldSize::minimum_length_for(
+                u.max_len::<Options>(self.is_in_tlv_struct, &self.transformation_props)?,
+            ),
+        )?;
+
+        let type_field_size = u.get_type_field_size::<Options>();
+        if LengthFieldSize::minimum_length_for(v.selector as usize) > type_field_size {
+            panic!(
+                "Selector {} of variant {} does not fit into type field of size {}",
+                v.selector, v.name, type_field_size
+            );
+        }
+        match type_field_size {
+            LengthFieldSize::OneByte => self.writer.write_u8(v.selector as u8)?,
+            LengthFieldSize::TwoBytes => self
+                .writer
+                .write_ux(v.selector as u16, Options::BYTE_ORDER)?,
+            LengthFieldSize::FourBytes => self.writer.write_ux(v.selector, Options::BYTE_ORDER)?,
+        }
+        //the type field is not part of the length, except in tlv structs where
+        //a receiver must be able to skip the whole member using the length
+        if !self.is_in_tlv_struct {
+            self.length_delimited_sections.last_mut().unwrap().3 = usize::from(type_field_size);
+        }
+        self.is_in_tlv_struct = false;
+
+        Ok((v.variant_type.as_deref(), configured))
+    }
+
     fn internal_write_str(&mut self, v: &str) -> Result<()> {
         if !Options::STRING_ENCODING.is_utf16_variant() {
             self.writer.write(v.as_bytes())
@@ -541,19 +925,19 @@ impl<Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> SomeIpSerializer<Opt
     }
 }
 
-impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
-    for &'a mut SomeIpSerializer<Options, Writer>
+impl<'a, 't, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
+    for &'a mut SomeIpSerializer<'t, Options, Writer>
 {
     type Ok = ();
     type Error = Error;
 
-    type SerializeSeq = SomeIpSeqSerializer<'a, Options, Writer>;
-    type SerializeTuple = Phony;
-    type SerializeTupleStruct = Phony;
-    type SerializeTupleVariant = Phony;
-    type SerializeMap = Phony;
-    type SerializeStruct = SomeIpStructSerializer<'a, Options, Writer>;
-    type SerializeStructVariant = Phony;
+    type SerializeSeq = SomeIpSeqSerializer<'a, 't, Options, Writer>;
+    type SerializeTuple = SomeIpTupleSerializer<'a, 't, Options, Writer>;
+    type SerializeTupleStruct = SomeIpStructSerializer<'a, 't, Options, Writer>;
+    type SerializeTupleVariant = SomeIpUnionSerializer<'a, 't, Options, Writer>;
+    type SerializeMap = SomeIpMapSerializer<'a, 't, Options, Writer>;
+    type SerializeStruct = SomeIpStructLikeSerializer<'a, 't, Options, Writer>;
+    type SerializeStructVariant = SomeIpUnionSerializer<'a, 't, Options, Writer>;
 
     fn serialize_bool(self, v: bool) -> Result<()> {
         if v {
@@ -642,7 +1026,12 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
             if Options::STRING_WITH_TERMINATOR {
                 self.internal_write_str("\0")?;
             }
-            let actual_len = self.writer.len() - begin;
+            let mut actual_len = self.writer.len() - begin;
+
+            if s.padding && actual_len < s.max_size {
+                self.writer.put_zeros(s.max_size - actual_len)?;
+                actual_len = s.max_size;
+            }
 
             if actual_len < s.min_size {
                 return Err(Error::NotEnoughData {
@@ -667,8 +1056,17 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
-        if let SomeIpType::Sequence(s) = self.next_type {
-            if !matches!(s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
+        if let SomeIpType::UnknownMembers = self.next_type {
+            let members = UnknownMembers::decode(v).ok_or_else(|| {
+                Error::Message("Unknown members were not encoded by UnknownMembers".into())
+            })?;
+            for member in members {
+                member.tag.serialize(&mut *self)?;
+                self.writer.write(&member.data)?;
+            }
+            Ok(())
+        } else if let SomeIpType::Sequence(s) = self.next_type {
+            if !matches!(*s.element_type, SomeIpType::Primitive(SomeIpPrimitive::U8)) {
                 panic!("Expeceted Primitive(u8) bu found {}", s.element_type)
             }
             if v.len() < s.min_elements {
@@ -734,15 +1132,23 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
 
     fn serialize_unit_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
+        name: &'static str,
+        variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        let value = if let SomeIpType::Enum(e) = self.next_type {
-            e.name_to_value(variant)
-                .unwrap_or_else(|| panic!("Enum {} has no field {}", e.name, variant))
-        } else {
-            panic!("Expeceted an enum but found {}", self.next_type)
+        let value = match self.next_type {
+            SomeIpType::Enum(e) => e
+                .name_to_value(variant)
+                .unwrap_or_else(|| panic!("Enum {} has no field {}", e.name, variant)),
+            SomeIpType::Union(u) => {
+                let (variant_type, length_field_size) =
+                    self.begin_union(name, variant_index, variant)?;
+                if variant_type.is_some() {
+                    panic!("Variant {} of union {} must carry data", variant, u.name);
+                }
+                return self.end_length_delimited_section(length_field_size);
+            }
+            _ => panic!("Expeceted an enum but found {}", self.next_type),
         };
 
         match value {
@@ -767,14 +1173,23 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
 
     fn serialize_newtype_variant<T: Serialize + ?Sized>(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _value: &T,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
     ) -> Result<()> {
-        unimplemented!(
-            "Newtype variants (e.g.: enum Foo {{ Bar(u32) }} ) are not supported by someip"
-        )
+        if let SomeIpType::Enum(e) = self.next_type {
+            //the other variant of an enum carries the raw value
+            if e.other.as_deref() != Some(variant) {
+                panic!("Variant {} of enum {} carries data", variant, e.name);
+            }
+            return value.serialize(self);
+        }
+        let (variant_type, length_field_size) = self.begin_union(name, variant_index, variant)?;
+        self.next_type = variant_type
+            .unwrap_or_else(|| panic!("Variant {} of {} carries no data", variant, self.next_type));
+        value.serialize(&mut *self)?;
+        self.end_length_delimited_section(length_field_size)
     }
 
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
@@ -783,46 +1198,76 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
         Ok(seq_serializer)
     }
 
-    fn serialize_tuple(self, _len: usize) -> Result<Phony> {
-        unimplemented!("Tuples (e.g.: (u32, i64,...) ) are not supported by someip")
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        //fixed size arrays are serialized as tuples by serde
+        if let SomeIpType::Sequence(_) = self.next_type {
+            Ok(SomeIpTupleSerializer::Sequence(
+                self.serialize_seq(Some(len))?,
+            ))
+        } else {
+            Ok(SomeIpTupleSerializer::Struct(
+                self.serialize_tuple_struct("", len)?,
+            ))
+        }
     }
 
-    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Phony> {
-        unimplemented!(
-            "Tuple structs (e.g.: struct Foor(u32, i64, ...) ) are not supported by someip"
-        )
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        let mut struct_serializer = SomeIpStructSerializer::new(self, len)?;
+        struct_serializer.start()?;
+        Ok(struct_serializer)
     }
 
     fn serialize_tuple_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
-    ) -> Result<Phony> {
-        unimplemented!(
-            "Tuple variants (e.g.: enum Foo {{ Bar(u32, i64, ...) }} ) are not supported by someip"
-        )
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        self.serialize_struct_variant(name, variant_index, variant, len)
     }
 
-    fn serialize_map(self, _len: Option<usize>) -> Result<Phony> {
-        unimplemented!("Maps are not supported by someip")
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        let mut map_serializer = SomeIpMapSerializer::new(self, len)?;
+        map_serializer.start()?;
+        Ok(map_serializer)
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        if let SomeIpType::Bitfield(b) = self.next_type {
+            return Ok(SomeIpStructLikeSerializer::Bitfield(
+                SomeIpBitfieldSerializer {
+                    serializer: self,
+                    bitfield_type: b,
+                    value: 0,
+                },
+            ));
+        }
         let mut struct_serializer = SomeIpStructSerializer::new(self, len)?;
         struct_serializer.start()?;
-        Ok(struct_serializer)
+        Ok(SomeIpStructLikeSerializer::Struct(struct_serializer))
     }
 
     fn serialize_struct_variant(
         self,
-        _name: &'static str,
-        _variant_index: u32,
-        _variant: &'static str,
-        _len: usize,
-    ) -> Result<Phony> {
-        unimplemented!("Struct variants (e.g.: enum Foo {{ Bar{{ a: u32, b: i64, ...}} }} ) are not supported by someip")
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        let (variant_type, length_field_size) = self.begin_union(name, variant_index, variant)?;
+        self.next_type = variant_type
+            .unwrap_or_else(|| panic!("Variant {} of {} carries no data", variant, self.next_type));
+        let mut inner = SomeIpStructSerializer::new(self, len)?;
+        inner.start()?;
+        Ok(SomeIpUnionSerializer {
+            inner,
+            length_field_size,
+        })
     }
 
     fn is_human_readable(&self) -> bool {
@@ -833,7 +1278,7 @@ impl<'a, Options: SomeIpOptions + ?Sized, Writer: SomeIpWriter> Serializer
 #[inline]
 fn to_x_manuel<Options, T, Buf: SomeIpWriter>(
     value: &T,
-    someip_type: &'static SomeIpType,
+    someip_type: &SomeIpType,
     buf: Buf,
 ) -> Result<Buf>
 where
@@ -850,6 +1295,16 @@ where
     Ok(serializer.finish())
 }
 
+//Used to serialize values whose type is only known at runtime, see SomeIpValue
+#[inline]
+pub(crate) fn to_vec_with_type<Options, T>(value: &T, someip_type: &SomeIpType) -> Result<Vec<u8>>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Serialize,
+{
+    to_x_manuel::<Options, _, _>(value, someip_type, Vec::default())
+}
+
 /// Serialises the value to a [Vec<u8>]
 ///
 /// # Panics
@@ -926,10 +1381,7 @@ fn test_bool() {
 fn test_u8() {
     assert_eq!(vec![42], to_vec::<ExampleOptions, _>(&42u8).unwrap());
     assert_eq!(vec![0], to_vec::<ExampleOptions, _>(&0u8).unwrap());
-    assert_eq!(
-        vec![255],
-        to_vec::<ExampleOptions, _>(&u8::max_value()).unwrap()
-    );
+    assert_eq!(vec![255], to_vec::<ExampleOptions, _>(&u8::MAX).unwrap());
 }
 
 #[test]
@@ -937,14 +1389,8 @@ fn test_i8() {
     assert_eq!(vec![42], to_vec::<ExampleOptions, _>(&42i8).unwrap());
     assert_eq!(vec![0], to_vec::<ExampleOptions, _>(&0i8).unwrap());
     assert_eq!(vec![0xFF], to_vec::<ExampleOptions, _>(&-1i8).unwrap());
-    assert_eq!(
-        vec![0x7F],
-        to_vec::<ExampleOptions, _>(&i8::max_value()).unwrap()
-    );
-    assert_eq!(
-        vec![0x80],
-        to_vec::<ExampleOptions, _>(&i8::min_value()).unwrap()
-    );
+    assert_eq!(vec![0x7F], to_vec::<ExampleOptions, _>(&i8::MAX).unwrap());
+    assert_eq!(vec![0x80], to_vec::<ExampleOptions, _>(&i8::MIN).unwrap());
 }
 
 #[cfg(test)]
@@ -968,43 +1414,43 @@ macro_rules! test_endianess {
 test_endianess!(test_u16,
     42u16 => vec![0, 42],
     0u16 => vec![0, 0],
-    u16::max_value() => vec![0xFF, 0xFF]);
+    u16::MAX => vec![0xFF, 0xFF]);
 
 #[cfg(test)]
 test_endianess!(test_i16,
     42i16 => vec![0, 42],
     0i16 => vec![0, 0],
-    i16::max_value() => vec![0x7F, 0xFF],
+    i16::MAX => vec![0x7F, 0xFF],
     -1i16 => vec![0xFF, 0xFF],
-    i16::min_value() => vec![0x80, 0]);
+    i16::MIN => vec![0x80, 0]);
 
 #[cfg(test)]
 test_endianess!(test_u32,
     42u32 => vec![0, 0, 0, 42],
     0u32 => vec![0; 4],
-    u32::max_value() => vec![0xFF; 4]);
+    u32::MAX => vec![0xFF; 4]);
 
 #[cfg(test)]
 test_endianess!(test_i32,
     42i32 => vec![0, 0, 0, 42],
     0i32 => vec![0; 4],
-    i32::max_value() => vec![0x7F, 0xFF, 0xFF, 0xFF],
+    i32::MAX => vec![0x7F, 0xFF, 0xFF, 0xFF],
     -1i32 => vec![0xFF; 4],
-    i32::min_value() => vec![0x80, 0, 0, 0]);
+    i32::MIN => vec![0x80, 0, 0, 0]);
 
 #[cfg(test)]
 test_endianess!(test_u64,
     42u64 => vec![0, 0, 0, 0, 0, 0, 0, 42],
     0u64 => vec![0; 8],
-    u64::max_value() => vec![0xFF; 8]);
+    u64::MAX => vec![0xFF; 8]);
 
 #[cfg(test)]
 test_endianess!(test_i64,
     42i64 => vec![0, 0, 0, 0, 0, 0, 0, 42],
     0i64 => vec![0; 8],
-    i64::max_value() => vec![0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+    i64::MAX => vec![0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
     -1i64 => vec![0xFF; 8],
-    i64::min_value() => vec![0x80, 0, 0, 0, 0, 0, 0, 0]);
+    i64::MIN => vec![0x80, 0, 0, 0, 0, 0, 0, 0]);
 
 #[cfg(test)]
 test_endianess!(test_f32,
@@ -1031,34 +1477,59 @@ test_endianess!(test_f64,
     f64::MIN => vec![0xFF, 0xEF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
 
 #[test]
-fn test_enum() {
+fn test_enum() {
+    #[derive(Debug, serde::Serialize)]
+    enum TestEnum {
+        First,
+        Second,
+        Third,
+    }
+
+    impl SomeIp for TestEnum {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+            name: SomeIpRef::Static("TestEnum"),
+            raw_type: SomeIpPrimitive::I16,
+            values: SomeIpRef::Static(&[
+                (SomeIpRef::Static("First"), SomeIpEnumValue::I16(0)),
+                (SomeIpRef::Static("Second"), SomeIpEnumValue::I16(42)),
+                (SomeIpRef::Static("Third"), SomeIpEnumValue::I16(-1337)),
+            ]),
+            other: None,
+        });
+    }
+
+    let result = to_vec::<ExampleOptions, _>(&TestEnum::First).unwrap();
+    assert_eq!(vec![0, 0], result);
+
+    let result = to_vec::<ExampleOptions, _>(&TestEnum::Second).unwrap();
+    assert_eq!(vec![0, 42], result);
+
+    let result = to_vec::<ExampleOptions, _>(&TestEnum::Third).unwrap();
+    assert_eq!(vec![0xFA, 0xC7], result);
+}
+
+#[test]
+fn test_enum_other() {
     #[derive(Debug, serde::Serialize)]
     enum TestEnum {
         First,
-        Second,
-        Third,
+        Unknown(u16),
     }
 
     impl SomeIp for TestEnum {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Enum(SomeIpEnum {
-            name: "TestEnum",
-            raw_type: SomeIpPrimitive::I16,
-            values: &[
-                ("First", SomeIpEnumValue::I16(0)),
-                ("Second", SomeIpEnumValue::I16(42)),
-                ("Third", SomeIpEnumValue::I16(-1337)),
-            ],
+            name: SomeIpRef::Static("TestEnum"),
+            raw_type: SomeIpPrimitive::U16,
+            values: SomeIpRef::Static(&[(SomeIpRef::Static("First"), SomeIpEnumValue::U16(1))]),
+            other: Some(SomeIpRef::Static("Unknown")),
         });
     }
 
     let result = to_vec::<ExampleOptions, _>(&TestEnum::First).unwrap();
-    assert_eq!(vec![0, 0], result);
-
-    let result = to_vec::<ExampleOptions, _>(&TestEnum::Second).unwrap();
-    assert_eq!(vec![0, 42], result);
+    assert_eq!(vec![0, 1], result);
 
-    let result = to_vec::<ExampleOptions, _>(&TestEnum::Third).unwrap();
-    assert_eq!(vec![0xFA, 0xC7], result);
+    let result = to_vec::<ExampleOptions, _>(&TestEnum::Unknown(0x1234)).unwrap();
+    assert_eq!(vec![0x12, 0x34], result);
 }
 
 #[test]
@@ -1067,7 +1538,7 @@ fn test_bytes() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 32,
         min_elements: 0,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(32);
@@ -1091,7 +1562,7 @@ fn test_bytes_no_length_field() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 4,
-        element_type: &u8::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
     let mut bytes = BytesMut::with_capacity(4);
@@ -1107,7 +1578,7 @@ fn test_i16_seq() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 0,
-        element_type: &i16::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1123,7 +1594,7 @@ fn test_i16_seq_no_length_field() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 4,
-        element_type: &i16::SOMEIP_TYPE,
+        element_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1139,12 +1610,12 @@ fn test_2_dimensional_seq() {
     const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
         max_elements: 4,
         min_elements: 0,
-        element_type: &SomeIpType::Sequence(SomeIpSequence {
+        element_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
             max_elements: 3,
             min_elements: 0,
-            element_type: &i8::SOMEIP_TYPE,
+            element_type: SomeIpRef::Static(&i8::SOMEIP_TYPE),
             length_field_size: Some(LengthFieldSize::OneByte),
-        }),
+        })),
         length_field_size: Some(LengthFieldSize::TwoBytes),
     });
 
@@ -1161,6 +1632,7 @@ fn test_string() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     assert_eq!(
@@ -1175,6 +1647,7 @@ fn test_string_no_length_field() {
         max_size: 2,
         min_size: 2,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     assert_eq!(
@@ -1189,6 +1662,7 @@ fn test_string_utf8_bom() {
         max_size: 5,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1207,6 +1681,7 @@ fn test_string_utf8_terminator() {
         max_size: 5,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1225,6 +1700,7 @@ fn test_string_utf16() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -1244,6 +1720,7 @@ fn test_string_utf16_bom() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1263,6 +1740,7 @@ fn test_string_utf16_bom_le() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1283,6 +1761,7 @@ fn test_string_utf16_terminator() {
         max_size: 6,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
     struct Options;
     impl SomeIpOptions for Options {
@@ -1296,6 +1775,50 @@ fn test_string_utf16_terminator() {
     );
 }
 
+#[test]
+fn test_string_padding() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        max_size: 5,
+        min_size: 5,
+        length_field_size: None,
+        padding: true,
+    });
+
+    assert_eq!(
+        vec![0x68, 0x69, 0, 0, 0],
+        to_x_manuel::<ExampleOptions, _, _>(&"hi", &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+    assert_eq!(
+        vec![0x68, 0x65, 0x6C, 0x6C, 0x6F],
+        to_x_manuel::<ExampleOptions, _, _>(&"hello", &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+    assert!(matches!(
+        to_x_manuel::<ExampleOptions, _, _>(&"hello!", &SOMEIP_TYPE, Vec::default()),
+        Err(Error::TooMuchData { max: 5, actual: 6 })
+    ));
+}
+
+#[test]
+fn test_string_padding_utf16_terminator() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::String(SomeIpString {
+        max_size: 8,
+        min_size: 8,
+        length_field_size: None,
+        padding: true,
+    });
+
+    struct Options;
+    impl SomeIpOptions for Options {
+        const STRING_ENCODING: StringEncoding = StringEncoding::Utf16;
+        const STRING_WITH_TERMINATOR: bool = true;
+    }
+
+    assert_eq!(
+        vec![0, 0x68, 0, 0x69, 0, 0, 0, 0],
+        to_x_manuel::<Options, _, _>(&"hi", &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+}
+
 #[test]
 fn test_struct() {
     #[derive(Debug, serde::Serialize)]
@@ -1306,33 +1829,37 @@ fn test_struct() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: None,
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: None,
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: None,
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: None,
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1358,33 +1885,37 @@ fn test_struct_beginning_length_field() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: None,
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: None,
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: None,
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: false,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1410,33 +1941,37 @@ fn test_struct_tlv() {
 
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1461,33 +1996,37 @@ fn test_struct_tlv_message_wrapper() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
-                    name: "first",
+                    name: SomeIpRef::Static("first"),
                     id: Some(1),
-                    field_type: &i16::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&i16::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "second",
+                    name: SomeIpRef::Static("second"),
                     id: Some(2),
-                    field_type: &u32::SOMEIP_TYPE,
+                    field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                    default: None,
                 },
                 SomeIpField {
-                    name: "third",
+                    name: SomeIpRef::Static("third"),
                     id: Some(3),
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                         max_elements: 5,
                         min_elements: 1,
-                        element_type: &u8::SOMEIP_TYPE,
+                        element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                         length_field_size: Some(LengthFieldSize::OneByte),
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
             uses_tlv_serialization: true,
             is_message_wrapper: true,
             length_field_size: Some(LengthFieldSize::TwoBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1510,21 +2049,23 @@ fn test_uses_smallest_length_field_size() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[SomeIpField {
-                name: "a",
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
                 id: Some(0),
-                field_type: &SomeIpType::Sequence(SomeIpSequence {
+                field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
                     max_elements: 1 << 24,
                     min_elements: 0,
-                    element_type: &u8::SOMEIP_TYPE,
+                    element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
                     length_field_size: Some(LengthFieldSize::FourBytes),
-                }),
-            }],
+                })),
+                default: None,
+            }]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::FourBytes),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1545,16 +2086,18 @@ fn test_optional() {
     }
     impl SomeIp for Test {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
-            name: "Test",
-            fields: &[SomeIpField {
-                name: "a",
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
                 id: Some(0),
-                field_type: &u32::SOMEIP_TYPE,
-            }],
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            }]),
             uses_tlv_serialization: true,
             is_message_wrapper: false,
             length_field_size: Some(LengthFieldSize::OneByte),
             transformation_properties: None,
+            extensible: false,
         });
     }
 
@@ -1579,6 +2122,7 @@ fn test_newtype() {
             min_size: 0,
             max_size: 42,
             length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
         });
     }
 
@@ -1598,6 +2142,7 @@ fn test_append_to_vec() {
             min_size: 0,
             max_size: 42,
             length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
         });
     }
 
@@ -1621,25 +2166,29 @@ fn test_struct_in_struct() {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
             is_message_wrapper: false,
             length_field_size: None,
-            name: "Outer",
+            name: SomeIpRef::Static("Outer"),
             uses_tlv_serialization: true,
             transformation_properties: None,
-            fields: &[SomeIpField {
+            fields: SomeIpRef::Static(&[SomeIpField {
                 id: Some(1),
-                name: "inner",
-                field_type: &SomeIpType::Struct(SomeIpStruct {
+                name: SomeIpRef::Static("inner"),
+                field_type: SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
                     is_message_wrapper: false,
                     length_field_size: None,
-                    name: "Inner",
+                    name: SomeIpRef::Static("Inner"),
                     uses_tlv_serialization: true,
                     transformation_properties: None,
-                    fields: &[SomeIpField {
+                    fields: SomeIpRef::Static(&[SomeIpField {
                         id: Some(1),
-                        name: "some_field",
-                        field_type: &u32::SOMEIP_TYPE,
-                    }],
-                }),
-            }],
+                        name: SomeIpRef::Static("some_field"),
+                        field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                        default: None,
+                    }]),
+                    extensible: false,
+                })),
+                default: None,
+            }]),
+            extensible: false,
         });
     }
 
@@ -1662,34 +2211,39 @@ fn test_struct_with_props() {
         const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
             is_message_wrapper: false,
             length_field_size: None,
-            name: "TestStruct",
+            name: SomeIpRef::Static("TestStruct"),
             uses_tlv_serialization: true,
             transformation_properties: Some(SomeIpTransforationProperties {
                 size_of_array_length_field: Some(LengthFieldSize::OneByte),
                 size_of_string_length_field: Some(LengthFieldSize::TwoBytes),
                 size_of_struct_length_field: Some(LengthFieldSize::FourBytes),
+                alignment: None,
             }),
-            fields: &[
+            fields: SomeIpRef::Static(&[
                 SomeIpField {
                     id: Some(1),
-                    name: "a",
-                    field_type: &SomeIpType::String(SomeIpString {
+                    name: SomeIpRef::Static("a"),
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
                         min_size: 0,
                         max_size: 1337,
                         length_field_size: None,
-                    }),
+                        padding: false,
+                    })),
+                    default: None,
                 },
                 SomeIpField {
                     id: Some(2),
-                    name: "b",
-                    field_type: &SomeIpType::Sequence(SomeIpSequence {
-                        element_type: &u16::SOMEIP_TYPE,
+                    name: SomeIpRef::Static("b"),
+                    field_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+                        element_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
                         min_elements: 0,
                         max_elements: 42,
                         length_field_size: None,
-                    }),
+                    })),
+                    default: None,
                 },
-            ],
+            ]),
+            extensible: false,
         });
     }
 
@@ -1715,6 +2269,7 @@ fn test_string_utf16le_ints_be() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -1733,6 +2288,7 @@ fn test_string_utf16be_ints_le() {
         max_size: 4,
         min_size: 0,
         length_field_size: Some(LengthFieldSize::OneByte),
+        padding: false,
     });
 
     struct Options;
@@ -1744,3 +2300,647 @@ fn test_string_utf16be_ints_le() {
     let result = to_x_manuel::<Options, _, _>(&"hi", &SOMEIP_TYPE, Vec::default()).unwrap();
     assert_eq!(vec![4, 0, 0x68, 0, 0x69], result);
 }
+
+#[cfg(test)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub(crate) enum TestUnion {
+    Empty,
+    Newtype(u32),
+    Struct { a: u8, b: u16 },
+    Tuple(u8, u8),
+}
+
+#[cfg(test)]
+impl SomeIp for TestUnion {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Union(SomeIpUnion {
+        name: SomeIpRef::Static("TestUnion"),
+        variants: SomeIpRef::Static(&[
+            SomeIpUnionVariant {
+                name: SomeIpRef::Static("Empty"),
+                selector: 0,
+                variant_type: None,
+            },
+            SomeIpUnionVariant {
+                name: SomeIpRef::Static("Newtype"),
+                selector: 1,
+                variant_type: Some(SomeIpRef::Static(&u32::SOMEIP_TYPE)),
+            },
+            SomeIpUnionVariant {
+                name: SomeIpRef::Static("Struct"),
+                selector: 2,
+                variant_type: Some(SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
+                    name: SomeIpRef::Static("TestUnion::Struct"),
+                    fields: SomeIpRef::Static(&[
+                        SomeIpField {
+                            name: SomeIpRef::Static("a"),
+                            id: None,
+                            field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                            default: None,
+                        },
+                        SomeIpField {
+                            name: SomeIpRef::Static("b"),
+                            id: None,
+                            field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                            default: None,
+                        },
+                    ]),
+                    uses_tlv_serialization: false,
+                    is_message_wrapper: false,
+                    length_field_size: None,
+                    transformation_properties: None,
+                    extensible: false,
+                }))),
+            },
+            SomeIpUnionVariant {
+                name: SomeIpRef::Static("Tuple"),
+                selector: 3,
+                variant_type: Some(SomeIpRef::Static(&SomeIpType::Struct(SomeIpStruct {
+                    name: SomeIpRef::Static("TestUnion::Tuple"),
+                    fields: SomeIpRef::Static(&[
+                        SomeIpField {
+                            name: SomeIpRef::Static("0"),
+                            id: None,
+                            field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                            default: None,
+                        },
+                        SomeIpField {
+                            name: SomeIpRef::Static("1"),
+                            id: None,
+                            field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                            default: None,
+                        },
+                    ]),
+                    uses_tlv_serialization: false,
+                    is_message_wrapper: false,
+                    length_field_size: None,
+                    transformation_properties: None,
+                    extensible: false,
+                }))),
+            },
+        ]),
+        length_field_size: Some(LengthFieldSize::OneByte),
+        type_field_size: Some(LengthFieldSize::OneByte),
+    });
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(
+        vec![0, 0],
+        to_vec::<ExampleOptions, _>(&TestUnion::Empty).unwrap()
+    );
+    assert_eq!(
+        vec![4, 1, 0, 0, 0, 42],
+        to_vec::<ExampleOptions, _>(&TestUnion::Newtype(42)).unwrap()
+    );
+    assert_eq!(
+        vec![3, 2, 1, 0, 2],
+        to_vec::<ExampleOptions, _>(&TestUnion::Struct { a: 1, b: 2 }).unwrap()
+    );
+    assert_eq!(
+        vec![2, 3, 1, 2],
+        to_vec::<ExampleOptions, _>(&TestUnion::Tuple(1, 2)).unwrap()
+    );
+}
+
+#[test]
+fn test_union_default_type_field_size() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Union(SomeIpUnion {
+        name: SomeIpRef::Static("Test"),
+        variants: SomeIpRef::Static(&[SomeIpUnionVariant {
+            name: SomeIpRef::Static("Newtype"),
+            selector: 1,
+            variant_type: Some(SomeIpRef::Static(&u32::SOMEIP_TYPE)),
+        }]),
+        length_field_size: None,
+        type_field_size: None,
+    });
+
+    assert_eq!(
+        vec![0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 42],
+        to_x_manuel::<ExampleOptions, _, _>(&TestUnion::Newtype(42), &SOMEIP_TYPE, Vec::default())
+            .unwrap()
+    );
+    assert_eq!(
+        vec![4, 0, 0, 0, 1, 0, 0, 0, 42, 0, 0, 0],
+        to_x_manuel::<test::LittleEndianOptions, _, _>(
+            &TestUnion::Newtype(42),
+            &SOMEIP_TYPE,
+            Vec::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_union_default_length_field_size() {
+    struct NoDefaultLengthOptions;
+    impl SomeIpOptions for NoDefaultLengthOptions {
+        const DEFAULT_LENGTH_FIELD_SIZE: Option<LengthFieldSize> = None;
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    struct Test {
+        a: TestUnion,
+    }
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("Test"),
+        fields: SomeIpRef::Static(&[SomeIpField {
+            name: SomeIpRef::Static("a"),
+            id: None,
+            field_type: SomeIpRef::Static(&SomeIpType::Union(SomeIpUnion {
+                name: SomeIpRef::Static("TestUnion"),
+                variants: SomeIpRef::Static(&[SomeIpUnionVariant {
+                    name: SomeIpRef::Static("Newtype"),
+                    selector: 1,
+                    variant_type: Some(SomeIpRef::Static(&u32::SOMEIP_TYPE)),
+                }]),
+                length_field_size: None,
+                type_field_size: None,
+            })),
+            default: None,
+        }]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: true,
+        length_field_size: None,
+        transformation_properties: Some(SomeIpTransforationProperties {
+            size_of_array_length_field: Some(LengthFieldSize::TwoBytes),
+            size_of_struct_length_field: Some(LengthFieldSize::TwoBytes),
+            size_of_string_length_field: Some(LengthFieldSize::TwoBytes),
+            alignment: None,
+        }),
+        extensible: false,
+    });
+
+    //the transformation properties do not apply and without any default four bytes are used
+    assert_eq!(
+        vec![0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 42],
+        to_x_manuel::<NoDefaultLengthOptions, _, _>(
+            &Test {
+                a: TestUnion::Newtype(42)
+            },
+            &SOMEIP_TYPE,
+            Vec::default()
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_union_in_tlv_struct() {
+    #[derive(Debug, serde::Serialize)]
+    struct Test {
+        a: TestUnion,
+    }
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: Some(1),
+                field_type: SomeIpRef::Static(&TestUnion::SOMEIP_TYPE),
+                default: None,
+            }]),
+            uses_tlv_serialization: true,
+            is_message_wrapper: true,
+            length_field_size: None,
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    assert_eq!(
+        vec![0x50, 1, 5, 1, 0, 0, 0, 42],
+        to_vec::<ExampleOptions, _>(&Test {
+            a: TestUnion::Newtype(42)
+        })
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_bitfield() {
+    #[derive(Debug, serde::Serialize)]
+    struct Test {
+        a: bool,
+        b: bool,
+        c: bool,
+    }
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Bitfield(SomeIpBitfield {
+            name: SomeIpRef::Static("Test"),
+            flags: SomeIpRef::Static(&[
+                (SomeIpRef::Static("a"), 0),
+                (SomeIpRef::Static("b"), 3),
+                (SomeIpRef::Static("c"), 15),
+            ]),
+            raw_type: SomeIpPrimitive::U16,
+        });
+    }
+
+    let value = Test {
+        a: true,
+        b: true,
+        c: false,
+    };
+    assert_eq!(
+        vec![0x00, 0x09],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+    assert_eq!(
+        vec![0x09, 0x00],
+        to_vec::<test::LittleEndianOptions, _>(&value).unwrap()
+    );
+
+    let value = Test {
+        a: false,
+        b: false,
+        c: true,
+    };
+    assert_eq!(
+        vec![0x80, 0x00],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
+
+#[test]
+fn test_map() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Map(SomeIpMap {
+        min_elements: 0,
+        max_elements: 2,
+        key_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+        value_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+            min_size: 0,
+            max_size: 10,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
+        })),
+        length_field_size: Some(LengthFieldSize::OneByte),
+    });
+
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(1u8, "a");
+    map.insert(2u8, "bc");
+    assert_eq!(
+        vec![7, 1, 1, 0x61, 2, 2, 0x62, 0x63],
+        to_x_manuel::<ExampleOptions, _, _>(&map, &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+
+    map.insert(3u8, "d");
+    assert!(matches!(
+        to_x_manuel::<ExampleOptions, _, _>(&map, &SOMEIP_TYPE, Vec::default()),
+        Err(Error::TooMuchData { max: 2, actual: 3 })
+    ));
+}
+
+#[test]
+fn test_array() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 3,
+        max_elements: 3,
+        element_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+        length_field_size: None,
+    });
+
+    assert_eq!(
+        vec![0, 1, 0, 2, 0, 3],
+        to_x_manuel::<ExampleOptions, _, _>(&[1u16, 2, 3], &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+}
+
+#[test]
+fn test_nested_array() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 2,
+        max_elements: 2,
+        element_type: SomeIpRef::Static(&SomeIpType::Sequence(SomeIpSequence {
+            min_elements: 2,
+            max_elements: 2,
+            element_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+            length_field_size: None,
+        })),
+        length_field_size: None,
+    });
+
+    assert_eq!(
+        vec![1, 2, 3, 4],
+        to_x_manuel::<ExampleOptions, _, _>(&[[1u8, 2], [3, 4]], &SOMEIP_TYPE, Vec::default())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_array_with_length_field() {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+        min_elements: 2,
+        max_elements: 2,
+        element_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+            min_size: 0,
+            max_size: 10,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            padding: false,
+        })),
+        length_field_size: Some(LengthFieldSize::OneByte),
+    });
+
+    assert_eq!(
+        vec![5, 1, 0x61, 2, 0x62, 0x63],
+        to_x_manuel::<ExampleOptions, _, _>(&["a", "bc"], &SOMEIP_TYPE, Vec::default()).unwrap()
+    );
+}
+
+#[test]
+fn test_tuple_struct() {
+    #[derive(Debug, serde::Serialize)]
+    struct Test(u8, u16, String);
+
+    impl SomeIp for Test {
+        const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+            name: SomeIpRef::Static("Test"),
+            fields: SomeIpRef::Static(&[
+                SomeIpField {
+                    name: SomeIpRef::Static("0"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("1"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                    default: None,
+                },
+                SomeIpField {
+                    name: SomeIpRef::Static("2"),
+                    id: None,
+                    field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                        min_size: 0,
+                        max_size: 10,
+                        length_field_size: Some(LengthFieldSize::OneByte),
+                        padding: false,
+                    })),
+                    default: None,
+                },
+            ]),
+            uses_tlv_serialization: false,
+            is_message_wrapper: false,
+            length_field_size: Some(LengthFieldSize::OneByte),
+            transformation_properties: None,
+            extensible: false,
+        });
+    }
+
+    assert_eq!(
+        vec![6, 1, 0, 2, 2, 0x68, 0x69],
+        to_vec::<ExampleOptions, _>(&Test(1, 2, "hi".into())).unwrap()
+    );
+}
+
+#[test]
+fn test_tuple() {
+    assert_eq!(
+        vec![1, 0, 2, 0, 0, 0, 3],
+        to_vec::<ExampleOptions, _>(&(1u8, 2i16, 3u32)).unwrap()
+    );
+    assert_eq!(
+        vec![1, 2, 3],
+        to_vec::<ExampleOptions, _>(&(1u8, (2u8, 3u8))).unwrap()
+    );
+}
+
+#[cfg(test)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub(crate) struct TestAligned {
+    pub(crate) a: u8,
+    pub(crate) b: String,
+    pub(crate) c: u16,
+}
+
+#[cfg(test)]
+impl SomeIp for TestAligned {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("TestAligned"),
+        fields: SomeIpRef::Static(&[
+            SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: None,
+                field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("b"),
+                id: None,
+                field_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+                    min_size: 0,
+                    max_size: 10,
+                    length_field_size: None,
+                    padding: false,
+                })),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("c"),
+                id: None,
+                field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                default: None,
+            },
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: Some(LengthFieldSize::OneByte),
+        transformation_properties: Some(SomeIpTransforationProperties {
+            size_of_array_length_field: None,
+            size_of_struct_length_field: None,
+            size_of_string_length_field: Some(LengthFieldSize::OneByte),
+            alignment: Some(Alignment::ThirtyTwoBits),
+        }),
+        extensible: false,
+    });
+}
+
+#[cfg(test)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub(crate) struct TestAlignedTlv {
+    pub(crate) a: u8,
+    pub(crate) b: Option<u16>,
+    pub(crate) c: u32,
+}
+
+#[cfg(test)]
+impl SomeIp for TestAlignedTlv {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("TestAlignedTlv"),
+        fields: SomeIpRef::Static(&[
+            SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: Some(1),
+                field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("b"),
+                id: Some(2),
+                field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("c"),
+                id: Some(3),
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            },
+        ]),
+        uses_tlv_serialization: true,
+        is_message_wrapper: false,
+        length_field_size: Some(LengthFieldSize::FourBytes),
+        transformation_properties: Some(SomeIpTransforationProperties {
+            size_of_array_length_field: None,
+            size_of_struct_length_field: None,
+            size_of_string_length_field: None,
+            alignment: Some(Alignment::SixtyFourBits),
+        }),
+        extensible: false,
+    });
+}
+
+#[test]
+fn test_struct_aligned() {
+    let value = TestAligned {
+        a: 1,
+        b: "hi".into(),
+        c: 0x1234,
+    };
+    assert_eq!(
+        vec![12, 1, 0, 0, 0, 2, 0x68, 0x69, 0, 0x12, 0x34, 0, 0],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
+
+#[test]
+fn test_struct_aligned_tlv() {
+    let value = TestAlignedTlv {
+        a: 1,
+        b: None,
+        c: 5,
+    };
+    assert_eq!(
+        vec![0, 0, 0, 16, 0, 1, 1, 0, 0, 0, 0, 0, 0x20, 3, 0, 0, 0, 5, 0, 0],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
+
+#[cfg(test)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub(crate) struct TestExtensible {
+    pub(crate) a: u16,
+    pub(crate) b: u8,
+    #[serde(default)]
+    pub(crate) c: u32,
+}
+
+#[cfg(test)]
+impl SomeIp for TestExtensible {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("TestExtensible"),
+        fields: SomeIpRef::Static(&[
+            SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: None,
+                field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("b"),
+                id: None,
+                field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                default: Some(SomeIpDefault::Unsigned(7)),
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("c"),
+                id: None,
+                field_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+                default: None,
+            },
+        ]),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: true,
+    });
+}
+
+#[test]
+fn test_struct_extensible() {
+    TestExtensible::SOMEIP_TYPE.verify();
+    let value = TestExtensible { a: 1, b: 2, c: 3 };
+    assert_eq!(
+        vec![0, 0, 0, 7, 0, 1, 2, 0, 0, 0, 3],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
+
+#[cfg(test)]
+#[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq)]
+pub(crate) struct TestUnknownMembers {
+    pub(crate) a: u8,
+    pub(crate) unknown: UnknownMembers,
+    pub(crate) c: Option<u16>,
+}
+
+#[cfg(test)]
+impl SomeIp for TestUnknownMembers {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+        name: SomeIpRef::Static("TestUnknownMembers"),
+        fields: SomeIpRef::Static(&[
+            SomeIpField {
+                name: SomeIpRef::Static("a"),
+                id: Some(1),
+                field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("unknown"),
+                id: None,
+                field_type: SomeIpRef::Static(&UnknownMembers::SOMEIP_TYPE),
+                default: None,
+            },
+            SomeIpField {
+                name: SomeIpRef::Static("c"),
+                id: Some(3),
+                field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+                default: None,
+            },
+        ]),
+        uses_tlv_serialization: true,
+        is_message_wrapper: false,
+        length_field_size: Some(LengthFieldSize::FourBytes),
+        transformation_properties: None,
+        extensible: false,
+    });
+}
+
+#[test]
+fn test_struct_unknown_members() {
+    use super::unknown_members::UnknownMember;
+
+    TestUnknownMembers::SOMEIP_TYPE.verify();
+    let value = TestUnknownMembers {
+        a: 1,
+        unknown: UnknownMembers(vec![
+            UnknownMember {
+                tag: 0x2004,
+                data: vec![0, 0, 0, 5],
+            },
+            UnknownMember {
+                tag: 0x5005,
+                data: vec![2, 0xAA, 0xBB],
+            },
+        ]),
+        c: Some(3),
+    };
+    assert_eq!(
+        vec![0, 0, 0, 18, 0, 1, 1, 0x20, 4, 0, 0, 0, 5, 0x50, 5, 2, 0xAA, 0xBB, 0x10, 3, 0, 3],
+        to_vec::<ExampleOptions, _>(&value).unwrap()
+    );
+}
@@ -0,0 +1,440 @@
+//! Provides the segmentation and reassembly of large messages as defined by SOME/IP-TP.
+//!
+//! Messages that do not fit into a single UDP datagram are split into segments, every segment
+//! carries the someip header with the TP flag set in the [MessageType](super::header::MessageType)
+//! followed by a TP header containing the offset of the segment and a more segments flag.
+//! All segments except the last one must have a length that is a multiple of 16 bytes.
+//! ```
+//! use serde_someip::header::{MessageType, SomeIpHeader};
+//! use serde_someip::options::ExampleOptions;
+//! use serde_someip::tp::{to_segments, Reassembler};
+//! use std::time::Duration;
+//!
+//! let header = SomeIpHeader::new(0x1234, 0x8001, MessageType::Notification);
+//! let segments = to_segments::<ExampleOptions, _>(&header, &(1u64, 2u64, 3u64, 4u64, 5u64, 6u64, 7u64, 8u64), 16).unwrap();
+//! assert_eq!(4, segments.len());
+//!
+//! let mut reassembler = Reassembler::new(Duration::from_secs(1), 4096);
+//! let mut message = None;
+//! for segment in segments.iter().rev() {
+//!     message = reassembler.push(segment).unwrap();
+//! }
+//! let (decoded, payload) = message.unwrap();
+//! assert_eq!(header, decoded);
+//! let payload: (u64, u64, u64, u64, u64, u64, u64, u64) =
+//!     serde_someip::from_slice::<ExampleOptions, _>(&payload).unwrap();
+//! assert_eq!((1, 2, 3, 4, 5, 6, 7, 8), payload);
+//! ```
+
+use super::error::{Error, Result};
+use super::header::{MessageType, SomeIpHeader, HEADER_LEN};
+use super::options::SomeIpOptions;
+use super::types::SomeIp;
+
+use serde::Serialize;
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+/// The size of the TP header that follows the someip header of every segment.
+pub const TP_HEADER_LEN: usize = 4;
+
+//the length of all segments except the last must be a multiple of this
+const SEGMENT_ALIGNMENT: usize = 16;
+
+const MORE_SEGMENTS_FLAG: u32 = 0x1;
+const OFFSET_MASK: u32 = 0xFFFF_FFF0;
+
+/// Splits the payload of a message into segments of at most `max_segment_len` bytes of payload each.
+///
+/// Every returned segment is a complete someip message including the someip and TP header.
+/// `max_segment_len` is rounded down to a multiple of 16 bytes. If the payload fits into a single
+/// segment it is not segmented and a single unmodified message is returned.
+///
+/// # Panics
+/// This function panics if `max_segment_len` is less than 16.
+pub fn segment(header: &SomeIpHeader, payload: &[u8], max_segment_len: usize) -> Vec<Vec<u8>> {
+    if max_segment_len < SEGMENT_ALIGNMENT {
+        panic!(
+            "The maximum segment length must be at least {} bytes, was={}",
+            SEGMENT_ALIGNMENT, max_segment_len
+        );
+    }
+    if payload.len() <= max_segment_len {
+        let mut message = Vec::with_capacity(HEADER_LEN + payload.len());
+        message.extend_from_slice(&encode_header(header, payload.len()));
+        message.extend_from_slice(payload);
+        return vec![message];
+    }
+
+    let max_segment_len = max_segment_len - max_segment_len % SEGMENT_ALIGNMENT;
+    let mut tp_header = header.clone();
+    tp_header.message_type = header.message_type.with_tp();
+    payload
+        .chunks(max_segment_len)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let offset = index * max_segment_len;
+            let more_segments = offset + chunk.len() < payload.len();
+            let mut tp_field = offset as u32;
+            if more_segments {
+                tp_field |= MORE_SEGMENTS_FLAG;
+            }
+            let mut segment = Vec::with_capacity(HEADER_LEN + TP_HEADER_LEN + chunk.len());
+            segment.extend_from_slice(&encode_header(&tp_header, TP_HEADER_LEN + chunk.len()));
+            segment.extend_from_slice(&tp_field.to_be_bytes());
+            segment.extend_from_slice(chunk);
+            segment
+        })
+        .collect()
+}
+
+#[inline]
+fn encode_header(header: &SomeIpHeader, payload_len: usize) -> [u8; HEADER_LEN] {
+    //the offset field of the tp header only has 32 bits so segmented payloads always fit
+    header
+        .encode(payload_len)
+        .unwrap_or_else(|_| panic!("Cannot segment payload with len={}", payload_len))
+}
+
+/// Serializes the value and splits it into segments, see [segment].
+///
+/// # Panics
+/// This function panics if the implementation of the [SomeIp](super::SomeIp) trait
+/// produces invalid type information or this information is incompatible with the [Serialize](serde::Serialize) implementation.
+/// It also panics if `max_segment_len` is less than 16.
+pub fn to_segments<Options, T>(
+    header: &SomeIpHeader,
+    value: &T,
+    max_segment_len: usize,
+) -> Result<Vec<Vec<u8>>>
+where
+    Options: SomeIpOptions + ?Sized,
+    T: Serialize + SomeIp,
+{
+    let payload = super::to_vec::<Options, _>(value)?;
+    Ok(segment(header, &payload, max_segment_len))
+}
+
+/// Provides the current time to the [Reassembler].
+///
+/// This is implemented for every `Fn() -> Instant` so that tests can control the time.
+pub trait Clock {
+    /// The current time.
+    fn now(&self) -> Instant;
+}
+
+/// A [Clock] using [Instant::now].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl<F: Fn() -> Instant> Clock for F {
+    #[inline]
+    fn now(&self) -> Instant {
+        self()
+    }
+}
+
+//segments belong to the same message if they have the same message id, request id and message type
+type MessageKey = (u32, u32, MessageType);
+
+struct PendingMessage {
+    header: SomeIpHeader,
+    segments: BTreeMap<usize, Vec<u8>>,
+    total_len: Option<usize>,
+    started: Instant,
+}
+
+impl PendingMessage {
+    //returns the payload if all segments have been received
+    fn try_complete(&self) -> Option<Vec<u8>> {
+        let total_len = self.total_len?;
+        let mut received = 0;
+        for (offset, data) in &self.segments {
+            if *offset > received {
+                return None;
+            }
+            received = received.max(offset + data.len());
+        }
+        if received < total_len {
+            return None;
+        }
+        let mut payload = vec![0; total_len];
+        for (offset, data) in &self.segments {
+            payload[*offset..offset + data.len()].copy_from_slice(data);
+        }
+        Some(payload)
+    }
+}
+
+/// Reassembles segmented messages.
+///
+/// Segments can be pushed in any order and duplicated segments are ignored.
+/// If not all segments of a message are received within the timeout the message is discarded.
+pub struct Reassembler<C: Clock = SystemClock> {
+    clock: C,
+    timeout: Duration,
+    max_message_len: usize,
+    pending: HashMap<MessageKey, PendingMessage>,
+}
+
+impl Reassembler<SystemClock> {
+    /// Creates a reassembler that discards messages that are not complete after `timeout` or
+    /// that are longer than `max_message_len` bytes.
+    pub fn new(timeout: Duration, max_message_len: usize) -> Reassembler<SystemClock> {
+        Reassembler::with_clock(SystemClock, timeout, max_message_len)
+    }
+}
+
+impl<C: Clock> Reassembler<C> {
+    /// Creates a reassembler using the given [Clock], see [new](Reassembler::new).
+    pub fn with_clock(clock: C, timeout: Duration, max_message_len: usize) -> Reassembler<C> {
+        Reassembler {
+            clock,
+            timeout,
+            max_message_len,
+            pending: HashMap::default(),
+        }
+    }
+
+    /// The number of messages of which some but not all segments have been received.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Discards all messages that were not completed within the timeout.
+    ///
+    /// This happens automatically whenever a segment is pushed.
+    pub fn remove_expired(&mut self) {
+        let now = self.clock.now();
+        let timeout = self.timeout;
+        self.pending
+            .retain(|_, pending| now.saturating_duration_since(pending.started) < timeout);
+    }
+
+    /// Pushes a received message, which may be a segment.
+    ///
+    /// Returns the header and payload once a message is complete. The header contains the message type of
+    /// the complete message, without the TP flag. Messages that are not segmented are returned immediately.
+    pub fn push(&mut self, message: &[u8]) -> Result<Option<(SomeIpHeader, Vec<u8>)>> {
+        self.remove_expired();
+
+        let (header, payload_len) = SomeIpHeader::decode(message)?;
+        let payload = &message[HEADER_LEN..];
+        if payload.len() < payload_len {
+            return Err(Error::TooShort);
+        } else if payload.len() > payload_len {
+            return Err(Error::NotAllBytesConsumed(payload.len() - payload_len));
+        }
+        if !header.message_type.is_tp() {
+            return Ok(Some((header, payload.to_vec())));
+        }
+
+        if payload.len() < TP_HEADER_LEN {
+            return Err(Error::TooShort);
+        }
+        let tp_field = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+        let offset = (tp_field & OFFSET_MASK) as usize;
+        let more_segments = tp_field & MORE_SEGMENTS_FLAG != 0;
+        let data = &payload[TP_HEADER_LEN..];
+        let end = offset + data.len();
+        if more_segments && data.len() % SEGMENT_ALIGNMENT != 0 {
+            return Err(Error::InvalidSegment(format!(
+                "Only the last segment may have a length that is not a multiple of {}, len={}",
+                SEGMENT_ALIGNMENT,
+                data.len()
+            )));
+        }
+        if end > self.max_message_len {
+            return Err(Error::InvalidSegment(format!(
+                "Segment ends at {} but the maximum message length is {}",
+                end, self.max_message_len
+            )));
+        }
+
+        let key = (
+            header.message_id(),
+            header.request_id(),
+            header.message_type,
+        );
+        let now = self.clock.now();
+        let pending = self.pending.entry(key).or_insert_with(|| PendingMessage {
+            header,
+            segments: BTreeMap::default(),
+            total_len: None,
+            started: now,
+        });
+        if !more_segments {
+            if pending.total_len.is_some_and(|len| len != end) {
+                return Err(Error::InvalidSegment(format!(
+                    "Received a second last segment ending at {}",
+                    end
+                )));
+            }
+            pending.total_len = Some(end);
+        }
+        if pending.total_len.is_some_and(|len| end > len) {
+            return Err(Error::InvalidSegment(format!(
+                "Segment ends at {} after the end of the message",
+                end
+            )));
+        }
+        //duplicates keep the first segment that was received
+        pending
+            .segments
+            .entry(offset)
+            .or_insert_with(|| data.to_vec());
+
+        if let Some(payload) = pending.try_complete() {
+            let mut pending = self.pending.remove(&key).unwrap();
+            pending.header.message_type = pending.header.message_type.without_tp();
+            Ok(Some((pending.header, payload)))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[cfg(test)]
+use super::header::ReturnCode;
+
+#[test]
+fn test_segment() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let payload: Vec<u8> = (0..40).collect();
+    let segments = segment(&header, &payload, 20);
+    assert_eq!(3, segments.len());
+
+    let mut expected = vec![0x12, 0x34, 0, 1, 0, 0, 0, 28, 0, 0, 0, 0, 1, 0, 0x20, 0];
+    expected.extend_from_slice(&[0, 0, 0, 1]);
+    expected.extend(0..16);
+    assert_eq!(expected, segments[0]);
+
+    let mut expected = vec![0x12, 0x34, 0, 1, 0, 0, 0, 28, 0, 0, 0, 0, 1, 0, 0x20, 0];
+    expected.extend_from_slice(&[0, 0, 0, 0x11]);
+    expected.extend(16..32);
+    assert_eq!(expected, segments[1]);
+
+    let mut expected = vec![0x12, 0x34, 0, 1, 0, 0, 0, 20, 0, 0, 0, 0, 1, 0, 0x20, 0];
+    expected.extend_from_slice(&[0, 0, 0, 0x20]);
+    expected.extend(32..40);
+    assert_eq!(expected, segments[2]);
+}
+
+#[test]
+fn test_segment_small_payload() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    let segments = segment(&header, &[1, 2, 3], 16);
+    assert_eq!(
+        vec![vec![
+            0x12, 0x34, 0, 1, 0, 0, 0, 11, 0, 0, 0, 0, 1, 0, 0, 0, 1, 2, 3
+        ]],
+        segments
+    );
+}
+
+#[test]
+fn test_reassemble_out_of_order_and_duplicates() {
+    let mut header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Response);
+    header.return_code = ReturnCode::Ok;
+    let payload: Vec<u8> = (0..100).collect();
+    let segments = segment(&header, &payload, 32);
+    assert_eq!(4, segments.len());
+
+    let mut reassembler = Reassembler::new(Duration::from_secs(1), 1024);
+    assert!(reassembler.push(&segments[3]).unwrap().is_none());
+    assert!(reassembler.push(&segments[1]).unwrap().is_none());
+    assert!(reassembler.push(&segments[1]).unwrap().is_none());
+    assert!(reassembler.push(&segments[0]).unwrap().is_none());
+    assert_eq!(1, reassembler.pending());
+    assert_eq!(
+        Some((header, payload)),
+        reassembler.push(&segments[2]).unwrap()
+    );
+    assert_eq!(0, reassembler.pending());
+}
+
+#[test]
+fn test_reassemble_interleaved_sessions() {
+    let mut first = SomeIpHeader::new(0x1234, 0x0001, MessageType::Request);
+    first.session_id = 1;
+    let mut second = first.clone();
+    second.session_id = 2;
+    let segments1 = segment(&first, &[1; 40], 16);
+    let segments2 = segment(&second, &[2; 20], 16);
+
+    let mut reassembler = Reassembler::new(Duration::from_secs(1), 1024);
+    assert!(reassembler.push(&segments1[0]).unwrap().is_none());
+    assert!(reassembler.push(&segments2[1]).unwrap().is_none());
+    assert!(reassembler.push(&segments1[1]).unwrap().is_none());
+    assert_eq!(
+        Some((second, vec![2; 20])),
+        reassembler.push(&segments2[0]).unwrap()
+    );
+    assert_eq!(
+        Some((first, vec![1; 40])),
+        reassembler.push(&segments1[2]).unwrap()
+    );
+}
+
+#[test]
+fn test_reassemble_timeout() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let start = Instant::now();
+    let now = Rc::new(Cell::new(start));
+    let clock = {
+        let now = now.clone();
+        move || now.get()
+    };
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Notification);
+    let segments = segment(&header, &[1; 40], 16);
+
+    let mut reassembler = Reassembler::with_clock(clock, Duration::from_millis(100), 1024);
+    assert!(reassembler.push(&segments[0]).unwrap().is_none());
+    assert!(reassembler.push(&segments[1]).unwrap().is_none());
+    now.set(start + Duration::from_millis(100));
+    //the first two segments expired
+    assert!(reassembler.push(&segments[2]).unwrap().is_none());
+    assert_eq!(1, reassembler.pending());
+    assert!(reassembler.push(&segments[0]).unwrap().is_none());
+    now.set(start + Duration::from_millis(199));
+    assert_eq!(
+        Some((header, vec![1; 40])),
+        reassembler.push(&segments[1]).unwrap()
+    );
+}
+
+#[test]
+fn test_reassemble_invalid_segments() {
+    let header = SomeIpHeader::new(0x1234, 0x0001, MessageType::Notification);
+    let mut reassembler = Reassembler::new(Duration::from_secs(1), 32);
+
+    let segments = segment(&header, &[1; 40], 16);
+    assert!(matches!(
+        reassembler.push(&segments[2]),
+        Err(Error::InvalidSegment(_))
+    ));
+
+    let mut unaligned = segment(&header, &[1; 40], 16).remove(0);
+    unaligned.pop();
+    unaligned[7] -= 1;
+    assert!(matches!(
+        reassembler.push(&unaligned),
+        Err(Error::InvalidSegment(_))
+    ));
+
+    assert!(matches!(
+        reassembler.push(&segments[0][..30]),
+        Err(Error::TooShort)
+    ));
+}
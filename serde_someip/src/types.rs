@@ -5,7 +5,9 @@ use super::error::Result;
 use super::length_fields::LengthFieldSize;
 use super::options::{apply_defaults, select_length_field_size, SomeIpOptions};
 use super::wire_type::WireType;
-use std::fmt::{Display, Formatter};
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+use std::sync::Arc;
 
 pub(crate) trait SomeIpSize {
     fn wanted_length_field<Options: SomeIpOptions + ?Sized>(
@@ -21,8 +23,109 @@ pub(crate) trait SomeIpSize {
     ) -> Result<usize>;
 }
 
+/// A reference to a part of a type description, either `'static` or shared through an [Arc].
+///
+/// Type descriptions created by `#[derive(SomeIp)]` only use [Static](SomeIpRef::Static),
+/// [Shared](SomeIpRef::Shared) allows type descriptions that are only known at runtime,
+/// for example loaded from a configuration file, without leaking memory.
+/// ```
+/// # use serde_someip::types::*;
+/// let sequence = SomeIpType::Sequence(SomeIpSequence {
+///     max_elements: 4,
+///     min_elements: 0,
+///     element_type: SomeIpRef::new(SomeIpType::Primitive(SomeIpPrimitive::U16)),
+///     length_field_size: None,
+/// });
+/// sequence.verify();
+/// ```
+pub enum SomeIpRef<T: ?Sized + 'static> {
+    /// A `'static` reference, usually to a constant.
+    Static(&'static T),
+    /// A reference counted value.
+    Shared(Arc<T>),
+}
+
+impl<T> SomeIpRef<T> {
+    /// Moves the value into a new [Arc].
+    #[inline]
+    pub fn new(value: T) -> SomeIpRef<T> {
+        SomeIpRef::Shared(Arc::new(value))
+    }
+}
+
+impl<T: ?Sized> Deref for SomeIpRef<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        match self {
+            SomeIpRef::Static(value) => value,
+            SomeIpRef::Shared(value) => value,
+        }
+    }
+}
+
+impl<T: ?Sized> Clone for SomeIpRef<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        match self {
+            SomeIpRef::Static(value) => SomeIpRef::Static(value),
+            SomeIpRef::Shared(value) => SomeIpRef::Shared(value.clone()),
+        }
+    }
+}
+
+impl<T: Debug + ?Sized> Debug for SomeIpRef<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: Display + ?Sized> Display for SomeIpRef<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: PartialEq + ?Sized> PartialEq for SomeIpRef<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<T: Eq + ?Sized> Eq for SomeIpRef<T> {}
+
+impl<T: ?Sized> From<&'static T> for SomeIpRef<T> {
+    #[inline]
+    fn from(value: &'static T) -> Self {
+        SomeIpRef::Static(value)
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for SomeIpRef<T> {
+    #[inline]
+    fn from(value: Arc<T>) -> Self {
+        SomeIpRef::Shared(value)
+    }
+}
+
+impl From<String> for SomeIpRef<str> {
+    #[inline]
+    fn from(value: String) -> Self {
+        SomeIpRef::Shared(value.into())
+    }
+}
+
+impl<T> From<Vec<T>> for SomeIpRef<[T]> {
+    #[inline]
+    fn from(value: Vec<T>) -> Self {
+        SomeIpRef::Shared(value.into())
+    }
+}
+
 /// All primitives defined by SomeIp.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SomeIpPrimitive {
     /// A bool.
     Bool,
@@ -94,7 +197,7 @@ impl Display for SomeIpPrimitive {
 
 /// An enum that can hold the serialized value
 /// for any possible enum supported by someip.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SomeIpEnumValue {
     /// For enums with raw_type = u8.
     U8(u8),
@@ -115,7 +218,7 @@ pub enum SomeIpEnumValue {
 }
 
 impl SomeIpEnumValue {
-    fn matches(&self, other: &SomeIpPrimitive) -> bool {
+    pub(crate) fn matches(&self, other: &SomeIpPrimitive) -> bool {
         match self {
             SomeIpEnumValue::U8(_) => {
                 matches!(other, SomeIpPrimitive::U8)
@@ -172,15 +275,21 @@ impl SomeIpEnumValue {
 }
 
 /// All the data needed to de/serialize a enum.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SomeIpEnum {
     /// The name of the enum. Usefull for debuging messages.
-    pub name: &'static str,
+    pub name: SomeIpRef<str>,
     /// The values that the enum has as a tuple of `(VariantName, Value)`.
     /// All SomeIpEnumValues must match [raw_type](SomeIpEnum::raw_type).
-    pub values: &'static [(&'static str, SomeIpEnumValue)],
+    pub values: SomeIpRef<[(SomeIpRef<str>, SomeIpEnumValue)]>,
     /// The primitive type used for de/serialization.
     pub raw_type: SomeIpPrimitive,
+    /// The name of the variant that all unknown values are mapped onto during deserialization.
+    ///
+    /// If this is a unit variant it must be part of [values](SomeIpEnum::values) and is serialized with that value.
+    /// Otherwise it must be a newtype variant carrying the raw value, which is serialized as is.
+    /// If `None` unknown values cause [Error::InvalidEnumValue](crate::Error::InvalidEnumValue).
+    pub other: Option<SomeIpRef<str>>,
 }
 
 impl SomeIpEnum {
@@ -192,23 +301,53 @@ impl SomeIpEnum {
 
     /// Maps the given name onto the value used for serialization.
     #[inline]
-    pub fn name_to_value(&self, name: &'static str) -> Option<&'static SomeIpEnumValue> {
+    pub fn name_to_value(&self, name: &str) -> Option<&SomeIpEnumValue> {
         self.values
             .iter()
-            .find_map(|(n, v)| if name == *n { Some(v) } else { None })
+            .find_map(|(n, v)| if name == &**n { Some(v) } else { None })
     }
 
     /// Maps the given vale onto the variant name, essentially the inverse of [name_to_value](SomeIpEnum::name_to_value).
     #[inline]
-    pub fn value_to_name(&self, value: &SomeIpEnumValue) -> Option<&'static str> {
+    pub fn value_to_name(&self, value: &SomeIpEnumValue) -> Option<&str> {
         self.values
             .iter()
-            .find_map(|(n, v)| if value == v { Some(*n) } else { None })
+            .find_map(|(n, v)| if value == v { Some(&**n) } else { None })
+    }
+}
+
+/// All the data needed to de/serialize a bitfield.
+///
+/// A bitfield is serialized as a single unsigned primitive where every flag is represented by one bit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SomeIpBitfield {
+    /// The name of the bitfield. Usefull for debuging messages.
+    pub name: SomeIpRef<str>,
+    /// The flags of the bitfield as a tuple of `(FlagName, Bit)`.
+    /// Bit `0` is the least significant bit of [raw_type](SomeIpBitfield::raw_type).
+    pub flags: SomeIpRef<[(SomeIpRef<str>, u8)]>,
+    /// The primitive type used for de/serialization, must be one of u8, u16, u32 or u64.
+    pub raw_type: SomeIpPrimitive,
+}
+
+impl SomeIpBitfield {
+    /// Retrieves the bit of the flag with the given name.
+    #[inline]
+    pub fn flag_to_bit(&self, name: &str) -> Option<u8> {
+        self.flags
+            .iter()
+            .find_map(|(n, b)| if name == &**n { Some(*b) } else { None })
+    }
+
+    /// A mask with all bits set that are assigned to a flag.
+    #[inline]
+    pub fn known_bits(&self) -> u64 {
+        self.flags.iter().fold(0, |mask, (_, b)| mask | 1 << b)
     }
 }
 
 /// All the data needed to de/serialize a string, except for encoding that comes from [SomeIpOptions](super::options::SomeIpOptions).
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SomeIpString {
     /// The maximum size of the string in bytes *after* encoding.
     pub max_size: usize,
@@ -216,6 +355,11 @@ pub struct SomeIpString {
     pub min_size: usize,
     /// The length field size to use for this string.
     pub length_field_size: Option<LengthFieldSize>,
+    /// If the string is padded with zeros upto `max_size`.
+    ///
+    /// Padded strings always have a fixed size, so `min_size` must equal `max_size`.
+    /// The deserializer strips any trailing zeros.
+    pub padding: bool,
 }
 
 impl SomeIpSize for SomeIpString {
@@ -257,31 +401,135 @@ impl SomeIpSize for SomeIpString {
 }
 
 /// All the data needed to de/serialize a sequence.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SomeIpSequence {
     /// The maximum number of elements this sequence may have.
     pub max_elements: usize,
     /// The minimum number of elements this sequence must have.
     pub min_elements: usize,
     /// The type of the elements inside this sequence.
-    pub element_type: &'static SomeIpType,
+    pub element_type: SomeIpRef<SomeIpType>,
     /// The length field size to use for this sequence.
     pub length_field_size: Option<LengthFieldSize>,
 }
 
+/// All the data needed to de/serialize a map.
+///
+/// A map is serialized like a sequence of structs with the two fields key and value,
+/// which is how associative arrays are represented by autosar.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SomeIpMap {
+    /// The maximum number of entries this map may have.
+    pub max_elements: usize,
+    /// The minimum number of entries this map must have.
+    pub min_elements: usize,
+    /// The type of the keys inside this map.
+    pub key_type: SomeIpRef<SomeIpType>,
+    /// The type of the values inside this map.
+    pub value_type: SomeIpRef<SomeIpType>,
+    /// The length field size to use for this map.
+    pub length_field_size: Option<LengthFieldSize>,
+}
+
 /// All the data needed to de/serialize one field of a [SomeIpStruct].
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SomeIpField {
     /// The name of the field.
-    pub name: &'static str,
+    pub name: SomeIpRef<str>,
     /// The tlv id of this field. Must be `Some(...)` for TLV structs and `None` for non TLV structs.
     pub id: Option<u16>,
     /// The type of this field.
-    pub field_type: &'static SomeIpType,
+    pub field_type: SomeIpRef<SomeIpType>,
+    /// The value to use if this field is missing in an [extensible](SomeIpStruct::extensible) struct.
+    ///
+    /// If `None` the field is reported as missing to serde, which can then fill it using `#[serde(default)]`.
+    pub default: Option<SomeIpDefault>,
+}
+
+/// The default value of a field, only primitives are supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SomeIpDefault {
+    /// A default for a bool.
+    Bool(bool),
+    /// A default for any integer type.
+    Unsigned(u64),
+    /// A default for any signed integer type, use [SomeIpDefault::Unsigned] for positive values.
+    Signed(i64),
+}
+
+impl SomeIpDefault {
+    fn matches(&self, other: &SomeIpPrimitive) -> bool {
+        let value = match self {
+            SomeIpDefault::Bool(_) => return *other == SomeIpPrimitive::Bool,
+            SomeIpDefault::Unsigned(v) => *v as i128,
+            SomeIpDefault::Signed(v) => *v as i128,
+        };
+        let (min, max) = match other {
+            SomeIpPrimitive::U8 => (0, u8::MAX as i128),
+            SomeIpPrimitive::U16 => (0, u16::MAX as i128),
+            SomeIpPrimitive::U32 => (0, u32::MAX as i128),
+            SomeIpPrimitive::U64 => (0, u64::MAX as i128),
+            SomeIpPrimitive::I8 => (i8::MIN as i128, i8::MAX as i128),
+            SomeIpPrimitive::I16 => (i16::MIN as i128, i16::MAX as i128),
+            SomeIpPrimitive::I32 => (i32::MIN as i128, i32::MAX as i128),
+            SomeIpPrimitive::I64 => (i64::MIN as i128, i64::MAX as i128),
+            SomeIpPrimitive::Bool | SomeIpPrimitive::F32 | SomeIpPrimitive::F64 => return false,
+        };
+        min <= value && value <= max
+    }
+}
+
+impl Display for SomeIpDefault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SomeIpDefault::Bool(v) => write!(f, "{}", v),
+            SomeIpDefault::Unsigned(v) => write!(f, "{}", v),
+            SomeIpDefault::Signed(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// The alignment of struct members as defined by the someip transformation properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Alignment {
+    /// Members are aligned to 8 bit, which means no padding is ever inserted.
+    EightBits,
+    /// Members are aligned to 16 bit.
+    SixteenBits,
+    /// Members are aligned to 32 bit.
+    ThirtyTwoBits,
+    /// Members are aligned to 64 bit.
+    SixtyFourBits,
+}
+
+impl Alignment {
+    /// The alignment in bytes.
+    #[inline]
+    pub fn bytes(&self) -> usize {
+        match self {
+            Alignment::EightBits => 1,
+            Alignment::SixteenBits => 2,
+            Alignment::ThirtyTwoBits => 4,
+            Alignment::SixtyFourBits => 8,
+        }
+    }
+
+    /// The number of padding bytes needed after `offset` bytes to reach the next aligned offset.
+    #[inline]
+    pub(crate) fn padding_for(&self, offset: usize) -> usize {
+        (self.bytes() - offset % self.bytes()) % self.bytes()
+    }
+}
+
+impl Display for Alignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.bytes() * 8)
+    }
 }
 
 /// The SomeIp transformation properties as defined by arxml.
-/// This can be used to define the length fields for all types used by a struct.
+/// This can be used to define the length fields and the alignment for all types used by a struct.
+/// Unions are not affected, they always use [SomeIpUnion::length_field_size].
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SomeIpTransforationProperties {
     /// The size of length fields for arrays.
@@ -290,9 +538,15 @@ pub struct SomeIpTransforationProperties {
     pub size_of_struct_length_field: Option<LengthFieldSize>,
     /// The size of length fields for strings.
     pub size_of_string_length_field: Option<LengthFieldSize>,
+    /// The alignment of struct members.
+    ///
+    /// If present every member of a struct is followed by zeros until the next member starts at
+    /// an aligned offset, the offsets are relative to the beginning of the struct after its length field.
+    /// This also pads dynamic length data like strings or sequences.
+    pub alignment: Option<Alignment>,
 }
 
-trait SomeIpTransforationPropertiesExt {
+pub(crate) trait SomeIpTransforationPropertiesExt {
     fn get_array_length_field(&self, from_type: Option<LengthFieldSize>)
         -> Option<LengthFieldSize>;
     fn get_struct_length_field(
@@ -303,6 +557,7 @@ trait SomeIpTransforationPropertiesExt {
         &self,
         from_type: Option<LengthFieldSize>,
     ) -> Option<LengthFieldSize>;
+    fn get_alignment(&self) -> Option<Alignment>;
 }
 
 impl SomeIpTransforationPropertiesExt for SomeIpTransforationProperties {
@@ -327,6 +582,10 @@ impl SomeIpTransforationPropertiesExt for SomeIpTransforationProperties {
     ) -> Option<LengthFieldSize> {
         self.size_of_string_length_field.or(from_type)
     }
+    #[inline]
+    fn get_alignment(&self) -> Option<Alignment> {
+        self.alignment
+    }
 }
 
 impl SomeIpTransforationPropertiesExt for Option<SomeIpTransforationProperties> {
@@ -360,15 +619,19 @@ impl SomeIpTransforationPropertiesExt for Option<SomeIpTransforationProperties>
             None => from_type,
         }
     }
+    #[inline]
+    fn get_alignment(&self) -> Option<Alignment> {
+        self.as_ref().and_then(|p| p.get_alignment())
+    }
 }
 
 /// All the data needed to de/serialize a struct.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SomeIpStruct {
     /// The name of the struct. Usefull for debugging messages.
-    pub name: &'static str,
+    pub name: SomeIpRef<str>,
     /// All fields of this struct.
-    pub fields: &'static [SomeIpField],
+    pub fields: SomeIpRef<[SomeIpField]>,
     /// Is this struct a TLV struct?
     pub uses_tlv_serialization: bool,
     /// Is this struct a message wrapper?
@@ -379,13 +642,20 @@ pub struct SomeIpStruct {
     pub length_field_size: Option<LengthFieldSize>,
     /// The transformation properties to use for all types in this struct.
     pub transformation_properties: Option<SomeIpTransforationProperties>,
+    /// Is this struct extensible?
+    ///
+    /// Extensible structs always have a length field, members missing at the end of the struct
+    /// are filled from their [default](SomeIpField::default) and unknown bytes at the end of the struct are skipped.
+    /// This allows newer versions of a struct to append members without breaking older receivers.
+    /// Only non TLV structs can be extensible since TLV structs already support this.
+    pub extensible: bool,
 }
 
 impl SomeIpStruct {
     /// Retrieves the field with the given name.
     #[inline]
-    pub fn field_by_name(&self, name: &'static str) -> Option<&SomeIpField> {
-        self.fields.iter().find(|f| f.name == name)
+    pub fn field_by_name(&self, name: &str) -> Option<&SomeIpField> {
+        self.fields.iter().find(|f| &*f.name == name)
     }
 
     /// Retrieves the field with the given id.
@@ -405,21 +675,98 @@ impl SomeIpStruct {
     pub fn uses_tlv(&self) -> bool {
         self.uses_tlv_serialization
     }
+
+    /// Retrieves the field that collects unknown members, see [UnknownMembers](super::UnknownMembers).
+    #[inline]
+    pub fn unknown_members_field(&self) -> Option<&SomeIpField> {
+        self.fields
+            .iter()
+            .find(|f| matches!(*f.field_type, SomeIpType::UnknownMembers))
+    }
+}
+
+/// All the data needed to de/serialize one variant of a [SomeIpUnion].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SomeIpUnionVariant {
+    /// The name of the variant.
+    pub name: SomeIpRef<str>,
+    /// The value of the type field that selects this variant.
+    ///
+    /// The selector `0` is reserved for the empty union and can only be used by a variant without data.
+    pub selector: u32,
+    /// The type of the data carried by this variant, `None` for variants without data.
+    ///
+    /// Tuple and struct variants are represented by a [SomeIpStruct], usually without a length field
+    /// since the union already has one.
+    pub variant_type: Option<SomeIpRef<SomeIpType>>,
+}
+
+/// All the data needed to de/serialize a union, also called variant by someip.
+///
+/// A union is serialized as a length field, followed by a type field holding the
+/// [selector](SomeIpUnionVariant::selector) of the variant, followed by the data of the variant.
+/// The length field only covers the data and not the type field, unless the union is a member
+/// of a tlv struct. There the length also covers the type field so that receivers not knowing
+/// the member can skip it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SomeIpUnion {
+    /// The name of the union. Usefull for debugging messages.
+    pub name: SomeIpRef<str>,
+    /// All variants of this union.
+    pub variants: SomeIpRef<[SomeIpUnionVariant]>,
+    /// The length field size to use for this union.
+    /// If `None` [SomeIpOptions::DEFAULT_LENGTH_FIELD_SIZE](super::options::SomeIpOptions::DEFAULT_LENGTH_FIELD_SIZE)
+    /// is used and if that is `None` as well four bytes as mandated by the someip standard.
+    ///
+    /// Unlike the other length fields this is never taken from [SomeIpTransforationProperties],
+    /// so union length field sizes configured for a whole interface must be set here.
+    pub length_field_size: Option<LengthFieldSize>,
+    /// The size of the type field of this union.
+    /// If `None` [SomeIpOptions::DEFAULT_UNION_TYPE_FIELD_SIZE](super::options::SomeIpOptions::DEFAULT_UNION_TYPE_FIELD_SIZE) is used.
+    pub type_field_size: Option<LengthFieldSize>,
+}
+
+impl SomeIpUnion {
+    /// Retrieves the variant with the given name.
+    #[inline]
+    pub fn variant_by_name(&self, name: &str) -> Option<&SomeIpUnionVariant> {
+        self.variants.iter().find(|v| &*v.name == name)
+    }
+
+    /// Retrieves the variant with the given selector.
+    #[inline]
+    pub fn variant_by_selector(&self, selector: u32) -> Option<&SomeIpUnionVariant> {
+        self.variants.iter().find(|v| v.selector == selector)
+    }
+
+    #[inline]
+    pub(crate) fn get_type_field_size<Options: SomeIpOptions + ?Sized>(&self) -> LengthFieldSize {
+        self.type_field_size
+            .unwrap_or(Options::DEFAULT_UNION_TYPE_FIELD_SIZE)
+    }
 }
 
 /// All the data needed to de/serialize any tpye supported by someip.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SomeIpType {
     /// Indicates a primitve.
     Primitive(SomeIpPrimitive),
     /// Indicates an enum.
     Enum(SomeIpEnum),
+    /// Indicates a bitfield.
+    Bitfield(SomeIpBitfield),
     /// Indicates a string.
     String(SomeIpString),
     /// Indicates a sequence.
     Sequence(SomeIpSequence),
+    /// Indicates a map.
+    Map(SomeIpMap),
     /// Indicates a struct.
     Struct(SomeIpStruct),
+    /// Indicates a union.
+    Union(SomeIpUnion),
+    /// Indicates the members of a TLV struct with unknown ids, see [UnknownMembers](super::UnknownMembers).
+    UnknownMembers,
 }
 
 impl SomeIpType {
@@ -428,6 +775,7 @@ impl SomeIpType {
         match self {
             SomeIpType::Primitive(prim) => prim.get_wire_type(),
             SomeIpType::Enum(e) => e.raw_type.get_wire_type(),
+            SomeIpType::Bitfield(b) => b.raw_type.get_wire_type(),
             _ => WireType::LengthDelimitedFromConfig,
         }
     }
@@ -438,9 +786,13 @@ impl Display for SomeIpType {
         match self {
             SomeIpType::Primitive(p) => formatter.write_fmt(format_args!("Primitve({})", p)),
             SomeIpType::Enum(e) => formatter.write_fmt(format_args!("Enum({})", e.name)),
+            SomeIpType::Bitfield(b) => formatter.write_fmt(format_args!("Bitfield({})", b.name)),
             SomeIpType::String(_) => formatter.write_str("String"),
             SomeIpType::Sequence(_) => formatter.write_str("Sequence"),
+            SomeIpType::Map(_) => formatter.write_str("Map"),
             SomeIpType::Struct(s) => formatter.write_fmt(format_args!("Struct({})", s.name)),
+            SomeIpType::Union(u) => formatter.write_fmt(format_args!("Union({})", u.name)),
+            SomeIpType::UnknownMembers => formatter.write_str("UnknownMembers"),
         }
     }
 }
@@ -484,6 +836,49 @@ impl SomeIpSize for SomeIpSequence {
     }
 }
 
+impl SomeIpSize for SomeIpMap {
+    fn wanted_length_field<Options: SomeIpOptions + ?Sized>(
+        &self,
+        is_in_tlv_struct: bool,
+        props: &Option<SomeIpTransforationProperties>,
+    ) -> Result<Option<LengthFieldSize>> {
+        if !self.is_const_size() || is_in_tlv_struct {
+            let size =
+                apply_defaults::<Options>(props.get_array_length_field(self.length_field_size));
+            if size.is_none() {
+                panic!("Required a length field size but none was specified");
+            }
+            Ok(size)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn is_const_size(&self) -> bool {
+        self.min_elements == self.max_elements
+            && self.key_type.is_const_size()
+            && self.value_type.is_const_size()
+    }
+
+    fn max_len<Options: SomeIpOptions + ?Sized>(
+        &self,
+        is_in_tlv_struct: bool,
+        props: &Option<SomeIpTransforationProperties>,
+    ) -> Result<usize> {
+        let size = self.wanted_length_field::<Options>(is_in_tlv_struct, props)?;
+        let len = self.max_elements
+            * (self.key_type.max_len::<Options>(false, props)?
+                + self.value_type.max_len::<Options>(false, props)?);
+        if let Some(size) = size {
+            let size = select_length_field_size::<Options>(size, len, is_in_tlv_struct)?;
+            Ok(len + usize::from(size))
+        } else {
+            Ok(len)
+        }
+    }
+}
+
 impl SomeIpSize for SomeIpStruct {
     fn wanted_length_field<Options: SomeIpOptions + ?Sized>(
         &self,
@@ -494,7 +889,7 @@ impl SomeIpSize for SomeIpStruct {
             return Ok(None);
         }
 
-        let needs_length_field = is_in_tlv_struct || self.uses_tlv();
+        let needs_length_field = is_in_tlv_struct || self.uses_tlv() || self.extensible;
         let mut size = None;
         if needs_length_field || self.length_field_size.is_some() {
             size = apply_defaults::<Options>(props.get_struct_length_field(self.length_field_size));
@@ -510,7 +905,9 @@ impl SomeIpSize for SomeIpStruct {
 
     #[inline]
     fn is_const_size(&self) -> bool {
-        !self.uses_tlv() && self.fields.iter().all(|f| f.field_type.is_const_size())
+        !self.uses_tlv()
+            && !self.extensible
+            && self.fields.iter().all(|f| f.field_type.is_const_size())
     }
 
     fn max_len<Options: SomeIpOptions + ?Sized>(
@@ -518,12 +915,21 @@ impl SomeIpSize for SomeIpStruct {
         is_in_tlv_struct: bool,
         props: &Option<SomeIpTransforationProperties>,
     ) -> Result<usize> {
+        let props = if self.transformation_properties.is_some() {
+            &self.transformation_properties
+        } else {
+            props
+        };
         let size = self.wanted_length_field::<Options>(is_in_tlv_struct, props)?;
+        let alignment = props.get_alignment();
         let mut len = 0;
-        for f in self.fields {
+        for f in self.fields.iter() {
             len += f
                 .field_type
                 .max_len::<Options>(self.uses_tlv_serialization, props)?;
+            if let Some(alignment) = alignment {
+                len += alignment.padding_for(len);
+            }
         }
         if let Some(size) = size {
             let size = select_length_field_size::<Options>(size, len, is_in_tlv_struct)?;
@@ -534,6 +940,42 @@ impl SomeIpSize for SomeIpStruct {
     }
 }
 
+impl SomeIpSize for SomeIpUnion {
+    fn wanted_length_field<Options: SomeIpOptions + ?Sized>(
+        &self,
+        _is_in_tlv_struct: bool,
+        _props: &Option<SomeIpTransforationProperties>,
+    ) -> Result<Option<LengthFieldSize>> {
+        //unions always have a length field and the transformation properties do not apply to them
+        Ok(Some(
+            apply_defaults::<Options>(self.length_field_size).unwrap_or(LengthFieldSize::FourBytes),
+        ))
+    }
+
+    #[inline]
+    fn is_const_size(&self) -> bool {
+        false
+    }
+
+    fn max_len<Options: SomeIpOptions + ?Sized>(
+        &self,
+        is_in_tlv_struct: bool,
+        props: &Option<SomeIpTransforationProperties>,
+    ) -> Result<usize> {
+        let size = self
+            .wanted_length_field::<Options>(is_in_tlv_struct, props)?
+            .unwrap();
+        let mut len = 0;
+        for v in self.variants.iter() {
+            if let Some(t) = &v.variant_type {
+                len = std::cmp::max(len, t.max_len::<Options>(false, props)?);
+            }
+        }
+        let size = select_length_field_size::<Options>(size, len, is_in_tlv_struct)?;
+        Ok(len + usize::from(size) + usize::from(self.get_type_field_size::<Options>()))
+    }
+}
+
 impl SomeIpSize for SomeIpType {
     #[inline]
     fn wanted_length_field<Options: SomeIpOptions + ?Sized>(
@@ -542,20 +984,26 @@ impl SomeIpSize for SomeIpType {
         props: &Option<SomeIpTransforationProperties>,
     ) -> Result<Option<LengthFieldSize>> {
         match self {
-            SomeIpType::Primitive(_) | SomeIpType::Enum(_) => Ok(None),
+            SomeIpType::Primitive(_) | SomeIpType::Enum(_) | SomeIpType::Bitfield(_) => Ok(None),
             SomeIpType::String(s) => s.wanted_length_field::<Options>(is_in_tlv_struct, props),
             SomeIpType::Sequence(s) => s.wanted_length_field::<Options>(is_in_tlv_struct, props),
+            SomeIpType::Map(m) => m.wanted_length_field::<Options>(is_in_tlv_struct, props),
             SomeIpType::Struct(s) => s.wanted_length_field::<Options>(is_in_tlv_struct, props),
+            SomeIpType::Union(u) => u.wanted_length_field::<Options>(is_in_tlv_struct, props),
+            SomeIpType::UnknownMembers => Ok(None),
         }
     }
 
     #[inline]
     fn is_const_size(&self) -> bool {
         match self {
-            SomeIpType::Primitive(_) | SomeIpType::Enum(_) => true,
+            SomeIpType::Primitive(_) | SomeIpType::Enum(_) | SomeIpType::Bitfield(_) => true,
             SomeIpType::String(s) => s.is_const_size(),
             SomeIpType::Sequence(s) => s.is_const_size(),
+            SomeIpType::Map(m) => m.is_const_size(),
             SomeIpType::Struct(s) => s.is_const_size(),
+            SomeIpType::Union(u) => u.is_const_size(),
+            SomeIpType::UnknownMembers => false,
         }
     }
 
@@ -568,9 +1016,14 @@ impl SomeIpSize for SomeIpType {
         match self {
             SomeIpType::Primitive(prim) => Ok(prim.get_len()),
             SomeIpType::Enum(e) => Ok(e.raw_type.get_len()),
+            SomeIpType::Bitfield(b) => Ok(b.raw_type.get_len()),
             SomeIpType::String(s) => s.max_len::<Options>(is_in_tlv_struct, props),
             SomeIpType::Sequence(s) => s.max_len::<Options>(is_in_tlv_struct, props),
+            SomeIpType::Map(m) => m.max_len::<Options>(is_in_tlv_struct, props),
             SomeIpType::Struct(s) => s.max_len::<Options>(is_in_tlv_struct, props),
+            SomeIpType::Union(u) => u.max_len::<Options>(is_in_tlv_struct, props),
+            //the unknown members are not known in advance, so they cannot be accounted for
+            SomeIpType::UnknownMembers => Ok(0),
         }
     }
 }
@@ -586,108 +1039,299 @@ pub trait VerifySomeIpType {
     /// # Examples
     /// ```
     /// # use serde_someip::types::*;
-    /// let e = SomeIpString{min_size: 0, max_size: 42, length_field_size: None};
+    /// let e = SomeIpString{min_size: 0, max_size: 42, length_field_size: None, padding: false};
     /// e.verify();
     /// ```
     ///
     /// ```should_panic
     /// # use serde_someip::types::*;
-    /// let e = SomeIpString{min_size: 43, max_size: 42, length_field_size: None};
+    /// let e = SomeIpString{min_size: 43, max_size: 42, length_field_size: None, padding: false};
     /// e.verify(); //panic here
     /// ```
     fn verify(&self);
 }
 
-impl VerifySomeIpType for SomeIpEnum {
-    fn verify(&self) {
+macro_rules! verify_by_validate {
+    ($($t:ty),*) => {
+        $(
+            impl VerifySomeIpType for $t {
+                fn verify(&self) {
+                    if let Err(e) = self.validate() {
+                        panic!("{}", e);
+                    }
+                }
+            }
+        )*
+    };
+}
+
+verify_by_validate!(
+    SomeIpEnum,
+    SomeIpBitfield,
+    SomeIpField,
+    SomeIpString,
+    SomeIpSequence,
+    SomeIpMap,
+    SomeIpStruct,
+    SomeIpUnion,
+    SomeIpType
+);
+
+//the checks behind verify, returning the reason instead of panicking so that
+//types from untrusted sources can be rejected with an error
+impl SomeIpEnum {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
         if matches!(
             self.raw_type,
             SomeIpPrimitive::Bool | SomeIpPrimitive::F32 | SomeIpPrimitive::F64
         ) {
-            panic!(
+            return Err(format!(
                 "Enums cannot use bool, f32 or f64 as raw type, was={}",
                 self.raw_type
-            );
+            ));
         }
-        self.values.iter().for_each(|(_, value)| {
+        for (_, value) in self.values.iter() {
             if !value.matches(&self.raw_type) {
-                panic!(
+                return Err(format!(
                     "All values of an enum must be of same type, expected={} was={}",
                     self.raw_type,
                     value.display_type()
-                );
+                ));
             }
-        });
+        }
+        Ok(())
     }
 }
 
-impl VerifySomeIpType for SomeIpField {
-    fn verify(&self) {
+impl SomeIpBitfield {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
+        if !matches!(
+            self.raw_type,
+            SomeIpPrimitive::U8
+                | SomeIpPrimitive::U16
+                | SomeIpPrimitive::U32
+                | SomeIpPrimitive::U64
+        ) {
+            return Err(format!(
+                "Bitfields must use u8, u16, u32 or u64 as raw type, was={}",
+                self.raw_type
+            ));
+        }
+        let bits = self.raw_type.get_len() * 8;
+        let mut seen_bits = 0u64;
+        for (name, bit) in self.flags.iter() {
+            if *bit as usize >= bits {
+                return Err(format!(
+                    "Bit {} of flag {} does not fit into bitfield {} of type {}",
+                    bit, name, self.name, self.raw_type
+                ));
+            }
+            if seen_bits & 1 << bit != 0 {
+                return Err(format!(
+                    "Bit {} is used by multiple flags of bitfield {}",
+                    bit, self.name
+                ));
+            }
+            seen_bits |= 1 << bit;
+        }
+        Ok(())
+    }
+}
+
+impl SomeIpField {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
         if let Some(id) = self.id {
             if id > 0xFFF {
-                panic!("Field ids must not be larger than 0xFFF, was {:X}", id);
+                return Err(format!(
+                    "Field ids must not be larger than 0xFFF, was {:X}",
+                    id
+                ));
+            }
+        }
+        if let Some(default) = &self.default {
+            match &*self.field_type {
+                SomeIpType::Primitive(p) if default.matches(p) => {}
+                _ => {
+                    return Err(format!(
+                        "Default {} of field {} does not match its type {}",
+                        default, self.name, self.field_type
+                    ))
+                }
             }
         }
-        self.field_type.verify();
+        self.field_type.validate()
     }
 }
 
-impl VerifySomeIpType for SomeIpString {
-    fn verify(&self) {
+impl SomeIpString {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
         if self.max_size < self.min_size {
-            panic!(
+            return Err(format!(
                 "max_size must be bigger or equal to min_size, was max=size={}, min_size={}",
                 self.max_size, self.min_size
-            );
+            ));
+        }
+        if self.max_size > u32::MAX as usize {
+            return Err(String::from(
+                "SomeIp only supports string upto 4GB in length",
+            ));
+        }
+        if self.padding && self.min_size != self.max_size {
+            return Err(format!(
+                "Padded strings must have a fixed size, was max_size={}, min_size={}",
+                self.max_size, self.min_size
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl SomeIpSequence {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
+        if self.max_elements < self.min_elements {
+            return Err(format!("max_elements must be bigger or equal to min_elements, was max_elements={}, min_elements={}",
+            self.max_elements, self.min_elements));
         }
-        if self.max_size > u32::max_value() as usize {
-            panic!("SomeIp only supports string upto 4GB in length");
+        if self.max_elements > u32::MAX as usize {
+            return Err(String::from(
+                "SomeIp only supports sequences with upto 2^32 elements",
+            ));
         }
+        self.element_type.validate()
     }
 }
 
-impl VerifySomeIpType for SomeIpSequence {
-    fn verify(&self) {
+impl SomeIpMap {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
         if self.max_elements < self.min_elements {
-            panic!("max_elements must be bigger or equal to min_elements, was max_elements={}, min_elements={}", 
-            self.max_elements, self.min_elements);
+            return Err(format!("max_elements must be bigger or equal to min_elements, was max_elements={}, min_elements={}",
+            self.max_elements, self.min_elements));
         }
-        if self.max_elements > u32::max_value() as usize {
-            panic!("SomeIp only supports sequences with upto 2^32 elements");
+        if self.max_elements > u32::MAX as usize {
+            return Err(String::from(
+                "SomeIp only supports maps with upto 2^32 elements",
+            ));
         }
-        self.element_type.verify();
+        self.key_type.validate()?;
+        self.value_type.validate()
     }
 }
 
-impl VerifySomeIpType for SomeIpStruct {
-    fn verify(&self) {
-        self.fields.iter().for_each(|field| {
-            if self.uses_tlv_serialization {
+impl SomeIpStruct {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
+        if self.extensible && self.uses_tlv_serialization {
+            return Err(format!(
+                "Struct {} uses tlv and therefore cannot be extensible",
+                self.name
+            ));
+        }
+        let unknown_members_fields = self
+            .fields
+            .iter()
+            .filter(|f| matches!(*f.field_type, SomeIpType::UnknownMembers))
+            .count();
+        if unknown_members_fields > 0 && !self.uses_tlv_serialization {
+            return Err(format!(
+                "Only tlv structs can collect unknown members, but struct {} is not tlv",
+                self.name
+            ));
+        }
+        if unknown_members_fields > 1 {
+            return Err(format!(
+                "Struct {} has more than one field collecting unknown members",
+                self.name
+            ));
+        }
+        for field in self.fields.iter() {
+            if field.default.is_some() && !self.extensible {
+                return Err(format!(
+                    "Field {} has a default but struct {} is not extensible",
+                    field.name, self.name
+                ));
+            }
+            if matches!(*field.field_type, SomeIpType::UnknownMembers) {
+                if field.id.is_some() {
+                    return Err(format!(
+                        "Field {} collects unknown members and therefore must not have an id",
+                        field.name
+                    ));
+                }
+            } else if self.uses_tlv_serialization {
                 if field.id.is_none() {
-                    panic!(
+                    return Err(format!(
                         "All fields in a tlv struct must have an id {} has none",
                         field.name
-                    );
+                    ));
                 }
             } else if field.id.is_some() {
-                panic!(
+                return Err(format!(
                     "No field in a non tlv struct must have an id, {} has some",
                     field.name
-                );
+                ));
+            }
+            field.validate()?;
+        }
+        Ok(())
+    }
+}
+
+impl SomeIpUnion {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
+        if self.variants.is_empty() {
+            return Err(format!(
+                "Union {} must have at least one variant",
+                self.name
+            ));
+        }
+        for (i, variant) in self.variants.iter().enumerate() {
+            if variant.selector == 0 && variant.variant_type.is_some() {
+                return Err(format!(
+                    "The selector 0 is reserved for the empty union, but variant {} of union {} has data",
+                    variant.name, self.name
+                ));
+            }
+            if let Some(size) = self.type_field_size {
+                let max = match size {
+                    LengthFieldSize::OneByte => u8::MAX as u32,
+                    LengthFieldSize::TwoBytes => u16::MAX as u32,
+                    LengthFieldSize::FourBytes => u32::MAX,
+                };
+                if variant.selector > max {
+                    return Err(format!(
+                        "Selector {} of variant {} does not fit into type field of size {}",
+                        variant.selector, variant.name, size
+                    ));
+                }
+            }
+            if self.variants[..i]
+                .iter()
+                .any(|v| v.selector == variant.selector)
+            {
+                return Err(format!(
+                    "Selectors must be unique within union {}, {} is used more than once",
+                    self.name, variant.selector
+                ));
             }
-            field.verify();
-        });
+            if let Some(t) = &variant.variant_type {
+                t.validate()?;
+            }
+        }
+        Ok(())
     }
 }
 
-impl VerifySomeIpType for SomeIpType {
-    fn verify(&self) {
+impl SomeIpType {
+    pub(crate) fn validate(&self) -> std::result::Result<(), String> {
         match self {
-            SomeIpType::Primitive(_) => {}
-            SomeIpType::Enum(e) => e.verify(),
-            SomeIpType::String(s) => s.verify(),
-            SomeIpType::Sequence(s) => s.verify(),
-            SomeIpType::Struct(s) => s.verify(),
+            SomeIpType::Primitive(_) => Ok(()),
+            SomeIpType::Enum(e) => e.validate(),
+            SomeIpType::Bitfield(b) => b.validate(),
+            SomeIpType::String(s) => s.validate(),
+            SomeIpType::Sequence(s) => s.validate(),
+            SomeIpType::Map(m) => m.validate(),
+            SomeIpType::Struct(s) => s.validate(),
+            SomeIpType::Union(u) => u.validate(),
+            SomeIpType::UnknownMembers => Ok(()),
         }
     }
 }
@@ -697,6 +1341,11 @@ impl VerifySomeIpType for SomeIpType {
 pub trait SomeIp {
     /// The SomeIpType data associated with the type.
     const SOMEIP_TYPE: SomeIpType;
+    /// A `'static` reference to [SOMEIP_TYPE](SomeIp::SOMEIP_TYPE).
+    ///
+    /// Since [SomeIpType] may contain an [Arc] a reference to the associated constant
+    /// is not promoted to `'static` outside of constants, use this instead. Do not override it.
+    const SOMEIP_TYPE_REF: &'static SomeIpType = &Self::SOMEIP_TYPE;
 }
 
 macro_rules! declare_primitive {
@@ -718,3 +1367,37 @@ declare_primitive!(i32 = SomeIpPrimitive::I32);
 declare_primitive!(i64 = SomeIpPrimitive::I64);
 declare_primitive!(f32 = SomeIpPrimitive::F32);
 declare_primitive!(f64 = SomeIpPrimitive::F64);
+
+macro_rules! declare_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: SomeIp),+> SomeIp for ($($name,)+) {
+            const SOMEIP_TYPE: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+                name: SomeIpRef::Static("Tuple"),
+                fields: SomeIpRef::Static(&[$(SomeIpField {
+                    name: SomeIpRef::Static(stringify!($idx)),
+                    id: None,
+                    field_type: SomeIpRef::Static(&$name::SOMEIP_TYPE),
+                    default: None,
+                }),+]),
+                uses_tlv_serialization: false,
+                is_message_wrapper: false,
+                length_field_size: None,
+                transformation_properties: None,
+                extensible: false,
+            });
+        }
+    };
+}
+
+declare_tuple!(A 0);
+declare_tuple!(A 0, B 1);
+declare_tuple!(A 0, B 1, C 2);
+declare_tuple!(A 0, B 1, C 2, D 3);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10);
+declare_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7, I 8, J 9, K 10, L 11);
@@ -0,0 +1,109 @@
+//! Provides [UnknownMembers] which preserves the members of a TLV struct whose ids are not known.
+//!
+//! This is mainly useful for gateways that deserialize a struct, modify it and serialize it again
+//! without dropping members added by newer versions of the struct.
+
+use super::types::{SomeIp, SomeIpType};
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use std::fmt::Formatter;
+
+/// A single member of a TLV struct with an unknown id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownMember {
+    /// The tag of the member, which contains the wire type and the id.
+    pub tag: u16,
+    /// The raw data of the member following the tag, including the length field if there is one.
+    pub data: Vec<u8>,
+}
+
+impl UnknownMember {
+    /// The id of the member.
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.tag & 0xFFF
+    }
+}
+
+/// Collects all members of a TLV struct with unknown ids.
+///
+/// A TLV struct can contain at most one field of this type which must be marked with `#[someip(unknown_members)]`
+/// when using the derive macro. During deserialization all members with unknown ids are collected in the order they were received,
+/// during serialization they are written back verbatim where the field is declared.
+///
+/// Note that the data is not converted in any way, so it must be serialized with the same [SomeIpOptions](super::SomeIpOptions)
+/// that were used for deserialization.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UnknownMembers(pub Vec<UnknownMember>);
+
+impl UnknownMembers {
+    //Members are passed through serde as bytes, every member is encoded as
+    //tag (u16 big endian), length of data (u32 big endian), data
+    pub(crate) fn encode(members: &[UnknownMember]) -> Vec<u8> {
+        let len = members.iter().map(|m| 6 + m.data.len()).sum();
+        let mut encoded = Vec::with_capacity(len);
+        for member in members {
+            encoded.extend_from_slice(&member.tag.to_be_bytes());
+            encoded.extend_from_slice(&(member.data.len() as u32).to_be_bytes());
+            encoded.extend_from_slice(&member.data);
+        }
+        encoded
+    }
+
+    pub(crate) fn decode(encoded: &[u8]) -> Option<Vec<UnknownMember>> {
+        let mut members = Vec::new();
+        let mut remaining = encoded;
+        while !remaining.is_empty() {
+            if remaining.len() < 6 {
+                return None;
+            }
+            let tag = u16::from_be_bytes([remaining[0], remaining[1]]);
+            let len = u32::from_be_bytes([remaining[2], remaining[3], remaining[4], remaining[5]])
+                as usize;
+            remaining = &remaining[6..];
+            if remaining.len() < len {
+                return None;
+            }
+            members.push(UnknownMember {
+                tag,
+                data: remaining[..len].to_vec(),
+            });
+            remaining = &remaining[len..];
+        }
+        Some(members)
+    }
+}
+
+impl SomeIp for UnknownMembers {
+    const SOMEIP_TYPE: SomeIpType = SomeIpType::UnknownMembers;
+}
+
+impl Serialize for UnknownMembers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&UnknownMembers::encode(&self.0))
+    }
+}
+
+struct UnknownMembersVisitor;
+
+impl<'de> Visitor<'de> for UnknownMembersVisitor {
+    type Value = UnknownMembers;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("encoded unknown members")
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<UnknownMembers, E> {
+        UnknownMembers::decode(v)
+            .map(UnknownMembers)
+            .ok_or_else(|| E::invalid_value(de::Unexpected::Bytes(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for UnknownMembers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<UnknownMembers, D::Error> {
+        deserializer.deserialize_byte_buf(UnknownMembersVisitor)
+    }
+}
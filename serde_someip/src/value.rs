@@ -0,0 +1,895 @@
+//! Provides [SomeIpValue] which can hold any value that can be described by a [SomeIpType].
+//!
+//! This is useful if the type of the data is only known at runtime, for example in diagnostic tools,
+//! since no rust type implementing [SomeIp](super::SomeIp) is needed to de/serialize the data.
+//! Instead [from_slice_dynamic] and [to_vec_dynamic] walk the given [SomeIpType] exactly like
+//! [from_slice](super::from_slice) and [to_vec](super::to_vec) walk [SomeIp::SOMEIP_TYPE](super::SomeIp::SOMEIP_TYPE).
+//! ```
+//! use serde_someip::options::ExampleOptions;
+//! use serde_someip::types::*;
+//! use serde_someip::value::{from_slice_dynamic, to_vec_dynamic, SomeIpValue};
+//!
+//! static POINT: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+//!     name: SomeIpRef::Static("Point"),
+//!     fields: SomeIpRef::Static(&[
+//!         SomeIpField {
+//!             name: SomeIpRef::Static("x"),
+//!             id: None,
+//!             field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+//!             default: None,
+//!         },
+//!         SomeIpField {
+//!             name: SomeIpRef::Static("y"),
+//!             id: None,
+//!             field_type: SomeIpRef::Static(&u16::SOMEIP_TYPE),
+//!             default: None,
+//!         },
+//!     ]),
+//!     uses_tlv_serialization: false,
+//!     is_message_wrapper: true,
+//!     length_field_size: None,
+//!     transformation_properties: None,
+//!     extensible: false,
+//! });
+//!
+//! let value = from_slice_dynamic::<ExampleOptions>(&[0, 1, 0, 2], &POINT).unwrap();
+//! assert_eq!(Some(&SomeIpValue::U16(2)), value.field("y"));
+//! assert_eq!(vec![0, 1, 0, 2], to_vec_dynamic::<ExampleOptions>(&value, &POINT).unwrap());
+//! ```
+
+use super::de::from_slice_with_type;
+use super::error::{Error, Result};
+use super::options::SomeIpOptions;
+use super::ser::{to_vec_with_type, UNION_VARIANT_BY_INDEX};
+use super::types::*;
+use super::unknown_members::UnknownMembers;
+
+use serde::de::{
+    self, Deserialize, DeserializeSeed, Deserializer, EnumAccess, MapAccess, SeqAccess,
+    VariantAccess, Visitor,
+};
+use serde::ser::{self, Serialize, SerializeMap, SerializeSeq, SerializeTuple, Serializer};
+
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+/// A value of any type that can be described by a [SomeIpType].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SomeIpValue {
+    /// A bool.
+    Bool(bool),
+    /// An u8.
+    U8(u8),
+    /// An u16.
+    U16(u16),
+    /// An u32.
+    U32(u32),
+    /// An u64.
+    U64(u64),
+    /// An i8.
+    I8(i8),
+    /// An i16.
+    I16(i16),
+    /// An i32.
+    I32(i32),
+    /// An i64.
+    I64(i64),
+    /// An f32.
+    F32(f32),
+    /// An f64.
+    F64(f64),
+    /// The value of an enum.
+    Enum {
+        /// The name of the variant, for unknown values this is the [other](SomeIpEnum::other) variant.
+        name: String,
+        /// The raw value, this is what is serialized.
+        value: SomeIpEnumValue,
+    },
+    /// The flags of a bitfield as a tuple of `(FlagName, IsSet)`.
+    Bitfield(Vec<(String, bool)>),
+    /// A string.
+    String(String),
+    /// The elements of a sequence.
+    Sequence(Vec<SomeIpValue>),
+    /// The entries of a map as a tuple of `(Key, Value)`.
+    Map(Vec<(SomeIpValue, SomeIpValue)>),
+    /// The fields of a struct as a tuple of `(FieldName, Value)`.
+    ///
+    /// Fields of TLV structs that were not present are missing.
+    Struct(Vec<(String, SomeIpValue)>),
+    /// The value of a union.
+    Union {
+        /// The name of the variant.
+        name: String,
+        /// The data carried by the variant, `None` for variants without data.
+        value: Option<Box<SomeIpValue>>,
+    },
+    /// The members of a TLV struct with unknown ids, see [UnknownMembers].
+    UnknownMembers(UnknownMembers),
+}
+
+impl SomeIpValue {
+    /// Retrieves the field with the given name if this is a struct.
+    pub fn field(&self, name: &str) -> Option<&SomeIpValue> {
+        if let SomeIpValue::Struct(fields) = self {
+            fields
+                .iter()
+                .find_map(|(n, v)| if name == n { Some(v) } else { None })
+        } else {
+            None
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            SomeIpValue::Bool(_) => "bool",
+            SomeIpValue::U8(_) => "u8",
+            SomeIpValue::U16(_) => "u16",
+            SomeIpValue::U32(_) => "u32",
+            SomeIpValue::U64(_) => "u64",
+            SomeIpValue::I8(_) => "i8",
+            SomeIpValue::I16(_) => "i16",
+            SomeIpValue::I32(_) => "i32",
+            SomeIpValue::I64(_) => "i64",
+            SomeIpValue::F32(_) => "f32",
+            SomeIpValue::F64(_) => "f64",
+            SomeIpValue::Enum { .. } => "enum",
+            SomeIpValue::Bitfield(_) => "bitfield",
+            SomeIpValue::String(_) => "string",
+            SomeIpValue::Sequence(_) => "sequence",
+            SomeIpValue::Map(_) => "map",
+            SomeIpValue::Struct(_) => "struct",
+            SomeIpValue::Union { .. } => "union",
+            SomeIpValue::UnknownMembers(_) => "unknown members",
+        }
+    }
+}
+
+/// Deserialises a value of the given type from a `u8` slice.
+///
+/// # Panics
+/// This function panics if `someip_type` is not valid.
+pub fn from_slice_dynamic<Options>(data: &[u8], someip_type: &SomeIpType) -> Result<SomeIpValue>
+where
+    Options: SomeIpOptions + ?Sized,
+{
+    from_slice_with_type::<Options, _>(data, someip_type, ValueSeed::<Options>::new(someip_type))
+}
+
+/// Serialises a value of the given type to a [Vec<u8>].
+///
+/// Fails with [Error::Message] if the value does not match the type.
+///
+/// # Panics
+/// This function panics if `someip_type` is not valid.
+pub fn to_vec_dynamic<Options>(value: &SomeIpValue, someip_type: &SomeIpType) -> Result<Vec<u8>>
+where
+    Options: SomeIpOptions + ?Sized,
+{
+    to_vec_with_type::<Options, _>(&TypedValue { value, someip_type }, someip_type)
+}
+
+//the name passed to the deserializer for structs and unions, it is ignored by the deserializer
+const VALUE_NAME: &str = "SomeIpValue";
+
+//Deserializes the value of the given type by requesting exactly what a rust type with the same type information would
+struct ValueSeed<'t, Options: ?Sized> {
+    someip_type: &'t SomeIpType,
+    phantom: PhantomData<Options>,
+}
+
+impl<'t, Options: SomeIpOptions + ?Sized> ValueSeed<'t, Options> {
+    #[inline]
+    fn new(someip_type: &'t SomeIpType) -> ValueSeed<'t, Options> {
+        ValueSeed {
+            someip_type,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, 't, Options: SomeIpOptions + ?Sized> DeserializeSeed<'de> for ValueSeed<'t, Options> {
+    type Value = SomeIpValue;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<SomeIpValue, D::Error> {
+        match self.someip_type {
+            SomeIpType::Primitive(p) => match p {
+                SomeIpPrimitive::Bool => bool::deserialize(deserializer).map(SomeIpValue::Bool),
+                SomeIpPrimitive::U8 => u8::deserialize(deserializer).map(SomeIpValue::U8),
+                SomeIpPrimitive::U16 => u16::deserialize(deserializer).map(SomeIpValue::U16),
+                SomeIpPrimitive::U32 => u32::deserialize(deserializer).map(SomeIpValue::U32),
+                SomeIpPrimitive::U64 => u64::deserialize(deserializer).map(SomeIpValue::U64),
+                SomeIpPrimitive::I8 => i8::deserialize(deserializer).map(SomeIpValue::I8),
+                SomeIpPrimitive::I16 => i16::deserialize(deserializer).map(SomeIpValue::I16),
+                SomeIpPrimitive::I32 => i32::deserialize(deserializer).map(SomeIpValue::I32),
+                SomeIpPrimitive::I64 => i64::deserialize(deserializer).map(SomeIpValue::I64),
+                SomeIpPrimitive::F32 => f32::deserialize(deserializer).map(SomeIpValue::F32),
+                SomeIpPrimitive::F64 => f64::deserialize(deserializer).map(SomeIpValue::F64),
+            },
+            SomeIpType::Enum(e) => {
+                let value = match e.raw_type {
+                    SomeIpPrimitive::U8 => SomeIpEnumValue::U8(u8::deserialize(deserializer)?),
+                    SomeIpPrimitive::U16 => SomeIpEnumValue::U16(u16::deserialize(deserializer)?),
+                    SomeIpPrimitive::U32 => SomeIpEnumValue::U32(u32::deserialize(deserializer)?),
+                    SomeIpPrimitive::U64 => SomeIpEnumValue::U64(u64::deserialize(deserializer)?),
+                    SomeIpPrimitive::I8 => SomeIpEnumValue::I8(i8::deserialize(deserializer)?),
+                    SomeIpPrimitive::I16 => SomeIpEnumValue::I16(i16::deserialize(deserializer)?),
+                    SomeIpPrimitive::I32 => SomeIpEnumValue::I32(i32::deserialize(deserializer)?),
+                    SomeIpPrimitive::I64 => SomeIpEnumValue::I64(i64::deserialize(deserializer)?),
+                    _ => panic!("Unsupported raw type for enums: {}", e.raw_type),
+                };
+                let name = e
+                    .value_to_name(&value)
+                    .or(e.other.as_deref())
+                    .ok_or_else(|| {
+                        de::Error::custom(Error::InvalidEnumValue {
+                            value: value.display_value(),
+                            name: e.name.to_string(),
+                        })
+                    })?;
+                Ok(SomeIpValue::Enum {
+                    name: name.to_owned(),
+                    value,
+                })
+            }
+            SomeIpType::Bitfield(b) => {
+                let value = match b.raw_type {
+                    SomeIpPrimitive::U8 => u8::deserialize(deserializer)? as u64,
+                    SomeIpPrimitive::U16 => u16::deserialize(deserializer)? as u64,
+                    SomeIpPrimitive::U32 => u32::deserialize(deserializer)? as u64,
+                    SomeIpPrimitive::U64 => u64::deserialize(deserializer)?,
+                    _ => panic!("Unsupported raw type for bitfields: {}", b.raw_type),
+                };
+                if Options::DESERIALIZER_STRICT_BITFIELD && value & !b.known_bits() != 0 {
+                    return Err(de::Error::custom(Error::InvalidBitfieldValue {
+                        value,
+                        name: b.name.to_string(),
+                    }));
+                }
+                Ok(SomeIpValue::Bitfield(
+                    b.flags
+                        .iter()
+                        .map(|(name, bit)| (name.to_string(), value & 1 << bit != 0))
+                        .collect(),
+                ))
+            }
+            SomeIpType::String(_) => String::deserialize(deserializer).map(SomeIpValue::String),
+            SomeIpType::Sequence(_) => {
+                deserializer.deserialize_seq(ValueVisitor::<Options>::new(self.someip_type))
+            }
+            SomeIpType::Map(_) => {
+                deserializer.deserialize_map(ValueVisitor::<Options>::new(self.someip_type))
+            }
+            SomeIpType::Struct(s) => {
+                let visitor = ValueVisitor::<Options>::new(self.someip_type);
+                if s.uses_tlv() {
+                    deserializer.deserialize_struct(VALUE_NAME, &[], visitor)
+                } else {
+                    deserializer.deserialize_tuple(s.field_count(), visitor)
+                }
+            }
+            SomeIpType::Union(_) => deserializer.deserialize_enum(
+                VALUE_NAME,
+                &[],
+                ValueVisitor::<Options>::new(self.someip_type),
+            ),
+            SomeIpType::UnknownMembers => {
+                UnknownMembers::deserialize(deserializer).map(SomeIpValue::UnknownMembers)
+            }
+        }
+    }
+}
+
+//Visits sequences, maps, structs and unions
+struct ValueVisitor<'t, Options: ?Sized> {
+    someip_type: &'t SomeIpType,
+    phantom: PhantomData<Options>,
+}
+
+impl<'t, Options: SomeIpOptions + ?Sized> ValueVisitor<'t, Options> {
+    #[inline]
+    fn new(someip_type: &'t SomeIpType) -> ValueVisitor<'t, Options> {
+        ValueVisitor {
+            someip_type,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'de, 't, Options: SomeIpOptions + ?Sized> Visitor<'de> for ValueVisitor<'t, Options> {
+    type Value = SomeIpValue;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "a value of type {}", self.someip_type)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(
+        self,
+        mut seq: A,
+    ) -> std::result::Result<SomeIpValue, A::Error> {
+        match self.someip_type {
+            SomeIpType::Sequence(s) => {
+                let mut elements = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+                while let Some(element) =
+                    seq.next_element_seed(ValueSeed::<Options>::new(&s.element_type))?
+                {
+                    elements.push(element);
+                }
+                Ok(SomeIpValue::Sequence(elements))
+            }
+            SomeIpType::Struct(s) => {
+                let mut fields = Vec::with_capacity(s.field_count());
+                for field in s.fields.iter() {
+                    //extensible structs may end early
+                    match seq.next_element_seed(ValueSeed::<Options>::new(&field.field_type))? {
+                        Some(value) => fields.push((field.name.to_string(), value)),
+                        None => break,
+                    }
+                }
+                Ok(SomeIpValue::Struct(fields))
+            }
+            _ => Err(de::Error::invalid_type(de::Unexpected::Seq, &self)),
+        }
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<SomeIpValue, A::Error> {
+        match self.someip_type {
+            SomeIpType::Map(m) => {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(key) = map.next_key_seed(ValueSeed::<Options>::new(&m.key_type))? {
+                    let value = map.next_value_seed(ValueSeed::<Options>::new(&m.value_type))?;
+                    entries.push((key, value));
+                }
+                Ok(SomeIpValue::Map(entries))
+            }
+            SomeIpType::Struct(s) => {
+                let mut fields = Vec::new();
+                while let Some(name) = map.next_key_seed(NameSeed)? {
+                    let field = s
+                        .fields
+                        .iter()
+                        .find(|f| *f.name == name)
+                        .ok_or_else(|| de::Error::unknown_field(&name, &[]))?;
+                    let value =
+                        map.next_value_seed(ValueSeed::<Options>::new(&field.field_type))?;
+                    fields.push((name, value));
+                }
+                Ok(SomeIpValue::Struct(fields))
+            }
+            _ => Err(de::Error::invalid_type(de::Unexpected::Map, &self)),
+        }
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> std::result::Result<SomeIpValue, A::Error> {
+        if let SomeIpType::Union(u) = self.someip_type {
+            let (name, variant) = data.variant_seed(NameSeed)?;
+            let variant_type = &u
+                .variant_by_name(&name)
+                .ok_or_else(|| de::Error::unknown_variant(&name, &[]))?
+                .variant_type;
+            let value = if let Some(variant_type) = variant_type {
+                Some(Box::new(variant.newtype_variant_seed(ValueSeed::<
+                    Options,
+                >::new(
+                    variant_type
+                ))?))
+            } else {
+                variant.unit_variant()?;
+                None
+            };
+            Ok(SomeIpValue::Union { name, value })
+        } else {
+            Err(de::Error::invalid_type(de::Unexpected::Enum, &self))
+        }
+    }
+}
+
+//Deserializes the name of a struct field or union variant
+struct NameSeed;
+
+impl<'de> DeserializeSeed<'de> for NameSeed {
+    type Value = String;
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<String, D::Error> {
+        deserializer.deserialize_identifier(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NameSeed {
+    type Value = String;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a name")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> std::result::Result<String, E> {
+        Ok(v.to_owned())
+    }
+}
+
+//Serializes the value by making exactly the calls a rust type with the same type information would
+struct TypedValue<'a> {
+    value: &'a SomeIpValue,
+    someip_type: &'a SomeIpType,
+}
+
+impl<'a> TypedValue<'a> {
+    fn mismatch<E: ser::Error>(&self) -> E {
+        E::custom(format!(
+            "Cannot serialize a {} as {}",
+            self.value.kind(),
+            self.someip_type
+        ))
+    }
+
+    fn serialize_struct<S: Serializer>(
+        &self,
+        s: &SomeIpStruct,
+        fields: &[(String, SomeIpValue)],
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        if let Some((name, _)) = fields
+            .iter()
+            .find(|(n, _)| !s.fields.iter().any(|f| *f.name == **n))
+        {
+            return Err(ser::Error::custom(format!(
+                "Struct {} has no field {}",
+                s.name, name
+            )));
+        }
+        //fields are serialized by their position since their names may not be 'static
+        let mut state = serializer.serialize_tuple(s.field_count())?;
+        for field in s.fields.iter() {
+            let value = fields
+                .iter()
+                .find_map(|(n, v)| if *n == *field.name { Some(v) } else { None });
+            match value {
+                Some(value) => state.serialize_element(&TypedValue {
+                    value,
+                    someip_type: &field.field_type,
+                })?,
+                None if matches!(*field.field_type, SomeIpType::UnknownMembers) => {
+                    state.serialize_element(&UnknownMembers::default())?
+                }
+                None if s.uses_tlv() => state.serialize_element(&None::<()>)?,
+                None => {
+                    return Err(ser::Error::custom(format!(
+                        "Field {} of struct {} is missing",
+                        field.name, s.name
+                    )))
+                }
+            }
+        }
+        state.end()
+    }
+}
+
+impl<'a> Serialize for TypedValue<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match (self.someip_type, self.value) {
+            (SomeIpType::Primitive(p), value) => match (p, value) {
+                (SomeIpPrimitive::Bool, SomeIpValue::Bool(v)) => serializer.serialize_bool(*v),
+                (SomeIpPrimitive::U8, SomeIpValue::U8(v)) => serializer.serialize_u8(*v),
+                (SomeIpPrimitive::U16, SomeIpValue::U16(v)) => serializer.serialize_u16(*v),
+                (SomeIpPrimitive::U32, SomeIpValue::U32(v)) => serializer.serialize_u32(*v),
+                (SomeIpPrimitive::U64, SomeIpValue::U64(v)) => serializer.serialize_u64(*v),
+                (SomeIpPrimitive::I8, SomeIpValue::I8(v)) => serializer.serialize_i8(*v),
+                (SomeIpPrimitive::I16, SomeIpValue::I16(v)) => serializer.serialize_i16(*v),
+                (SomeIpPrimitive::I32, SomeIpValue::I32(v)) => serializer.serialize_i32(*v),
+                (SomeIpPrimitive::I64, SomeIpValue::I64(v)) => serializer.serialize_i64(*v),
+                (SomeIpPrimitive::F32, SomeIpValue::F32(v)) => serializer.serialize_f32(*v),
+                (SomeIpPrimitive::F64, SomeIpValue::F64(v)) => serializer.serialize_f64(*v),
+                _ => Err(self.mismatch()),
+            },
+            (SomeIpType::Enum(e), SomeIpValue::Enum { value, .. })
+                if value.matches(&e.raw_type) =>
+            {
+                match value {
+                    SomeIpEnumValue::U8(v) => serializer.serialize_u8(*v),
+                    SomeIpEnumValue::U16(v) => serializer.serialize_u16(*v),
+                    SomeIpEnumValue::U32(v) => serializer.serialize_u32(*v),
+                    SomeIpEnumValue::U64(v) => serializer.serialize_u64(*v),
+                    SomeIpEnumValue::I8(v) => serializer.serialize_i8(*v),
+                    SomeIpEnumValue::I16(v) => serializer.serialize_i16(*v),
+                    SomeIpEnumValue::I32(v) => serializer.serialize_i32(*v),
+                    SomeIpEnumValue::I64(v) => serializer.serialize_i64(*v),
+                }
+            }
+            (SomeIpType::Bitfield(b), SomeIpValue::Bitfield(flags)) => {
+                let mut value = 0u64;
+                for (flag, is_set) in flags {
+                    let bit = b.flag_to_bit(flag).ok_or_else(|| {
+                        ser::Error::custom(format!("Bitfield {} has no flag {}", b.name, flag))
+                    })?;
+                    if *is_set {
+                        value |= 1 << bit;
+                    }
+                }
+                match b.raw_type {
+                    SomeIpPrimitive::U8 => serializer.serialize_u8(value as u8),
+                    SomeIpPrimitive::U16 => serializer.serialize_u16(value as u16),
+                    SomeIpPrimitive::U32 => serializer.serialize_u32(value as u32),
+                    SomeIpPrimitive::U64 => serializer.serialize_u64(value),
+                    _ => panic!("Unsupported raw type for bitfields: {}", b.raw_type),
+                }
+            }
+            (SomeIpType::String(_), SomeIpValue::String(v)) => serializer.serialize_str(v),
+            (SomeIpType::Sequence(s), SomeIpValue::Sequence(elements)) => {
+                let mut state = serializer.serialize_seq(Some(elements.len()))?;
+                for value in elements {
+                    state.serialize_element(&TypedValue {
+                        value,
+                        someip_type: &s.element_type,
+                    })?;
+                }
+                state.end()
+            }
+            (SomeIpType::Map(m), SomeIpValue::Map(entries)) => {
+                let mut state = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    state.serialize_entry(
+                        &TypedValue {
+                            value: key,
+                            someip_type: &m.key_type,
+                        },
+                        &TypedValue {
+                            value,
+                            someip_type: &m.value_type,
+                        },
+                    )?;
+                }
+                state.end()
+            }
+            (SomeIpType::Struct(s), SomeIpValue::Struct(fields)) => {
+                self.serialize_struct(s, fields, serializer)
+            }
+            (SomeIpType::Union(u), SomeIpValue::Union { name, value }) => {
+                //variants are selected by their position since their names may not be 'static
+                let (index, variant) = u
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, v)| *v.name == *name)
+                    .ok_or_else(|| {
+                        ser::Error::custom(format!("Union {} has no variant {}", u.name, name))
+                    })?;
+                let index = index as u32;
+                match (&variant.variant_type, value) {
+                    (None, None) => {
+                        serializer.serialize_unit_variant(UNION_VARIANT_BY_INDEX, index, "")
+                    }
+                    (Some(variant_type), Some(value)) => serializer.serialize_newtype_variant(
+                        UNION_VARIANT_BY_INDEX,
+                        index,
+                        "",
+                        &TypedValue {
+                            value,
+                            someip_type: variant_type,
+                        },
+                    ),
+                    _ => Err(ser::Error::custom(format!(
+                        "Variant {} of union {} does not match its data",
+                        name, u.name
+                    ))),
+                }
+            }
+            (SomeIpType::UnknownMembers, SomeIpValue::UnknownMembers(members)) => {
+                members.serialize(serializer)
+            }
+            _ => Err(self.mismatch()),
+        }
+    }
+}
+
+#[cfg(test)]
+use super::options::ExampleOptions;
+#[cfg(test)]
+use super::ser::{TestAligned, TestExtensible, TestUnion, TestUnknownMembers};
+#[cfg(test)]
+use super::unknown_members::UnknownMember;
+#[cfg(test)]
+use super::SomeIp;
+
+#[cfg(test)]
+fn round_trip<T: Serialize + SomeIp>(value: &T) -> SomeIpValue {
+    round_trip_as(value, &T::SOMEIP_TYPE)
+}
+
+#[cfg(test)]
+fn round_trip_as<T: Serialize>(value: &T, someip_type: &SomeIpType) -> SomeIpValue {
+    let serialized = to_vec_with_type::<ExampleOptions, _>(value, someip_type).unwrap();
+    let dynamic = from_slice_dynamic::<ExampleOptions>(&serialized, someip_type).unwrap();
+    assert_eq!(
+        serialized,
+        to_vec_dynamic::<ExampleOptions>(&dynamic, someip_type).unwrap()
+    );
+    dynamic
+}
+
+#[test]
+fn test_primitives() {
+    assert_eq!(SomeIpValue::Bool(true), round_trip(&true));
+    assert_eq!(SomeIpValue::I16(-2), round_trip(&-2i16));
+    assert_eq!(SomeIpValue::U64(42), round_trip(&42u64));
+    assert_eq!(SomeIpValue::F32(1.5), round_trip(&1.5f32));
+    assert!(matches!(
+        to_vec_dynamic::<ExampleOptions>(&SomeIpValue::U8(1), &u16::SOMEIP_TYPE),
+        Err(Error::Message(_))
+    ));
+}
+
+#[test]
+fn test_struct() {
+    let value = round_trip(&TestAligned {
+        a: 1,
+        b: String::from("hi"),
+        c: 2,
+    });
+    assert_eq!(
+        SomeIpValue::Struct(vec![
+            (String::from("a"), SomeIpValue::U8(1)),
+            (String::from("b"), SomeIpValue::String(String::from("hi"))),
+            (String::from("c"), SomeIpValue::U16(2)),
+        ]),
+        value
+    );
+    assert_eq!(Some(&SomeIpValue::U16(2)), value.field("c"));
+
+    let missing = SomeIpValue::Struct(vec![(String::from("a"), SomeIpValue::U8(1))]);
+    assert!(to_vec_dynamic::<ExampleOptions>(&missing, &TestAligned::SOMEIP_TYPE).is_err());
+}
+
+#[test]
+fn test_struct_extensible() {
+    //b is filled from its default, c has no default so it is missing
+    let value =
+        from_slice_dynamic::<ExampleOptions>(&[0, 0, 0, 2, 0, 1], &TestExtensible::SOMEIP_TYPE)
+            .unwrap();
+    assert_eq!(
+        SomeIpValue::Struct(vec![
+            (String::from("a"), SomeIpValue::U16(1)),
+            (String::from("b"), SomeIpValue::U8(7)),
+        ]),
+        value
+    );
+    assert!(to_vec_dynamic::<ExampleOptions>(&value, &TestExtensible::SOMEIP_TYPE).is_err());
+    round_trip(&TestExtensible { a: 1, b: 2, c: 3 });
+}
+
+#[test]
+fn test_struct_tlv() {
+    let value = round_trip(&TestUnknownMembers {
+        a: 1,
+        unknown: UnknownMembers(vec![UnknownMember {
+            tag: 0x2005,
+            data: vec![0, 0, 0, 7],
+        }]),
+        c: None,
+    });
+    assert_eq!(None, value.field("c"));
+    assert_eq!(Some(&SomeIpValue::U8(1)), value.field("a"));
+    assert!(matches!(
+        value.field("unknown"),
+        Some(SomeIpValue::UnknownMembers(m)) if m.0.len() == 1
+    ));
+
+    let value = round_trip(&TestUnknownMembers {
+        a: 1,
+        unknown: UnknownMembers::default(),
+        c: Some(3),
+    });
+    assert_eq!(Some(&SomeIpValue::U16(3)), value.field("c"));
+}
+
+#[test]
+fn test_union() {
+    assert_eq!(
+        SomeIpValue::Union {
+            name: String::from("Empty"),
+            value: None
+        },
+        round_trip(&TestUnion::Empty)
+    );
+    assert_eq!(
+        SomeIpValue::Union {
+            name: String::from("Newtype"),
+            value: Some(Box::new(SomeIpValue::U32(42)))
+        },
+        round_trip(&TestUnion::Newtype(42))
+    );
+    assert_eq!(
+        SomeIpValue::Union {
+            name: String::from("Struct"),
+            value: Some(Box::new(SomeIpValue::Struct(vec![
+                (String::from("a"), SomeIpValue::U8(1)),
+                (String::from("b"), SomeIpValue::U16(2)),
+            ])))
+        },
+        round_trip(&TestUnion::Struct { a: 1, b: 2 })
+    );
+    round_trip(&TestUnion::Tuple(1, 2));
+}
+
+#[cfg(test)]
+static TEST_SEQUENCE: SomeIpType = SomeIpType::Sequence(SomeIpSequence {
+    max_elements: 4,
+    min_elements: 0,
+    element_type: SomeIpRef::Static(&u32::SOMEIP_TYPE),
+    length_field_size: Some(super::length_fields::LengthFieldSize::OneByte),
+});
+
+#[cfg(test)]
+static TEST_MAP: SomeIpType = SomeIpType::Map(SomeIpMap {
+    max_elements: 4,
+    min_elements: 0,
+    key_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+    value_type: SomeIpRef::Static(&SomeIpType::String(SomeIpString {
+        max_size: 8,
+        min_size: 0,
+        length_field_size: None,
+        padding: false,
+    })),
+    length_field_size: Some(super::length_fields::LengthFieldSize::TwoBytes),
+});
+
+#[test]
+fn test_sequence_and_map() {
+    assert_eq!(
+        SomeIpValue::Sequence(vec![SomeIpValue::U32(1), SomeIpValue::U32(2)]),
+        round_trip_as(&vec![1u32, 2], &TEST_SEQUENCE)
+    );
+    let mut map = std::collections::BTreeMap::new();
+    map.insert(1u8, String::from("one"));
+    assert_eq!(
+        SomeIpValue::Map(vec![(
+            SomeIpValue::U8(1),
+            SomeIpValue::String(String::from("one"))
+        )]),
+        round_trip_as(&map, &TEST_MAP)
+    );
+}
+
+#[cfg(test)]
+static TEST_ENUM: SomeIpType = SomeIpType::Enum(SomeIpEnum {
+    name: SomeIpRef::Static("TestEnum"),
+    values: SomeIpRef::Static(&[
+        (SomeIpRef::Static("A"), SomeIpEnumValue::U8(1)),
+        (SomeIpRef::Static("B"), SomeIpEnumValue::U8(2)),
+    ]),
+    raw_type: SomeIpPrimitive::U8,
+    other: None,
+});
+
+#[cfg(test)]
+static TEST_BITFIELD: SomeIpType = SomeIpType::Bitfield(SomeIpBitfield {
+    name: SomeIpRef::Static("TestBitfield"),
+    flags: SomeIpRef::Static(&[(SomeIpRef::Static("a"), 0), (SomeIpRef::Static("b"), 3)]),
+    raw_type: SomeIpPrimitive::U16,
+});
+
+#[test]
+fn test_enum() {
+    let value = from_slice_dynamic::<ExampleOptions>(&[2], &TEST_ENUM).unwrap();
+    assert_eq!(
+        SomeIpValue::Enum {
+            name: String::from("B"),
+            value: SomeIpEnumValue::U8(2)
+        },
+        value
+    );
+    assert_eq!(
+        vec![2],
+        to_vec_dynamic::<ExampleOptions>(&value, &TEST_ENUM).unwrap()
+    );
+    assert!(from_slice_dynamic::<ExampleOptions>(&[3], &TEST_ENUM).is_err());
+    let wrong_raw_type = SomeIpValue::Enum {
+        name: String::from("B"),
+        value: SomeIpEnumValue::U16(2),
+    };
+    assert!(to_vec_dynamic::<ExampleOptions>(&wrong_raw_type, &TEST_ENUM).is_err());
+}
+
+#[test]
+fn test_bitfield() {
+    let value = from_slice_dynamic::<ExampleOptions>(&[0, 8], &TEST_BITFIELD).unwrap();
+    assert_eq!(
+        SomeIpValue::Bitfield(vec![(String::from("a"), false), (String::from("b"), true)]),
+        value
+    );
+    assert_eq!(
+        vec![0, 8],
+        to_vec_dynamic::<ExampleOptions>(&value, &TEST_BITFIELD).unwrap()
+    );
+    let unknown_flag = SomeIpValue::Bitfield(vec![(String::from("c"), true)]);
+    assert!(to_vec_dynamic::<ExampleOptions>(&unknown_flag, &TEST_BITFIELD).is_err());
+}
+
+#[cfg(test)]
+static TEST_STATIC_POINT: SomeIpType = SomeIpType::Struct(SomeIpStruct {
+    name: SomeIpRef::Static("Point"),
+    fields: SomeIpRef::Static(&[
+        SomeIpField {
+            name: SomeIpRef::Static("x"),
+            id: None,
+            field_type: SomeIpRef::Static(&u8::SOMEIP_TYPE),
+            default: None,
+        },
+        SomeIpField {
+            name: SomeIpRef::Static("y"),
+            id: None,
+            field_type: SomeIpRef::Static(&TEST_SEQUENCE),
+            default: None,
+        },
+    ]),
+    uses_tlv_serialization: false,
+    is_message_wrapper: false,
+    length_field_size: None,
+    transformation_properties: None,
+    extensible: false,
+});
+
+#[test]
+fn test_shared_type() {
+    //the same type as TEST_STATIC_POINT but created at runtime
+    let field = |name: &str, field_type: SomeIpType| SomeIpField {
+        name: String::from(name).into(),
+        id: None,
+        field_type: SomeIpRef::new(field_type),
+        default: None,
+    };
+    let point = SomeIpType::Struct(SomeIpStruct {
+        name: String::from("Point").into(),
+        fields: vec![
+            field("x", SomeIpType::Primitive(SomeIpPrimitive::U8)),
+            field(
+                "y",
+                SomeIpType::Sequence(SomeIpSequence {
+                    max_elements: 4,
+                    min_elements: 0,
+                    element_type: SomeIpRef::Static(u32::SOMEIP_TYPE_REF),
+                    length_field_size: Some(super::length_fields::LengthFieldSize::OneByte),
+                }),
+            ),
+        ]
+        .into(),
+        uses_tlv_serialization: false,
+        is_message_wrapper: false,
+        length_field_size: None,
+        transformation_properties: None,
+        extensible: false,
+    });
+    point.verify();
+    assert_eq!(TEST_STATIC_POINT, point);
+    assert_eq!(
+        TEST_STATIC_POINT
+            .max_len::<ExampleOptions>(false, &None)
+            .unwrap(),
+        point.max_len::<ExampleOptions>(false, &None).unwrap()
+    );
+
+    let value = SomeIpValue::Struct(vec![
+        (String::from("x"), SomeIpValue::U8(1)),
+        (
+            String::from("y"),
+            SomeIpValue::Sequence(vec![SomeIpValue::U32(2)]),
+        ),
+    ]);
+    let serialized = to_vec_dynamic::<ExampleOptions>(&value, &point).unwrap();
+    assert_eq!(
+        to_vec_dynamic::<ExampleOptions>(&value, &TEST_STATIC_POINT).unwrap(),
+        serialized
+    );
+    assert_eq!(
+        value,
+        from_slice_dynamic::<ExampleOptions>(&serialized, &point).unwrap()
+    );
+}
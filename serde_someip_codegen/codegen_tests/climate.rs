@@ -0,0 +1,111 @@
+// This file is @generated by serde_someip_codegen, do not edit it by hand.
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(raw_type = u16)]
+pub enum FanLevel {
+    #[someip(value = 0)]
+    Off,
+    #[someip(value = 2)]
+    Low,
+    #[someip(value = 3)]
+    Medium,
+    #[someip(value = 4)]
+    High,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(raw_type = u16)]
+pub enum ExtendedFanLevel {
+    #[someip(value = 0)]
+    Off,
+    #[someip(value = 2)]
+    Low,
+    #[someip(value = 3)]
+    Medium,
+    #[someip(value = 4)]
+    High,
+    #[someip(value = 10)]
+    Turbo,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(length_field_size = 2)]
+pub struct Zone {
+    /// The name that is displayed to the driver.
+    #[someip(max_size = 64, length_field_size = 1)]
+    pub name: String,
+    pub target: i16,
+    pub fan: FanLevel,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(length_field_size = 2, type_field_size = 1)]
+pub enum Source {
+    #[someip(selector = 1)]
+    SensorIndex(u8),
+    #[someip(selector = 2, fixed_size = 32)]
+    ExternalName(String),
+}
+
+/// Controls the climate of the vehicle.
+pub mod climate_control {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true)]
+    pub struct ZonesField {
+        #[someip(max_elements = 4, length_field_size = 1)]
+        pub zones: Vec<Zone>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true)]
+    pub struct TemperatureChangedEvent {
+        pub source: Source,
+        pub temperature: i16,
+        #[someip(max_elements = 256)]
+        pub raw: Vec<u8>,
+    }
+
+    /// Changes the settings of a zone.
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true)]
+    pub struct SetZoneRequest {
+        #[someip(id = 1)]
+        pub index: u8,
+        #[someip(id = 2)]
+        pub zone: Zone,
+        /// Only used for diagnostics.
+        #[someip(id = 3, max_size = 128)]
+        pub reason: String,
+    }
+
+    /// Changes the settings of a zone.
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true)]
+    pub struct SetZoneResponse {
+        pub accepted: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true)]
+    pub struct GetPresetsResponse {
+        #[someip(max_elements = 8, length_field_size = 2)]
+        pub presets: std::collections::HashMap<u16, Zone>,
+        pub max_fan: ExtendedFanLevel,
+        #[someip(min_elements = 1, max_elements = 16, length_field_size = 2)]
+        pub history: Vec<u32>,
+    }
+
+    /// The options of the SOME/IP deployment of ClimateControl.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Options;
+
+    impl serde_someip::SomeIpOptions for Options {
+        const STRING_ENCODING: serde_someip::options::StringEncoding = serde_someip::options::StringEncoding::Utf16Le;
+        const STRING_WITH_BOM: bool = true;
+        const STRING_WITH_TERMINATOR: bool = true;
+        const DEFAULT_LENGTH_FIELD_SIZE: Option<serde_someip::length_fields::LengthFieldSize> = Some(serde_someip::length_fields::LengthFieldSize::FourBytes);
+        const DEFAULT_UNION_TYPE_FIELD_SIZE: serde_someip::length_fields::LengthFieldSize = serde_someip::length_fields::LengthFieldSize::FourBytes;
+    }
+}
@@ -0,0 +1,47 @@
+include!("climate.rs");
+
+use serde_someip::SomeIpOptions;
+use std::collections::HashMap;
+
+fn main() {
+    let zone = Zone {
+        name: String::from("Driver"),
+        target: 215,
+        fan: FanLevel::Medium,
+    };
+
+    let request = climate_control::SetZoneRequest {
+        index: 0,
+        zone: zone.clone(),
+        reason: String::from("Too cold"),
+    };
+    let serialized = climate_control::Options::to_vec(&request).unwrap();
+    assert_eq!(
+        request,
+        climate_control::Options::from_slice(&serialized).unwrap()
+    );
+
+    let mut presets = HashMap::new();
+    presets.insert(1, zone);
+    let response = climate_control::GetPresetsResponse {
+        presets,
+        max_fan: ExtendedFanLevel::Turbo,
+        history: vec![1, 2, 3],
+    };
+    let serialized = climate_control::Options::to_vec(&response).unwrap();
+    assert_eq!(
+        response,
+        climate_control::Options::from_slice(&serialized).unwrap()
+    );
+
+    let event = climate_control::TemperatureChangedEvent {
+        source: Source::ExternalName(String::from("Rear sensor")),
+        temperature: -42,
+        raw: vec![0xCA, 0xFE],
+    };
+    let serialized = climate_control::Options::to_vec(&event).unwrap();
+    assert_eq!(
+        event,
+        climate_control::Options::from_slice(&serialized).unwrap()
+    );
+}
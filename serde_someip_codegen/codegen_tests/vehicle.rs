@@ -0,0 +1,77 @@
+// This file is @generated by serde_someip_codegen, do not edit it by hand.
+
+/// The selected gear.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(raw_type = i16)]
+pub enum Gear {
+    /// Driving backwards
+    #[someip(value = -1)]
+    Reverse,
+    #[someip(value = 0)]
+    Neutral,
+    #[someip(value = 1)]
+    Drive,
+}
+
+/// The status of the vehicle.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+pub struct VehicleStatus {
+    #[someip(min_size = 17, max_size = 17)]
+    pub vin: String,
+    pub gear: Gear,
+    #[someip(min_elements = 4, max_elements = 4)]
+    pub wheel_speeds: Vec<f32>,
+    #[someip(max_elements = 8, elements = (max_size = 64))]
+    pub drivers: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(length_field_size = 4, type_field_size = 1)]
+pub enum SettingValue {
+    #[someip(selector = 1)]
+    Number(i16),
+    #[someip(selector = 2, max_size = 64)]
+    Text(String),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+pub struct Setting {
+    #[someip(id = 1)]
+    pub key: u32,
+    #[someip(id = 2)]
+    pub value: SettingValue,
+    #[someip(id = 3, max_size = 64)]
+    pub comment: Option<String>,
+}
+
+/// Provides the vehicle status.
+pub mod vehicle_service {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true, arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+    pub struct StatusEvent {
+        pub status: VehicleStatus,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true, arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+    pub struct SpeedField {
+        pub speed: f32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true, arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+    pub struct ChangeSettingRequest {
+        pub setting: Setting,
+        pub force: u8,
+    }
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(message_wrapper = true, arrays_length_field_size = 4, strings_length_field_size = 2, alignment = 8)]
+    pub struct ChangeSettingResponse {
+        pub accepted: u8,
+    }
+}
@@ -0,0 +1,28 @@
+include!("vehicle.rs");
+
+use serde_someip::options::ExampleOptions;
+use serde_someip::SomeIpOptions;
+
+fn main() {
+    let status = vehicle_service::StatusEvent {
+        status: VehicleStatus {
+            vin: String::from("WVWZZZ1JZXW000001"),
+            gear: Gear::Reverse,
+            wheel_speeds: vec![1.0, 2.0, 3.0, 4.0],
+            drivers: vec![String::from("Alice"), String::from("Bob")],
+        },
+    };
+    let serialized = ExampleOptions::to_vec(&status).unwrap();
+    assert_eq!(status, ExampleOptions::from_slice(&serialized).unwrap());
+
+    let request = vehicle_service::ChangeSettingRequest {
+        setting: Setting {
+            key: 42,
+            value: SettingValue::Text(String::from("on")),
+            comment: None,
+        },
+        force: 1,
+    };
+    let serialized = ExampleOptions::to_vec(&request).unwrap();
+    assert_eq!(request, ExampleOptions::from_slice(&serialized).unwrap());
+}
@@ -0,0 +1,894 @@
+//! Reads the types and interfaces of AUTOSAR ARXML documents, see [generate_arxml](super::Config::generate_arxml).
+//!
+//! The following elements are supported:
+//! - `IMPLEMENTATION-DATA-TYPE`s of the categories `VALUE`, `TYPE_REFERENCE`, `STRING`, `ARRAY`, `VECTOR`,
+//!   `STRUCTURE` and `UNION`. Values with a `TEXTTABLE` compu method become enums,
+//!   structures become structs and unions become unions with the selectors `1`, `2`, ... in the order of their elements.
+//! - `SERVICE-INTERFACE`s with their events, fields and methods as well as `SENDER-RECEIVER-INTERFACE`s
+//!   and `CLIENT-SERVER-INTERFACE`s. Every interface becomes a module with a message wrapper per event (`<Event>Event`),
+//!   per field (`<Field>Field`) and per method (`<Method>Request` and `<Method>Response`).
+//!   No wrapper is generated for methods without in or out arguments, since their payload is empty.
+//! - `SOMEIP-TRANSFORMATION-PROPS` provide the length field sizes, the alignment and the union type field size.
+//!   Props that are mapped to interface elements by a `TRANSFORMATION-PROPS-TO-SERVICE-INTERFACE-ELEMENT-MAPPING`
+//!   apply to the message wrappers of those elements. If the documents contain exactly one `SOMEIP-TRANSFORMATION-PROPS`
+//!   they apply to all types.
+//! - `TLV-DATA-ID-DEFINITION`s provide the TLV ids of struct members and method arguments.
+
+use super::error::{Error, Result};
+use super::model::*;
+
+use roxmltree::{Document, Node};
+
+use std::collections::{HashMap, HashSet};
+
+type XmlNode<'a, 'input> = Node<'a, 'input>;
+
+#[inline]
+fn is(node: &XmlNode, tag: &str) -> bool {
+    node.is_element() && node.tag_name().name() == tag
+}
+
+#[inline]
+fn child<'a, 'input>(node: &XmlNode<'a, 'input>, tag: &str) -> Option<XmlNode<'a, 'input>> {
+    node.children().find(|c| is(c, tag))
+}
+
+fn children<'a, 'input: 'a>(
+    node: &XmlNode<'a, 'input>,
+    list_tag: &str,
+    tag: &'a str,
+) -> impl Iterator<Item = XmlNode<'a, 'input>> + 'a {
+    child(node, list_tag)
+        .into_iter()
+        .flat_map(move |list| list.children().filter(move |c| is(c, tag)))
+}
+
+#[inline]
+fn child_text<'a>(node: &XmlNode<'a, '_>, tag: &str) -> Option<&'a str> {
+    child(node, tag).and_then(|c| c.text()).map(str::trim)
+}
+
+#[inline]
+fn short_name<'a>(node: &XmlNode<'a, '_>) -> &'a str {
+    child_text(node, "SHORT-NAME").unwrap_or_default()
+}
+
+fn doc(node: &XmlNode) -> Option<String> {
+    let desc = child(node, "DESC")?;
+    let text = desc
+        .children()
+        .filter(|c| is(c, "L-2"))
+        .find_map(|c| c.text())?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+fn int_child<T: TryFrom<i128>>(node: &XmlNode, tag: &str) -> Result<Option<T>> {
+    match child_text(node, tag) {
+        None => Ok(None),
+        Some(text) => parse_int(text)
+            .and_then(|v| T::try_from(v).ok())
+            .map(Some)
+            .ok_or_else(|| {
+                Error::InvalidArxml(format!(
+                    "{} of {} is not a valid number: {}",
+                    tag,
+                    short_name(node),
+                    text
+                ))
+            }),
+    }
+}
+
+/// Length field sizes are given in bits in arxml, 0 means that there is no length field.
+fn length_field_size(node: &XmlNode, tag: &str) -> Result<Option<u8>> {
+    match int_child::<u32>(node, tag)? {
+        None | Some(0) => Ok(None),
+        Some(8) => Ok(Some(1)),
+        Some(16) => Ok(Some(2)),
+        Some(32) => Ok(Some(4)),
+        Some(v) => Err(Error::InvalidArxml(format!(
+            "{} of {} must be 0, 8, 16 or 32 bits, was {}",
+            tag,
+            short_name(node),
+            v
+        ))),
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct TransformationProps {
+    struct_props: StructProps,
+    union_length_field_size: Option<u8>,
+    union_type_field_size: Option<u8>,
+}
+
+impl TransformationProps {
+    fn read(node: &XmlNode) -> Result<Self> {
+        let alignment = match int_child::<u32>(node, "ALIGNMENT")? {
+            None | Some(0) => None,
+            Some(v @ (8 | 16 | 32 | 64)) => Some(v as u8),
+            Some(v) => {
+                return Err(Error::InvalidArxml(format!(
+                    "ALIGNMENT of {} must be 8, 16, 32 or 64 bits, was {}",
+                    short_name(node),
+                    v
+                )))
+            }
+        };
+        Ok(TransformationProps {
+            struct_props: StructProps {
+                length_field_size: None,
+                arrays_length_field_size: length_field_size(node, "SIZE-OF-ARRAY-LENGTH-FIELD")?,
+                structs_length_field_size: length_field_size(node, "SIZE-OF-STRUCT-LENGTH-FIELD")?,
+                strings_length_field_size: length_field_size(node, "SIZE-OF-STRING-LENGTH-FIELD")?,
+                alignment,
+            },
+            union_length_field_size: length_field_size(node, "SIZE-OF-UNION-LENGTH-FIELD")?,
+            union_type_field_size: length_field_size(node, "SIZE-OF-UNION-TYPE-SELECTOR-FIELD")?,
+        })
+    }
+}
+
+struct Reader<'a, 'input> {
+    documents: &'a [Document<'input>],
+    by_path: HashMap<String, XmlNode<'a, 'input>>,
+    tlv_ids: HashMap<String, u16>,
+    props_by_element: HashMap<String, TransformationProps>,
+    global_props: TransformationProps,
+    generated: HashSet<String>,
+    /// The paths of the type references that are currently being resolved, to detect cycles.
+    resolving: Vec<String>,
+    items: Vec<Item>,
+}
+
+/// The absolute path of an element, made of the SHORT-NAMEs of the element and its ancestors.
+fn path(node: &XmlNode) -> String {
+    let mut names: Vec<_> = node
+        .ancestors()
+        .filter_map(|a| child_text(&a, "SHORT-NAME"))
+        .collect();
+    names.reverse();
+    let mut path = String::new();
+    for name in names {
+        path.push('/');
+        path.push_str(name);
+    }
+    path
+}
+
+impl<'a, 'input> Reader<'a, 'input> {
+    /// All elements with the tag in document order.
+    fn all(&self, tag: &'a str) -> impl Iterator<Item = XmlNode<'a, 'input>> + 'a {
+        self.documents
+            .iter()
+            .flat_map(move |d| d.descendants().filter(move |n| is(n, tag)))
+    }
+
+    fn resolve(&self, reference: &str) -> Result<XmlNode<'a, 'input>> {
+        self.by_path
+            .get(reference)
+            .copied()
+            .ok_or_else(|| Error::InvalidArxml(format!("Cannot resolve reference {}", reference)))
+    }
+
+    fn read_tlv_ids(&mut self) -> Result<()> {
+        let definitions: Vec<_> = self.all("TLV-DATA-ID-DEFINITION").collect();
+        for definition in definitions {
+            let id = int_child::<u16>(&definition, "ID")?.ok_or_else(|| {
+                Error::InvalidArxml(String::from("TLV-DATA-ID-DEFINITION without an ID"))
+            })?;
+            if id > 0xFFF {
+                return Err(Error::InvalidArxml(format!(
+                    "TLV ids must be in 0..=0xFFF, was {}",
+                    id
+                )));
+            }
+            for tag in ["TLV-ARGUMENT-REF", "TLV-IMPL-RECORD-ELEMENT-REF"] {
+                if let Some(reference) = child_text(&definition, tag) {
+                    self.tlv_ids.entry(reference.to_owned()).or_insert(id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_transformation_props(&mut self) -> Result<()> {
+        let all_props: Vec<_> = self.all("SOMEIP-TRANSFORMATION-PROPS").collect();
+        if let [props] = &all_props[..] {
+            self.global_props = TransformationProps::read(props)?;
+        }
+        let mappings: Vec<_> = self
+            .all("TRANSFORMATION-PROPS-TO-SERVICE-INTERFACE-ELEMENT-MAPPING")
+            .collect();
+        for mapping in mappings {
+            let props = match child_text(&mapping, "TRANSFORMATION-PROPS-REF") {
+                Some(reference) => TransformationProps::read(&self.resolve(reference)?)?,
+                None => continue,
+            };
+            for element in children(
+                &mapping,
+                "SERVICE-INTERFACE-ELEMENT-REFS",
+                "SERVICE-INTERFACE-ELEMENT-REF",
+            ) {
+                if let Some(reference) = element.text() {
+                    self.props_by_element
+                        .insert(reference.trim().to_owned(), props.clone());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The content of the SW-DATA-DEF-PROPS of a type, elements or prototype.
+    fn def_props(node: &XmlNode<'a, 'input>, tag: &str) -> Option<XmlNode<'a, 'input>> {
+        let conditional = child(node, "SW-DATA-DEF-PROPS")
+            .and_then(|p| child(&p, "SW-DATA-DEF-PROPS-VARIANTS"))
+            .and_then(|p| child(&p, "SW-DATA-DEF-PROPS-CONDITIONAL"))?;
+        child(&conditional, tag)
+    }
+
+    fn primitive(&self, node: &XmlNode<'a, 'input>) -> Result<Primitive> {
+        let reference = Self::def_props(node, "BASE-TYPE-REF")
+            .and_then(|r| r.text())
+            .map(str::trim);
+        if let Some(base_type) = reference.and_then(|r| self.by_path.get(r)) {
+            let bits = int_child::<u32>(base_type, "BASE-TYPE-SIZE")?.unwrap_or_default();
+            let encoding = child_text(base_type, "BASE-TYPE-ENCODING").unwrap_or("NONE");
+            let primitive = match encoding {
+                "BOOLEAN" => Some(Primitive::Bool),
+                "2C" => Primitive::from_encoding(true, false, bits),
+                // someip only knows two's complement, other signed encodings cannot be represented
+                "1C" | "SM" => None,
+                "IEEE754" => Primitive::from_encoding(false, true, bits),
+                _ => Primitive::from_encoding(false, false, bits),
+            };
+            return primitive.ok_or_else(|| {
+                Error::InvalidArxml(format!(
+                    "Base type {} with encoding {} and {} bits is not supported",
+                    short_name(base_type),
+                    encoding,
+                    bits
+                ))
+            });
+        }
+        // the platform types are often defined in a document that is not part of the input
+        let name = reference
+            .and_then(|r| r.rsplit('/').next())
+            .unwrap_or_else(|| short_name(node));
+        platform_type(name).ok_or_else(|| {
+            Error::InvalidArxml(format!(
+                "Cannot determine the base type of {}",
+                short_name(node)
+            ))
+        })
+    }
+
+    /// The type of a top level IMPLEMENTATION-DATA-TYPE, structs, unions and enums are generated as items.
+    fn data_type(&mut self, node: XmlNode<'a, 'input>) -> Result<Type> {
+        let category = child_text(&node, "CATEGORY").unwrap_or_default();
+        let is_enum = self.texttable(&node)?.is_some();
+        if matches!(category, "STRUCTURE" | "UNION") || is_enum {
+            let name = short_name(&node).to_owned();
+            let path = path(&node);
+            if self.generated.insert(path) {
+                let item = self.item(&node, &name)?;
+                self.items.push(item);
+            }
+            Ok(Type::Named(name))
+        } else {
+            self.inline_type(node, short_name(&node))
+        }
+    }
+
+    fn texttable(&self, node: &XmlNode<'a, 'input>) -> Result<Option<XmlNode<'a, 'input>>> {
+        let reference = match Self::def_props(node, "COMPU-METHOD-REF").and_then(|r| r.text()) {
+            Some(reference) => reference.trim(),
+            None => return Ok(None),
+        };
+        let compu_method = self.resolve(reference)?;
+        let category = child_text(&compu_method, "CATEGORY").unwrap_or_default();
+        if !matches!(category, "TEXTTABLE" | "SCALE_LINEAR_AND_TEXTTABLE") {
+            return Ok(None);
+        }
+        // boolean texttables only name the two values
+        if self.primitive(node).is_ok_and(|p| !p.is_integer()) {
+            return Ok(None);
+        }
+        Ok(Some(compu_method))
+    }
+
+    fn item(&mut self, node: &XmlNode<'a, 'input>, name: &str) -> Result<Item> {
+        // references back to an item resolve to its name, so the item starts a new chain of references
+        let resolving = std::mem::take(&mut self.resolving);
+        let item = self.item_of_category(node, name);
+        self.resolving = resolving;
+        item
+    }
+
+    fn item_of_category(&mut self, node: &XmlNode<'a, 'input>, name: &str) -> Result<Item> {
+        if let Some(compu_method) = self.texttable(node)? {
+            return self.enumeration(node, &compu_method, name).map(Item::Enum);
+        }
+        match child_text(node, "CATEGORY").unwrap_or_default() {
+            "STRUCTURE" => self.structure(node, name).map(Item::Struct),
+            _ => self.union(node, name).map(Item::Union),
+        }
+    }
+
+    fn enumeration(
+        &mut self,
+        node: &XmlNode<'a, 'input>,
+        compu_method: &XmlNode<'a, 'input>,
+        name: &str,
+    ) -> Result<Enum> {
+        let raw_type = match child_text(node, "CATEGORY") {
+            Some("TYPE_REFERENCE") => match self.inline_type(*node, name)? {
+                Type::Primitive(p) => p,
+                _ => {
+                    return Err(Error::InvalidArxml(format!(
+                        "The enum {} must refer to an integer type",
+                        name
+                    )))
+                }
+            },
+            _ => self.primitive(node)?,
+        };
+        let scales = child(compu_method, "COMPU-INTERNAL-TO-PHYS")
+            .into_iter()
+            .flat_map(|c| children(&c, "COMPU-SCALES", "COMPU-SCALE").collect::<Vec<_>>());
+        let mut variants = Vec::new();
+        for scale in scales {
+            let text = child(&scale, "COMPU-CONST").and_then(|c| child_text(&c, "VT"));
+            let variant_name = match child_text(&scale, "SYMBOL").or(text) {
+                Some(n) => n,
+                None => continue,
+            };
+            let value = child_text(&scale, "LOWER-LIMIT")
+                .and_then(parse_int)
+                .ok_or_else(|| {
+                    Error::InvalidArxml(format!(
+                        "The value {} of enum {} has no valid LOWER-LIMIT",
+                        variant_name, name
+                    ))
+                })?;
+            variants.push(EnumVariant {
+                name: variant_name.to_owned(),
+                doc: doc(&scale),
+                value,
+            });
+        }
+        Ok(Enum {
+            name: name.to_owned(),
+            doc: doc(node),
+            raw_type,
+            variants,
+        })
+    }
+
+    fn structure(&mut self, node: &XmlNode<'a, 'input>, name: &str) -> Result<Struct> {
+        let mut fields = Vec::new();
+        for element in children(node, "SUB-ELEMENTS", "IMPLEMENTATION-DATA-TYPE-ELEMENT") {
+            let field_name = short_name(&element);
+            fields.push(Field {
+                name: field_name.to_owned(),
+                doc: doc(&element),
+                id: self.tlv_ids.get(&path(&element)).copied(),
+                optional: child_text(&element, "IS-OPTIONAL") == Some("true"),
+                field_type: self.inline_type(element, &format!("{}_{}", name, field_name))?,
+            });
+        }
+        check_fields(name, &fields, Error::InvalidArxml)?;
+        Ok(Struct {
+            name: name.to_owned(),
+            doc: doc(node),
+            message_wrapper: false,
+            props: self.global_props.struct_props.clone(),
+            fields,
+        })
+    }
+
+    fn union(&mut self, node: &XmlNode<'a, 'input>, name: &str) -> Result<Union> {
+        let mut variants = Vec::new();
+        for (i, element) in
+            children(node, "SUB-ELEMENTS", "IMPLEMENTATION-DATA-TYPE-ELEMENT").enumerate()
+        {
+            let variant_name = short_name(&element);
+            variants.push(UnionVariant {
+                name: variant_name.to_owned(),
+                doc: doc(&element),
+                selector: i as u32 + 1,
+                variant_type: self.inline_type(element, &format!("{}_{}", name, variant_name))?,
+            });
+        }
+        Ok(Union {
+            name: name.to_owned(),
+            doc: doc(node),
+            length_field_size: self.global_props.union_length_field_size,
+            type_field_size: self.global_props.union_type_field_size,
+            variants,
+        })
+    }
+
+    /// The type of an IMPLEMENTATION-DATA-TYPE or IMPLEMENTATION-DATA-TYPE-ELEMENT that is used directly,
+    /// anonymous structs and unions of elements are generated as items named after `name`.
+    fn inline_type(&mut self, node: XmlNode<'a, 'input>, name: &str) -> Result<Type> {
+        match child_text(&node, "CATEGORY").unwrap_or_default() {
+            "TYPE_REFERENCE" => {
+                let reference = Self::def_props(&node, "IMPLEMENTATION-DATA-TYPE-REF")
+                    .and_then(|r| r.text())
+                    .ok_or_else(|| {
+                        Error::InvalidArxml(format!(
+                            "The type reference {} does not refer to a type",
+                            short_name(&node)
+                        ))
+                    })?;
+                let target = self.resolve(reference.trim())?;
+                let target_path = path(&target);
+                if self.resolving.contains(&target_path) {
+                    return Err(Error::InvalidArxml(format!(
+                        "The type reference {} is defined in terms of itself",
+                        target_path
+                    )));
+                }
+                self.resolving.push(target_path);
+                let data_type = self.data_type(target);
+                self.resolving.pop();
+                data_type
+            }
+            "VALUE" => self.primitive(&node).map(Type::Primitive),
+            "STRING" => {
+                let text_props = Self::def_props(&node, "SW-TEXT-PROPS");
+                let max_size = text_props
+                    .map(|p| int_child::<usize>(&p, "SW-MAX-TEXT-SIZE"))
+                    .transpose()?
+                    .flatten()
+                    .ok_or_else(|| {
+                        Error::InvalidArxml(format!(
+                            "The string {} has no SW-MAX-TEXT-SIZE",
+                            short_name(&node)
+                        ))
+                    })?;
+                let fixed = text_props.and_then(|p| child_text(&p, "ARRAY-SIZE-SEMANTICS"))
+                    == Some("FIXED-SIZE");
+                Ok(Type::String {
+                    min_size: if fixed { max_size } else { 0 },
+                    max_size,
+                    length_field_size: None,
+                    padded: false,
+                })
+            }
+            category @ ("ARRAY" | "VECTOR") => {
+                let element = children(&node, "SUB-ELEMENTS", "IMPLEMENTATION-DATA-TYPE-ELEMENT")
+                    .next()
+                    .ok_or_else(|| {
+                        Error::InvalidArxml(format!(
+                            "The array {} has no element",
+                            short_name(&node)
+                        ))
+                    })?;
+                let max_elements =
+                    int_child::<usize>(&element, "ARRAY-SIZE")?.ok_or_else(|| {
+                        Error::InvalidArxml(format!(
+                            "The array {} has no ARRAY-SIZE",
+                            short_name(&node)
+                        ))
+                    })?;
+                let fixed = category == "ARRAY"
+                    && child_text(&element, "ARRAY-SIZE-SEMANTICS") != Some("VARIABLE-SIZE");
+                Ok(Type::Sequence {
+                    min_elements: if fixed { max_elements } else { 0 },
+                    max_elements,
+                    length_field_size: None,
+                    element_type: Box::new(self.inline_type(element, name)?),
+                })
+            }
+            "STRUCTURE" | "UNION" => {
+                let path = path(&node);
+                if self.generated.insert(path) {
+                    let item = self.item(&node, name)?;
+                    self.items.push(item);
+                }
+                Ok(Type::Named(name.to_owned()))
+            }
+            category => Err(Error::InvalidArxml(format!(
+                "The category {} of {} is not supported",
+                category,
+                short_name(&node)
+            ))),
+        }
+    }
+
+    fn typed_field(&mut self, prototype: &XmlNode<'a, 'input>) -> Result<Field> {
+        let reference = child_text(prototype, "TYPE-TREF").ok_or_else(|| {
+            Error::InvalidArxml(format!("{} has no TYPE-TREF", short_name(prototype)))
+        })?;
+        let target = self.resolve(reference)?;
+        Ok(Field {
+            name: short_name(prototype).to_owned(),
+            doc: doc(prototype),
+            id: self.tlv_ids.get(&path(prototype)).copied(),
+            optional: false,
+            field_type: self.data_type(target)?,
+        })
+    }
+
+    fn wrapper(
+        &self,
+        element: &XmlNode<'a, 'input>,
+        name: String,
+        fields: Vec<Field>,
+    ) -> Result<Item> {
+        check_fields(&name, &fields, Error::InvalidArxml)?;
+        let props = self
+            .props_by_element
+            .get(&path(element))
+            .unwrap_or(&self.global_props);
+        Ok(Item::Struct(Struct {
+            name,
+            doc: doc(element),
+            message_wrapper: true,
+            props: props.struct_props.clone(),
+            fields,
+        }))
+    }
+
+    fn interface(&mut self, node: &XmlNode<'a, 'input>) -> Result<Module> {
+        let mut items = Vec::new();
+        for list in ["EVENTS", "DATA-ELEMENTS"] {
+            for event in children(node, list, "VARIABLE-DATA-PROTOTYPE") {
+                let field = self.typed_field(&event)?;
+                let name = format!("{}_event", short_name(&event));
+                items.push(self.wrapper(&event, name, vec![field])?);
+            }
+        }
+        for field in children(node, "FIELDS", "FIELD") {
+            let data = self.typed_field(&field)?;
+            let name = format!("{}_field", short_name(&field));
+            items.push(self.wrapper(&field, name, vec![data])?);
+        }
+        for list in ["METHODS", "OPERATIONS"] {
+            for method in children(node, list, "CLIENT-SERVER-OPERATION") {
+                let mut request = Vec::new();
+                let mut response = Vec::new();
+                for argument in children(&method, "ARGUMENTS", "ARGUMENT-DATA-PROTOTYPE") {
+                    let field = self.typed_field(&argument)?;
+                    match child_text(&argument, "DIRECTION").unwrap_or("IN") {
+                        "IN" => request.push(field),
+                        "OUT" => response.push(field),
+                        _ => {
+                            request.push(field.clone());
+                            response.push(field);
+                        }
+                    }
+                }
+                let name = short_name(&method);
+                // someip has no empty structs, the payload is simply empty
+                if !request.is_empty() {
+                    items.push(self.wrapper(&method, format!("{}_request", name), request)?);
+                }
+                if !response.is_empty() && child_text(&method, "FIRE-AND-FORGET") != Some("true") {
+                    items.push(self.wrapper(&method, format!("{}_response", name), response)?);
+                }
+            }
+        }
+        Ok(Module {
+            name: short_name(node).to_owned(),
+            doc: doc(node),
+            items,
+            modules: Vec::new(),
+        })
+    }
+}
+
+fn platform_type(name: &str) -> Option<Primitive> {
+    Some(match name {
+        "boolean" | "bool" => Primitive::Bool,
+        "uint8" | "UInt8" => Primitive::U8,
+        "uint16" | "UInt16" => Primitive::U16,
+        "uint32" | "UInt32" => Primitive::U32,
+        "uint64" | "UInt64" => Primitive::U64,
+        "sint8" | "int8" | "Int8" => Primitive::I8,
+        "sint16" | "int16" | "Int16" => Primitive::I16,
+        "sint32" | "int32" | "Int32" => Primitive::I32,
+        "sint64" | "int64" | "Int64" => Primitive::I64,
+        "float32" | "Float32" => Primitive::F32,
+        "float64" | "Float64" => Primitive::F64,
+        _ => return None,
+    })
+}
+
+/// Reads all data types and interfaces of the documents.
+pub(crate) fn read(documents: &[&str]) -> Result<Module> {
+    let documents = documents
+        .iter()
+        .map(|d| Document::parse(d).map_err(|e| Error::InvalidXml(e.to_string())))
+        .collect::<Result<Vec<_>>>()?;
+    let mut by_path = HashMap::new();
+    for document in &documents {
+        for node in document.descendants().filter(|n| n.is_element()) {
+            if !is(&node, "SHORT-NAME") && child(&node, "SHORT-NAME").is_some() {
+                by_path.entry(path(&node)).or_insert(node);
+            }
+        }
+    }
+    let mut reader = Reader {
+        documents: &documents,
+        by_path,
+        tlv_ids: HashMap::new(),
+        props_by_element: HashMap::new(),
+        global_props: TransformationProps::default(),
+        generated: HashSet::new(),
+        resolving: Vec::new(),
+        items: Vec::new(),
+    };
+    reader.read_tlv_ids()?;
+    reader.read_transformation_props()?;
+
+    let data_types: Vec<_> = reader.all("IMPLEMENTATION-DATA-TYPE").collect();
+    for data_type in data_types {
+        let category = child_text(&data_type, "CATEGORY").unwrap_or_default();
+        if matches!(category, "STRUCTURE" | "UNION") || reader.texttable(&data_type)?.is_some() {
+            reader.data_type(data_type)?;
+        }
+    }
+    let mut modules = Vec::new();
+    for tag in [
+        "SERVICE-INTERFACE",
+        "SENDER-RECEIVER-INTERFACE",
+        "CLIENT-SERVER-INTERFACE",
+    ] {
+        let interfaces: Vec<_> = reader.all(tag).collect();
+        for interface in interfaces {
+            modules.push(reader.interface(&interface)?);
+        }
+    }
+    Ok(Module {
+        name: String::new(),
+        doc: None,
+        items: reader.items,
+        modules,
+    })
+}
+
+#[cfg(test)]
+use super::Config;
+
+#[cfg(test)]
+const VEHICLE: &str = include_str!("../codegen_tests/vehicle.arxml");
+
+#[cfg(test)]
+fn with_data_types(elements: &str) -> String {
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<AUTOSAR xmlns="http://autosar.org/schema/r4.0">
+  <AR-PACKAGES>
+    <AR-PACKAGE>
+      <SHORT-NAME>DataTypes</SHORT-NAME>
+      <ELEMENTS>{}</ELEMENTS>
+    </AR-PACKAGE>
+  </AR-PACKAGES>
+</AUTOSAR>"#,
+        elements
+    )
+}
+
+#[test]
+fn test_vehicle() {
+    assert_eq!(
+        include_str!("../codegen_tests/vehicle.rs"),
+        Config::new().generate_arxml(&[VEHICLE]).unwrap()
+    );
+}
+
+#[test]
+fn test_multiple_documents() {
+    let uint16 = with_data_types(
+        r#"<IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>uint16</SHORT-NAME>
+          <CATEGORY>VALUE</CATEGORY>
+        </IMPLEMENTATION-DATA-TYPE>"#,
+    );
+    let point = r#"<AUTOSAR><AR-PACKAGES><AR-PACKAGE>
+      <SHORT-NAME>Geometry</SHORT-NAME>
+      <ELEMENTS>
+        <IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Point</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>X</SHORT-NAME>
+              <CATEGORY>TYPE_REFERENCE</CATEGORY>
+              <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+                <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/uint16</IMPLEMENTATION-DATA-TYPE-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>
+      </ELEMENTS>
+    </AR-PACKAGE></AR-PACKAGES></AUTOSAR>"#;
+    let module = read(&[point, &uint16]).unwrap();
+    assert_eq!(
+        vec![Item::Struct(Struct {
+            name: String::from("Point"),
+            doc: None,
+            message_wrapper: false,
+            props: StructProps::default(),
+            fields: vec![Field {
+                name: String::from("X"),
+                doc: None,
+                id: None,
+                optional: false,
+                field_type: Type::Primitive(Primitive::U16),
+            }],
+        })],
+        module.items
+    );
+    assert!(matches!(read(&[point]), Err(Error::InvalidArxml(_))));
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(read(&["<AUTOSAR>"]), Err(Error::InvalidXml(_))));
+
+    let string_without_size = with_data_types(
+        r#"<IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Wrapper</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>Name</SHORT-NAME>
+              <CATEGORY>STRING</CATEGORY>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>"#,
+    );
+    assert!(matches!(
+        read(&[&string_without_size]),
+        Err(Error::InvalidArxml(_))
+    ));
+
+    let optional_without_tlv = with_data_types(
+        r#"<IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Wrapper</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>Flag</SHORT-NAME>
+              <CATEGORY>VALUE</CATEGORY>
+              <IS-OPTIONAL>true</IS-OPTIONAL>
+              <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+                <BASE-TYPE-REF>/AUTOSAR_Platform/BaseTypes/boolean</BASE-TYPE-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>"#,
+    );
+    assert!(matches!(
+        read(&[&optional_without_tlv]),
+        Err(Error::InvalidArxml(_))
+    ));
+
+    let bad_props = with_data_types(
+        r#"<SOMEIP-TRANSFORMATION-PROPS>
+          <SHORT-NAME>Props</SHORT-NAME>
+          <SIZE-OF-ARRAY-LENGTH-FIELD>24</SIZE-OF-ARRAY-LENGTH-FIELD>
+        </SOMEIP-TRANSFORMATION-PROPS>"#,
+    );
+    assert!(matches!(read(&[&bad_props]), Err(Error::InvalidArxml(_))));
+
+    let sign_magnitude = with_data_types(
+        r#"<SW-BASE-TYPE>
+          <SHORT-NAME>SInt8</SHORT-NAME>
+          <BASE-TYPE-SIZE>8</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>SM</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+        <IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Wrapper</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>Offset</SHORT-NAME>
+              <CATEGORY>VALUE</CATEGORY>
+              <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+                <BASE-TYPE-REF>/DataTypes/SInt8</BASE-TYPE-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>"#,
+    );
+    assert!(matches!(
+        read(&[&sign_magnitude]),
+        Err(Error::InvalidArxml(_))
+    ));
+    assert!(
+        read(&[&sign_magnitude.replace("<BASE-TYPE-ENCODING>SM", "<BASE-TYPE-ENCODING>2C")])
+            .is_ok()
+    );
+
+    let reference_cycle = with_data_types(
+        r#"<IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>A</SHORT-NAME>
+          <CATEGORY>TYPE_REFERENCE</CATEGORY>
+          <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+            <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/B</IMPLEMENTATION-DATA-TYPE-REF>
+          </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+        </IMPLEMENTATION-DATA-TYPE>
+        <IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>B</SHORT-NAME>
+          <CATEGORY>TYPE_REFERENCE</CATEGORY>
+          <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+            <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/A</IMPLEMENTATION-DATA-TYPE-REF>
+          </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+        </IMPLEMENTATION-DATA-TYPE>
+        <IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Wrapper</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>Value</SHORT-NAME>
+              <CATEGORY>TYPE_REFERENCE</CATEGORY>
+              <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+                <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/A</IMPLEMENTATION-DATA-TYPE-REF>
+              </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>"#,
+    );
+    assert!(matches!(
+        read(&[&reference_cycle]),
+        Err(Error::InvalidArxml(_))
+    ));
+}
+
+#[test]
+fn test_recursive_reference() {
+    // a struct may contain itself through a vector and a type reference
+    let tree = with_data_types(
+        r#"<IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Node</SHORT-NAME>
+          <CATEGORY>TYPE_REFERENCE</CATEGORY>
+          <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+            <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/Tree</IMPLEMENTATION-DATA-TYPE-REF>
+          </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+        </IMPLEMENTATION-DATA-TYPE>
+        <IMPLEMENTATION-DATA-TYPE>
+          <SHORT-NAME>Tree</SHORT-NAME>
+          <CATEGORY>STRUCTURE</CATEGORY>
+          <SUB-ELEMENTS>
+            <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              <SHORT-NAME>Children</SHORT-NAME>
+              <CATEGORY>VECTOR</CATEGORY>
+              <SUB-ELEMENTS>
+                <IMPLEMENTATION-DATA-TYPE-ELEMENT>
+                  <SHORT-NAME>Child</SHORT-NAME>
+                  <CATEGORY>TYPE_REFERENCE</CATEGORY>
+                  <ARRAY-SIZE>4</ARRAY-SIZE>
+                  <SW-DATA-DEF-PROPS><SW-DATA-DEF-PROPS-VARIANTS><SW-DATA-DEF-PROPS-CONDITIONAL>
+                    <IMPLEMENTATION-DATA-TYPE-REF>/DataTypes/Node</IMPLEMENTATION-DATA-TYPE-REF>
+                  </SW-DATA-DEF-PROPS-CONDITIONAL></SW-DATA-DEF-PROPS-VARIANTS></SW-DATA-DEF-PROPS>
+                </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+              </SUB-ELEMENTS>
+            </IMPLEMENTATION-DATA-TYPE-ELEMENT>
+          </SUB-ELEMENTS>
+        </IMPLEMENTATION-DATA-TYPE>
+        <SERVICE-INTERFACE>
+          <SHORT-NAME>Forest</SHORT-NAME>
+          <EVENTS>
+            <VARIABLE-DATA-PROTOTYPE>
+              <SHORT-NAME>Grown</SHORT-NAME>
+              <TYPE-TREF>/DataTypes/Node</TYPE-TREF>
+            </VARIABLE-DATA-PROTOTYPE>
+          </EVENTS>
+        </SERVICE-INTERFACE>"#,
+    );
+    let module = read(&[&tree]).unwrap();
+    assert_eq!(1, module.items.len());
+    assert_eq!(1, module.modules.len());
+}
@@ -0,0 +1,43 @@
+//! This module contains the [Error] type used by the code generator.
+
+use std::fmt::{Display, Formatter};
+
+/// The error type used by the code generator.
+#[derive(Debug)]
+pub enum Error {
+    /// A document is not well formed xml.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidXml(String),
+    /// A document is well formed but does not describe types that can be generated,
+    /// for example because a string has no maximum size or a reference cannot be resolved.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidArxml(String),
+    /// A Franca IDL or deployment document cannot be parsed or does not describe types that can be generated,
+    /// for example because an array has no maximum length or a type cannot be resolved.
+    /// The [String] parameter gives more info as to what went wrong.
+    InvalidFranca(String),
+    /// Reading an input or writing the output failed.
+    IoError(std::io::Error),
+}
+
+/// The result type used by the code generator.
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Error::InvalidXml(v) => formatter.write_fmt(format_args!("Invalid xml: {}", v)),
+            Error::InvalidArxml(v) => formatter.write_fmt(format_args!("Invalid arxml: {}", v)),
+            Error::InvalidFranca(v) => formatter.write_fmt(format_args!("Invalid franca: {}", v)),
+            Error::IoError(v) => formatter.write_fmt(format_args!("Io Error: {}", v)),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IoError(e)
+    }
+}
@@ -0,0 +1,1620 @@
+//! Reads the types and interfaces of Franca IDL models and their SOME/IP deployments,
+//! see [generate_franca](super::Config::generate_franca).
+//!
+//! The following parts of `.fidl` files are supported:
+//! - `typeCollection`s and the types of `interface`s: `typedef`, `array`, `enumeration`, `struct`, `union` and `map`,
+//!   including `extends` for enumerations, structs and unions as well as implicit arrays like `UInt8[]`.
+//!   Enumerators without a value continue the numbering of the previous enumerator.
+//!   Unions use the selectors `1`, `2`, ... in the order of their members.
+//! - `interface`s with their `attribute`s, `method`s and `broadcast`s. Every interface becomes a module with
+//!   a message wrapper per attribute (`<Attribute>Field`), per broadcast (`<Broadcast>Event`) and per method
+//!   (`<Method>Request` and `<Method>Response`). No wrapper is generated for methods without in or out arguments.
+//!   Every module also contains an `Options` type implementing `SomeIpOptions` for the deployment of the interface.
+//!
+//! `.fdepl` files provide the deployment properties of `define ... for interface` and `define ... for typeCollection`
+//! blocks, which must name the type collection or interface by its fully qualified name like `org.example.Types`.
+//! Properties directly in such a block are the defaults for all elements of the block.
+//! The following properties are supported:
+//! - `SomeIpStringLength` (required for strings), `SomeIpStringLengthWidth` and `SomeIpStringEncoding`
+//!   (`utf8`, `utf16le` or `utf16be`). The string encoding is an option of the whole interface,
+//!   so all strings of an interface must use the same encoding.
+//! - `SomeIpArrayMaxLength` (required for arrays), `SomeIpArrayMinLength` and `SomeIpArrayLengthWidth`,
+//!   likewise `SomeIpByteBufferMaxLength` and `SomeIpByteBufferMinLength` for byte buffers
+//!   and `SomeIpMapMaxLength`, `SomeIpMapMinLength` and `SomeIpMapLengthWidth` for maps.
+//!   A length width of `0` means that the array or string has a fixed size.
+//! - `SomeIpStructLengthWidth`, `SomeIpUnionLengthWidth` and `SomeIpUnionTypeWidth`.
+//! - `EnumBackingType` (for example `UInt16`) or `SomeIpEnumWidth` in bytes, enumerations default to `UInt8`.
+//! - `SomeIpTlvId` on struct members and arguments for TLV encoded structs and methods.
+//!
+//! All widths are given in bytes. Strings are encoded with a BOM and a terminator as required by someip.
+
+use super::error::{Error, Result};
+use super::model::*;
+
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Str(String),
+    Punct(char),
+    Doc(String),
+}
+
+#[inline]
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '*')
+}
+
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>> {
+    let chars: Vec<char> = input.chars().collect();
+    let starts_with = |i: usize, s: &str| {
+        s.chars()
+            .enumerate()
+            .all(|(j, c)| chars.get(i + j) == Some(&c))
+    };
+    let find = |from: usize, s: &str| (from..chars.len()).find(|&i| starts_with(i, s));
+    let lines = |from: usize, to: usize| chars[from..to].iter().filter(|&&c| c == '\n').count();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            if c == '\n' {
+                line += 1;
+            }
+            i += 1;
+        } else if starts_with(i, "//") {
+            i = find(i, "\n").unwrap_or(chars.len());
+        } else if starts_with(i, "/*") || starts_with(i, "<**") {
+            let (end, len) = if c == '/' { ("*/", 2) } else { ("**>", 3) };
+            let stop = find(i + len, end).ok_or_else(|| {
+                Error::InvalidFranca(format!("line {}: Unterminated comment", line))
+            })?;
+            if c == '<' {
+                let text: String = chars[i + 3..stop].iter().collect();
+                tokens.push((Token::Doc(text), line));
+            }
+            line += lines(i, stop);
+            i = stop + end.len();
+        } else if c == '"' {
+            let stop = find(i + 1, "\"").ok_or_else(|| {
+                Error::InvalidFranca(format!("line {}: Unterminated string", line))
+            })?;
+            tokens.push((Token::Str(chars[i + 1..stop].iter().collect()), line));
+            line += lines(i, stop);
+            i = stop + 1;
+        } else if is_word_char(c)
+            || (c == '-' && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit()))
+        {
+            let start = i;
+            i += 1;
+            while i < chars.len() && is_word_char(chars[i]) {
+                i += 1;
+            }
+            tokens.push((Token::Word(chars[start..i].iter().collect()), line));
+        } else {
+            tokens.push((Token::Punct(c), line));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// Franca uses structured comments like `<** @description: The speed in km/h **>`.
+fn doc_text(comment: &str) -> Option<String> {
+    let text = match comment.find("@description") {
+        Some(start) => {
+            let text = comment[start + "@description".len()..].trim_start();
+            let text = text.strip_prefix(':').unwrap_or(text);
+            match text.find(" @").or_else(|| text.find("\n@")) {
+                Some(end) => &text[..end],
+                None => text,
+            }
+        }
+        None => comment,
+    };
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    doc: Option<String>,
+}
+
+impl Parser {
+    fn new(input: &str) -> Result<Self> {
+        Ok(Parser {
+            tokens: tokenize(input)?,
+            pos: 0,
+            doc: None,
+        })
+    }
+
+    fn error<T>(&self, message: impl AsRef<str>) -> Result<T> {
+        let line = self
+            .tokens
+            .get(self.pos)
+            .or_else(|| self.tokens.last())
+            .map_or(0, |t| t.1);
+        Err(Error::InvalidFranca(format!(
+            "line {}: {}",
+            line,
+            message.as_ref()
+        )))
+    }
+
+    /// Returns the next token that is not a doc comment, doc comments are remembered for [take_doc](Self::take_doc).
+    fn peek(&mut self) -> Option<&Token> {
+        while let Some((Token::Doc(text), _)) = self.tokens.get(self.pos) {
+            self.doc = doc_text(text);
+            self.pos += 1;
+        }
+        self.tokens.get(self.pos).map(|t| &t.0)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.peek()?;
+        self.pos += 1;
+        Some(self.tokens[self.pos - 1].0.clone())
+    }
+
+    fn take_doc(&mut self) -> Option<String> {
+        self.peek();
+        self.doc.take()
+    }
+
+    fn is_word(&mut self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w == word)
+    }
+
+    fn is_punct(&mut self, punct: char) -> bool {
+        self.peek() == Some(&Token::Punct(punct))
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        let is = self.is_word(word);
+        if is {
+            self.pos += 1;
+        }
+        is
+    }
+
+    fn eat_punct(&mut self, punct: char) -> bool {
+        let is = self.is_punct(punct);
+        if is {
+            self.pos += 1;
+        }
+        is
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<()> {
+        if self.eat_word(word) {
+            Ok(())
+        } else {
+            self.error(format!("Expected {}", word))
+        }
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<()> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            self.error(format!("Expected {}", punct))
+        }
+    }
+
+    fn word(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(Token::Word(w)) => {
+                let w = w.clone();
+                self.pos += 1;
+                Ok(w)
+            }
+            _ => self.error("Expected a name"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Str(s)) => Ok(s),
+            _ => self.error("Expected a string"),
+        }
+    }
+
+    /// Skips everything up to the `}` that matches an already consumed `{`.
+    fn skip_block(&mut self) -> Result<()> {
+        let mut depth = 1;
+        while depth > 0 {
+            match self.next() {
+                Some(Token::Punct('{')) => depth += 1,
+                Some(Token::Punct('}')) => depth -= 1,
+                Some(_) => {}
+                None => return self.error("Unexpected end of file"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Skips everything up to and including the next `{` and the block it opens.
+    fn skip_definition(&mut self) -> Result<()> {
+        loop {
+            match self.next() {
+                Some(Token::Punct('{')) => return self.skip_block(),
+                Some(_) => {}
+                None => return self.error("Unexpected end of file"),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TypeRef {
+    name: String,
+    array: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Member {
+    type_ref: TypeRef,
+    name: String,
+    doc: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Enumerator {
+    name: String,
+    doc: Option<String>,
+    value: Option<i128>,
+}
+
+#[derive(Debug, Clone)]
+enum Definition {
+    Typedef(TypeRef),
+    Array(TypeRef),
+    Map(TypeRef, TypeRef),
+    Enumeration {
+        extends: Option<String>,
+        enumerators: Vec<Enumerator>,
+    },
+    Struct {
+        extends: Option<String>,
+        members: Vec<Member>,
+    },
+    Union {
+        extends: Option<String>,
+        members: Vec<Member>,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct TypeDef {
+    owner: String,
+    name: String,
+    doc: Option<String>,
+    definition: Definition,
+}
+
+#[derive(Debug, Clone)]
+struct Method {
+    name: String,
+    doc: Option<String>,
+    fire_and_forget: bool,
+    ins: Vec<Member>,
+    outs: Vec<Member>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Interface {
+    fqn: String,
+    name: String,
+    doc: Option<String>,
+    attributes: Vec<Member>,
+    methods: Vec<Method>,
+    broadcasts: Vec<Method>,
+}
+
+#[derive(Debug, Default)]
+struct Fidl {
+    types: Vec<TypeDef>,
+    interfaces: Vec<Interface>,
+}
+
+fn qualify(package: &str, name: &str) -> String {
+    if package.is_empty() || name.is_empty() {
+        format!("{}{}", package, name)
+    } else {
+        format!("{}.{}", package, name)
+    }
+}
+
+impl Parser {
+    fn type_ref(&mut self) -> Result<TypeRef> {
+        let name = self.word()?;
+        let array = self.eat_punct('[');
+        if array {
+            self.expect_punct(']')?;
+        }
+        Ok(TypeRef { name, array })
+    }
+
+    fn extends(&mut self) -> Result<Option<String>> {
+        if self.eat_word("extends") {
+            self.word().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn members(&mut self) -> Result<Vec<Member>> {
+        self.expect_punct('{')?;
+        let mut members = Vec::new();
+        while !self.eat_punct('}') {
+            let doc = self.take_doc();
+            let type_ref = self.type_ref()?;
+            members.push(Member {
+                type_ref,
+                name: self.word()?,
+                doc,
+            });
+        }
+        Ok(members)
+    }
+
+    fn enumerators(&mut self) -> Result<Vec<Enumerator>> {
+        self.expect_punct('{')?;
+        let mut enumerators = Vec::new();
+        while !self.eat_punct('}') {
+            let doc = self.take_doc();
+            let name = self.word()?;
+            let value = if self.eat_punct('=') {
+                let text = match self.next() {
+                    Some(Token::Word(w)) | Some(Token::Str(w)) => w,
+                    _ => return self.error("Expected the value of an enumerator"),
+                };
+                match parse_int(&text) {
+                    Some(v) => Some(v),
+                    None => return self.error(format!("Invalid value {} of {}", text, name)),
+                }
+            } else {
+                None
+            };
+            self.eat_punct(',');
+            enumerators.push(Enumerator { name, doc, value });
+        }
+        Ok(enumerators)
+    }
+
+    fn method(&mut self, broadcast: bool) -> Result<Method> {
+        let name = self.word()?;
+        if self.eat_punct(':') {
+            self.word()?;
+        }
+        let fire_and_forget = self.eat_word("fireAndForget");
+        self.eat_word("selective");
+        let mut method = Method {
+            name,
+            doc: None,
+            fire_and_forget,
+            ins: Vec::new(),
+            outs: Vec::new(),
+        };
+        self.expect_punct('{')?;
+        loop {
+            if self.eat_punct('}') {
+                return Ok(method);
+            } else if !broadcast && self.eat_word("in") {
+                method.ins = self.members()?;
+            } else if self.eat_word("out") {
+                method.outs = self.members()?;
+            } else if !broadcast && self.eat_word("error") {
+                // application errors are not part of the payload
+                if self.eat_punct('{') {
+                    self.skip_block()?;
+                } else {
+                    self.word()?;
+                }
+            } else {
+                return self.error(format!("Unexpected content in {}", method.name));
+            }
+        }
+    }
+
+    /// Parses the body of a type collection or interface after the opening `{`.
+    fn body(
+        &mut self,
+        owner: &str,
+        types: &mut Vec<TypeDef>,
+        mut interface: Option<&mut Interface>,
+    ) -> Result<()> {
+        loop {
+            let doc = self.take_doc();
+            let keyword = match self.next() {
+                Some(Token::Punct('}')) => return Ok(()),
+                Some(Token::Word(w)) => w,
+                _ => return self.error("Expected a definition"),
+            };
+            let definition = match keyword.as_str() {
+                "version" | "contract" => {
+                    self.expect_punct('{')?;
+                    self.skip_block()?;
+                    continue;
+                }
+                "public" => {
+                    self.doc = doc;
+                    continue;
+                }
+                "const" => {
+                    self.type_ref()?;
+                    self.word()?;
+                    self.expect_punct('=')?;
+                    if self.eat_punct('{') {
+                        self.skip_block()?;
+                    } else {
+                        self.next();
+                    }
+                    continue;
+                }
+                "attribute" | "method" | "broadcast" => {
+                    let interface = match interface.as_deref_mut() {
+                        Some(i) => i,
+                        None => return self.error(format!("{} outside of an interface", keyword)),
+                    };
+                    match keyword.as_str() {
+                        "attribute" => {
+                            let type_ref = self.type_ref()?;
+                            let name = self.word()?;
+                            while self.eat_word("readonly")
+                                || self.eat_word("noSubscriptions")
+                                || self.eat_word("noRead")
+                            {}
+                            interface.attributes.push(Member {
+                                type_ref,
+                                name,
+                                doc,
+                            });
+                        }
+                        "method" => {
+                            let method = self.method(false)?;
+                            interface.methods.push(Method { doc, ..method });
+                        }
+                        _ => {
+                            let broadcast = self.method(true)?;
+                            interface.broadcasts.push(Method { doc, ..broadcast });
+                        }
+                    }
+                    continue;
+                }
+                "typedef" => {
+                    let name = self.word()?;
+                    self.expect_word("is")?;
+                    (name, Definition::Typedef(self.type_ref()?))
+                }
+                "array" => {
+                    let name = self.word()?;
+                    self.expect_word("of")?;
+                    (name, Definition::Array(self.type_ref()?))
+                }
+                "map" => {
+                    let name = self.word()?;
+                    self.expect_punct('{')?;
+                    let key = self.type_ref()?;
+                    self.expect_word("to")?;
+                    let value = self.type_ref()?;
+                    self.expect_punct('}')?;
+                    (name, Definition::Map(key, value))
+                }
+                "enumeration" => {
+                    let name = self.word()?;
+                    let extends = self.extends()?;
+                    let enumerators = self.enumerators()?;
+                    (
+                        name,
+                        Definition::Enumeration {
+                            extends,
+                            enumerators,
+                        },
+                    )
+                }
+                "struct" => {
+                    let name = self.word()?;
+                    let extends = self.extends()?;
+                    if self.is_word("polymorphic") {
+                        return self
+                            .error(format!("The polymorphic struct {} is not supported", name));
+                    }
+                    let members = self.members()?;
+                    (name, Definition::Struct { extends, members })
+                }
+                "union" => {
+                    let name = self.word()?;
+                    let extends = self.extends()?;
+                    let members = self.members()?;
+                    (name, Definition::Union { extends, members })
+                }
+                _ => return self.error(format!("Unexpected {}", keyword)),
+            };
+            types.push(TypeDef {
+                owner: owner.to_owned(),
+                name: definition.0,
+                doc,
+                definition: definition.1,
+            });
+        }
+    }
+
+    fn fidl(&mut self, fidl: &mut Fidl) -> Result<()> {
+        let package = if self.eat_word("package") {
+            self.word()?
+        } else {
+            String::new()
+        };
+        loop {
+            let doc = self.take_doc();
+            match self.next() {
+                None => return Ok(()),
+                Some(Token::Word(w)) if w == "import" => {
+                    if !self.eat_word("model") {
+                        self.word()?;
+                        self.expect_word("from")?;
+                    }
+                    self.string()?;
+                }
+                Some(Token::Word(w)) if w == "typeCollection" => {
+                    let name = if self.is_punct('{') {
+                        String::new()
+                    } else {
+                        self.word()?
+                    };
+                    self.expect_punct('{')?;
+                    self.body(&qualify(&package, &name), &mut fidl.types, None)?;
+                }
+                Some(Token::Word(w)) if w == "interface" => {
+                    let name = self.word()?;
+                    // extends and manages are not needed for the types
+                    while !self.eat_punct('{') {
+                        if self.next().is_none() {
+                            return self.error("Unexpected end of file");
+                        }
+                    }
+                    let mut interface = Interface {
+                        fqn: qualify(&package, &name),
+                        name,
+                        doc,
+                        ..Default::default()
+                    };
+                    self.body(
+                        &interface.fqn.clone(),
+                        &mut fidl.types,
+                        Some(&mut interface),
+                    )?;
+                    fidl.interfaces.push(interface);
+                }
+                _ => return self.error("Expected typeCollection or interface"),
+            }
+        }
+    }
+}
+
+/// The deployment properties of an element and its children.
+#[derive(Debug, Default)]
+struct Deployment {
+    props: HashMap<String, String>,
+    children: HashMap<String, Deployment>,
+}
+
+impl Parser {
+    /// Parses the body of a deployment after the opening `{`.
+    fn deployment(&mut self) -> Result<Deployment> {
+        let mut deployment = Deployment::default();
+        loop {
+            let mut name = match self.next() {
+                Some(Token::Punct('}')) => return Ok(deployment),
+                Some(Token::Word(w)) => w,
+                _ => return self.error("Expected a property or an element"),
+            };
+            if self.eat_punct('=') {
+                let value = if self.eat_punct('{') {
+                    let mut values = Vec::new();
+                    while !self.eat_punct('}') {
+                        match self.next() {
+                            Some(Token::Word(v)) | Some(Token::Str(v)) => values.push(v),
+                            Some(Token::Punct(',')) => {}
+                            _ => return self.error(format!("Invalid value of {}", name)),
+                        }
+                    }
+                    values.join(",")
+                } else {
+                    match self.next() {
+                        Some(Token::Word(v)) | Some(Token::Str(v)) => v,
+                        _ => return self.error(format!("Invalid value of {}", name)),
+                    }
+                };
+                deployment.props.insert(name, value);
+            } else if name == "use" {
+                self.word()?;
+            } else {
+                // for example `struct Point {`, `method foo {` or `in {`
+                while let Some(Token::Word(w)) = self.peek() {
+                    name = w.clone();
+                    self.pos += 1;
+                }
+                self.expect_punct('{')?;
+                let child = self.deployment()?;
+                deployment.children.insert(name, child);
+            }
+        }
+    }
+
+    fn fdepl(&mut self, deployments: &mut HashMap<String, Deployment>) -> Result<()> {
+        loop {
+            match self.next() {
+                None => return Ok(()),
+                Some(Token::Word(w)) if w == "import" => {
+                    self.string()?;
+                }
+                Some(Token::Word(w)) if w == "package" => {
+                    self.word()?;
+                }
+                Some(Token::Word(w)) if w == "define" => {
+                    self.word()?;
+                    self.expect_word("for")?;
+                    let kind = self.word()?;
+                    if kind == "interface" || kind == "typeCollection" {
+                        let fqn = self.word()?;
+                        self.expect_punct('{')?;
+                        let deployment = self.deployment()?;
+                        deployments.insert(fqn, deployment);
+                    } else {
+                        self.skip_definition()?;
+                    }
+                }
+                Some(Token::Word(w)) if w == "specification" => self.skip_definition()?,
+                _ => return self.error("Expected define"),
+            }
+        }
+    }
+}
+
+/// The deployments that apply to a use of a type, the first one that has a property wins.
+/// Properties of arrays, byte buffers and maps are only taken from `deployments[sized_from..]`,
+/// so that the element types of an array do not pick up the length of the array.
+#[derive(Clone)]
+struct Props<'d> {
+    deployments: Vec<&'d Deployment>,
+    sized_from: usize,
+}
+
+impl<'d> Props<'d> {
+    fn new(deployments: impl IntoIterator<Item = Option<&'d Deployment>>) -> Self {
+        Props {
+            deployments: deployments.into_iter().flatten().collect(),
+            sized_from: 0,
+        }
+    }
+
+    fn with(&self, deployments: impl IntoIterator<Item = Option<&'d Deployment>>) -> Self {
+        let mut props = self.clone();
+        props.deployments.extend(deployments.into_iter().flatten());
+        props
+    }
+
+    fn elements(&self, deployments: impl IntoIterator<Item = Option<&'d Deployment>>) -> Self {
+        let mut props = self.with(deployments);
+        props.sized_from = self.deployments.len();
+        props
+    }
+
+    fn get(&self, key: &str) -> Option<&'d str> {
+        let sized = ["SomeIpArray", "SomeIpByteBuffer", "SomeIpMap"]
+            .iter()
+            .any(|p| key.starts_with(p));
+        let from = if sized { self.sized_from } else { 0 };
+        self.deployments[from.min(self.deployments.len())..]
+            .iter()
+            .find_map(|d| d.props.get(key))
+            .map(String::as_str)
+    }
+
+    fn int(&self, key: &str) -> Result<Option<i128>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(v) => parse_int(v).map(Some).ok_or_else(|| {
+                Error::InvalidFranca(format!("{} must be a number, was {}", key, v))
+            }),
+        }
+    }
+
+    fn size(&self, key: &str) -> Result<Option<usize>> {
+        self.int(key)?
+            .map(|v| {
+                usize::try_from(v).map_err(|_| {
+                    Error::InvalidFranca(format!("{} must not be negative, was {}", key, v))
+                })
+            })
+            .transpose()
+    }
+
+    /// Widths are given in bytes, `0` means that there is no length field.
+    fn width(&self, key: &str) -> Result<Option<u8>> {
+        match self.int(key)? {
+            None => Ok(None),
+            Some(v @ (0 | 1 | 2 | 4)) => Ok(Some(v as u8)),
+            Some(v) => Err(Error::InvalidFranca(format!(
+                "{} must be 0, 1, 2 or 4, was {}",
+                key, v
+            ))),
+        }
+    }
+}
+
+fn string_encoding(value: &str) -> Result<StringEncoding> {
+    match value {
+        "utf8" => Ok(StringEncoding::Utf8),
+        "utf16le" => Ok(StringEncoding::Utf16Le),
+        "utf16be" => Ok(StringEncoding::Utf16Be),
+        _ => Err(Error::InvalidFranca(format!(
+            "The string encoding {} is not supported",
+            value
+        ))),
+    }
+}
+
+fn collect_encodings(deployment: &Deployment, encodings: &mut Vec<String>) {
+    if let Some(encoding) = deployment.props.get("SomeIpStringEncoding") {
+        if !encodings.contains(encoding) {
+            encodings.push(encoding.clone());
+        }
+    }
+    for child in deployment.children.values() {
+        collect_encodings(child, encodings);
+    }
+}
+
+fn primitive(name: &str) -> Option<Primitive> {
+    Some(match name {
+        "Boolean" => Primitive::Bool,
+        "UInt8" => Primitive::U8,
+        "UInt16" => Primitive::U16,
+        "UInt32" => Primitive::U32,
+        "UInt64" => Primitive::U64,
+        "Int8" => Primitive::I8,
+        "Int16" => Primitive::I16,
+        "Int32" => Primitive::I32,
+        "Int64" => Primitive::I64,
+        "Float" => Primitive::F32,
+        "Double" => Primitive::F64,
+        _ => return None,
+    })
+}
+
+struct Resolver<'f> {
+    fidl: &'f Fidl,
+    deployments: &'f HashMap<String, Deployment>,
+    generated: HashSet<(String, String)>,
+    /// The owners and names of the types that are currently being resolved, to detect cycles.
+    resolving: Vec<(String, String)>,
+    items: HashMap<String, Vec<Item>>,
+}
+
+impl<'f> Resolver<'f> {
+    /// The `define` block of a type collection or interface, blocks are matched by their fully qualified name.
+    fn block(&self, owner: &str) -> Option<&'f Deployment> {
+        self.deployments.get(owner)
+    }
+
+    fn type_deployment(&self, type_def: &TypeDef) -> Option<&'f Deployment> {
+        self.block(&type_def.owner)?.children.get(&type_def.name)
+    }
+
+    /// Finds a type by its plain or qualified name, types of the `owner` are preferred.
+    fn find(&self, name: &str, owner: &str) -> Result<&'f TypeDef> {
+        let fidl = self.fidl;
+        // references may be qualified partially, for example `Types.Point` or `a.b.Types.Point`
+        let matches = |t: &&TypeDef| {
+            let qualified = qualify(&t.owner, &t.name);
+            qualified == name || qualified.ends_with(&format!(".{}", name))
+        };
+        fidl.types
+            .iter()
+            .filter(matches)
+            .find(|t| t.owner == owner)
+            .or_else(|| fidl.types.iter().find(matches))
+            .ok_or_else(|| Error::InvalidFranca(format!("Cannot resolve the type {}", name)))
+    }
+
+    fn sized(
+        &self,
+        props: &Props,
+        kind: &str,
+        what: &str,
+        width_key: &str,
+    ) -> Result<(usize, usize, Option<u8>)> {
+        let max = props
+            .size(&format!("SomeIp{}MaxLength", kind))?
+            .ok_or_else(|| {
+                Error::InvalidFranca(format!("{} requires SomeIp{}MaxLength", what, kind))
+            })?;
+        let min = props
+            .size(&format!("SomeIp{}MinLength", kind))?
+            .unwrap_or(0);
+        Ok(match props.width(width_key)? {
+            Some(0) => (max, max, None),
+            width => (min, max, width),
+        })
+    }
+
+    fn use_type(
+        &mut self,
+        type_ref: &TypeRef,
+        props: &Props<'f>,
+        owner: &str,
+        what: &str,
+    ) -> Result<Type> {
+        if type_ref.array {
+            let element = TypeRef {
+                name: type_ref.name.clone(),
+                array: false,
+            };
+            let element_type = self.use_type(&element, &props.elements([]), owner, what)?;
+            let (min_elements, max_elements, length_field_size) =
+                self.sized(props, "Array", what, "SomeIpArrayLengthWidth")?;
+            return Ok(Type::Sequence {
+                min_elements,
+                max_elements,
+                length_field_size,
+                element_type: Box::new(element_type),
+            });
+        }
+        if let Some(p) = primitive(&type_ref.name) {
+            return Ok(Type::Primitive(p));
+        }
+        match type_ref.name.as_str() {
+            "String" => {
+                let max_size = props.size("SomeIpStringLength")?.ok_or_else(|| {
+                    Error::InvalidFranca(format!("{} requires SomeIpStringLength", what))
+                })?;
+                return Ok(match props.width("SomeIpStringLengthWidth")? {
+                    // fixed length strings are padded to their length
+                    Some(0) => Type::String {
+                        min_size: max_size,
+                        max_size,
+                        length_field_size: None,
+                        padded: true,
+                    },
+                    width => Type::String {
+                        min_size: 0,
+                        max_size,
+                        length_field_size: width,
+                        padded: false,
+                    },
+                });
+            }
+            "ByteBuffer" => {
+                let (min_elements, max_elements, length_field_size) =
+                    self.sized(props, "ByteBuffer", what, "SomeIpArrayLengthWidth")?;
+                return Ok(Type::Sequence {
+                    min_elements,
+                    max_elements,
+                    length_field_size,
+                    element_type: Box::new(Type::Primitive(Primitive::U8)),
+                });
+            }
+            _ => {}
+        }
+        let type_def = self.find(&type_ref.name, owner)?;
+        let key = (type_def.owner.clone(), type_def.name.clone());
+        if self.resolving.contains(&key) {
+            return Err(Error::InvalidFranca(format!(
+                "The type {} is defined in terms of itself",
+                type_def.name
+            )));
+        }
+        self.resolving.push(key);
+        let used = self.use_definition(type_def, props, what);
+        self.resolving.pop();
+        used
+    }
+
+    fn use_definition(
+        &mut self,
+        type_def: &'f TypeDef,
+        props: &Props<'f>,
+        what: &str,
+    ) -> Result<Type> {
+        let props = props.with([self.type_deployment(type_def), self.block(&type_def.owner)]);
+        match &type_def.definition {
+            Definition::Typedef(target) => self.use_type(target, &props, &type_def.owner, what),
+            Definition::Array(element) => {
+                let element_type =
+                    self.use_type(element, &props.elements([]), &type_def.owner, what)?;
+                let (min_elements, max_elements, length_field_size) =
+                    self.sized(&props, "Array", what, "SomeIpArrayLengthWidth")?;
+                Ok(Type::Sequence {
+                    min_elements,
+                    max_elements,
+                    length_field_size,
+                    element_type: Box::new(element_type),
+                })
+            }
+            Definition::Map(key, value) => {
+                let key_type = self.use_type(key, &props.elements([]), &type_def.owner, what)?;
+                if !matches!(key_type, Type::Primitive(p) if p.is_integer())
+                    && !matches!(key_type, Type::String { .. })
+                {
+                    return Err(Error::InvalidFranca(format!(
+                        "The keys of the map {} must be integers or strings",
+                        type_def.name
+                    )));
+                }
+                let value_type =
+                    self.use_type(value, &props.elements([]), &type_def.owner, what)?;
+                let (min_elements, max_elements, length_field_size) =
+                    self.sized(&props, "Map", what, "SomeIpMapLengthWidth")?;
+                Ok(Type::Map {
+                    min_elements,
+                    max_elements,
+                    length_field_size,
+                    key_type: Box::new(key_type),
+                    value_type: Box::new(value_type),
+                })
+            }
+            _ => {
+                self.generate(type_def)?;
+                Ok(Type::Named(type_def.name.clone()))
+            }
+        }
+    }
+
+    fn generate(&mut self, type_def: &'f TypeDef) -> Result<()> {
+        if !self
+            .generated
+            .insert((type_def.owner.clone(), type_def.name.clone()))
+        {
+            return Ok(());
+        }
+        // references back to the type resolve to its name, so the type starts a new chain of references
+        let resolving = std::mem::take(&mut self.resolving);
+        let item = self.item(type_def);
+        self.resolving = resolving;
+        if let Some(item) = item? {
+            self.items
+                .entry(type_def.owner.clone())
+                .or_default()
+                .push(item);
+        }
+        Ok(())
+    }
+
+    fn item(&mut self, type_def: &'f TypeDef) -> Result<Option<Item>> {
+        Ok(Some(match &type_def.definition {
+            Definition::Enumeration { .. } => Item::Enum(self.enumeration(type_def)?),
+            Definition::Struct { .. } => Item::Struct(self.structure(type_def)?),
+            Definition::Union { .. } => Item::Union(self.union(type_def)?),
+            _ => return Ok(None),
+        }))
+    }
+
+    /// The type followed by the types it extends, directly or indirectly.
+    fn bases(&self, type_def: &'f TypeDef) -> Result<Vec<&'f TypeDef>> {
+        let mut bases = vec![type_def];
+        let mut current = type_def;
+        while let Definition::Enumeration {
+            extends: Some(base),
+            ..
+        }
+        | Definition::Struct {
+            extends: Some(base),
+            ..
+        }
+        | Definition::Union {
+            extends: Some(base),
+            ..
+        } = &current.definition
+        {
+            current = self.find(base, &current.owner)?;
+            if bases.iter().any(|t| std::ptr::eq(*t, current)) {
+                return Err(Error::InvalidFranca(format!(
+                    "{} extends itself",
+                    type_def.name
+                )));
+            }
+            bases.push(current);
+        }
+        Ok(bases)
+    }
+
+    /// The members of a struct or union including those of the types it extends.
+    fn members(&self, type_def: &'f TypeDef) -> Result<Vec<(&'f Member, &'f TypeDef)>> {
+        let mut all = Vec::new();
+        for t in self.bases(type_def)?.into_iter().rev() {
+            match &t.definition {
+                Definition::Struct { members, .. } | Definition::Union { members, .. } => {
+                    all.extend(members.iter().map(|m| (m, t)))
+                }
+                _ => {
+                    return Err(Error::InvalidFranca(format!(
+                        "{} is not a struct or union",
+                        t.name
+                    )))
+                }
+            }
+        }
+        Ok(all)
+    }
+
+    fn field(
+        &mut self,
+        member: &Member,
+        owner: &str,
+        deployment: Option<&'f Deployment>,
+        what: String,
+    ) -> Result<Field> {
+        let props = Props::new([deployment, self.block(owner)]);
+        let id = Props::new([deployment])
+            .int("SomeIpTlvId")?
+            .map(|id| {
+                u16::try_from(id)
+                    .ok()
+                    .filter(|id| *id <= 0xFFF)
+                    .ok_or_else(|| {
+                        Error::InvalidFranca(format!("The TLV id of {} must be in 0..=0xFFF", what))
+                    })
+            })
+            .transpose()?;
+        Ok(Field {
+            name: member.name.clone(),
+            doc: member.doc.clone(),
+            id,
+            optional: false,
+            field_type: self.use_type(&member.type_ref, &props, owner, &what)?,
+        })
+    }
+
+    fn structure(&mut self, type_def: &'f TypeDef) -> Result<Struct> {
+        let mut fields = Vec::new();
+        for (member, declaring) in self.members(type_def)? {
+            let deployment = self
+                .type_deployment(declaring)
+                .and_then(|d| d.children.get(&member.name));
+            let what = format!("{}.{}", type_def.name, member.name);
+            fields.push(self.field(member, &declaring.owner, deployment, what)?);
+        }
+        check_fields(&type_def.name, &fields, Error::InvalidFranca)?;
+        let props = Props::new([self.type_deployment(type_def), self.block(&type_def.owner)]);
+        Ok(Struct {
+            name: type_def.name.clone(),
+            doc: type_def.doc.clone(),
+            message_wrapper: false,
+            props: StructProps {
+                length_field_size: props.width("SomeIpStructLengthWidth")?.filter(|w| *w != 0),
+                ..Default::default()
+            },
+            fields,
+        })
+    }
+
+    fn union(&mut self, type_def: &'f TypeDef) -> Result<Union> {
+        let mut variants = Vec::new();
+        for (i, (member, declaring)) in self.members(type_def)?.into_iter().enumerate() {
+            let deployment = self
+                .type_deployment(declaring)
+                .and_then(|d| d.children.get(&member.name));
+            let what = format!("{}.{}", type_def.name, member.name);
+            let props = Props::new([deployment, self.block(&declaring.owner)]);
+            variants.push(UnionVariant {
+                name: member.name.clone(),
+                doc: member.doc.clone(),
+                selector: i as u32 + 1,
+                variant_type: self.use_type(&member.type_ref, &props, &declaring.owner, &what)?,
+            });
+        }
+        let props = Props::new([self.type_deployment(type_def), self.block(&type_def.owner)]);
+        let width = |key: &str| match props.width(key)? {
+            Some(0) => Err(Error::InvalidFranca(format!(
+                "The union {} must have a {} greater than 0",
+                type_def.name, key
+            ))),
+            width => Ok(width),
+        };
+        Ok(Union {
+            name: type_def.name.clone(),
+            doc: type_def.doc.clone(),
+            length_field_size: width("SomeIpUnionLengthWidth")?,
+            type_field_size: width("SomeIpUnionTypeWidth")?,
+            variants,
+        })
+    }
+
+    /// The enumerators of an enumeration including those of the enumerations it extends.
+    fn enumerators(&self, bases: &[&'f TypeDef]) -> Result<Vec<EnumVariant>> {
+        let mut variants: Vec<EnumVariant> = Vec::new();
+        for t in bases.iter().rev() {
+            let enumerators = match &t.definition {
+                Definition::Enumeration { enumerators, .. } => enumerators,
+                _ => {
+                    return Err(Error::InvalidFranca(format!(
+                        "{} is not an enumeration",
+                        t.name
+                    )))
+                }
+            };
+            for enumerator in enumerators {
+                let value = match enumerator.value {
+                    Some(v) => v,
+                    None => variants.last().map_or(0, |v| v.value + 1),
+                };
+                variants.push(EnumVariant {
+                    name: enumerator.name.clone(),
+                    doc: enumerator.doc.clone(),
+                    value,
+                });
+            }
+        }
+        Ok(variants)
+    }
+
+    fn enumeration(&mut self, type_def: &'f TypeDef) -> Result<Enum> {
+        let bases = self.bases(type_def)?;
+        let variants = self.enumerators(&bases)?;
+        // an extended enumeration keeps the backing type of its base
+        let mut deployments: Vec<_> = bases.iter().map(|t| self.type_deployment(t)).collect();
+        deployments.push(self.block(&type_def.owner));
+        let props = Props::new(deployments);
+        let raw_type = if let Some(backing) = props.get("EnumBackingType") {
+            primitive(backing)
+                .filter(|p| p.is_integer())
+                .ok_or_else(|| {
+                    Error::InvalidFranca(format!(
+                        "The backing type {} of {} is not an integer",
+                        backing, type_def.name
+                    ))
+                })?
+        } else if let Some(width) = props.int("SomeIpEnumWidth")? {
+            let signed = variants.iter().any(|v| v.value < 0);
+            u32::try_from(width * 8)
+                .ok()
+                .and_then(|bits| Primitive::from_encoding(signed, false, bits))
+                .ok_or_else(|| {
+                    Error::InvalidFranca(format!(
+                        "SomeIpEnumWidth of {} must be 1, 2, 4 or 8, was {}",
+                        type_def.name, width
+                    ))
+                })?
+        } else {
+            Primitive::U8
+        };
+        let (min, max) = match raw_type {
+            Primitive::U8 => (0, u8::MAX as i128),
+            Primitive::U16 => (0, u16::MAX as i128),
+            Primitive::U32 => (0, u32::MAX as i128),
+            Primitive::U64 => (0, u64::MAX as i128),
+            Primitive::I8 => (i8::MIN as i128, i8::MAX as i128),
+            Primitive::I16 => (i16::MIN as i128, i16::MAX as i128),
+            Primitive::I32 => (i32::MIN as i128, i32::MAX as i128),
+            _ => (i64::MIN as i128, i64::MAX as i128),
+        };
+        if let Some(v) = variants.iter().find(|v| v.value < min || v.value > max) {
+            return Err(Error::InvalidFranca(format!(
+                "The value {} of {}.{} does not fit into {}",
+                v.value,
+                type_def.name,
+                v.name,
+                raw_type.rust_name()
+            )));
+        }
+        Ok(Enum {
+            name: type_def.name.clone(),
+            doc: type_def.doc.clone(),
+            raw_type,
+            variants,
+        })
+    }
+
+    /// Collects the types used by a type reference, including the types they use in turn.
+    fn referenced(
+        &self,
+        type_ref: &TypeRef,
+        owner: &str,
+        used: &mut Vec<&'f TypeDef>,
+    ) -> Result<()> {
+        if primitive(&type_ref.name).is_some()
+            || matches!(type_ref.name.as_str(), "String" | "ByteBuffer")
+        {
+            return Ok(());
+        }
+        let type_def = self.find(&type_ref.name, owner)?;
+        if used.iter().any(|t| std::ptr::eq(*t, type_def)) {
+            return Ok(());
+        }
+        used.push(type_def);
+        match &type_def.definition {
+            Definition::Typedef(target) | Definition::Array(target) => {
+                self.referenced(target, &type_def.owner, used)
+            }
+            Definition::Map(key, value) => {
+                self.referenced(key, &type_def.owner, used)?;
+                self.referenced(value, &type_def.owner, used)
+            }
+            Definition::Enumeration { .. } => Ok(()),
+            Definition::Struct { .. } | Definition::Union { .. } => {
+                // the members of the bases are deployed with the bases
+                for base in self.bases(type_def)? {
+                    if !used.iter().any(|t| std::ptr::eq(*t, base)) {
+                        used.push(base);
+                    }
+                }
+                for (member, declaring) in self.members(type_def)? {
+                    self.referenced(&member.type_ref, &declaring.owner, used)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn wrapper(
+        &mut self,
+        name: String,
+        doc: Option<String>,
+        interface: &Interface,
+        members: &[Member],
+        deployment: Option<&'f Deployment>,
+    ) -> Result<Item> {
+        let mut fields = Vec::new();
+        for member in members {
+            let what = format!("{}.{}", name, member.name);
+            let member_deployment = deployment.and_then(|d| d.children.get(&member.name));
+            fields.push(self.field(member, &interface.fqn, member_deployment, what)?);
+        }
+        check_fields(&name, &fields, Error::InvalidFranca)?;
+        Ok(Item::Struct(Struct {
+            name,
+            doc,
+            message_wrapper: true,
+            props: StructProps::default(),
+            fields,
+        }))
+    }
+
+    fn interface(&mut self, interface: &'f Interface) -> Result<Module> {
+        let block = self.block(&interface.fqn);
+        let child = |name: &str| block.and_then(|b| b.children.get(name));
+        let mut items = Vec::new();
+        for attribute in &interface.attributes {
+            items.push(self.wrapper(
+                format!("{}_field", attribute.name),
+                attribute.doc.clone(),
+                interface,
+                std::slice::from_ref(attribute),
+                block,
+            )?);
+        }
+        for broadcast in &interface.broadcasts {
+            let deployment = child(&broadcast.name).and_then(|d| d.children.get("out"));
+            items.push(self.wrapper(
+                format!("{}_event", broadcast.name),
+                broadcast.doc.clone(),
+                interface,
+                &broadcast.outs,
+                deployment,
+            )?);
+        }
+        for method in &interface.methods {
+            let deployment = child(&method.name);
+            // someip has no empty structs, the payload is simply empty
+            if !method.ins.is_empty() {
+                items.push(self.wrapper(
+                    format!("{}_request", method.name),
+                    method.doc.clone(),
+                    interface,
+                    &method.ins,
+                    deployment.and_then(|d| d.children.get("in")),
+                )?);
+            }
+            if !method.outs.is_empty() && !method.fire_and_forget {
+                items.push(self.wrapper(
+                    format!("{}_response", method.name),
+                    method.doc.clone(),
+                    interface,
+                    &method.outs,
+                    deployment.and_then(|d| d.children.get("out")),
+                )?);
+            }
+        }
+
+        // only the deployments of the interface and of the types it uses apply to its strings
+        let mut used = Vec::new();
+        let members = interface
+            .attributes
+            .iter()
+            .chain(interface.broadcasts.iter().flat_map(|b| &b.outs))
+            .chain(
+                interface
+                    .methods
+                    .iter()
+                    .flat_map(|m| m.ins.iter().chain(&m.outs)),
+            );
+        for member in members {
+            self.referenced(&member.type_ref, &interface.fqn, &mut used)?;
+        }
+        let mut encodings = Vec::new();
+        if let Some(block) = block {
+            collect_encodings(block, &mut encodings);
+        }
+        for type_def in used {
+            if let Some(deployment) = self.type_deployment(type_def) {
+                collect_encodings(deployment, &mut encodings);
+            }
+            let default = self
+                .block(&type_def.owner)
+                .and_then(|b| b.props.get("SomeIpStringEncoding"));
+            if let Some(encoding) = default.filter(|e| !encodings.contains(e)) {
+                encodings.push(encoding.clone());
+            }
+        }
+        encodings.sort();
+        let string_encoding = match &encodings[..] {
+            [] => StringEncoding::Utf8,
+            [encoding] => string_encoding(encoding)?,
+            _ => {
+                return Err(Error::InvalidFranca(format!(
+                    "The interface {} uses the string encodings {}, but only one encoding per interface is supported",
+                    interface.name,
+                    encodings.join(", ")
+                )))
+            }
+        };
+        items.push(Item::Options(Options {
+            name: String::from("Options"),
+            doc: Some(format!(
+                "The options of the SOME/IP deployment of {}.",
+                interface.name
+            )),
+            string_encoding,
+            string_with_bom: true,
+            string_with_terminator: true,
+            default_length_field_size: Some(4),
+            default_union_type_field_size: 4,
+        }));
+
+        let mut types = self.items.remove(&interface.fqn).unwrap_or_default();
+        types.extend(items);
+        Ok(Module {
+            name: interface.name.clone(),
+            doc: interface.doc.clone(),
+            items: types,
+            modules: Vec::new(),
+        })
+    }
+}
+
+/// Reads all types and interfaces of the fidl documents with the deployments of the fdepl documents.
+pub(crate) fn read(fidl_documents: &[&str], fdepl_documents: &[&str]) -> Result<Module> {
+    let mut fidl = Fidl::default();
+    for document in fidl_documents {
+        Parser::new(document)?.fidl(&mut fidl)?;
+    }
+    let mut deployments = HashMap::new();
+    for document in fdepl_documents {
+        Parser::new(document)?.fdepl(&mut deployments)?;
+    }
+    for deployment in deployments.values() {
+        let mut encodings = Vec::new();
+        collect_encodings(deployment, &mut encodings);
+        for encoding in encodings {
+            string_encoding(&encoding)?;
+        }
+    }
+
+    let mut resolver = Resolver {
+        fidl: &fidl,
+        deployments: &deployments,
+        generated: HashSet::new(),
+        resolving: Vec::new(),
+        items: HashMap::new(),
+    };
+    for type_def in &fidl.types {
+        resolver.generate(type_def)?;
+    }
+    let mut modules = Vec::new();
+    for interface in &fidl.interfaces {
+        modules.push(resolver.interface(interface)?);
+    }
+    let mut items = Vec::new();
+    let mut owners: Vec<&str> = Vec::new();
+    for type_def in &fidl.types {
+        if !owners.contains(&type_def.owner.as_str()) {
+            owners.push(&type_def.owner);
+        }
+    }
+    for owner in owners {
+        items.extend(resolver.items.remove(owner).unwrap_or_default());
+    }
+    Ok(Module {
+        name: String::new(),
+        doc: None,
+        items,
+        modules,
+    })
+}
+
+#[cfg(test)]
+use super::Config;
+
+#[cfg(test)]
+const CLIMATE_FIDL: &str = include_str!("../codegen_tests/climate.fidl");
+#[cfg(test)]
+const CLIMATE_FDEPL: &str = include_str!("../codegen_tests/climate.fdepl");
+
+#[test]
+fn test_climate() {
+    assert_eq!(
+        include_str!("../codegen_tests/climate.rs"),
+        Config::new()
+            .generate_franca(&[CLIMATE_FIDL], &[CLIMATE_FDEPL])
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_fidl() {
+    let fidl = r#"
+        package a.b
+        typeCollection {
+            <** @description: A point.
+                @author: nobody **>
+            struct Point { Int32 x /* the x */ Int32 y }
+            enumeration Signed { Low = -1 High = 0x10 }
+            typedef Points is Point[]
+        }"#;
+    let fdepl = r#"
+        define spec for typeCollection a.b {
+            SomeIpStructLengthWidth = 0
+            enumeration Signed { SomeIpEnumWidth = 2 }
+        }"#;
+    let module = read(&[fidl], &[fdepl]).unwrap();
+    assert_eq!(2, module.items.len());
+    match &module.items[0] {
+        Item::Struct(s) => {
+            assert_eq!(Some("A point."), s.doc.as_deref());
+            assert_eq!(None, s.props.length_field_size);
+            assert_eq!(2, s.fields.len());
+        }
+        item => panic!("Expected the struct Point, got {:?}", item),
+    }
+    match &module.items[1] {
+        Item::Enum(e) => {
+            assert_eq!(Primitive::I16, e.raw_type);
+            assert_eq!(
+                vec![-1, 16],
+                e.variants.iter().map(|v| v.value).collect::<Vec<_>>()
+            );
+        }
+        item => panic!("Expected the enum Signed, got {:?}", item),
+    }
+}
+
+#[test]
+fn test_recursive() {
+    // a struct may contain itself through an array and a typedef
+    let fidl = "typeCollection T { typedef Children is Node[] struct Node { Children children } }";
+    let fdepl = "define spec for typeCollection T { SomeIpArrayMaxLength = 4 }";
+    let module = read(&[fidl], &[fdepl]).unwrap();
+    assert_eq!(1, module.items.len());
+}
+
+#[test]
+fn test_invalid() {
+    assert!(matches!(
+        read(&["typeCollection { struct Point { Int32 x "], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+    assert!(matches!(
+        read(&["typeCollection T { /* unterminated "], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let unknown_type = "typeCollection T { struct Point { Coordinate x } }";
+    assert!(matches!(
+        read(&[unknown_type], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let string_without_length = "typeCollection T { struct Person { String name } }";
+    assert!(matches!(
+        read(&[string_without_length], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let array = "typeCollection T { array Bytes of UInt8 struct Data { Bytes bytes } }";
+    assert!(matches!(read(&[array], &[]), Err(Error::InvalidFranca(_))));
+    let element_without_length = r#"
+        define spec for typeCollection T {
+            struct Data { bytes { SomeIpArrayMaxLength = 4 } }
+        }"#;
+    assert!(read(&[array], &[element_without_length]).is_ok());
+    let enum_too_small = r#"
+        typeCollection T { enumeration E { A = 256 } }"#;
+    assert!(matches!(
+        read(&[enum_too_small], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let interface = r#"
+        typeCollection T { struct Name { String name } }
+        interface I { attribute String title attribute Name name }"#;
+    let mixed_encodings = r#"
+        define spec for typeCollection T {
+            SomeIpStringLength = 8
+            SomeIpStringEncoding = utf8
+        }
+        define spec for interface I {
+            SomeIpStringLength = 8
+            SomeIpStringEncoding = utf16be
+        }"#;
+    assert!(matches!(
+        read(&[interface], &[mixed_encodings]),
+        Err(Error::InvalidFranca(_))
+    ));
+    // the encodings of types the interface does not use do not matter
+    let unused_name = interface.replace("attribute Name name", "");
+    assert!(read(&[&unused_name], &[mixed_encodings]).is_ok());
+
+    let union_without_type_field = r#"
+        define spec for typeCollection T {
+            union U { SomeIpUnionTypeWidth = 0 }
+        }"#;
+    assert!(matches!(
+        read(
+            &["typeCollection T { union U { UInt8 a UInt16 b } }"],
+            &[union_without_type_field]
+        ),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let typedef_cycle = "typeCollection T { typedef A is B typedef B is A struct S { A a } }";
+    assert!(matches!(
+        read(&[typedef_cycle], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+    let extends_cycle =
+        "typeCollection T { struct A extends B { UInt8 a } struct B extends A { UInt8 b } }";
+    assert!(matches!(
+        read(&[extends_cycle], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+    let enumeration_cycle = "typeCollection T { enumeration E extends E { A } }";
+    assert!(matches!(
+        read(&[enumeration_cycle], &[]),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    // deployments must name the type collection by its fully qualified name
+    let unqualified = "define spec for typeCollection Types { SomeIpStringLength = 8 }";
+    assert!(matches!(
+        read(
+            &["package a.b typeCollection Types { struct Name { String name } }"],
+            &[unqualified]
+        ),
+        Err(Error::InvalidFranca(_))
+    ));
+
+    let partial_tlv = r#"
+        define spec for typeCollection T {
+            struct S { a { SomeIpTlvId = 1 } }
+        }"#;
+    assert!(matches!(
+        read(
+            &["typeCollection T { struct S { UInt8 a UInt8 b } }"],
+            &[partial_tlv]
+        ),
+        Err(Error::InvalidFranca(_))
+    ));
+}
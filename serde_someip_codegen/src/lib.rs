@@ -0,0 +1,132 @@
+//! Generates rust types for the [`serde_someip`] crate from AUTOSAR and Franca interface descriptions.
+//!
+//! The generated structs, enums and unions use `#[derive(SomeIp)]` with the attributes that match the
+//! description, so they can no longer drift from the specification. The generator is meant to be used
+//! from a build script:
+//! ```no_run
+//! // in build.rs
+//! let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("interfaces.rs");
+//! serde_someip_codegen::Config::new()
+//!     .compile_arxml(&["interfaces/vehicle.arxml"], &out)
+//!     .unwrap();
+//! println!("cargo:rerun-if-changed=interfaces/vehicle.arxml");
+//! ```
+//! The generated file is then included into the crate:
+//! ```ignore
+//! include!(concat!(env!("OUT_DIR"), "/interfaces.rs"));
+//! ```
+//! The generated code derives `serde::Serialize`, `serde::Deserialize` and `serde_someip::SomeIp`,
+//! so the crate must depend on `serde` with the `derive` feature and on `serde_someip` with the `derive` feature.
+//!
+//! Franca IDL models are generated with [compile_franca](Config::compile_franca) from their `.fidl` files
+//! together with the `.fdepl` files that contain the SOME/IP deployment. Every Franca interface also gets
+//! an `Options` type implementing `SomeIpOptions` that matches its deployment.
+//!
+//! See the [arxml] and [franca] modules for the supported elements.
+//!
+//! [`serde_someip`]: https://docs.rs/serde_someip
+#![deny(missing_docs)]
+
+pub mod arxml;
+pub mod error;
+pub mod franca;
+mod model;
+mod rust;
+
+pub use error::{Error, Result};
+
+use std::path::Path;
+
+/// Configures the generated code.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The derives that are added to every generated type in addition to
+    /// `serde::Serialize`, `serde::Deserialize` and `serde_someip::SomeIp`.
+    /// Defaults to `Debug`, `Clone` and `PartialEq`.
+    pub derives: Vec<String>,
+}
+
+impl Config {
+    /// Creates the default config.
+    pub fn new() -> Self {
+        Config {
+            derives: vec![
+                String::from("Debug"),
+                String::from("Clone"),
+                String::from("PartialEq"),
+            ],
+        }
+    }
+
+    /// Generates the rust code for the types and interfaces of the ARXML documents.
+    ///
+    /// All documents are read together so they can refer to each other,
+    /// for example a document with the service interfaces can use the platform types of another document.
+    pub fn generate_arxml(&self, documents: &[&str]) -> Result<String> {
+        Ok(rust::generate(&arxml::read(documents)?, self))
+    }
+
+    /// Reads the ARXML files, generates the rust code for them and writes it to `output`.
+    pub fn compile_arxml<P: AsRef<Path>>(
+        &self,
+        inputs: &[P],
+        output: impl AsRef<Path>,
+    ) -> Result<()> {
+        let documents = read_all(inputs)?;
+        let documents: Vec<_> = documents.iter().map(String::as_str).collect();
+        std::fs::write(output, self.generate_arxml(&documents)?)?;
+        Ok(())
+    }
+
+    /// Generates the rust code for the types and interfaces of the Franca IDL documents
+    /// using the SOME/IP deployment properties of the fdepl documents.
+    ///
+    /// Like for [generate_arxml](Self::generate_arxml) all documents are read together.
+    pub fn generate_franca(
+        &self,
+        fidl_documents: &[&str],
+        fdepl_documents: &[&str],
+    ) -> Result<String> {
+        Ok(rust::generate(
+            &franca::read(fidl_documents, fdepl_documents)?,
+            self,
+        ))
+    }
+
+    /// Reads the fidl and fdepl files, generates the rust code for them and writes it to `output`.
+    pub fn compile_franca<P: AsRef<Path>, Q: AsRef<Path>>(
+        &self,
+        fidl_inputs: &[P],
+        fdepl_inputs: &[Q],
+        output: impl AsRef<Path>,
+    ) -> Result<()> {
+        let fidl_documents = read_all(fidl_inputs)?;
+        let fdepl_documents = read_all(fdepl_inputs)?;
+        let fidl_documents: Vec<_> = fidl_documents.iter().map(String::as_str).collect();
+        let fdepl_documents: Vec<_> = fdepl_documents.iter().map(String::as_str).collect();
+        std::fs::write(
+            output,
+            self.generate_franca(&fidl_documents, &fdepl_documents)?,
+        )?;
+        Ok(())
+    }
+}
+
+fn read_all<P: AsRef<Path>>(inputs: &[P]) -> Result<Vec<String>> {
+    Ok(inputs
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect::<std::io::Result<Vec<_>>>()?)
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_generated_code_compiles() {
+    let t = trybuild::TestCases::new();
+    t.pass("codegen_tests/*_main.rs")
+}
@@ -0,0 +1,219 @@
+//! The language independent description of the generated types.
+//!
+//! The readers of the different interface description languages fill this model
+//! which is then turned into rust code by [rust](super::rust).
+
+use super::error::{Error, Result};
+
+/// The primitive types of someip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Primitive {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl Primitive {
+    pub(crate) fn from_encoding(signed: bool, float: bool, bits: u32) -> Option<Primitive> {
+        match (signed, float, bits) {
+            (_, true, 32) => Some(Primitive::F32),
+            (_, true, 64) => Some(Primitive::F64),
+            (false, false, 8) => Some(Primitive::U8),
+            (false, false, 16) => Some(Primitive::U16),
+            (false, false, 32) => Some(Primitive::U32),
+            (false, false, 64) => Some(Primitive::U64),
+            (true, false, 8) => Some(Primitive::I8),
+            (true, false, 16) => Some(Primitive::I16),
+            (true, false, 32) => Some(Primitive::I32),
+            (true, false, 64) => Some(Primitive::I64),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn rust_name(&self) -> &'static str {
+        match self {
+            Primitive::Bool => "bool",
+            Primitive::U8 => "u8",
+            Primitive::U16 => "u16",
+            Primitive::U32 => "u32",
+            Primitive::U64 => "u64",
+            Primitive::I8 => "i8",
+            Primitive::I16 => "i16",
+            Primitive::I32 => "i32",
+            Primitive::I64 => "i64",
+            Primitive::F32 => "f32",
+            Primitive::F64 => "f64",
+        }
+    }
+
+    pub(crate) fn is_integer(&self) -> bool {
+        !matches!(self, Primitive::Bool | Primitive::F32 | Primitive::F64)
+    }
+}
+
+/// The use of a type, for example as the type of a field.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Type {
+    Primitive(Primitive),
+    String {
+        min_size: usize,
+        max_size: usize,
+        length_field_size: Option<u8>,
+        /// Whether a string with a fixed size is padded with zeros to its size.
+        padded: bool,
+    },
+    Sequence {
+        min_elements: usize,
+        max_elements: usize,
+        length_field_size: Option<u8>,
+        element_type: Box<Type>,
+    },
+    Map {
+        min_elements: usize,
+        max_elements: usize,
+        length_field_size: Option<u8>,
+        key_type: Box<Type>,
+        value_type: Box<Type>,
+    },
+    /// A struct, enum or union that is generated as its own item.
+    Named(String),
+}
+
+/// The properties of a struct that correspond to the someip transformation properties of autosar.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct StructProps {
+    pub(crate) length_field_size: Option<u8>,
+    pub(crate) arrays_length_field_size: Option<u8>,
+    pub(crate) structs_length_field_size: Option<u8>,
+    pub(crate) strings_length_field_size: Option<u8>,
+    pub(crate) alignment: Option<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Field {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) id: Option<u16>,
+    pub(crate) optional: bool,
+    pub(crate) field_type: Type,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Struct {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) message_wrapper: bool,
+    pub(crate) props: StructProps,
+    pub(crate) fields: Vec<Field>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct EnumVariant {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) value: i128,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Enum {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) raw_type: Primitive,
+    pub(crate) variants: Vec<EnumVariant>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnionVariant {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) selector: u32,
+    pub(crate) variant_type: Type,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Union {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) length_field_size: Option<u8>,
+    pub(crate) type_field_size: Option<u8>,
+    pub(crate) variants: Vec<UnionVariant>,
+}
+
+/// The string encodings of someip, each maps to a variant of `StringEncoding`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StringEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+/// A type implementing `SomeIpOptions` with the options that are global for an interface.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Options {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) string_encoding: StringEncoding,
+    pub(crate) string_with_bom: bool,
+    pub(crate) string_with_terminator: bool,
+    pub(crate) default_length_field_size: Option<u8>,
+    pub(crate) default_union_type_field_size: u8,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Item {
+    Struct(Struct),
+    Enum(Enum),
+    Union(Union),
+    Options(Options),
+}
+
+/// A group of items, every interface is generated as its own rust module.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Module {
+    pub(crate) name: String,
+    pub(crate) doc: Option<String>,
+    pub(crate) items: Vec<Item>,
+    pub(crate) modules: Vec<Module>,
+}
+
+/// Parses a decimal or `0x` prefixed hexadecimal integer with an optional sign.
+pub(crate) fn parse_int(text: &str) -> Option<i128> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(t) => (true, t),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    let value = if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        i128::from_str_radix(hex, 16).ok()?
+    } else {
+        text.parse::<i128>().ok()?
+    };
+    Some(if negative { -value } else { value })
+}
+
+/// Checks that the fields of a struct either all or none have a TLV id and that
+/// only TLV structs have optional fields, `error` creates the error of the calling reader.
+pub(crate) fn check_fields(name: &str, fields: &[Field], error: fn(String) -> Error) -> Result<()> {
+    let with_id = fields.iter().filter(|f| f.id.is_some()).count();
+    if with_id != 0 && with_id != fields.len() {
+        return Err(error(format!(
+            "Either all or none of the members of {} must have a TLV id",
+            name
+        )));
+    }
+    if with_id == 0 && fields.iter().any(|f| f.optional) {
+        return Err(error(format!(
+            "{} has optional members but does not use TLV ids",
+            name
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,541 @@
+//! Turns the [model](super::model) into rust code that uses `#[derive(SomeIp)]`.
+
+use super::model::*;
+use super::Config;
+
+use heck::{ToSnakeCase, ToUpperCamelCase};
+
+use std::fmt::Write;
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+fn escape(ident: String) -> String {
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", ident)
+    } else if matches!(ident.as_str(), "self" | "Self" | "super" | "crate") {
+        format!("{}_", ident)
+    } else if KEYWORDS.contains(&ident.as_str()) {
+        format!("r#{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// The identifier of a struct, enum, union or variant.
+pub(crate) fn type_ident(name: &str) -> String {
+    escape(name.to_upper_camel_case())
+}
+
+/// The identifier of a field or module.
+pub(crate) fn field_ident(name: &str) -> String {
+    escape(name.to_snake_case())
+}
+
+fn write_doc(out: &mut String, indent: &str, doc: &Option<String>) {
+    if let Some(doc) = doc {
+        for line in doc.lines().map(str::trim) {
+            if line.is_empty() {
+                writeln!(out, "{}///", indent).unwrap();
+            } else {
+                writeln!(out, "{}/// {}", indent, line).unwrap();
+            }
+        }
+    }
+}
+
+fn write_someip_attrs(out: &mut String, indent: &str, attrs: &[String]) {
+    if !attrs.is_empty() {
+        writeln!(out, "{}#[someip({})]", indent, attrs.join(", ")).unwrap();
+    }
+}
+
+fn push_length_field_size(attrs: &mut Vec<String>, name: &str, size: Option<u8>) {
+    if let Some(size) = size {
+        attrs.push(format!("{} = {}", name, size));
+    }
+}
+
+/// Returns the rust type and the attributes needed by `#[derive(SomeIp)]` for a use of `ty`.
+fn type_use(ty: &Type) -> (String, Vec<String>) {
+    match ty {
+        Type::Primitive(p) => (p.rust_name().to_owned(), Vec::new()),
+        Type::String {
+            min_size,
+            max_size,
+            length_field_size,
+            padded,
+        } => {
+            let mut attrs = Vec::new();
+            if *padded {
+                attrs.push(format!("fixed_size = {}", max_size));
+            } else {
+                if *min_size != 0 {
+                    attrs.push(format!("min_size = {}", min_size));
+                }
+                attrs.push(format!("max_size = {}", max_size));
+            }
+            push_length_field_size(&mut attrs, "length_field_size", *length_field_size);
+            (String::from("String"), attrs)
+        }
+        Type::Sequence {
+            min_elements,
+            max_elements,
+            length_field_size,
+            element_type,
+        } => {
+            let (element, element_attrs) = type_use(element_type);
+            let mut attrs = Vec::new();
+            if *min_elements != 0 {
+                attrs.push(format!("min_elements = {}", min_elements));
+            }
+            attrs.push(format!("max_elements = {}", max_elements));
+            push_length_field_size(&mut attrs, "length_field_size", *length_field_size);
+            if !element_attrs.is_empty() {
+                attrs.push(format!("elements = ({})", element_attrs.join(", ")));
+            }
+            (format!("Vec<{}>", element), attrs)
+        }
+        Type::Map {
+            min_elements,
+            max_elements,
+            length_field_size,
+            key_type,
+            value_type,
+        } => {
+            let (key, key_attrs) = type_use(key_type);
+            let (value, value_attrs) = type_use(value_type);
+            let mut attrs = Vec::new();
+            if *min_elements != 0 {
+                attrs.push(format!("min_elements = {}", min_elements));
+            }
+            attrs.push(format!("max_elements = {}", max_elements));
+            push_length_field_size(&mut attrs, "length_field_size", *length_field_size);
+            if !key_attrs.is_empty() {
+                attrs.push(format!("keys = ({})", key_attrs.join(", ")));
+            }
+            if !value_attrs.is_empty() {
+                attrs.push(format!("values = ({})", value_attrs.join(", ")));
+            }
+            (
+                format!("std::collections::HashMap<{}, {}>", key, value),
+                attrs,
+            )
+        }
+        Type::Named(name) => (type_ident(name), Vec::new()),
+    }
+}
+
+fn write_derives(out: &mut String, indent: &str, config: &Config) {
+    let mut derives = config.derives.clone();
+    derives.extend(
+        [
+            "serde::Serialize",
+            "serde::Deserialize",
+            "serde_someip::SomeIp",
+        ]
+        .iter()
+        .map(|d| d.to_string()),
+    );
+    writeln!(out, "{}#[derive({})]", indent, derives.join(", ")).unwrap();
+}
+
+fn write_struct(out: &mut String, indent: &str, s: &Struct, config: &Config) {
+    write_doc(out, indent, &s.doc);
+    write_derives(out, indent, config);
+    let mut attrs = Vec::new();
+    if s.message_wrapper {
+        attrs.push(String::from("message_wrapper = true"));
+    }
+    push_length_field_size(&mut attrs, "length_field_size", s.props.length_field_size);
+    push_length_field_size(
+        &mut attrs,
+        "arrays_length_field_size",
+        s.props.arrays_length_field_size,
+    );
+    push_length_field_size(
+        &mut attrs,
+        "structs_length_field_size",
+        s.props.structs_length_field_size,
+    );
+    push_length_field_size(
+        &mut attrs,
+        "strings_length_field_size",
+        s.props.strings_length_field_size,
+    );
+    if let Some(alignment) = s.props.alignment {
+        attrs.push(format!("alignment = {}", alignment));
+    }
+    write_someip_attrs(out, indent, &attrs);
+    writeln!(out, "{}pub struct {} {{", indent, type_ident(&s.name)).unwrap();
+    let field_indent = format!("{}    ", indent);
+    for field in &s.fields {
+        write_doc(out, &field_indent, &field.doc);
+        let (mut field_type, type_attrs) = type_use(&field.field_type);
+        let mut attrs = Vec::new();
+        if let Some(id) = field.id {
+            attrs.push(format!("id = {}", id));
+        }
+        attrs.extend(type_attrs);
+        write_someip_attrs(out, &field_indent, &attrs);
+        if field.optional {
+            field_type = format!("Option<{}>", field_type);
+        }
+        writeln!(
+            out,
+            "{}pub {}: {},",
+            field_indent,
+            field_ident(&field.name),
+            field_type
+        )
+        .unwrap();
+    }
+    writeln!(out, "{}}}", indent).unwrap();
+}
+
+fn write_enum(out: &mut String, indent: &str, e: &Enum, config: &Config) {
+    write_doc(out, indent, &e.doc);
+    write_derives(out, indent, config);
+    write_someip_attrs(
+        out,
+        indent,
+        &[format!("raw_type = {}", e.raw_type.rust_name())],
+    );
+    writeln!(out, "{}pub enum {} {{", indent, type_ident(&e.name)).unwrap();
+    let variant_indent = format!("{}    ", indent);
+    for variant in &e.variants {
+        write_doc(out, &variant_indent, &variant.doc);
+        write_someip_attrs(
+            out,
+            &variant_indent,
+            &[format!("value = {}", variant.value)],
+        );
+        writeln!(out, "{}{},", variant_indent, type_ident(&variant.name)).unwrap();
+    }
+    writeln!(out, "{}}}", indent).unwrap();
+}
+
+fn write_union(out: &mut String, indent: &str, u: &Union, config: &Config) {
+    write_doc(out, indent, &u.doc);
+    write_derives(out, indent, config);
+    let mut attrs = Vec::new();
+    push_length_field_size(&mut attrs, "length_field_size", u.length_field_size);
+    push_length_field_size(&mut attrs, "type_field_size", u.type_field_size);
+    write_someip_attrs(out, indent, &attrs);
+    writeln!(out, "{}pub enum {} {{", indent, type_ident(&u.name)).unwrap();
+    let variant_indent = format!("{}    ", indent);
+    for variant in &u.variants {
+        write_doc(out, &variant_indent, &variant.doc);
+        let (variant_type, type_attrs) = type_use(&variant.variant_type);
+        let mut attrs = vec![format!("selector = {}", variant.selector)];
+        attrs.extend(type_attrs);
+        write_someip_attrs(out, &variant_indent, &attrs);
+        writeln!(
+            out,
+            "{}{}({}),",
+            variant_indent,
+            type_ident(&variant.name),
+            variant_type
+        )
+        .unwrap();
+    }
+    writeln!(out, "{}}}", indent).unwrap();
+}
+
+fn is_named(ty: &Type) -> bool {
+    match ty {
+        Type::Named(_) => true,
+        Type::Sequence { element_type, .. } => is_named(element_type),
+        Type::Map {
+            key_type,
+            value_type,
+            ..
+        } => is_named(key_type) || is_named(value_type),
+        _ => false,
+    }
+}
+
+fn length_field_size_variant(size: u8) -> &'static str {
+    match size {
+        1 => "OneByte",
+        2 => "TwoBytes",
+        _ => "FourBytes",
+    }
+}
+
+fn write_options(out: &mut String, indent: &str, o: &Options) {
+    write_doc(out, indent, &o.doc);
+    writeln!(out, "{}#[derive(Debug, Clone, Copy)]", indent).unwrap();
+    writeln!(out, "{}pub struct {};", indent, type_ident(&o.name)).unwrap();
+    out.push('\n');
+    writeln!(
+        out,
+        "{}impl serde_someip::SomeIpOptions for {} {{",
+        indent,
+        type_ident(&o.name)
+    )
+    .unwrap();
+    let inner = format!("{}    ", indent);
+    let encoding = match o.string_encoding {
+        StringEncoding::Utf8 => "Utf8",
+        StringEncoding::Utf16Le => "Utf16Le",
+        StringEncoding::Utf16Be => "Utf16Be",
+    };
+    writeln!(
+        out,
+        "{}const STRING_ENCODING: serde_someip::options::StringEncoding = serde_someip::options::StringEncoding::{};",
+        inner, encoding
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "{}const STRING_WITH_BOM: bool = {};",
+        inner, o.string_with_bom
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "{}const STRING_WITH_TERMINATOR: bool = {};",
+        inner, o.string_with_terminator
+    )
+    .unwrap();
+    let default_length_field_size = match o.default_length_field_size {
+        Some(size) => format!(
+            "Some(serde_someip::length_fields::LengthFieldSize::{})",
+            length_field_size_variant(size)
+        ),
+        None => String::from("None"),
+    };
+    writeln!(
+        out,
+        "{}const DEFAULT_LENGTH_FIELD_SIZE: Option<serde_someip::length_fields::LengthFieldSize> = {};",
+        inner, default_length_field_size
+    )
+    .unwrap();
+    writeln!(
+        out,
+        "{}const DEFAULT_UNION_TYPE_FIELD_SIZE: serde_someip::length_fields::LengthFieldSize = serde_someip::length_fields::LengthFieldSize::{};",
+        inner,
+        length_field_size_variant(o.default_union_type_field_size)
+    )
+    .unwrap();
+    writeln!(out, "{}}}", indent).unwrap();
+}
+
+/// Whether the items of the module refer to other items, only then `use super::*` is needed.
+fn uses_named_types(module: &Module) -> bool {
+    module.items.iter().any(|item| match item {
+        Item::Struct(s) => s.fields.iter().any(|f| is_named(&f.field_type)),
+        Item::Enum(_) | Item::Options(_) => false,
+        Item::Union(u) => u.variants.iter().any(|v| is_named(&v.variant_type)),
+    })
+}
+
+fn write_items(out: &mut String, indent: &str, module: &Module, config: &Config) {
+    let mut first = true;
+    for item in &module.items {
+        if !first {
+            out.push('\n');
+        }
+        first = false;
+        match item {
+            Item::Struct(s) => write_struct(out, indent, s, config),
+            Item::Enum(e) => write_enum(out, indent, e, config),
+            Item::Union(u) => write_union(out, indent, u, config),
+            Item::Options(o) => write_options(out, indent, o),
+        }
+    }
+    for sub_module in &module.modules {
+        if !first {
+            out.push('\n');
+        }
+        first = false;
+        write_doc(out, indent, &sub_module.doc);
+        writeln!(
+            out,
+            "{}pub mod {} {{",
+            indent,
+            field_ident(&sub_module.name)
+        )
+        .unwrap();
+        let inner_indent = format!("{}    ", indent);
+        if uses_named_types(sub_module) {
+            writeln!(out, "{}use super::*;", inner_indent).unwrap();
+            out.push('\n');
+        }
+        write_items(out, &inner_indent, sub_module, config);
+        writeln!(out, "{}}}", indent).unwrap();
+    }
+}
+
+/// Renders the module as the content of a rust file, the items of the module are at the top level.
+pub(crate) fn generate(module: &Module, config: &Config) -> String {
+    let mut out = String::from(
+        "// This file is @generated by serde_someip_codegen, do not edit it by hand.\n\n",
+    );
+    write_items(&mut out, "", module, config);
+    out
+}
+
+#[cfg(test)]
+fn test_config() -> Config {
+    Config {
+        derives: vec![String::from("Debug")],
+    }
+}
+
+#[test]
+fn test_idents() {
+    assert_eq!("VehicleSpeed", type_ident("vehicle_speed"));
+    assert_eq!("_1stGear", type_ident("1st_gear"));
+    assert_eq!("Self_", type_ident("self"));
+    assert_eq!("vehicle_speed", field_ident("VehicleSpeed"));
+    assert_eq!("r#type", field_ident("Type"));
+}
+
+#[test]
+fn test_struct() {
+    let module = Module {
+        items: vec![Item::Struct(Struct {
+            name: String::from("Status"),
+            doc: Some(String::from("The status.")),
+            message_wrapper: true,
+            props: StructProps {
+                arrays_length_field_size: Some(4),
+                alignment: Some(32),
+                ..Default::default()
+            },
+            fields: vec![
+                Field {
+                    name: String::from("Names"),
+                    doc: None,
+                    id: Some(1),
+                    optional: true,
+                    field_type: Type::Sequence {
+                        min_elements: 0,
+                        max_elements: 8,
+                        length_field_size: None,
+                        element_type: Box::new(Type::String {
+                            min_size: 0,
+                            max_size: 16,
+                            length_field_size: Some(1),
+                            padded: false,
+                        }),
+                    },
+                },
+                Field {
+                    name: String::from("type"),
+                    doc: None,
+                    id: Some(2),
+                    optional: false,
+                    field_type: Type::Named(String::from("gear_type")),
+                },
+            ],
+        })],
+        ..Default::default()
+    };
+    assert_eq!(
+        r#"// This file is @generated by serde_someip_codegen, do not edit it by hand.
+
+/// The status.
+#[derive(Debug, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+#[someip(message_wrapper = true, arrays_length_field_size = 4, alignment = 32)]
+pub struct Status {
+    #[someip(id = 1, max_elements = 8, elements = (max_size = 16, length_field_size = 1))]
+    pub names: Option<Vec<String>>,
+    #[someip(id = 2)]
+    pub r#type: GearType,
+}
+"#,
+        generate(&module, &test_config())
+    );
+}
+
+#[test]
+fn test_enum_and_union() {
+    let module = Module {
+        name: String::from("root"),
+        doc: None,
+        items: vec![],
+        modules: vec![Module {
+            name: String::from("Drive"),
+            doc: None,
+            items: vec![
+                Item::Enum(Enum {
+                    name: String::from("Gear"),
+                    doc: None,
+                    raw_type: Primitive::I8,
+                    variants: vec![
+                        EnumVariant {
+                            name: String::from("REVERSE"),
+                            doc: Some(String::from("Backwards")),
+                            value: -1,
+                        },
+                        EnumVariant {
+                            name: String::from("NEUTRAL"),
+                            doc: None,
+                            value: 0,
+                        },
+                    ],
+                }),
+                Item::Union(Union {
+                    name: String::from("Value"),
+                    doc: None,
+                    length_field_size: Some(4),
+                    type_field_size: Some(1),
+                    variants: vec![
+                        UnionVariant {
+                            name: String::from("number"),
+                            doc: None,
+                            selector: 1,
+                            variant_type: Type::Primitive(Primitive::U32),
+                        },
+                        UnionVariant {
+                            name: String::from("text"),
+                            doc: None,
+                            selector: 2,
+                            variant_type: Type::String {
+                                min_size: 2,
+                                max_size: 10,
+                                length_field_size: None,
+                                padded: false,
+                            },
+                        },
+                    ],
+                }),
+            ],
+            modules: vec![],
+        }],
+    };
+    assert_eq!(
+        r#"// This file is @generated by serde_someip_codegen, do not edit it by hand.
+
+pub mod drive {
+    #[derive(Debug, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(raw_type = i8)]
+    pub enum Gear {
+        /// Backwards
+        #[someip(value = -1)]
+        Reverse,
+        #[someip(value = 0)]
+        Neutral,
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, serde_someip::SomeIp)]
+    #[someip(length_field_size = 4, type_field_size = 1)]
+    pub enum Value {
+        #[someip(selector = 1)]
+        Number(u32),
+        #[someip(selector = 2, min_size = 2, max_size = 10)]
+        Text(String),
+    }
+}
+"#,
+        generate(&module, &test_config())
+    );
+}
@@ -1,6 +1,8 @@
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::spanned::Spanned;
-use syn::{parenthesized, parse2, token::Paren, Attribute, Ident, LitBool, LitInt, Token, Type};
+use syn::{
+    parenthesized, parse2, token::Paren, Attribute, Expr, Ident, LitBool, LitInt, Token, Type,
+};
 
 use quote::quote;
 
@@ -16,6 +18,7 @@ pub(crate) enum AttributeValueType {
     Int,
     Type,
     Inner,
+    Call,
 }
 
 impl Display for AttributeValueType {
@@ -25,6 +28,7 @@ impl Display for AttributeValueType {
             AttributeValueType::Int => fmt.write_str("integer"),
             AttributeValueType::Type => fmt.write_str("type"),
             AttributeValueType::Inner => fmt.write_str("(values, ...)"),
+            AttributeValueType::Call => fmt.write_str("name(key = value, ...)"),
         }
     }
 }
@@ -34,6 +38,7 @@ pub(crate) enum AttributeValue {
     Int(LitInt),
     Type(Type),
     Inner(SomeIpAttribute),
+    Call(AttributeCall),
 }
 
 impl AttributeValue {
@@ -44,6 +49,7 @@ impl AttributeValue {
             AttributeValue::Int(_) => expected == &AttributeValueType::Int,
             AttributeValue::Type(_) => expected == &AttributeValueType::Type,
             AttributeValue::Inner(_) => expected == &AttributeValueType::Inner,
+            AttributeValue::Call(_) => expected == &AttributeValueType::Call,
         }
     }
 
@@ -54,6 +60,7 @@ impl AttributeValue {
             AttributeValue::Int(_) => AttributeValueType::Int,
             AttributeValue::Type(_) => AttributeValueType::Type,
             AttributeValue::Inner(_) => AttributeValueType::Inner,
+            AttributeValue::Call(_) => AttributeValueType::Call,
         }
     }
 
@@ -64,6 +71,7 @@ impl AttributeValue {
             AttributeValue::Int(v) => v.span(),
             AttributeValue::Type(v) => v.span(),
             AttributeValue::Inner(v) => v.span,
+            AttributeValue::Call(v) => v.ident.span(),
         }
     }
 
@@ -98,6 +106,54 @@ impl AttributeValue {
             _ => panic!(),
         }
     }
+
+    #[inline]
+    pub(crate) fn unwrap_call(&self) -> &AttributeCall {
+        match self {
+            AttributeValue::Call(v) => v,
+            _ => panic!(),
+        }
+    }
+}
+
+//a value like `profile4(data_id = 0x1234, offset = 8)` whose arguments are arbitrary expressions
+pub(crate) struct AttributeCall {
+    pub(crate) ident: Ident,
+    pub(crate) span: Span,
+    pub(crate) args: Vec<(Ident, Expr)>,
+}
+
+impl AttributeCall {
+    #[inline]
+    pub(crate) fn get_optional(&self, key: &str) -> Option<&Expr> {
+        self.args
+            .iter()
+            .find(|(ident, _)| ident == key)
+            .map(|(_, expr)| expr)
+    }
+}
+
+impl Parse for AttributeCall {
+    fn parse(input: ParseStream) -> Result<AttributeCall> {
+        let ident: Ident = input.parse()?;
+        let content;
+        let span = parenthesized!(content in input).span;
+        let values = content.parse_terminated::<_, Token![,]>(parse_call_arg)?;
+        let mut args: Vec<(Ident, Expr)> = Vec::with_capacity(values.len());
+        for (ident, expr) in values {
+            if args.iter().any(|(other, _)| other == &ident) {
+                return Err(Error::new(ident.span(), "Duplicate attribute"));
+            }
+            args.push((ident, expr));
+        }
+        Ok(AttributeCall { ident, span, args })
+    }
+}
+
+fn parse_call_arg(input: ParseStream) -> Result<(Ident, Expr)> {
+    let ident: Ident = input.parse()?;
+    input.parse::<Token![=]>()?;
+    Ok((ident, input.parse()?))
 }
 
 pub(crate) struct AttributeKeyValue {
@@ -132,6 +188,27 @@ impl AttributeKeyValue {
             format!("Attribute {} must have a value of 1, 2, or 4", self.ident),
         ))
     }
+
+    #[inline]
+    pub(crate) fn to_alignment(&self) -> Result<TokenStream> {
+        if let AttributeValue::Int(v) = &self.value {
+            match v.base10_parse::<u8>()? {
+                8 => return Ok(quote! {Some(serde_someip::types::Alignment::EightBits)}),
+                16 => return Ok(quote! {Some(serde_someip::types::Alignment::SixteenBits)}),
+                32 => return Ok(quote! {Some(serde_someip::types::Alignment::ThirtyTwoBits)}),
+                64 => return Ok(quote! {Some(serde_someip::types::Alignment::SixtyFourBits)}),
+                _ => {}
+            }
+        }
+
+        Err(Error::new(
+            self.value.span(),
+            format!(
+                "Attribute {} must have a value of 8, 16, 32 or 64",
+                self.ident
+            ),
+        ))
+    }
 }
 
 impl AsRef<AttributeValue> for AttributeKeyValue {
@@ -227,7 +304,16 @@ impl SomeIpAttribute {
 impl Parse for AttributeKeyValue {
     fn parse(input: ParseStream) -> Result<AttributeKeyValue> {
         let ident: Ident = input.parse()?;
+        //flags like `unknown_members` are short for `unknown_members = true`
+        if input.is_empty() || input.peek(Token![,]) {
+            let value = AttributeValue::Bool(LitBool::new(true, ident.span()));
+            return Ok(AttributeKeyValue { ident, value });
+        }
         input.parse::<Token![=]>()?;
+        if input.peek(Ident) && input.peek2(Paren) {
+            let value = AttributeValue::Call(input.parse()?);
+            return Ok(AttributeKeyValue { ident, value });
+        }
         let lookahead = input.lookahead1();
         let value = if lookahead.peek(LitBool) {
             AttributeValue::Bool(input.parse()?)
@@ -0,0 +1,80 @@
+use super::attribute::*;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Error, Result};
+use syn::{Attribute, Generics, Ident};
+
+//the supported profiles and the arguments of their `ProfileXConfig::new`
+const PROFILES: &[(&str, &[&str])] = &[
+    ("profile4", &["data_id"]),
+    ("profile5", &["data_id", "data_length"]),
+    ("profile6", &["data_id"]),
+    ("profile7", &["data_id"]),
+    ("profile11", &["data_id", "data_length"]),
+    ("profile22", &["data_id_list", "data_length"]),
+];
+
+pub(crate) fn derive(attrs: &[Attribute], ident: &Ident, generics: &Generics) -> TokenStream {
+    derive_e2e(attrs, ident, generics).unwrap_or_else(|e| e.to_compile_error())
+}
+
+fn derive_e2e(attrs: &[Attribute], ident: &Ident, generics: &Generics) -> Result<TokenStream> {
+    let attr = get_optional_someip_attr(attrs)?;
+    let call = match attr.as_ref().and_then(|a| a.get_optional("e2e")) {
+        Some(v) if matches!(v.as_ref(), AttributeValue::Call(_)) => v.as_ref().unwrap_call(),
+        //wrong types are reported by the struct itself
+        _ => return Ok(quote! {}),
+    };
+
+    let name = call.ident.to_string();
+    let required = PROFILES
+        .iter()
+        .find(|(profile, _)| *profile == name)
+        .map(|(_, required)| *required)
+        .ok_or_else(|| {
+            Error::new(
+                call.ident.span(),
+                "Unknown E2E profile: Only profile4, profile5, profile6, profile7, profile11 or profile22 are supported",
+            )
+        })?;
+
+    let mut required_args = Vec::with_capacity(required.len());
+    for key in required {
+        let arg = call.get_optional(key).ok_or_else(|| {
+            Error::new(call.span, format!("Required attribute {} not found", key))
+        })?;
+        required_args.push(arg);
+    }
+    let optional_args = call
+        .args
+        .iter()
+        .filter(|(key, _)| !required.iter().any(|r| key == r))
+        .map(|(key, expr)| quote! {#key: #expr});
+
+    let number = name.trim_start_matches("profile");
+    let profile = format_ident!("Profile{}", number);
+    let config = format_ident!("Profile{}Config", number);
+    let new_config = quote! {serde_someip::e2e::#config::new(#(#required_args),*)};
+    let config = if call.args.len() == required.len() {
+        new_config
+    } else {
+        quote! {
+            serde_someip::e2e::#config {
+                #(#optional_args,)*
+                ..#new_config
+            }
+        }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics serde_someip::e2e::E2EProtected for #ident #ty_generics #where_clause {
+            type Profile = serde_someip::e2e::#profile;
+
+            fn e2e_profile() -> Self::Profile {
+                serde_someip::e2e::#profile::new(#config)
+            }
+        }
+    })
+}
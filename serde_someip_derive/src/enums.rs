@@ -1,4 +1,5 @@
 use super::attribute::*;
+use super::structs::{derive_fields, derive_type};
 
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
@@ -35,6 +36,20 @@ impl RawType {
         }
     }
 
+    #[inline]
+    fn name(self) -> &'static str {
+        match self {
+            RawType::U8 => "u8",
+            RawType::U16 => "u16",
+            RawType::U32 => "u32",
+            RawType::U64 => "u64",
+            RawType::I8 => "i8",
+            RawType::I16 => "i16",
+            RawType::I32 => "i32",
+            RawType::I64 => "i64",
+        }
+    }
+
     #[inline]
     fn parse(&self, value: &LitInt) -> Result<u64> {
         Ok(match self {
@@ -51,21 +66,50 @@ impl RawType {
 }
 
 pub(crate) fn derive(attrs: &[Attribute], data: DataEnum, ident: &Ident) -> TokenStream {
+    match is_union(attrs, &data) {
+        Ok(true) => {
+            return derive_union(attrs, data, ident).unwrap_or_else(|e| e.to_compile_error())
+        }
+        Ok(false) => {}
+        Err(e) => return e.to_compile_error(),
+    }
+
     let raw_type_result = parse_raw_type(attrs, data.enum_token.span);
     if let Err(e) = raw_type_result {
         return e.to_compile_error();
     }
     let (raw_type, enum_value_type) = raw_type_result.unwrap();
     let mut seen_values = HashSet::default();
-    let values = data.variants.iter().map(|v| {
-        parse_value(v, enum_value_type, &mut seen_values).unwrap_or_else(|e| e.to_compile_error())
-    });
+    let mut values = Vec::with_capacity(data.variants.len());
+    let mut other = quote! {None};
+    let mut has_other = false;
+    for variant in &data.variants {
+        match parse_value(variant, enum_value_type, &mut seen_values) {
+            Ok((value, is_other)) => {
+                if is_other {
+                    if has_other {
+                        return Error::new(
+                            variant.ident.span(),
+                            "Only one variant can be the other variant",
+                        )
+                        .to_compile_error();
+                    }
+                    has_other = true;
+                    let name = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+                    other = quote! {Some(serde_someip::types::SomeIpRef::Static(#name))};
+                }
+                values.extend(value);
+            }
+            Err(e) => values.push(e.to_compile_error()),
+        }
+    }
     let name = LitStr::new(&ident.to_string(), ident.span());
     quote! {
             serde_someip::types::SomeIpType::Enum(serde_someip::types::SomeIpEnum {
-                name: #name,
+                name: serde_someip::types::SomeIpRef::Static(#name),
                 raw_type: #raw_type,
-                values: &[#(#values),*],
+                values: serde_someip::types::SomeIpRef::Static(&[#(#values),*]),
+                other: #other,
             })
     }
 }
@@ -132,13 +176,34 @@ fn parse_raw_type(attrs: &[Attribute], span: Span) -> Result<(TokenStream, RawTy
     }
 }
 
+//Returns the value of the variant if it has one and if it is the other variant
 fn parse_value(
     variant: &Variant,
     raw_type: RawType,
     seen_values: &mut HashSet<u64>,
-) -> Result<TokenStream> {
-    match variant.fields {
+) -> Result<(Option<TokenStream>, bool)> {
+    let attr = get_someip_attr(&variant.attrs, variant.ident.span())?;
+    let is_other = matches!(
+        attr.get_optional("other").map(|o| o.as_ref()),
+        Some(AttributeValue::Bool(b)) if b.value
+    );
+    match &variant.fields {
         Fields::Unit => {}
+        Fields::Unnamed(fields) if is_other && fields.unnamed.len() == 1 => {
+            //the other variant may carry the raw value instead of having a value
+            attr.check(&[("other", AttributeValueType::Bool)], &[], &[])?;
+            let ty = &fields.unnamed.first().unwrap().ty;
+            if !matches!(ty, Type::Path(p) if p.path.is_ident(raw_type.name())) {
+                return Err(Error::new(
+                    ty.span(),
+                    format!(
+                        "The other variant must carry the raw_type {}",
+                        raw_type.name()
+                    ),
+                ));
+            }
+            return Ok((None, true));
+        }
         _ => {
             return Err(Error::new(
                 variant.ident.span(),
@@ -147,8 +212,11 @@ fn parse_value(
         }
     }
 
-    let attr = get_someip_attr(&variant.attrs, variant.ident.span())?;
-    attr.check(&[("value", AttributeValueType::Int)], &[], &[])?;
+    attr.check(
+        &[("value", AttributeValueType::Int)],
+        &[("other", AttributeValueType::Bool)],
+        &[],
+    )?;
 
     let ident = LitStr::new(&variant.ident.to_string(), variant.ident.span());
     let value = attr.get("value").as_ref().unwrap_int();
@@ -157,5 +225,125 @@ fn parse_value(
         return Err(Error::new(value.span(), "Duplicate value"));
     }
     let enum_value_type = raw_type.into_tokens();
-    Ok(quote! {(#ident, #enum_value_type(#value))})
+    Ok((
+        Some(quote! {(serde_someip::types::SomeIpRef::Static(#ident), #enum_value_type(#value))}),
+        is_other,
+    ))
+}
+
+fn is_union(attrs: &[Attribute], data: &DataEnum) -> Result<bool> {
+    if let Some(attr) = get_optional_someip_attr(attrs)? {
+        if attr.get_optional("raw_type").is_some() {
+            return Ok(false);
+        }
+    }
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Ok(true);
+        }
+        if let Some(attr) = get_optional_someip_attr(&variant.attrs)? {
+            if attr.get_optional("selector").is_some() {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
+}
+
+fn derive_union(attrs: &[Attribute], data: DataEnum, ident: &Ident) -> Result<TokenStream> {
+    let (lfsize, type_field_size) = if let Some(attr) = get_optional_someip_attr(attrs)? {
+        attr.check(
+            &[],
+            &[
+                ("length_field_size", AttributeValueType::Int),
+                ("type_field_size", AttributeValueType::Int),
+            ],
+            &[],
+        )?;
+        let lfsize = if let Some(attr) = attr.get_optional("length_field_size") {
+            attr.to_length_field_size()?
+        } else {
+            quote! {None}
+        };
+        let type_field_size = if let Some(attr) = attr.get_optional("type_field_size") {
+            attr.to_length_field_size()?
+        } else {
+            quote! {None}
+        };
+        (lfsize, type_field_size)
+    } else {
+        (quote! {None}, quote! {None})
+    };
+
+    let mut seen_selectors = HashSet::default();
+    let variants = data.variants.iter().map(|v| {
+        parse_union_variant(v, ident, &mut seen_selectors).unwrap_or_else(|e| e.to_compile_error())
+    });
+    let name = LitStr::new(&ident.to_string(), ident.span());
+    Ok(quote! {
+            serde_someip::types::SomeIpType::Union(serde_someip::types::SomeIpUnion {
+                name: serde_someip::types::SomeIpRef::Static(#name),
+                variants: serde_someip::types::SomeIpRef::Static(&[#(#variants),*]),
+                length_field_size: #lfsize,
+                type_field_size: #type_field_size,
+            })
+    })
+}
+
+fn parse_union_variant(
+    variant: &Variant,
+    union_ident: &Ident,
+    seen_selectors: &mut HashSet<u32>,
+) -> Result<TokenStream> {
+    let attr = get_someip_attr(&variant.attrs, variant.ident.span())?;
+    attr.check(&[("selector", AttributeValueType::Int)], &[], &["*"])?;
+    let selector = attr.get("selector").as_ref().unwrap_int();
+    let parsed_selector = selector.base10_parse::<u32>()?;
+    if !seen_selectors.insert(parsed_selector) {
+        return Err(Error::new(selector.span(), "Duplicate selector"));
+    }
+
+    let ident = LitStr::new(&variant.ident.to_string(), variant.ident.span());
+    let variant_type = match &variant.fields {
+        Fields::Unit => {
+            attr.check(&[("selector", AttributeValueType::Int)], &[], &[])?;
+            quote! {None}
+        }
+        _ if parsed_selector == 0 => return Err(Error::new(
+            selector.span(),
+            "The selector 0 is reserved for the empty union and can only be used by unit variants",
+        )),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            let field = fields.unnamed.first().unwrap();
+            let ty = derive_type(Some(&attr), &field.ty, variant.ident.span(), true, false)?;
+            quote! {Some(serde_someip::types::SomeIpRef::Static(&#ty))}
+        }
+        fields => {
+            attr.check(&[("selector", AttributeValueType::Int)], &[], &[])?;
+            let (derived_fields, is_tlv) = match fields {
+                Fields::Named(f) => derive_fields(&f.named, f.brace_token.span, false)?,
+                Fields::Unnamed(f) => derive_fields(&f.unnamed, f.paren_token.span, false)?,
+                Fields::Unit => unreachable!(),
+            };
+            let name = LitStr::new(
+                &format!("{}::{}", union_ident, variant.ident),
+                variant.ident.span(),
+            );
+            quote! {Some(serde_someip::types::SomeIpRef::Static(&serde_someip::types::SomeIpType::Struct(serde_someip::types::SomeIpStruct {
+                name: serde_someip::types::SomeIpRef::Static(#name),
+                fields: serde_someip::types::SomeIpRef::Static(&[#(#derived_fields),*]),
+                uses_tlv_serialization: #is_tlv,
+                is_message_wrapper: false,
+                length_field_size: None,
+                transformation_properties: None,
+                extensible: false,
+            })))}
+        }
+    };
+
+    Ok(quote! {serde_someip::types::SomeIpUnionVariant {
+        name: serde_someip::types::SomeIpRef::Static(#ident),
+        selector: #selector,
+        variant_type: #variant_type,
+    }})
 }
@@ -5,6 +5,7 @@ use quote::{quote, quote_spanned};
 use syn::{parse_macro_input, Data, DeriveInput};
 
 pub(crate) mod attribute;
+mod e2e;
 mod enums;
 mod structs;
 
@@ -17,6 +18,11 @@ pub fn derive_someip(input: proc_macro::TokenStream) -> proc_macro::TokenStream
     let generics = input.generics;
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let e2e = match &input.data {
+        Data::Struct(_) => e2e::derive(&input.attrs, &ident, &generics),
+        _ => quote! {},
+    };
+
     let someip_type = match input.data {
         Data::Struct(s) => structs::derive(&input.attrs, s, &ident),
         Data::Enum(e) => enums::derive(&input.attrs, e, &ident),
@@ -32,6 +38,8 @@ pub fn derive_someip(input: proc_macro::TokenStream) -> proc_macro::TokenStream
         impl #impl_generics serde_someip::types::SomeIp for #ident #ty_generics #where_clause {
             const SOMEIP_TYPE: serde_someip::types::SomeIpType = #someip_type;
         }
+
+        #e2e
     }
     .into()
 }
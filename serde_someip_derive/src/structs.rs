@@ -3,9 +3,11 @@ use super::attribute::*;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned};
 use syn::parse::{Error, Result};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
+use syn::token::Comma;
 use syn::{
-    Attribute, DataStruct, Expr, Fields, FieldsNamed, FieldsUnnamed, GenericArgument, Ident,
+    Attribute, DataStruct, Expr, Field, Fields, FieldsNamed, FieldsUnnamed, GenericArgument, Ident,
     LitInt, LitStr, PathArguments, Type,
 };
 
@@ -20,7 +22,12 @@ pub(crate) fn derive(attrs: &[Attribute], data: DataStruct, ident: &Ident) -> To
 
     let res = match data.fields {
         Fields::Named(fields) => derive_normal_struct(attrs, fields, ident),
-        Fields::Unnamed(fields) => derive_newtype_struct(attrs, fields, data.struct_token.span),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            derive_newtype_struct(attrs, fields, data.struct_token.span)
+        }
+        Fields::Unnamed(fields) => {
+            derive_struct(attrs, &fields.unnamed, fields.paren_token.span, ident)
+        }
         Fields::Unit => unreachable!(),
     };
     if let Err(e) = res {
@@ -35,16 +42,33 @@ fn derive_normal_struct(
     fields: FieldsNamed,
     ident: &Ident,
 ) -> Result<TokenStream> {
-    let (lfsize, is_message_wrapper, transformation_props) =
+    if let Some(attr) = get_optional_someip_attr(attrs)? {
+        if attr.get_optional("bitfield").is_some() {
+            return derive_bitfield(&attr, fields, ident);
+        }
+    }
+    derive_struct(attrs, &fields.named, fields.brace_token.span, ident)
+}
+
+fn derive_struct(
+    attrs: &[Attribute],
+    fields: &Punctuated<Field, Comma>,
+    span: Span,
+    ident: &Ident,
+) -> Result<TokenStream> {
+    let (lfsize, is_message_wrapper, is_extensible, transformation_props) =
         if let Some(attr) = get_optional_someip_attr(attrs)? {
             attr.check(
                 &[],
                 &[
                     ("message_wrapper", AttributeValueType::Bool),
+                    ("extensible", AttributeValueType::Bool),
                     ("length_field_size", AttributeValueType::Int),
                     ("arrays_length_field_size", AttributeValueType::Int),
                     ("structs_length_field_size", AttributeValueType::Int),
                     ("strings_length_field_size", AttributeValueType::Int),
+                    ("alignment", AttributeValueType::Int),
+                    ("e2e", AttributeValueType::Call),
                 ],
                 &[],
             )?;
@@ -54,22 +78,172 @@ fn derive_normal_struct(
             } else {
                 false
             };
+            if let (Some(e2e), false) = (attr.get_optional("e2e"), is_message_wrapper) {
+                return Err(Error::new(
+                    e2e.as_ref().span(),
+                    "E2E protection can only be used on message wrappers",
+                ));
+            }
+            let is_extensible = if let Some(v) = attr.get_optional("extensible") {
+                v.as_ref().unwrap_bool().value
+            } else {
+                false
+            };
             let transformation_props = get_transformation_props(&attr)?;
-            (lfsize, is_message_wrapper, transformation_props)
+            (
+                lfsize,
+                is_message_wrapper,
+                is_extensible,
+                transformation_props,
+            )
         } else {
-            (quote! {None}, false, quote! {None})
+            (quote! {None}, false, false, quote! {None})
         };
 
+    let (derived_fields, is_tlv) = derive_fields(fields, span, is_extensible)?;
+    if is_tlv && fields.iter().all(|f| f.ident.is_none()) {
+        return Err(Error::new(
+            span,
+            "Tuple structs cannot use tlv serialization, so the fields must not have an id",
+        ));
+    }
+    if is_tlv && is_extensible {
+        return Err(Error::new(
+            span,
+            "TLV structs cannot be extensible, since they always support missing or unknown fields",
+        ));
+    }
+    let name = LitStr::new(&ident.to_string(), ident.span());
+
+    Ok(quote! {
+        serde_someip::types::SomeIpType::Struct(serde_someip::types::SomeIpStruct {
+            name: serde_someip::types::SomeIpRef::Static(#name),
+            fields: serde_someip::types::SomeIpRef::Static(&[#(#derived_fields),*]),
+            uses_tlv_serialization: #is_tlv,
+            is_message_wrapper: #is_message_wrapper,
+            length_field_size: #lfsize,
+            transformation_properties: #transformation_props,
+            extensible: #is_extensible,
+        })
+    })
+}
+
+fn derive_bitfield(
+    attr: &SomeIpAttribute,
+    fields: FieldsNamed,
+    ident: &Ident,
+) -> Result<TokenStream> {
+    attr.check(&[("bitfield", AttributeValueType::Type)], &[], &[])?;
+    let raw_type = attr.get("bitfield").as_ref().unwrap_type();
+    let (raw_type, bits) = match raw_type {
+        Type::Path(p) if p.path.is_ident("u8") => {
+            (quote! {serde_someip::types::SomeIpPrimitive::U8}, 8)
+        }
+        Type::Path(p) if p.path.is_ident("u16") => {
+            (quote! {serde_someip::types::SomeIpPrimitive::U16}, 16)
+        }
+        Type::Path(p) if p.path.is_ident("u32") => {
+            (quote! {serde_someip::types::SomeIpPrimitive::U32}, 32)
+        }
+        Type::Path(p) if p.path.is_ident("u64") => {
+            (quote! {serde_someip::types::SomeIpPrimitive::U64}, 64)
+        }
+        _ => {
+            return Err(Error::new(
+                raw_type.span(),
+                "Unsupported bitfield type: Only u8, u16, u32 or u64 are supported",
+            ))
+        }
+    };
+
+    let mut seen_bits: HashSet<u8> = HashSet::default();
+    let mut flags = Vec::with_capacity(fields.named.len());
+    for field in fields.named {
+        let ident = field.ident.unwrap();
+        match &field.ty {
+            Type::Path(p) if p.path.is_ident("bool") => {}
+            ty => {
+                return Err(Error::new(
+                    ty.span(),
+                    "The flags of a bitfield must be bools",
+                ))
+            }
+        }
+        let attr = get_someip_attr(&field.attrs, ident.span())?;
+        attr.check(&[("bit", AttributeValueType::Int)], &[], &[])?;
+        let bit = attr.get("bit").as_ref().unwrap_int();
+        let parsed_bit = bit.base10_parse::<u8>()?;
+        if parsed_bit >= bits {
+            return Err(Error::new(
+                bit.span(),
+                format!("The bit must be between 0 and {} inclusive", bits - 1),
+            ));
+        }
+        if !seen_bits.insert(parsed_bit) {
+            return Err(Error::new(bit.span(), "Duplicate bit"));
+        }
+        let ident = LitStr::new(&ident.to_string(), ident.span());
+        flags.push(quote! {(serde_someip::types::SomeIpRef::Static(#ident), #bit)});
+    }
+
+    let name = LitStr::new(&ident.to_string(), ident.span());
+    Ok(quote! {
+        serde_someip::types::SomeIpType::Bitfield(serde_someip::types::SomeIpBitfield {
+            name: serde_someip::types::SomeIpRef::Static(#name),
+            flags: serde_someip::types::SomeIpRef::Static(&[#(#flags),*]),
+            raw_type: #raw_type,
+        })
+    })
+}
+
+pub(crate) fn derive_fields(
+    fields: &Punctuated<Field, Comma>,
+    span: Span,
+    is_extensible: bool,
+) -> Result<(Vec<TokenStream>, bool)> {
     let mut encountered_ids = 0;
     let mut seen_ids: HashSet<u16> = HashSet::default();
-    let mut derived_fields = Vec::with_capacity(fields.named.len());
-    for field in fields.named {
+    let mut unknown_members_span = None;
+    let mut derived_fields = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
         let attrs = get_optional_someip_attr(&field.attrs)?;
-        let ident = field.ident.unwrap();
+        //unnamed fields are named by their index just like serde does
+        let (name, span) = if let Some(ident) = &field.ident {
+            (ident.to_string(), ident.span())
+        } else {
+            (index.to_string(), field.ty.span())
+        };
+
+        let unknown_members = attrs
+            .as_ref()
+            .and_then(|a| a.get_optional("unknown_members"))
+            .filter(|v| matches!(v.as_ref(), AttributeValue::Bool(b) if b.value));
 
         let id = if let Some(attrs) = &attrs {
-            attrs.check(&[], &[("id", AttributeValueType::Int)], &["*"])?;
-            if let Some(id) = attrs.get_optional("id") {
+            attrs.check(
+                &[],
+                &[
+                    ("id", AttributeValueType::Int),
+                    ("unknown_members", AttributeValueType::Bool),
+                ],
+                &["*"],
+            )?;
+            if let Some(unknown_members) = unknown_members {
+                if unknown_members_span.is_some() {
+                    return Err(Error::new(
+                        unknown_members.as_ref().span(),
+                        "Only one field can collect the unknown members",
+                    ));
+                }
+                if let Some(id) = attrs.get_optional("id") {
+                    return Err(Error::new(
+                        id.as_ref().span(),
+                        "The field collecting the unknown members cannot have an id",
+                    ));
+                }
+                unknown_members_span = Some(unknown_members.as_ref().span());
+                quote! {None}
+            } else if let Some(id) = attrs.get_optional("id") {
                 let id = id.as_ref().unwrap_int();
                 encountered_ids += 1;
                 let parsed_id = id.base10_parse::<u16>()?;
@@ -91,45 +265,66 @@ fn derive_normal_struct(
             quote! {None}
         };
 
-        let ty = derive_type(
-            attrs.as_ref(),
-            &field.ty,
-            ident.span(),
-            true,
-            encountered_ids > 0,
-        )
-        .unwrap_or_else(|e| e.to_compile_error());
+        let default = if let Some(default) = attrs.as_ref().and_then(|a| a.get_optional("default"))
+        {
+            derive_default(default, is_extensible)?
+        } else {
+            quote! {None}
+        };
 
-        let ident = LitStr::new(&ident.to_string(), ident.span());
+        let ty = derive_type(attrs.as_ref(), &field.ty, span, true, encountered_ids > 0)
+            .unwrap_or_else(|e| e.to_compile_error());
+
+        let name = LitStr::new(&name, span);
         derived_fields.push(quote! {
             serde_someip::types::SomeIpField{
-                name: #ident,
+                name: serde_someip::types::SomeIpRef::Static(#name),
                 id: #id,
-                field_type: &#ty,
+                field_type: serde_someip::types::SomeIpRef::Static(&#ty),
+                default: #default,
             }
         });
     }
 
-    if encountered_ids != 0 && encountered_ids != derived_fields.len() {
+    let id_less_fields = if unknown_members_span.is_some() { 1 } else { 0 };
+    if encountered_ids != 0 && encountered_ids + id_less_fields != derived_fields.len() {
         return Err(Error::new(
-            fields.brace_token.span,
+            span,
             "Either all fields or none must have an id",
         ));
     }
+    if let (Some(span), 0) = (unknown_members_span, encountered_ids) {
+        return Err(Error::new(
+            span,
+            "Unknown members can only be collected in TLV structs",
+        ));
+    }
 
-    let is_tlv = encountered_ids > 0;
-    let name = LitStr::new(&ident.to_string(), ident.span());
+    Ok((derived_fields, encountered_ids > 0))
+}
 
-    Ok(quote! {
-        serde_someip::types::SomeIpType::Struct(serde_someip::types::SomeIpStruct {
-            name: #name,
-            fields: &[#(#derived_fields),*],
-            uses_tlv_serialization: #is_tlv,
-            is_message_wrapper: #is_message_wrapper,
-            length_field_size: #lfsize,
-            transformation_properties: #transformation_props,
-        })
-    })
+fn derive_default(default: &AttributeKeyValue, is_extensible: bool) -> Result<TokenStream> {
+    if !is_extensible {
+        return Err(Error::new(
+            default.as_ref().span(),
+            "A default can only be used in extensible structs",
+        ));
+    }
+    match default.as_ref() {
+        AttributeValue::Bool(v) => Ok(quote! {Some(serde_someip::types::SomeIpDefault::Bool(#v))}),
+        AttributeValue::Int(v) if v.base10_digits().starts_with('-') => {
+            let v = v.base10_parse::<i64>()?;
+            Ok(quote! {Some(serde_someip::types::SomeIpDefault::Signed(#v))})
+        }
+        AttributeValue::Int(v) => {
+            let v = v.base10_parse::<u64>()?;
+            Ok(quote! {Some(serde_someip::types::SomeIpDefault::Unsigned(#v))})
+        }
+        v => Err(Error::new(
+            v.span(),
+            "A default must be an integer or bool literal",
+        )),
+    }
 }
 
 fn derive_newtype_struct(
@@ -137,12 +332,6 @@ fn derive_newtype_struct(
     fields: FieldsUnnamed,
     span: Span,
 ) -> Result<TokenStream> {
-    if fields.unnamed.len() != 1 {
-        return Err(Error::new(
-            fields.paren_token.span,
-            "Only newtype structs (tuple structs with exactly one paramter) are supported",
-        ));
-    }
     let field = fields.unnamed.first().unwrap();
     derive_type(
         get_optional_someip_attr(attrs)?.as_ref(),
@@ -153,7 +342,7 @@ fn derive_newtype_struct(
     )
 }
 
-fn derive_type(
+pub(crate) fn derive_type(
     attrs: Option<&SomeIpAttribute>,
     ty: &Type,
     span: Span,
@@ -175,10 +364,13 @@ fn derive_type(
         derive_string_type(attrs, span, is_outer)
     } else if let Some(elem) = is_sequence(ty) {
         derive_sequence_type(attrs, elem, span, is_outer)
+    } else if let Some((key, value)) = is_map(ty) {
+        derive_map_type(attrs, key, value, span, is_outer)
     } else if let Some((elem, len)) = is_array(ty) {
         derive_array_type(attrs, elem, len, span)
     } else {
-        Ok(quote! {#ty::SOMEIP_TYPE})
+        //tuples like (u32, f64) must be wrapped in angle brackets
+        Ok(quote! {<#ty>::SOMEIP_TYPE})
     }
 }
 
@@ -195,22 +387,34 @@ fn derive_string_type(
         };
         Error::new(span, message)
     })?;
-    attr.check(
-        &[("max_size", AttributeValueType::Int)],
-        &[
-            ("min_size", AttributeValueType::Int),
-            ("length_field_size", AttributeValueType::Int),
-        ],
-        &["treat_as", "id"],
-    )?;
+    let (min_size, max_size, padding) = if let Some(fixed_size) = attr.get_optional("fixed_size") {
+        attr.check(
+            &[("fixed_size", AttributeValueType::Int)],
+            &[("length_field_size", AttributeValueType::Int)],
+            &["treat_as", "id", "selector"],
+        )?;
+        let fixed_size = fixed_size.as_ref().unwrap_int().clone();
+        (fixed_size.clone(), fixed_size, true)
+    } else {
+        attr.check(
+            &[("max_size", AttributeValueType::Int)],
+            &[
+                ("min_size", AttributeValueType::Int),
+                ("length_field_size", AttributeValueType::Int),
+            ],
+            &["treat_as", "id", "selector"],
+        )?;
+        let min_size = get_min_sizes(attr, "min_size");
+        let max_size = attr.get("max_size").as_ref().unwrap_int().clone();
+        (min_size, max_size, false)
+    };
     let lfsize = derive_length_field_size(attr)?;
-    let min_size = get_min_sizes(attr, "min_size");
-    let max_size = attr.get("max_size").as_ref().unwrap_int();
     Ok(
         quote! {serde_someip::types::SomeIpType::String(serde_someip::types::SomeIpString {
             min_size: #min_size,
             max_size: #max_size,
-            length_field_size: #lfsize
+            length_field_size: #lfsize,
+            padding: #padding,
         })},
     )
 }
@@ -237,7 +441,7 @@ fn derive_sequence_type(
             ("length_field_size", AttributeValueType::Int),
             ("elements", AttributeValueType::Inner),
         ],
-        &["treat_as", "id"],
+        &["treat_as", "id", "selector"],
     )?;
 
     let min_elements = get_min_sizes(attr, "min_elements");
@@ -254,7 +458,57 @@ fn derive_sequence_type(
         quote! {serde_someip::types::SomeIpType::Sequence(serde_someip::types::SomeIpSequence {
             min_elements: #min_elements,
             max_elements: #max_elements,
-            element_type: &#element_type,
+            element_type: serde_someip::types::SomeIpRef::Static(&#element_type),
+            length_field_size: #lfsize,
+        })},
+    )
+}
+
+fn derive_map_type(
+    attr: Option<&SomeIpAttribute>,
+    key: &Type,
+    value: &Type,
+    span: Span,
+    is_outer: bool,
+) -> Result<TokenStream> {
+    let attr = attr.ok_or_else(|| {
+        let message = if is_outer {
+            "A someip attribute is required for map types (HashMap, BTreeMap)"
+        } else {
+            "A elements attribute is required for element map types (HashMap, BTreeMap)"
+        };
+        Error::new(span, message)
+    })?;
+
+    attr.check(
+        &[("max_elements", AttributeValueType::Int)],
+        &[
+            ("min_elements", AttributeValueType::Int),
+            ("length_field_size", AttributeValueType::Int),
+            ("keys", AttributeValueType::Inner),
+            ("values", AttributeValueType::Inner),
+        ],
+        &["treat_as", "id", "selector"],
+    )?;
+
+    let min_elements = get_min_sizes(attr, "min_elements");
+    let max_elements = attr.get("max_elements").as_ref().unwrap_int();
+
+    let lfsize = derive_length_field_size(attr)?;
+
+    let key_attr = attr.get_optional("keys").map(|a| a.as_ref().unwrap_inner());
+    let key_type = derive_type(key_attr, key, attr.span, false, false)?;
+    let value_attr = attr
+        .get_optional("values")
+        .map(|a| a.as_ref().unwrap_inner());
+    let value_type = derive_type(value_attr, value, attr.span, false, false)?;
+
+    Ok(
+        quote! {serde_someip::types::SomeIpType::Map(serde_someip::types::SomeIpMap {
+            min_elements: #min_elements,
+            max_elements: #max_elements,
+            key_type: serde_someip::types::SomeIpRef::Static(&#key_type),
+            value_type: serde_someip::types::SomeIpRef::Static(&#value_type),
             length_field_size: #lfsize,
         })},
     )
@@ -273,7 +527,7 @@ fn derive_array_type(
                 ("length_field_size", AttributeValueType::Int),
                 ("elements", AttributeValueType::Inner),
             ],
-            &["treat_as", "id"],
+            &["treat_as", "id", "selector"],
         )?;
         let lfsize = derive_length_field_size(attr)?;
         (
@@ -292,7 +546,7 @@ fn derive_array_type(
         quote! {serde_someip::types::SomeIpType::Sequence(serde_someip::types::SomeIpSequence {
             min_elements: #len,
             max_elements: #len,
-            element_type: &#element_type,
+            element_type: serde_someip::types::SomeIpRef::Static(&#element_type),
             length_field_size: #lfsize,
         })},
     )
@@ -318,12 +572,19 @@ fn get_transformation_props(attr: &SomeIpAttribute) -> Result<TokenStream> {
     } else {
         quote! {None}
     };
+    let alignment = if let Some(attr) = attr.get_optional("alignment") {
+        at_least_one = true;
+        attr.to_alignment()?
+    } else {
+        quote! {None}
+    };
 
     Ok(if at_least_one {
         quote! {Some(serde_someip::types::SomeIpTransforationProperties {
             size_of_array_length_field: #arrays,
             size_of_struct_length_field: #structs,
             size_of_string_length_field: #strings,
+            alignment: #alignment,
         })}
     } else {
         quote! {None}
@@ -395,6 +656,25 @@ fn is_sequence(ty: &Type) -> Option<&Type> {
     None
 }
 
+#[inline]
+fn is_map(ty: &Type) -> Option<(&Type, &Type)> {
+    if let Type::Path(p) = ty {
+        if let Some(v) = p.path.segments.iter().last() {
+            if v.ident == "HashMap" || v.ident == "BTreeMap" {
+                if let PathArguments::AngleBracketed(args) = &v.arguments {
+                    let mut args = args.args.iter();
+                    if let (Some(GenericArgument::Type(key)), Some(GenericArgument::Type(value))) =
+                        (args.next(), args.next())
+                    {
+                        return Some((key, value));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
 #[inline]
 fn is_option(ty: &Type) -> Option<&Type> {
     if let Type::Path(p) = ty {